@@ -0,0 +1,69 @@
+use astroport::{asset::AssetInfo, router::SwapOperation};
+
+use crate::{error::ContractError, state::Config};
+
+/// ## Description
+/// Builds the Astroport router [`SwapOperation`] hops to swap from `offer_asset` to `target_asset`
+/// through each of the intermediate assets in `route`, in order.
+/// ## Arguments
+/// * `offer_asset` - The [`AssetInfo`] being spent in the swap.
+///
+/// * `route` - The intermediate [`AssetInfo`]s to swap through.
+///
+/// * `target_asset` - The [`AssetInfo`] being purchased in the swap.
+pub fn build_route(
+    offer_asset: &AssetInfo,
+    route: &[AssetInfo],
+    target_asset: &AssetInfo,
+) -> Vec<SwapOperation> {
+    let assets: Vec<&AssetInfo> = std::iter::once(offer_asset)
+        .chain(route.iter())
+        .chain(std::iter::once(target_asset))
+        .collect();
+
+    assets
+        .windows(2)
+        .map(|pair| SwapOperation::AstroSwap {
+            offer_asset_info: pair[0].clone(),
+            ask_asset_info: pair[1].clone(),
+        })
+        .collect()
+}
+
+/// ## Description
+/// Validates a `route` of intermediate assets for a DCA order, so that every intermediate asset is
+/// whitelisted and the route does not exceed `max_hops`.
+///
+/// Returns a [`ContractError`] if the route is invalid.
+/// ## Arguments
+/// * `config` - The [`Config`] of the contract, containing the whitelisted tokens.
+///
+/// * `max_hops` - The maximum amount of hops that the route can have.
+///
+/// * `initial_asset` - The [`AssetInfo`] being spent by the order.
+///
+/// * `target_asset` - The [`AssetInfo`] being purchased by the order.
+///
+/// * `route` - The intermediate [`AssetInfo`]s to swap through.
+pub fn validate_route(
+    config: &Config,
+    max_hops: u32,
+    initial_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+    route: &[AssetInfo],
+) -> Result<(), ContractError> {
+    let hops = route.len() as u32 + 1;
+    if hops > max_hops {
+        return Err(ContractError::MaxHopsAssertion { hops });
+    }
+
+    for asset in route {
+        if !config.is_whitelisted_asset(asset) || asset == initial_asset || asset == target_asset {
+            return Err(ContractError::InvalidHopRoute {
+                token: asset.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -2,19 +2,46 @@ use std::str::FromStr;
 
 use crate::error::ContractError;
 use crate::handlers::{
-    add_bot_tip, cancel_dca_order, create_dca_order, modify_dca_order, perform_dca_purchase,
-    update_config, update_user_config, withdraw, CreateDcaOrder, ModifyDcaOrderParameters,
+    add_bot_tip, add_hop_asset, add_tip_asset, apply_pending_config, auto_stake_reply,
+    blacklist_asset, callback, cancel_dca_order, cancel_dca_orders, claim_ownership,
+    claim_pool_share, claim_purchased, claim_referral_rewards, create_dca_order, create_dca_orders,
+    drop_ownership_proposal, fee_on_transfer_reply, ibc_transfer_reply, instantiate_nft,
+    instantiate_nft_reply, join_pooled_order, leave_pooled_order, modify_dca_order, notifier_reply,
+    pause_asset, pause_dca_order, perform_dca_purchase, perform_dca_purchase_reply,
+    perform_dca_purchases, perform_dca_purchases_reply, propose_new_owner, prune_archived_orders,
+    receive, receive_ibc, refresh_pair, register_bot, register_keeper_job, remove_hop_asset,
+    remove_tip_asset, resume_dca_order, set_interchain_controller, set_keeper, set_member_group,
+    set_notifier, set_operator, set_oracle, split_swap_reply, sudo_execute_due_purchases,
+    sudo_ibc_lifecycle_complete, sudo_set_paused, sudo_update_owner, sweep_expired, sweep_surplus,
+    top_up_dca_order, transfer_order, unblacklist_asset, unbond_bot, unpause_asset, update_config,
+    update_user_config, withdraw, withdraw_bot_bond, withdraw_from_dca_order, CreateDcaOrder,
+    ModifyDcaOrderParameters, UpdateConfigParameters, AUTO_STAKE_REPLY_ID,
+    FEE_ON_TRANSFER_REPLY_ID, IBC_TRANSFER_REPLY_ID, NFT_INSTANTIATE_REPLY_ID, NOTIFIER_REPLY_ID,
+    PERFORM_DCA_PURCHASES_REPLY_ID, PERFORM_DCA_PURCHASE_REPLY_ID, SPLIT_SWAP_REPLY_ID,
+};
+use crate::queries::{
+    get_all_users, get_archived_orders, get_bots, get_config, get_interchain_controller,
+    get_keeper, get_member_group, get_notifier, get_operators, get_oracle, get_order_stats,
+    get_orders_by_controller, get_orders_by_pair, get_pending_config, get_pool_share,
+    get_protocol_fees, get_purchase_history, get_ready_orders, get_reconciliation, get_stats,
+    get_tip_balances, get_user_config, get_user_dca_orders, simulate_order_plan, simulate_purchase,
+};
+use crate::state::{
+    user_dca, Config, LegacyDcaInfo, CONFIG, GLOBAL_STATS, INITIAL_ASSET_INDEX, LEGACY_ORDERS,
+    LEGACY_USER_DCA, NEXT_PURCHASE_INDEX,
 };
-use crate::queries::{get_config, get_user_config, get_user_dca_orders};
-use crate::state::{Config, CONFIG};
 
 use astroport::asset::addr_validate_to_lower;
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    attr, entry_point, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Reply, Response, StdResult, Storage,
 };
 
-use astroport_dca::dca::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use cw2::set_contract_version;
+use astroport_dca::dca::{
+    DcaInfo, ExecuteMsg, InstantiateMsg, MigrateMsg, PurchaseRequest, QueryMsg, SudoMsg,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Map;
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-dca";
@@ -47,6 +74,20 @@ pub fn instantiate(
     // validate that factory_addr and router_addr is an address
     let factory_addr = addr_validate_to_lower(deps.api, &msg.factory_addr)?;
     let router_addr = addr_validate_to_lower(deps.api, &msg.router_addr)?;
+    let staking_addr = msg
+        .staking_addr
+        .map(|staking_addr| addr_validate_to_lower(deps.api, &staking_addr))
+        .transpose()?;
+    let fee_collector = msg
+        .fee_collector
+        .map(|fee_collector| addr_validate_to_lower(deps.api, &fee_collector))
+        .transpose()?;
+    let owner = msg
+        .owner
+        .map(|owner| addr_validate_to_lower(deps.api, &owner))
+        .transpose()?;
+    let mut fee_tiers = msg.fee_tiers;
+    fee_tiers.sort_by_key(|tier| tier.min_xastro);
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -57,7 +98,30 @@ pub fn instantiate(
         max_spread,
         factory_addr,
         router_addr,
+        staking_addr,
+        protocol_fee_bps: msg.protocol_fee_bps,
+        fee_collector,
+        fee_tiers,
+        referral_share: msg.referral_share,
+        owner,
+        config_timelock: msg.config_timelock,
+        paused: false,
+        paused_assets: vec![],
+        nft_addr: None,
+        max_oracle_deviation: None,
+        registered_bots_only: false,
+        bot_bond: None,
+        fairness_window: None,
+        allowed_assets: vec![],
+        allowed_assets_only: false,
+        blacklisted_assets: vec![],
+        min_liquidity: None,
+        fee_on_transfer_tokens: vec![],
+        min_deposit: None,
+        min_dca_amount: None,
+        swap_backend: msg.swap_backend,
     };
+    config.validate_protocol_fee()?;
 
     CONFIG.save(deps.storage, &config)?;
 
@@ -65,31 +129,364 @@ pub fn instantiate(
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Moves any DCA orders stored in the legacy [`LEGACY_USER_DCA`] format, where each user had a
+/// single [`Vec<LegacyDcaInfo>`], into the indexed [`user_dca`] map keyed by the user address and
+/// order id.
+fn migrate_legacy_orders(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_orders = LEGACY_USER_DCA
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, Vec<LegacyDcaInfo>)>>>()?;
+
+    for (user, orders) in legacy_orders {
+        for order in orders {
+            user_dca().save(storage, (&user, order.id), &order.into())?;
+        }
+
+        LEGACY_USER_DCA.remove(storage, &user);
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Rewrites the DCA orders stored in [`user_dca`] with an interval in seconds, as
+/// [`LegacyDcaInfo`], so that their interval is stored as a
+/// [`Duration::Time`](astroport_dca::dca::Duration::Time).
+///
+/// The orders are replaced without loading them as a [`DcaInfo`], which they cannot be deserialized
+/// as, so their previous index entries are not removed. The `next_purchase` index is rebuilt by
+/// [`migrate`] once every order is stored as a [`DcaInfo`].
+fn migrate_order_intervals(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_orders = LEGACY_ORDERS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for ((user, id), order) in legacy_orders {
+        user_dca().replace(storage, (&user, id), Some(&DcaInfo::from(order)), None)?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Removes every raw entry of `index`, including the entries stored under a previous key format
+/// that saving an order again would not remove, so that the index can be rebuilt from the stored
+/// orders or dropped altogether.
+fn clear_index(storage: &mut dyn Storage, index: &Map<Vec<u8>, u32>) {
+    let keys: Vec<Vec<u8>> = index
+        .keys_raw(storage, None, None, Order::Ascending)
+        .collect();
+
+    for key in keys {
+        index.remove(storage, key);
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Checks that the contract being migrated is a DCA contract of a
+/// version this build can migrate from, runs the state migrations of that version, rebuilds the
+/// indexes of the stored orders and sets the new contract version.
+///
+/// Returns a [`ContractError::MigrationError`] if the stored contract name or version cannot be
+/// migrated from, otherwise returns a [`Response`] with the previous and new contract versions.
 /// ## Arguments
-/// * `_deps` - A [`DepsMut`] that contains the dependencies.
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
 ///
 /// * `_env` - The [`Env`] of the blockchain.
 ///
 /// * `_msg` - The [`MigrateMsg`] to migrate the contract.
+///
+/// ## Migrations
+/// * **1.0.0** Moves the DCA orders stored in the legacy format into the indexed map.
+///
+/// * **1.1.0** Stores the interval of every DCA order as a duration in seconds.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrationError {
+            contract: contract_version.contract,
+            version: contract_version.version,
+        });
+    }
+
+    match contract_version.version.as_ref() {
+        "1.0.0" => migrate_legacy_orders(deps.storage)?,
+        "1.1.0" => migrate_order_intervals(deps.storage)?,
+        _ => {
+            return Err(ContractError::MigrationError {
+                contract: contract_version.contract,
+                version: contract_version.version,
+            })
+        }
+    }
+
+    // the next purchase index is rebuilt, as it was keyed by the next purchase alone before the
+    // unit of the interval of each order, and before paused orders were indexed apart
+    clear_index(deps.storage, &NEXT_PURCHASE_INDEX);
+
+    // the orders are no longer indexed by their initial asset alone, as nothing queried them by it
+    clear_index(deps.storage, &INITIAL_ASSET_INDEX);
+
+    // save every order again, so that any indexes added since they were stored are populated
+    let orders = user_dca()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let active_orders = orders.len() as u64;
+    for ((user, id), order) in orders {
+        user_dca().save(deps.storage, (&user, id), &order)?;
+    }
+
+    // the global statistics start counting the orders stored before they were kept
+    let mut stats = GLOBAL_STATS.may_load(deps.storage)?.unwrap_or_default();
+    stats.active_orders = active_orders;
+    GLOBAL_STATS.save(deps.storage, &stats)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("previous_contract_name", contract_version.contract),
+        attr("previous_contract_version", contract_version.version),
+        attr("new_contract_name", CONTRACT_NAME),
+        attr("new_contract_version", CONTRACT_VERSION),
+    ]))
 }
 
 #[cfg(test)]
 mod test {
-    use astroport_dca::dca::MigrateMsg;
-    use cosmwasm_std::{testing::{mock_dependencies, mock_env}, Response};
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{DcaInfo, Delivery, Duration, MigrateMsg};
+    use cosmwasm_std::{
+        attr,
+        testing::{mock_dependencies, mock_env},
+        Addr, Order, Response, StdResult, Uint128,
+    };
+    use cw2::{get_contract_version, set_contract_version};
+    use cw_storage_plus::{Index, MultiIndex, PrimaryKey};
 
-    use super::migrate;
+    use crate::{
+        error::ContractError,
+        state::{
+            get_user_orders, user_dca, LegacyDcaInfo, INITIAL_ASSET_INDEX, LEGACY_ORDERS,
+            LEGACY_USER_DCA, NEXT_PURCHASE_INDEX,
+        },
+    };
+
+    use super::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 
     #[test]
     fn can_migrate() {
         let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
 
         let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
-        assert_eq!(res, Response::new());
+        assert_eq!(
+            res,
+            Response::new().add_attributes(vec![
+                attr("previous_contract_name", CONTRACT_NAME),
+                attr("previous_contract_version", "1.0.0"),
+                attr("new_contract_name", CONTRACT_NAME),
+                attr("new_contract_version", CONTRACT_VERSION),
+            ])
+        );
+        assert_eq!(
+            get_contract_version(&deps.storage).unwrap().version,
+            CONTRACT_VERSION
+        );
+    }
+
+    #[test]
+    fn cannot_migrate_incompatible_contract() {
+        let mut deps = mock_dependencies();
+
+        set_contract_version(&mut deps.storage, "crates.io:other-contract", "1.0.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::MigrationError {
+                contract: "crates.io:other-contract".to_string(),
+                version: "1.0.0".to_string()
+            }
+        );
+
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::MigrationError {
+                contract: CONTRACT_NAME.to_string(),
+                version: "0.1.0".to_string()
+            }
+        );
+    }
+
+    fn legacy_order(id: u64) -> LegacyDcaInfo {
+        LegacyDcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(100_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: 1_000,
+            last_purchase: 0,
+            dca_amount: Uint128::new(25_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+        }
+    }
+
+    #[test]
+    fn does_migrate_legacy_orders() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+
+        let user = Addr::unchecked("creator");
+        LEGACY_USER_DCA
+            .save(
+                &mut deps.storage,
+                &user,
+                &vec![legacy_order(1), legacy_order(3)],
+            )
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        // legacy storage should be cleared, and the orders moved to the indexed map
+        assert_eq!(
+            LEGACY_USER_DCA.may_load(&deps.storage, &user).unwrap(),
+            None
+        );
+        assert_eq!(
+            get_user_orders(&deps.storage, &user).unwrap(),
+            vec![legacy_order(1).into(), legacy_order(3).into()]
+        );
+    }
+
+    #[test]
+    fn does_migrate_order_intervals() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.1.0").unwrap();
+
+        let user = Addr::unchecked("creator");
+        for id in [1, 2] {
+            LEGACY_ORDERS
+                .save(&mut deps.storage, (&user, id), &legacy_order(id))
+                .unwrap();
+        }
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        // the interval of the orders should now be stored in seconds
+        let orders: Vec<DcaInfo> = get_user_orders(&deps.storage, &user).unwrap();
+        assert_eq!(orders.len(), 2);
+        for order in orders {
+            assert_eq!(order.interval, Duration::Time(1_000));
+        }
+
+        // the orders should still be found through the next purchase index
+        let due = user_dca()
+            .idx
+            .next_purchase
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(due, 2);
+    }
+
+    #[test]
+    fn does_rebuild_next_purchase_index() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.1.0").unwrap();
+
+        let user = Addr::unchecked("creator");
+        LEGACY_ORDERS
+            .save(&mut deps.storage, (&user, 1), &legacy_order(1))
+            .unwrap();
+
+        // the order was indexed by its next purchase alone before the unit of its interval
+        let legacy_index: MultiIndex<u64, DcaInfo, (&Addr, u64)> = MultiIndex::new(
+            |_pk, order: &DcaInfo| order.next_purchase_at().value(),
+            "user_dca_orders",
+            "user_dca_orders__next_purchase",
+        );
+        legacy_index
+            .save(
+                &mut deps.storage,
+                &(&user, 1u64).joined_key(),
+                &legacy_order(1).into(),
+            )
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        // only the entry of the order in the current key format should remain in the index
+        let entries = NEXT_PURCHASE_INDEX
+            .keys_raw(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(entries, 1);
+
+        let due = user_dca()
+            .idx
+            .next_purchase
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, (user, 1));
+        assert_eq!(due[0].1.interval, Duration::Time(1_000));
+    }
+
+    #[test]
+    fn does_clear_initial_asset_index() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.1.0").unwrap();
+
+        let user = Addr::unchecked("creator");
+        LEGACY_ORDERS
+            .save(&mut deps.storage, (&user, 1), &legacy_order(1))
+            .unwrap();
+
+        // the order was indexed by its initial asset on its own
+        let legacy_index: MultiIndex<String, DcaInfo, (&Addr, u64)> = MultiIndex::new(
+            |_pk, order: &DcaInfo| order.initial_asset.info.to_string(),
+            "user_dca_orders",
+            "user_dca_orders__initial_asset",
+        );
+        legacy_index
+            .save(
+                &mut deps.storage,
+                &(&user, 1u64).joined_key(),
+                &legacy_order(1).into(),
+            )
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let entries = INITIAL_ASSET_INDEX
+            .keys_raw(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(entries, 0);
     }
 }
 
@@ -107,40 +504,226 @@ mod test {
 /// ## Execution Messages
 /// * **ExecuteMsg::AddBotTip { assets: Vec<Asset> }** Adds a bot tip to fund DCA purchases.
 ///
-/// * **ExecuteMsg::CancelDcaOrder { initial_asset }** Cancels an existing DCA order.
+/// * **ExecuteMsg::CancelDcaOrder { id, user, withdraw_tips }** Cancels an existing DCA order,
+/// optionally withdrawing the bot tip of the user if it was their last order.
+///
+/// * **ExecuteMsg::CancelDcaOrders { ids, user, withdraw_tips }** Cancels multiple existing DCA
+/// orders, or every DCA order of the user, optionally withdrawing the bot tip of the user if no
+/// orders remain.
+///
+/// * **ExecuteMsg::ClaimPurchased { id, user }** Sends the purchased assets of a DCA order held by
+/// the contract to the owner of the order.
+///
+/// * **ExecuteMsg::ClaimReferralRewards {}** Sends the share of the protocol fees accrued to the
+/// sender as a referrer.
+///
+/// * **ExecuteMsg::JoinPooledOrder { user, id }** Adds the native tokens sent to a pooled DCA
+/// order, minting shares of the order to the sender.
+///
+/// * **ExecuteMsg::ClaimPoolShare { user, id }** Sends the purchased assets of a pooled DCA order
+/// owed to the shares of the sender.
+///
+/// * **ExecuteMsg::LeavePooledOrder { user, id }** Burns the shares of the sender in a pooled DCA
+/// order, refunding their share of its deposit along with their unclaimed purchases.
 ///
 /// * **ExecuteMsg::CreateDcaOrder {
 ///         initial_asset,
 ///         target_asset,
 ///         interval,
-///         dca_amount
+///         schedule,
+///         max_delay,
+///         jitter,
+///         dca_amount,
+///         strategy,
+///         first_purchase,
+///         expires_at,
+///         max_purchases,
+///         max_failures,
+///         failure_policy,
+///         swap_tip_bps,
+///         tip_escalation,
+///         tip_replenish,
+///         allowed_bots,
+///         allow_low_liquidity,
+///         max_price,
+///         only_below_price,
+///         max_price_impact,
+///         stop_price,
+///         take_profit_price,
+///         direction,
+///         distribution,
+///         targets,
+///         pooled,
+///         max_spread,
+///         min_receive_per_purchase,
+///         route,
+///         auto_route,
+///         delivery,
+///         recipient,
+///         purchase_hooks,
+///         auto_stake,
+///         zap,
+///         referrer,
+///         owner,
+///         pull,
 ///     }** Creates a new DCA order where `initial_asset` will purchase `target_asset`.
 ///
+/// * **ExecuteMsg::CreateDcaOrders { orders }** Creates multiple new DCA orders at once.
+///
 /// * **ExecuteMsg::ModifyDcaOrder {
-///         old_initial_asset,
+///         id,
 ///         new_initial_asset,
 ///         new_target_asset,
 ///         new_interval,
+///         new_schedule,
 ///         new_dca_amount,
-///         should_reset_purchase_time,
+///         new_first_purchase,
+///         user,
 ///     }** Modifies an existing DCA order, allowing the user to change certain parameters.
 ///
-/// * **ExecuteMsg::PerformDcaPurchase { user, hops }** Performs a DCA purchase on behalf of a
-/// specified user given a hop route.
+/// * **ExecuteMsg::PerformDcaPurchase {
+///         user,
+///         id,
+///         hops,
+///         fee_redeem,
+///         deadline,
+///         accepted_tips,
+///     }** Performs a DCA purchase on behalf of a specified user given a hop route.
+///
+/// * **ExecuteMsg::PerformDcaPurchases { purchases }** Performs multiple DCA purchases, where a
+/// purchase that fails does not revert the others.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Proposes a new owner of the contract,
+/// who can claim the ownership for `expires_in` seconds.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes the pending proposal of a new owner.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims the ownership of the contract by the proposed new
+/// owner.
 ///
 /// * **ExecuteMsg::UpdateConfig {
 ///         max_hops,
 ///         per_hop_fee,
 ///         whitelisted_tokens,
-///         max_spread
-///     }** Updates the contract configuration with the specified input parameters.
+///         max_spread,
+///         staking_addr,
+///         protocol_fee_bps,
+///         fee_collector,
+///         fee_tiers,
+///         referral_share,
+///         factory_addr,
+///         router_addr,
+///         config_timelock,
+///         paused,
+///         max_oracle_deviation,
+///         registered_bots_only,
+///         bot_bond,
+///         fairness_window,
+///         allowed_assets,
+///         allowed_assets_only,
+///         min_liquidity,
+///         fee_on_transfer_tokens,
+///         min_deposit,
+///         min_dca_amount,
+///     }** Updates the contract configuration with the specified input parameters, scheduling
+/// the sensitive changes to take effect once the configuration timelock has passed.
+///
+/// * **ExecuteMsg::ApplyPendingConfig {}** Applies the pending configuration changes whose
+/// timelock has passed.
+///
+/// * **ExecuteMsg::PauseAsset { asset }** Pauses the DCA purchases of orders spending or
+/// purchasing an asset.
+///
+/// * **ExecuteMsg::UnpauseAsset { asset }** Resumes the DCA purchases of orders spending or
+/// purchasing a paused asset.
 ///
 /// * **ExecuteMsg::UpdateUserConfig {
 ///         max_hops,
 ///         max_spread,
+///         tip_priority,
 ///     }** Updates a users configuration with the new input parameters.
 ///
 /// * **ExecuteMsg::Withdraw { tip }** Withdraws a bot tip from the contract.
+///
+/// * **ExecuteMsg::Receive(msg)** Receives a cw20 token sent to the contract, executing the
+/// contained [`astroport_dca::dca::Cw20HookMsg`].
+///
+/// * **ExecuteMsg::ReceiveIbc(msg)** Receives native tokens transferred over IBC with an
+/// ibc-hooks memo, creating a DCA order for its owner with the contained
+/// [`astroport_dca::dca::Cw20HookMsg`].
+///
+/// * **ExecuteMsg::Callback(msg)** Executes a [`astroport_dca::dca::CallbackMsg`] sent by the
+/// contract to itself.
+///
+/// * **ExecuteMsg::TopUpDcaOrder { id, amount }** Adds funds to an existing DCA order.
+///
+/// * **ExecuteMsg::WithdrawFromDcaOrder { id, amount, user }** Withdraws part of the remaining
+/// funds of an existing DCA order.
+///
+/// * **ExecuteMsg::PauseDcaOrder { id, user }** Pauses an existing DCA order.
+///
+/// * **ExecuteMsg::ResumeDcaOrder { id, user }** Resumes a paused DCA order.
+///
+/// * **ExecuteMsg::SetOperator { operator, allowed }** Allows or disallows an operator to manage
+/// the DCA orders of the sender.
+///
+/// * **ExecuteMsg::SetMemberGroup { group }** Sets the cw4 group whose members can claim, pause and
+/// resume the DCA orders of the sender.
+///
+/// * **ExecuteMsg::SetNotifier { notifier }** Sets the contract notified of the lifecycle events of
+/// the DCA orders of the sender.
+///
+/// * **ExecuteMsg::SetInterchainController { controller }** Declares the sender to be an
+/// interchain account owned by a controller on a remote chain.
+///
+/// * **ExecuteMsg::SweepExpired { user, id }** Refunds and removes an expired DCA order, or an
+/// order of a blacklisted asset.
+///
+/// * **ExecuteMsg::TransferOrder { id, new_owner }** Transfers a DCA order of the sender to a new
+/// owner.
+///
+/// * **ExecuteMsg::InstantiateNft { code_id, name, symbol }** Instantiates the companion cw721
+/// contract minting an NFT for each new DCA order.
+///
+/// * **ExecuteMsg::SetOracle { initial_asset, target_asset, oracle_addr }** Sets or removes the
+/// oracle that the DCA purchases of an asset pair are checked against.
+///
+/// * **ExecuteMsg::SetKeeper { keeper }** Sets or removes the CronCat tasks contract that keeper
+/// jobs of DCA orders are registered on.
+///
+/// * **ExecuteMsg::RegisterKeeperJob { id, user, interval, funds }** Registers a recurring task on
+/// the keeper performing the DCA purchases of an order, funded from the tip balance of its user.
+///
+/// * **ExecuteMsg::PruneArchivedOrders { retention, limit }** Removes the archived DCA orders that
+/// were completed longer ago than the retention period.
+///
+/// * **ExecuteMsg::SweepSurplus { assets }** Sends the surplus of the assets held by the contract
+/// over its tracked liabilities to the fee collector.
+///
+/// * **ExecuteMsg::RegisterBot {}** Registers the sender as a bot, tracking the performance of its
+/// DCA purchases and holding its bond.
+///
+/// * **ExecuteMsg::UnbondBot {}** Starts the unbonding period of the bond of a registered bot.
+///
+/// * **ExecuteMsg::WithdrawBotBond {}** Withdraws the bond of a registered bot once its unbonding
+/// period has passed.
+///
+/// * **ExecuteMsg::AddTipAsset { asset }** Whitelists a tip asset or updates its tip per hop.
+///
+/// * **ExecuteMsg::RemoveTipAsset { asset }** Removes a whitelisted tip asset.
+///
+/// * **ExecuteMsg::AddHopAsset { asset }** Whitelists an asset that hop routes can swap through.
+///
+/// * **ExecuteMsg::RemoveHopAsset { asset }** Removes a whitelisted hop asset that no stored route
+/// of a DCA order is using.
+///
+/// * **ExecuteMsg::BlacklistAsset { asset }** Blacklists an asset that DCA orders can no longer
+/// spend or purchase.
+///
+/// * **ExecuteMsg::UnblacklistAsset { asset }** Removes an asset from the blacklist.
+///
+/// * **ExecuteMsg::RefreshPair { asset_infos }** Resolves the cached address of an Astroport pair
+/// from the factory again.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -154,24 +737,103 @@ pub fn execute(
             whitelisted_tokens,
             whitelisted_fee_assets,
             max_spread,
+            staking_addr,
+            protocol_fee_bps,
+            fee_collector,
+            fee_tiers,
+            referral_share,
+            factory_addr,
+            router_addr,
+            config_timelock,
+            paused,
+            max_oracle_deviation,
+            registered_bots_only,
+            bot_bond,
+            fairness_window,
+            allowed_assets,
+            allowed_assets_only,
+            min_liquidity,
+            fee_on_transfer_tokens,
+            min_deposit,
+            min_dca_amount,
         } => update_config(
             deps,
+            env,
             info,
-            max_hops,
-            whitelisted_fee_assets,
-            whitelisted_tokens,
-            max_spread,
+            UpdateConfigParameters {
+                max_hops,
+                whitelisted_fee_assets,
+                whitelisted_tokens,
+                max_spread,
+                staking_addr,
+                protocol_fee_bps,
+                fee_collector,
+                fee_tiers,
+                referral_share,
+                factory_addr,
+                router_addr,
+                config_timelock,
+                paused,
+                max_oracle_deviation,
+                registered_bots_only,
+                bot_bond,
+                fairness_window,
+                allowed_assets,
+                allowed_assets_only,
+                min_liquidity,
+                fee_on_transfer_tokens,
+                min_deposit,
+                min_dca_amount,
+            },
         ),
+        ExecuteMsg::ApplyPendingConfig {} => apply_pending_config(deps, env),
+        ExecuteMsg::PauseAsset { asset } => pause_asset(deps, info, asset),
+        ExecuteMsg::UnpauseAsset { asset } => unpause_asset(deps, info, asset),
         ExecuteMsg::UpdateUserConfig {
             max_hops,
             max_spread,
-        } => update_user_config(deps, info, max_hops, max_spread),
+            tip_priority,
+        } => update_user_config(deps, info, max_hops, max_spread, tip_priority),
         ExecuteMsg::CreateDcaOrder {
             initial_asset,
             target_asset,
             interval,
+            schedule,
+            max_delay,
+            jitter,
             dca_amount,
+            strategy,
             first_purchase,
+            expires_at,
+            max_purchases,
+            max_failures,
+            failure_policy,
+            swap_tip_bps,
+            tip_escalation,
+            tip_replenish,
+            allowed_bots,
+            allow_low_liquidity,
+            max_price,
+            only_below_price,
+            max_price_impact,
+            stop_price,
+            take_profit_price,
+            direction,
+            distribution,
+            targets,
+            pooled,
+            max_spread,
+            min_receive_per_purchase,
+            route,
+            auto_route,
+            delivery,
+            recipient,
+            purchase_hooks,
+            auto_stake,
+            zap,
+            referrer,
+            owner,
+            pull,
         } => create_dca_order(
             deps,
             env,
@@ -180,10 +842,50 @@ pub fn execute(
                 initial_asset,
                 target_asset,
                 interval,
+                schedule,
+                max_delay,
+                jitter,
                 dca_amount,
+                strategy,
                 first_purchase,
+                expires_at,
+                max_purchases,
+                max_failures,
+                failure_policy,
+                swap_tip_bps,
+                tip_escalation,
+                tip_replenish,
+                allowed_bots,
+                allow_low_liquidity,
+                max_price,
+                only_below_price,
+                max_price_impact,
+                stop_price,
+                take_profit_price,
+                direction,
+                distribution,
+                targets,
+                pooled,
+                max_spread,
+                min_receive_per_purchase,
+                route,
+                auto_route,
+                delivery,
+                recipient,
+                purchase_hooks,
+                auto_stake,
+                zap,
+                referrer,
+                owner,
+                pull,
             },
         ),
+        ExecuteMsg::CreateDcaOrders { orders } => create_dca_orders(
+            deps,
+            env,
+            info,
+            orders.into_iter().map(CreateDcaOrder::from).collect(),
+        ),
         ExecuteMsg::AddBotTip { assets } => add_bot_tip(deps, env, info, assets),
         ExecuteMsg::Withdraw { assets } => withdraw(deps, info, assets),
         ExecuteMsg::PerformDcaPurchase {
@@ -191,15 +893,53 @@ pub fn execute(
             hops,
             id,
             fee_redeem,
-        } => perform_dca_purchase(deps, env, info, user, id, hops, fee_redeem),
-        ExecuteMsg::CancelDcaOrder { id } => cancel_dca_order(deps, info, id),
+            deadline,
+            accepted_tips,
+        } => perform_dca_purchase(
+            deps,
+            env,
+            info,
+            PurchaseRequest {
+                user,
+                id,
+                hops,
+                fee_redeem,
+                deadline,
+                accepted_tips,
+            },
+        ),
+        ExecuteMsg::PerformDcaPurchases { purchases } => {
+            perform_dca_purchases(deps, env, info, purchases)
+        }
+        ExecuteMsg::CancelDcaOrder {
+            id,
+            user,
+            withdraw_tips,
+        } => cancel_dca_order(deps, info, id, user, withdraw_tips),
+        ExecuteMsg::CancelDcaOrders {
+            ids,
+            user,
+            withdraw_tips,
+        } => cancel_dca_orders(deps, info, ids, user, withdraw_tips),
+        ExecuteMsg::ClaimPurchased { id, user } => claim_purchased(deps, info, id, user),
+        ExecuteMsg::ClaimReferralRewards {} => claim_referral_rewards(deps, info),
+        ExecuteMsg::JoinPooledOrder { user, id } => join_pooled_order(deps, info, user, id),
+        ExecuteMsg::ClaimPoolShare { user, id } => claim_pool_share(deps, info, user, id),
+        ExecuteMsg::LeavePooledOrder { user, id } => leave_pooled_order(deps, info, user, id),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            propose_new_owner(deps, env, info, owner, expires_in)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => drop_ownership_proposal(deps, info),
+        ExecuteMsg::ClaimOwnership {} => claim_ownership(deps, env, info),
         ExecuteMsg::ModifyDcaOrder {
             id,
             new_initial_asset,
             new_target_asset,
             new_interval,
+            new_schedule,
             new_dca_amount,
             new_first_purchase,
+            user,
         } => modify_dca_order(
             deps,
             env,
@@ -209,10 +949,142 @@ pub fn execute(
                 new_initial_asset,
                 new_target_asset,
                 new_interval,
+                new_schedule,
                 new_dca_amount,
                 new_first_purchase,
+                user,
             },
         ),
+        ExecuteMsg::Receive(cw20_msg) => receive(deps, env, info, cw20_msg),
+        ExecuteMsg::ReceiveIbc(msg) => receive_ibc(deps, env, info, msg),
+        ExecuteMsg::Callback(msg) => callback(deps, env, info, msg),
+        ExecuteMsg::TopUpDcaOrder { id, amount } => top_up_dca_order(deps, env, info, id, amount),
+        ExecuteMsg::WithdrawFromDcaOrder { id, amount, user } => {
+            withdraw_from_dca_order(deps, info, id, amount, user)
+        }
+        ExecuteMsg::PauseDcaOrder { id, user } => pause_dca_order(deps, info, id, user),
+        ExecuteMsg::ResumeDcaOrder { id, user } => resume_dca_order(deps, info, id, user),
+        ExecuteMsg::SetOperator { operator, allowed } => {
+            set_operator(deps, info, operator, allowed)
+        }
+        ExecuteMsg::SetMemberGroup { group } => set_member_group(deps, info, group),
+        ExecuteMsg::SetNotifier { notifier } => set_notifier(deps, info, notifier),
+        ExecuteMsg::SetInterchainController { controller } => {
+            set_interchain_controller(deps, info, controller)
+        }
+        ExecuteMsg::SweepExpired { user, id } => sweep_expired(deps, env, user, id),
+        ExecuteMsg::TransferOrder { id, new_owner } => transfer_order(deps, info, id, new_owner),
+        ExecuteMsg::InstantiateNft {
+            code_id,
+            name,
+            symbol,
+        } => instantiate_nft(deps, env, info, code_id, name, symbol),
+        ExecuteMsg::SetOracle {
+            initial_asset,
+            target_asset,
+            oracle_addr,
+        } => set_oracle(deps, info, initial_asset, target_asset, oracle_addr),
+        ExecuteMsg::SetKeeper { keeper } => set_keeper(deps, info, keeper),
+        ExecuteMsg::RegisterKeeperJob {
+            id,
+            user,
+            interval,
+            funds,
+        } => register_keeper_job(deps, env, info, id, user, interval, funds),
+        ExecuteMsg::PruneArchivedOrders { retention, limit } => {
+            prune_archived_orders(deps, env, info, retention, limit)
+        }
+        ExecuteMsg::SweepSurplus { assets } => sweep_surplus(deps, env, info, assets),
+        ExecuteMsg::RegisterBot {} => register_bot(deps, env, info),
+        ExecuteMsg::UnbondBot {} => unbond_bot(deps, env, info),
+        ExecuteMsg::WithdrawBotBond {} => withdraw_bot_bond(deps, env, info),
+        ExecuteMsg::AddTipAsset { asset } => add_tip_asset(deps, env, info, asset),
+        ExecuteMsg::RemoveTipAsset { asset } => remove_tip_asset(deps, env, info, asset),
+        ExecuteMsg::AddHopAsset { asset } => add_hop_asset(deps, env, info, asset),
+        ExecuteMsg::RemoveHopAsset { asset } => remove_hop_asset(deps, env, info, asset),
+        ExecuteMsg::BlacklistAsset { asset } => blacklist_asset(deps, info, asset),
+        ExecuteMsg::UnblacklistAsset { asset } => unblacklist_asset(deps, info, asset),
+        ExecuteMsg::RefreshPair { asset_infos } => refresh_pair(deps, asset_infos),
+    }
+}
+
+/// ## Description
+/// Exposes all the replies available in the contract.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `msg` - The [`Reply`] to handle.
+///
+/// ## Replies
+/// * **PERFORM_DCA_PURCHASE_REPLY_ID** Records the amount received from the router swap of a DCA
+/// purchase on the order.
+///
+/// * **PERFORM_DCA_PURCHASES_REPLY_ID** Records the error of a failed DCA purchase performed through
+/// [`ExecuteMsg::PerformDcaPurchases`] on the order it was performed for.
+///
+/// * **AUTO_STAKE_REPLY_ID** Sends the xASTRO received from staking the ASTRO of a DCA purchase to
+/// the recipient of the purchase.
+///
+/// * **FEE_ON_TRANSFER_REPLY_ID** Sends the amount of a token taking a tax on transfers received
+/// for a DCA purchase to the router.
+///
+/// * **SPLIT_SWAP_REPLY_ID** Adds the amount received from one of the router swaps of a DCA
+/// purchase split across several routes, recording the purchase once every swap has replied.
+///
+/// * **IBC_TRANSFER_REPLY_ID** Stores the ICS-20 transfer of a DCA purchase by its sequence, or
+/// holds its asset for the user to claim if it could not be sent.
+///
+/// * **NOTIFIER_REPLY_ID** Ignores a failed notification of the notifier of a user.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        PERFORM_DCA_PURCHASE_REPLY_ID => perform_dca_purchase_reply(deps, env, msg.result),
+        PERFORM_DCA_PURCHASES_REPLY_ID => perform_dca_purchases_reply(deps, env, msg.result),
+        AUTO_STAKE_REPLY_ID => auto_stake_reply(deps, env),
+        NFT_INSTANTIATE_REPLY_ID => instantiate_nft_reply(deps, msg.result),
+        FEE_ON_TRANSFER_REPLY_ID => fee_on_transfer_reply(deps, env),
+        SPLIT_SWAP_REPLY_ID => split_swap_reply(deps, env, msg.result),
+        IBC_TRANSFER_REPLY_ID => ibc_transfer_reply(deps, msg.result),
+        NOTIFIER_REPLY_ID => notifier_reply(msg.result),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+/// ## Description
+/// Exposes the privileged messages that can be executed by the chain, so that the contract can
+/// be managed through on-chain governance without the owner of the contract.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `msg` - The [`SudoMsg`] to execute.
+///
+/// ## Messages
+/// * **SudoMsg::Pause {}** Pauses the creation of DCA orders and DCA purchases.
+///
+/// * **SudoMsg::Resume {}** Resumes the creation of DCA orders and DCA purchases.
+///
+/// * **SudoMsg::UpdateOwner { owner }** Transfers the ownership of the contract to `owner`.
+///
+/// * **SudoMsg::IbcLifecycleComplete(msg)** Settles the ICS-20 transfer of a DCA purchase
+/// reported by ibc-hooks, holding the asset of a failed transfer for the user to claim.
+///
+/// * **SudoMsg::ExecuteDuePurchases { limit }** Performs the DCA purchases of up to `limit` due
+/// orders without a bot, as called by the scheduler of the chain.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::Pause {} => sudo_set_paused(deps, true),
+        SudoMsg::Resume {} => sudo_set_paused(deps, false),
+        SudoMsg::UpdateOwner { owner } => sudo_update_owner(deps, owner),
+        SudoMsg::IbcLifecycleComplete(msg) => sudo_ibc_lifecycle_complete(deps, msg),
+        SudoMsg::ExecuteDuePurchases { limit } => sudo_execute_due_purchases(deps, env, limit),
     }
 }
 
@@ -227,18 +1099,162 @@ pub fn execute(
 ///
 /// ## Queries
 /// * **QueryMsg::Config {}** Returns information about the configuration of the contract in a
-/// [`Config`] object.
+/// [`crate::state::ConfigResponse`] object, along with the pending proposal of a new owner.
 ///
 /// * **QueryMsg::UserConfig {}** Returns information about a specified users configuration set for
 /// DCA purchases in a [`UserConfig`] object.
 ///
-/// * **QueryMsg::UserDcaOrders {}** Returns information about a specified users current DCA orders
-/// set in a [`Vec<DcaInfo>`] object.
+/// * **QueryMsg::UserDcaOrders { user, direction }** Returns information about a specified users
+/// current DCA orders set, optionally of one direction, in a [`Vec<DcaInfo>`] object.
+///
+/// * **QueryMsg::PurchaseHistory { user, id, start_after, limit }** Returns the purchases performed
+/// for a DCA order of a specified user in a [`Vec<PurchaseQueryInfo>`] object.
+///
+/// * **QueryMsg::OrderStats { user, id }** Returns the statistics of a DCA order of a specified
+/// user in an [`OrderStatsResponse`] object.
+///
+/// * **QueryMsg::ReadyOrders { start_after, limit }** Returns the DCA orders of all users that a
+/// DCA purchase can currently be performed for in a [`Vec<ReadyOrderInfo>`] object.
+///
+/// * **QueryMsg::AllUsers { start_after, limit }** Returns the users that have DCA orders and the
+/// amount of orders of each user in a [`Vec<UserOrdersInfo>`] object.
+///
+/// * **QueryMsg::OrdersByPair { initial_asset, target_asset, start_after, limit, direction }**
+/// Returns the DCA orders of all users for an asset pair, optionally of one direction, in a
+/// [`Vec<UserDcaInfo>`] object.
+///
+/// * **QueryMsg::ProtocolFees {}** Returns the cumulative protocol fees collected for each asset in
+/// a [`Vec<Asset>`] object.
+///
+/// * **QueryMsg::Stats {}** Returns the global statistics of the contract for dashboards in a
+/// [`StatsResponse`] object.
+///
+/// * **QueryMsg::Reconciliation {}** Returns the balance of each asset held by the contract against
+/// its tracked liabilities in a [`Vec<AssetReconciliation>`] object.
+///
+/// * **QueryMsg::Operators { user }** Returns the operators allowed to manage the DCA orders of a
+/// user in a [`Vec<Addr>`] object.
+///
+/// * **QueryMsg::MemberGroup { user }** Returns the cw4 group whose members can claim, pause and
+/// resume the DCA orders of a user in an [`Option<Addr>`] object.
+///
+/// * **QueryMsg::Notifier { user }** Returns the contract notified of the lifecycle events of the
+/// DCA orders of a user in an [`Option<Addr>`] object.
+///
+/// * **QueryMsg::InterchainController { user }** Returns the controller on a remote chain that a
+/// user declared it is the interchain account of in an [`Option<InterchainController>`] object.
+///
+/// * **QueryMsg::OrdersByController { connection_id, controller, start_after, limit }** Returns
+/// the DCA orders of the interchain accounts owned by a controller on a remote chain in a
+/// [`Vec<UserDcaInfo>`] object.
+///
+/// * **QueryMsg::PendingConfig {}** Returns the configuration changes waiting for their timelock
+/// to pass in a [`Vec<crate::state::PendingConfigChange>`] object.
+///
+/// * **QueryMsg::Oracle { initial_asset, target_asset }** Returns the oracle that the DCA purchases
+/// of an asset pair are checked against in an [`Option<Addr>`] object.
+///
+/// * **QueryMsg::Keeper {}** Returns the tasks contract that keeper jobs are registered on in an
+/// [`Option<Addr>`] object.
+///
+/// * **QueryMsg::ArchivedOrders { user, start_after, limit }** Returns the completed DCA orders of
+/// a user along with their final stats in a [`Vec<ArchivedOrder>`] object.
+///
+/// * **QueryMsg::TipBalances { user }** Returns the tip balance of a user in the order it is
+/// redeemed by bots in a [`Vec<Asset>`] object.
+///
+/// * **QueryMsg::Bots { start_after, limit }** Returns the registered bots along with the
+/// performance of their DCA purchases in a [`Vec<BotInfo>`] object.
+///
+/// * **QueryMsg::SimulatePurchase { user, id, hops }** Returns the outcome of the next DCA purchase
+/// of a DCA order through a hop route, without performing it, in a [`SimulatePurchaseResponse`]
+/// object.
+///
+/// * **QueryMsg::SimulateOrderPlan { user, id }** Returns the remaining DCA purchases of a DCA
+/// order along with their estimated output and tips in an [`OrderPlanResponse`] object.
+///
+/// * **QueryMsg::PoolShare { user, id, depositor }** Returns the shares a depositor holds of a
+/// pooled DCA order along with their share of its deposit and purchases in a
+/// [`PoolShareResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&get_config(deps)?),
         QueryMsg::UserConfig { user } => to_binary(&get_user_config(deps, user)?),
-        QueryMsg::UserDcaOrders { user } => to_binary(&get_user_dca_orders(deps, env, user)?),
+        QueryMsg::UserDcaOrders { user, direction } => {
+            to_binary(&get_user_dca_orders(deps, env, user, direction)?)
+        }
+        QueryMsg::PurchaseHistory {
+            user,
+            id,
+            start_after,
+            limit,
+        } => to_binary(&get_purchase_history(deps, user, id, start_after, limit)?),
+        QueryMsg::OrderStats { user, id } => to_binary(&get_order_stats(deps, env, user, id)?),
+        QueryMsg::ReadyOrders { start_after, limit } => {
+            to_binary(&get_ready_orders(deps, env, start_after, limit)?)
+        }
+        QueryMsg::AllUsers { start_after, limit } => {
+            to_binary(&get_all_users(deps, start_after, limit)?)
+        }
+        QueryMsg::OrdersByPair {
+            initial_asset,
+            target_asset,
+            start_after,
+            limit,
+            direction,
+        } => to_binary(&get_orders_by_pair(
+            deps,
+            initial_asset,
+            target_asset,
+            start_after,
+            limit,
+            direction,
+        )?),
+        QueryMsg::ProtocolFees {} => to_binary(&get_protocol_fees(deps)?),
+        QueryMsg::Stats {} => to_binary(&get_stats(deps, env)?),
+        QueryMsg::Reconciliation {} => to_binary(&get_reconciliation(deps, env)?),
+        QueryMsg::PendingConfig {} => to_binary(&get_pending_config(deps)?),
+        QueryMsg::Operators { user } => to_binary(&get_operators(deps, user)?),
+        QueryMsg::MemberGroup { user } => to_binary(&get_member_group(deps, user)?),
+        QueryMsg::Notifier { user } => to_binary(&get_notifier(deps, user)?),
+        QueryMsg::InterchainController { user } => {
+            to_binary(&get_interchain_controller(deps, user)?)
+        }
+        QueryMsg::OrdersByController {
+            connection_id,
+            controller,
+            start_after,
+            limit,
+        } => to_binary(&get_orders_by_controller(
+            deps,
+            connection_id,
+            controller,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Oracle {
+            initial_asset,
+            target_asset,
+        } => to_binary(&get_oracle(deps, initial_asset, target_asset)?),
+        QueryMsg::Keeper {} => to_binary(&get_keeper(deps)?),
+        QueryMsg::ArchivedOrders {
+            user,
+            start_after,
+            limit,
+        } => to_binary(&get_archived_orders(deps, user, start_after, limit)?),
+        QueryMsg::TipBalances { user } => to_binary(&get_tip_balances(deps, user)?),
+        QueryMsg::Bots { start_after, limit } => to_binary(&get_bots(deps, start_after, limit)?),
+        QueryMsg::SimulatePurchase { user, id, hops } => {
+            to_binary(&simulate_purchase(deps, env, user, id, hops)?)
+        }
+        QueryMsg::SimulateOrderPlan { user, id } => {
+            to_binary(&simulate_order_plan(deps, env, user, id)?)
+        }
+        QueryMsg::PoolShare {
+            user,
+            id,
+            depositor,
+        } => to_binary(&get_pool_share(deps, user, id, depositor)?),
     }
 }
@@ -0,0 +1,34 @@
+use cosmwasm_std::CosmosMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes the messages sent to the tasks contract of CronCat, matching the `ExecuteMsg` of
+/// croncat-tasks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CroncatExecuteMsg {
+    CreateTask { task: Box<TaskRequest> },
+}
+
+/// Describes a recurring task performing `actions`, where the fields of CronCat left unspecified
+/// are not used
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TaskRequest {
+    pub interval: Interval,
+    pub stop_on_fail: bool,
+    pub actions: Vec<Action>,
+}
+
+/// Describes how often a task is run
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Interval {
+    Block(u64),
+}
+
+/// Describes a message sent by an agent of CronCat each time a task is run
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Action {
+    pub msg: CosmosMsg,
+    pub gas_limit: Option<u64>,
+}
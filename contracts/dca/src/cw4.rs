@@ -0,0 +1,28 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes the queries sent to the cw4 group of a user, matching the `QueryMsg` of cw4-group
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw4QueryMsg {
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+    TotalWeight {
+        at_height: Option<u64>,
+    },
+}
+
+/// Describes the response to a [`Cw4QueryMsg::Member`] query, where an address that is not a
+/// member of the group has no weight
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MemberResponse {
+    pub weight: Option<u64>,
+}
+
+/// Describes the response to a [`Cw4QueryMsg::TotalWeight`] query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalWeightResponse {
+    pub weight: u64,
+}
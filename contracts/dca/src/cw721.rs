@@ -0,0 +1,43 @@
+use cosmwasm_std::Empty;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes the message instantiating the companion cw721 contract, matching the
+/// `InstantiateMsg` of cw721-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw721InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub minter: String,
+}
+
+/// Describes the messages executed on the companion cw721 contract, matching the `ExecuteMsg` of
+/// cw721-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721ExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Option<Empty>,
+    },
+}
+
+/// Describes the queries sent to the companion cw721 contract, matching the `QueryMsg` of
+/// cw721-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721QueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+}
+
+/// Describes the response to a [`Cw721QueryMsg::OwnerOf`] query, ignoring the approvals of the
+/// token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerOfResponse {
+    pub owner: String,
+}
@@ -1,5 +1,5 @@
 use astroport::asset::{Asset, AssetInfo};
-use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 /// ## Description
@@ -27,6 +27,30 @@ pub enum ContractError {
     #[error("DCA amount is not equal to allowance set by token")]
     InvalidTokenDeposit {},
 
+    #[error("Contract {contract_addr} is not a cw20 token")]
+    InvalidToken { contract_addr: String },
+
+    #[error("Denom {denom} is not a valid tokenfactory denom of the form factory/{{creator}}/{{subdenom}}")]
+    InvalidTokenFactoryDenom { denom: String },
+
+    #[error("Pools of type {pair_type} are not supported in hop routes")]
+    UnsupportedPoolType { pair_type: String },
+
+    #[error("The pair swap backend can only swap through a single hop")]
+    InvalidSwapBackendRoute {},
+
+    #[error("Fee-on-transfer tokens can only be swapped through the router swap backend")]
+    FeeOnTransferSwapBackend {},
+
+    #[error("The deposit of the order must be at least {min}")]
+    DepositBelowMinimum { min: Uint128 },
+
+    #[error("The amount spent in each purchase must be at least {min}")]
+    DcaAmountBelowMinimum { min: Uint128 },
+
+    #[error("Fee-on-transfer tokens cannot be sent to the contract")]
+    FeeOnTransferSend {},
+
     #[error("Invalid hop route through {token} due to token whitelist")]
     InvalidHopRoute { token: String },
 
@@ -45,19 +69,286 @@ pub enum ContractError {
     #[error("DCA purchase occurred too early")]
     PurchaseTooEarly {},
 
+    #[error("DCA purchase was not performed within the maximum delay, and has been skipped")]
+    PurchaseSkipped {},
+
     #[error("Hop route does not end up at target_asset")]
     TargetAssetAssertion {},
 
     #[error("Asset balance is less than DCA purchase amount")]
     InsufficientBalance {},
 
+    #[error("Escrowed cw20 orders can not be increased from the token allowance")]
+    EscrowedDepositIncrease {},
+
+    #[error("DCA order is paused")]
+    OrderPaused {},
+
+    #[error("DCA order is not paused")]
+    OrderNotPaused {},
+
+    #[error("DCA order has expired")]
+    OrderExpired {},
+
+    #[error("DCA order has not expired")]
+    OrderNotExpired {},
+
+    #[error("DCA order cannot be transferred to its own user")]
+    SelfTransfer {},
+
+    #[error("DCA order spending a cw20 allowance cannot be transferred")]
+    AllowanceOrderTransfer {},
+
+    #[error("DCA order is represented by an NFT, which should be transferred instead")]
+    TokenizedOrderTransfer {},
+
+    #[error("The member group is not a cw4 group contract")]
+    InvalidMemberGroup {},
+
+    #[error("The controller of an interchain account must be an address over a connection")]
+    InvalidInterchainController {},
+
+    #[error("No keeper has been set to register jobs on")]
+    NoKeeper {},
+
+    #[error("Keeper jobs must run on a non zero interval, funded with a native tip asset, for an order with a stored route or auto routing")]
+    InvalidKeeperJob {},
+
+    #[error("Tokens received over IBC must be a single non zero native coin")]
+    InvalidIbcFunds {},
+
+    #[error(
+        "Only DCA orders with an owner on this chain can be created with tokens received over IBC"
+    )]
+    InvalidIbcHook {},
+
+    #[error("IBC delivery must send a native target asset over a channel to an address with a non zero timeout, without a recipient, purchase hooks, auto staking or zapping")]
+    InvalidIbcDelivery {},
+
+    #[error(
+        "No ICS-20 transfer of a DCA purchase was sent over {channel} with sequence {sequence}"
+    )]
+    NonexistentIbcTransfer { channel: String, sequence: u64 },
+
+    #[error("The NFT contract has already been instantiated")]
+    NftAlreadyInstantiated {},
+
+    #[error("Simulated price {price} is above the maximum price {max_price} of the DCA order")]
+    PriceAboveLimit { price: Decimal, max_price: Decimal },
+
+    #[error("Simulated purchase deviates by {deviation} from the oracle price, above the maximum deviation {max_deviation}")]
+    OracleDeviation {
+        deviation: Decimal,
+        max_deviation: Decimal,
+    },
+
+    #[error("No route could be found from the initial asset to the target asset")]
+    NoRouteAvailable {},
+
+    #[error("The pair of {offer_asset} and {ask_asset} holds less than the minimum liquidity")]
+    InsufficientLiquidity {
+        offer_asset: AssetInfo,
+        ask_asset: AssetInfo,
+    },
+
+    #[error("The deadline of the DCA purchase has passed")]
+    DeadlinePassed {},
+
+    #[error("A DCA purchase is already being performed for the order")]
+    PurchaseInProgress {},
+
+    #[error("The amount received from the swap could not be found")]
+    MissingReturnAmount {},
+
+    #[error("The swaps of the split purchase received {received}, less than the minimum receive of {minimum_receive}")]
+    SplitMinimumReceive {
+        received: Uint128,
+        minimum_receive: Uint128,
+    },
+
+    #[error("Reply with unknown id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("No DCA orders were provided")]
+    NoOrdersProvided {},
+
+    #[error("There are no purchased assets to claim for the DCA order")]
+    NothingToClaim {},
+
+    #[error("Purchase hooks cannot be used with escrowed delivery")]
+    EscrowedPurchaseHooks {},
+
+    #[error("The staking contract has not been configured")]
+    StakingNotConfigured {},
+
+    #[error("Auto staking is only available for immediately delivered ASTRO orders without hooks")]
+    InvalidAutoStake {},
+
+    #[error("The tranches of a DCA order must be non zero and sum to its deposit")]
+    InvalidTranches {},
+
+    #[error("Zapping requires a pair of the assets and immediate delivery without hooks, auto staking or value averaging")]
+    InvalidZap {},
+
+    #[error("The factory has no generator to stake the LP tokens in")]
+    GeneratorNotConfigured {},
+
+    #[error("The protocol fee cannot exceed {max} basis points")]
+    ProtocolFeeTooHigh { max: u16 },
+
+    #[error("A fee collector must be set to take a protocol fee")]
+    FeeCollectorNotSet {},
+
+    #[error("There is no surplus of the assets to sweep")]
+    NoSurplus {},
+
+    #[error("Fee tier discounts cannot exceed the whole protocol fee")]
+    InvalidFeeTier {},
+
+    #[error("The referral share cannot exceed the whole protocol fee")]
+    InvalidReferralShare {},
+
+    #[error("A DCA order cannot be referred by its own user")]
+    SelfReferral {},
+
+    #[error("A cw20 DCA order for another owner must be funded by sending the tokens")]
+    OwnerAllowanceDeposit {},
+
+    #[error("Only a cw20 DCA order spent from the allowance of the user can pull each purchase")]
+    InvalidPullOrder {},
+
+    #[error("There are no referral rewards to claim")]
+    NoReferralRewards {},
+
+    #[error("Cannot migrate from contract {contract} version {version}")]
+    MigrationError { contract: String, version: String },
+
+    #[error("The contract is paused")]
+    ContractPaused {},
+
+    #[error("DCA purchases of asset {asset} are paused")]
+    AssetPaused { asset: AssetInfo },
+
+    #[error("DCA orders cannot spend or purchase asset {asset}, which is not allowed")]
+    AssetNotAllowed { asset: AssetInfo },
+
+    #[error("Asset {asset} is blacklisted")]
+    AssetBlacklisted { asset: AssetInfo },
+
+    #[error("Asset {asset} is not blacklisted")]
+    AssetNotBlacklisted { asset: AssetInfo },
+
+    #[error("DCA purchases of asset {asset} are not paused")]
+    AssetNotPaused { asset: AssetInfo },
+
+    #[error("There are no pending configuration changes whose timelock has passed")]
+    NoPendingConfigChanges {},
+
     #[error("Initial asset and target asset are the same")]
     DuplicateAsset {},
 
+    #[error("DCA interval must be greater than zero")]
+    InvalidInterval {},
+
+    #[error("Maximum amount of DCA purchases must be greater than zero")]
+    InvalidMaxPurchases {},
+
+    #[error("Maximum amount of failed DCA purchases must be greater than zero, and slippage must be widened by a non zero step")]
+    InvalidFailurePolicy {},
+
+    #[error("The swap tip must be greater than zero and cannot exceed {max} basis points")]
+    InvalidSwapTip { max: u16 },
+
+    #[error("The bot of an order paying a share of each purchase cannot redeem the tip balance")]
+    SwapTipNotRedeemable {},
+
+    #[error("The maximum price impact must be greater than zero and cannot exceed one")]
+    InvalidMaxPriceImpact {},
+
+    #[error("The stop price must be greater than zero and below the maximum prices of the order")]
+    InvalidStopPrice {},
+
+    #[error(
+        "The take profit price must be greater than zero and above the stop price of the order"
+    )]
+    InvalidTakeProfitPrice {},
+
+    #[error("A distribution must follow an accumulating order with escrowed delivery, selling a non zero amount for another asset")]
+    InvalidDistribution {},
+
+    #[error("The target assets of an order must be at least two distinct assets other than the initial asset, including the target asset, with non zero weights summing to one")]
+    InvalidTargets {},
+
+    #[error("An order with several target assets must spend a token without a tax on transfers, be auto routed and delivered immediately, without price limits, a minimum receive, hooks, staking, zapping, a distribution or value averaging")]
+    InvalidMultiTargetOrder {},
+
+    #[error("A pooled order must hold its deposit in the contract and escrow its purchases, without an expiry, a maximum amount of purchases, a stop or take profit price, tranches, a distribution or several target assets")]
+    InvalidPooledOrder {},
+
+    #[error("The deposit and purchases of a pooled order can only be moved by its depositors joining, claiming from or leaving it")]
+    PooledOrder {},
+
+    #[error("The order is not a pooled order")]
+    NotPooledOrder {},
+
+    #[error("The sender holds no shares of the pooled order")]
+    NoPoolShares {},
+
+    #[error("The tip escalation must grow by a non zero step over a non zero period, up to a maximum of at least the step")]
+    InvalidTipEscalation {},
+
+    #[error("The tip balance can only be replenished by a non zero amount less than dca_amount from a deposit held by the contract")]
+    InvalidTipReplenish {},
+
+    #[error("Bot is already registered")]
+    BotAlreadyRegistered {},
+
+    #[error("Only registered bots can perform DCA purchases")]
+    UnregisteredBot {},
+
+    #[error("The sender is not allowed to perform the DCA purchases of the order")]
+    BotNotAllowed {},
+
+    #[error(
+        "The bot bond must be greater than zero and cannot slash more than {max} basis points"
+    )]
+    InvalidBotBond { max: u16 },
+
+    #[error("A bond of at least {bond} must be sent to register as a bot")]
+    InsufficientBotBond { bond: String },
+
+    #[error("The bot has no bond")]
+    BotNotBonded {},
+
+    #[error("The bond of the bot is already unbonding")]
+    BotAlreadyUnbonding {},
+
+    #[error("The bond of the bot can only be withdrawn once its unbonding period has passed")]
+    BotBondLocked {},
+
+    #[error("Only the assigned bot {bot} can perform the DCA purchase until the fairness window has passed")]
+    NotAssignedBot { bot: String },
+
+    #[error("Hop asset {asset} is already whitelisted")]
+    HopAssetAlreadyWhitelisted { asset: AssetInfo },
+
+    #[error("Hop asset {asset} is not whitelisted")]
+    NonWhitelistedHopAsset { asset: AssetInfo },
+
+    #[error("Hop asset {asset} is part of the route of DCA order {id} of {user}")]
+    HopAssetInUse {
+        asset: AssetInfo,
+        user: String,
+        id: u64,
+    },
+
+    #[error("DCA schedule must be valid and used with an interval in seconds")]
+    InvalidSchedule {},
+
     #[error("DCA amount is greater than deposited amount")]
     DepositTooSmall {},
 
-    #[error("Initial asset deposited is not divisible by the DCA amount")]
+    #[error("Tip is not divisible by the amount of the whitelisted fee asset")]
     IndivisibleDeposit {},
 
     #[error("Attempt to perform tip deposit with {sent}, but only received {received}")]
@@ -69,6 +360,9 @@ pub enum ContractError {
     #[error("Tip asset {asset} is not whitelisted")]
     NonWhitelistedTipAsset { asset: AssetInfo },
 
+    #[error("Tip asset {asset} is listed more than once in the tip priority")]
+    DuplicateTipPriority { asset: AssetInfo },
+
     #[error("Attempt to withdraw asset {asset} that was not deposited")]
     TipAssetNotDeposited { asset: AssetInfo },
 
@@ -0,0 +1,158 @@
+use astroport::{asset::AssetInfo, router::SwapOperation};
+use cosmwasm_std::{Decimal, Deps, StdResult, Uint128};
+
+use crate::{
+    build_route::build_route, error::ContractError, get_swap_simulation::get_swap_simulation,
+    price_impact::reserve_value, state::Config,
+};
+
+/// ## Description
+/// Returns the direct route from `offer_asset` to `target_asset` and, if `max_hops` allows it,
+/// every route through one of the whitelisted tokens.
+fn candidate_routes(
+    config: &Config,
+    max_hops: u32,
+    offer_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+) -> Vec<Vec<SwapOperation>> {
+    let mut candidates = vec![build_route(offer_asset, &[], target_asset)];
+
+    if max_hops >= 2 {
+        candidates.extend(
+            config
+                .whitelisted_tokens
+                .iter()
+                .filter(|token| *token != offer_asset && *token != target_asset)
+                .map(|token| build_route(offer_asset, &[token.clone()], target_asset)),
+        );
+    }
+
+    candidates
+}
+
+/// ## Description
+/// Finds the route with the best return when swapping `offer_amount` of `offer_asset` to
+/// `target_asset`, by simulating the direct route and, if `max_hops` allows it, every route
+/// through one of the whitelisted tokens.
+///
+/// Routes that can not be simulated, such as routes without a pool for one of the hops, are
+/// skipped.
+///
+/// Returns [`ContractError::NoRouteAvailable`] if no route could be simulated, otherwise the
+/// [`Vec<SwapOperation>`] hops of the best route.
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `config` - The [`Config`] of the contract, containing the whitelisted tokens and router.
+///
+/// * `max_hops` - The maximum amount of hops that a route can have.
+///
+/// * `offer_asset` - The [`AssetInfo`] being spent in the swap.
+///
+/// * `target_asset` - The [`AssetInfo`] being purchased in the swap.
+///
+/// * `offer_amount` - The [`Uint128`] amount of `offer_asset` being swapped.
+pub fn find_best_route(
+    deps: &Deps,
+    config: &Config,
+    max_hops: u32,
+    offer_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+    offer_amount: Uint128,
+) -> Result<Vec<SwapOperation>, ContractError> {
+    candidate_routes(config, max_hops, offer_asset, target_asset)
+        .into_iter()
+        .filter_map(|hops| {
+            get_swap_simulation(deps, &config.router_addr, offer_amount, hops.clone())
+                .ok()
+                .map(|amount| (amount, hops))
+        })
+        .max_by_key(|(amount, _)| *amount)
+        .map(|(_, hops)| hops)
+        .ok_or(ContractError::NoRouteAvailable {})
+}
+
+/// ## Description
+/// Splits `offer_amount` of `offer_asset` equally across the fewest of the routes to
+/// `target_asset` that [`find_best_route`] compares, taken from the best return down, such that
+/// their swaps together lose at most `max_price_impact` of their value at the ratio of the reserves
+/// of their pairs. The routes swap through separate pairs, so the swaps of a split do not move the
+/// prices of one another.
+///
+/// Returns [`None`] if no split across at least two routes stays within `max_price_impact`,
+/// otherwise each route of the split along with the amount swapped through it.
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `config` - The [`Config`] of the contract, containing the whitelisted tokens and router.
+///
+/// * `max_hops` - The maximum amount of hops that a route can have.
+///
+/// * `offer_asset` - The [`AssetInfo`] being spent in the swap.
+///
+/// * `target_asset` - The [`AssetInfo`] being purchased in the swap.
+///
+/// * `offer_amount` - The [`Uint128`] amount of `offer_asset` being swapped.
+///
+/// * `max_price_impact` - The [`Decimal`] share of the value of the swaps that can be lost.
+pub fn find_split_routes(
+    deps: &Deps,
+    config: &Config,
+    max_hops: u32,
+    offer_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+    offer_amount: Uint128,
+    max_price_impact: Decimal,
+) -> StdResult<Option<Vec<(Vec<SwapOperation>, Uint128)>>> {
+    let mut routes: Vec<(Uint128, Vec<SwapOperation>)> =
+        candidate_routes(config, max_hops, offer_asset, target_asset)
+            .into_iter()
+            .filter_map(|hops| {
+                get_swap_simulation(deps, &config.router_addr, offer_amount, hops.clone())
+                    .ok()
+                    .map(|amount| (amount, hops))
+            })
+            .collect();
+    routes.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    for parts in 2..=routes.len() {
+        // the first route also swaps the remainder of the equal parts
+        let part = offer_amount / Uint128::from(parts as u64);
+        let split: Vec<_> = routes
+            .iter()
+            .take(parts)
+            .enumerate()
+            .map(|(i, (_, hops))| match i {
+                0 => (
+                    hops.clone(),
+                    offer_amount - part * Uint128::from(parts as u64 - 1),
+                ),
+                _ => (hops.clone(), part),
+            })
+            .collect();
+
+        let mut value = Uint128::zero();
+        let mut returned = Uint128::zero();
+        for (hops, amount) in &split {
+            let hop_value = match reserve_value(deps, config, *amount, hops)? {
+                Some(hop_value) => hop_value,
+                None => return Ok(None),
+            };
+            value = value.checked_add(hop_value)?;
+            returned = returned.checked_add(get_swap_simulation(
+                deps,
+                &config.router_addr,
+                *amount,
+                hops.clone(),
+            )?)?;
+        }
+
+        if !value.is_zero()
+            && Decimal::from_ratio(value.saturating_sub(returned), value) <= max_price_impact
+        {
+            return Ok(Some(split));
+        }
+    }
+
+    Ok(None)
+}
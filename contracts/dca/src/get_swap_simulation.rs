@@ -0,0 +1,32 @@
+use astroport::router::{
+    QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse, SwapOperation,
+};
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
+
+/// ## Description
+/// Simulates the swap of `offer_amount` through the `operations` hop route with the Astroport
+/// router, returning the amount of the target asset that would be received as a [`Uint128`].
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `router_addr` - The address of the Astroport router contract.
+///
+/// * `offer_amount` - The [`Uint128`] amount of the first asset in the route being swapped.
+///
+/// * `operations` - The [`Vec<SwapOperation>`] of the hop route to simulate.
+pub fn get_swap_simulation(
+    deps: &Deps,
+    router_addr: &Addr,
+    offer_amount: Uint128,
+    operations: Vec<SwapOperation>,
+) -> StdResult<Uint128> {
+    let simulation: SimulateSwapOperationsResponse = deps.querier.query_wasm_smart(
+        router_addr,
+        &RouterQueryMsg::SimulateSwapOperations {
+            offer_amount,
+            operations,
+        },
+    )?;
+
+    Ok(simulation.amount)
+}
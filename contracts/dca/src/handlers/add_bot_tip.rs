@@ -1,5 +1,5 @@
 use astroport::asset::{Asset, AssetInfo};
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response};
 
 use crate::{
     error::ContractError,
@@ -43,6 +43,9 @@ pub fn add_bot_tip(
     }
 
     for asset in assets {
+        // the tokens deposited into the contract are not covered by the allowance of the user
+        let deposited = user_config.deposited_tip(&asset.info);
+
         // update user tip in state
         let balance = user_config
             .tip_balance
@@ -73,7 +76,9 @@ pub fn add_bot_tip(
                     get_token_allowance(&deps.as_ref(), &env, &info.sender, contract_addr)?;
 
                 let invalid_allowance = match balance.as_ref() {
-                    Some(balance) => allowance != balance.amount.checked_add(asset.amount)?,
+                    Some(balance) => {
+                        allowance != (balance.amount - deposited).checked_add(asset.amount)?
+                    }
                     None => allowance != asset.amount,
                 };
 
@@ -104,11 +109,48 @@ pub fn add_bot_tip(
     Ok(Response::new().add_attributes(vec![attr("action", "add_bot_tip")]))
 }
 
+/// ## Description
+/// Adds the cw20 tokens sent to the contract through [`astroport_dca::dca::Cw20HookMsg::AddBotTip`]
+/// to the tip balance of a user, held by the contract until they are paid to bots or withdrawn.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `user` - The [`Addr`] of the user who sent the tokens.
+///
+/// * `asset` - The [`Asset`] of the cw20 tokens received by the contract.
+pub fn deposit_bot_tip(
+    deps: DepsMut,
+    user: &Addr,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !config.is_whitelisted_fee_asset(&asset.info) {
+        return Err(ContractError::NonWhitelistedTipAsset { asset: asset.info });
+    }
+
+    let mut user_config = USER_CONFIG
+        .may_load(deps.storage, user)?
+        .unwrap_or_default();
+
+    user_config.add_held_tip(&asset)?;
+
+    USER_CONFIG.save(deps.storage, user, &user_config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_bot_tip"),
+        attr("tip", asset.to_string()),
+    ]))
+}
+
 #[cfg(test)]
 mod tests {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::ExecuteMsg;
-    use cosmwasm_std::{attr, coin, testing::mock_info, Addr, Response, Uint128};
+    use astroport_dca::dca::{Cw20HookMsg, ExecuteMsg};
+    use cosmwasm_std::{attr, coin, testing::mock_info, to_binary, Addr, Response, Uint128};
     use cw_multi_test::Executor;
 
     use crate::{
@@ -116,8 +158,8 @@ mod tests {
         error::ContractError,
         state::{UserConfig, USER_CONFIG},
         tests::{
-            app_mock_instantiate, mock_app, mock_creator, mock_instantiate, read_map,
-            store_cw20_token_code, store_dca_module_code,
+            app_mock_instantiate, mock_app, mock_astroport_instantiate, mock_creator,
+            mock_instantiate, read_map, store_cw20_token_code, store_dca_module_code,
         },
     };
 
@@ -603,4 +645,56 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn does_deposit_bot_tip_token() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        let token_tip = |amount: u128| Asset {
+            amount: Uint128::new(amount),
+            info: AssetInfo::Token {
+                contract_addr: cw20_addr.clone(),
+            },
+        };
+
+        // deposit 30,000 tokens into the contract
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: dca_addr.to_string(),
+                amount: Uint128::new(30_000),
+                msg: to_binary(&Cw20HookMsg::AddBotTip {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the deposited tokens should not need to be covered by the allowance of further tips
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: dca_addr.to_string(),
+                amount: Uint128::new(10_000),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![token_tip(10_000)],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_config = read_map(&app, dca_addr, &mock_creator().sender, USER_CONFIG);
+        assert_eq!(user_config.tip_balance, vec![token_tip(40_000)]);
+        assert_eq!(user_config.deposited_tip_balance, vec![token_tip(30_000)]);
+    }
 }
@@ -0,0 +1,138 @@
+use cosmwasm_std::{attr, DepsMut, Env, Response};
+
+use crate::{
+    error::ContractError,
+    state::{CONFIG, PENDING_CONFIG},
+};
+
+/// ## Description
+/// Applies every pending configuration change whose timelock has passed, in the order they were
+/// scheduled. This can be called by anyone, as the changes have already been made by the owner
+/// of the contract.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+pub fn apply_pending_config(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let (ready, pending): (Vec<_>, Vec<_>) = PENDING_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|change| change.effective_at <= env.block.time.seconds());
+
+    if ready.is_empty() {
+        return Err(ContractError::NoPendingConfigChanges {});
+    }
+
+    let applied = ready.len();
+    let mut config = CONFIG.load(deps.storage)?;
+    for change in ready {
+        change.apply(&mut config);
+    }
+    config.validate_protocol_fee()?;
+
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_CONFIG.save(deps.storage, &pending)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "apply_pending_config"),
+        attr("applied", applied.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::ExecuteMsg;
+    use cosmwasm_std::Addr;
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::{CONFIG, PENDING_CONFIG},
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    fn update_config(protocol_fee_bps: Option<u16>, config_timelock: Option<u64>) -> ExecuteMsg {
+        ExecuteMsg::UpdateConfig {
+            max_hops: None,
+            whitelisted_tokens: None,
+            whitelisted_fee_assets: None,
+            max_spread: None,
+            staking_addr: None,
+            protocol_fee_bps,
+            fee_collector: Some("collector".to_string()),
+            fee_tiers: None,
+            referral_share: None,
+            factory_addr: None,
+            router_addr: None,
+            config_timelock,
+            paused: None,
+            max_oracle_deviation: None,
+            registered_bots_only: None,
+            bot_bond: None,
+            fairness_window: None,
+            allowed_assets: None,
+            allowed_assets_only: None,
+            min_liquidity: None,
+            fee_on_transfer_tokens: None,
+            min_deposit: None,
+            min_dca_amount: None,
+        }
+    }
+
+    #[test]
+    fn does_apply_pending_config() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        // setting a timelock is not a sensitive change, so it takes effect immediately
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &update_config(None, Some(100)),
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &update_config(Some(30), None),
+            &[],
+        )
+        .unwrap();
+
+        // the fee increase should wait for the timelock to pass
+        let config = read_item(&app, &dca_addr, CONFIG);
+        assert_eq!(config.protocol_fee_bps, 0);
+        assert_eq!(config.fee_collector, Some(Addr::unchecked("collector")));
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("anyone"),
+                dca_addr.clone(),
+                &ExecuteMsg::ApplyPendingConfig {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NoPendingConfigChanges {}
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            dca_addr.clone(),
+            &ExecuteMsg::ApplyPendingConfig {},
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(read_item(&app, &dca_addr, CONFIG).protocol_fee_bps, 30);
+        assert!(read_item(&app, &dca_addr, PENDING_CONFIG).is_empty());
+    }
+}
@@ -0,0 +1,475 @@
+use astroport::{
+    asset::AssetInfo,
+    staking::{
+        ConfigResponse as StakingConfigResponse, Cw20HookMsg as StakingCw20HookMsg,
+        QueryMsg as StakingQueryMsg,
+    },
+};
+use cosmwasm_std::{
+    attr, to_binary, Addr, DepsMut, Env, QuerierWrapper, Response, StdResult, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+use crate::{
+    error::ContractError,
+    state::{Config, PendingStake, CONFIG, PENDING_STAKE},
+};
+
+/// The id of the reply to entering the staking contract with the ASTRO of a DCA purchase
+pub const AUTO_STAKE_REPLY_ID: u64 = 3;
+
+/// ## Description
+/// Returns the address of the staking contract set in the contract configuration along with its
+/// configuration.
+///
+/// Returns a [`ContractError::StakingNotConfigured`] if no staking contract has been set.
+pub(crate) fn query_staking_config(
+    querier: &QuerierWrapper,
+    config: &Config,
+) -> Result<(Addr, StakingConfigResponse), ContractError> {
+    let staking_addr = config
+        .staking_addr
+        .clone()
+        .ok_or(ContractError::StakingNotConfigured {})?;
+
+    let staking_config = querier.query_wasm_smart(&staking_addr, &StakingQueryMsg::Config {})?;
+
+    Ok((staking_addr, staking_config))
+}
+
+/// ## Description
+/// Returns the balance of the cw20 token `contract_addr` held by `address`.
+pub(crate) fn query_balance(
+    querier: &QuerierWrapper,
+    contract_addr: &Addr,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    let balance: BalanceResponse = querier.query_wasm_smart(
+        contract_addr,
+        &Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )?;
+
+    Ok(balance.balance)
+}
+
+/// ## Description
+/// Validates that a DCA order purchasing `target_asset` can be auto staked, which requires the
+/// staking contract to be configured and `target_asset` to be the ASTRO token it stakes.
+///
+/// Returns a [`ContractError`] if the order cannot be auto staked.
+/// ## Arguments
+/// * `querier` - The [`QuerierWrapper`] used to query the staking contract.
+///
+/// * `config` - The [`Config`] of the contract.
+///
+/// * `target_asset` - The [`AssetInfo`] purchased by the DCA order.
+pub(crate) fn validate_auto_stake(
+    querier: &QuerierWrapper,
+    config: &Config,
+    target_asset: &AssetInfo,
+) -> Result<(), ContractError> {
+    let (_, staking_config) = query_staking_config(querier, config)?;
+
+    match target_asset {
+        AssetInfo::Token { contract_addr }
+            if contract_addr == &staking_config.deposit_token_addr =>
+        {
+            Ok(())
+        }
+        _ => Err(ContractError::InvalidAutoStake {}),
+    }
+}
+
+/// ## Description
+/// Creates the submessage staking `amount` of purchased ASTRO held by the contract, storing the
+/// stake so that the xASTRO received can be sent to `recipient` once the staking contract replies.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns the [`SubMsg`] entering the staking
+/// contract.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `recipient` - The [`Addr`] to send the xASTRO received to.
+///
+/// * `amount` - The [`Uint128`] amount of ASTRO to stake.
+pub(crate) fn auto_stake(
+    deps: DepsMut,
+    env: &Env,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let (staking_addr, staking_config) = query_staking_config(&deps.querier, &config)?;
+
+    // the xASTRO minted is the increase of the balance of the contract once the staking replies
+    let balance = query_balance(
+        &deps.querier,
+        &staking_config.share_token_addr,
+        &env.contract.address,
+    )?;
+    PENDING_STAKE.save(
+        deps.storage,
+        &PendingStake {
+            recipient,
+            share_token: staking_config.share_token_addr,
+            balance,
+        },
+    )?;
+
+    Ok(SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: staking_config.deposit_token_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: staking_addr.to_string(),
+                amount,
+                msg: to_binary(&StakingCw20HookMsg::Enter {})?,
+            })?,
+            funds: vec![],
+        },
+        AUTO_STAKE_REPLY_ID,
+    ))
+}
+
+/// ## Description
+/// Sends the xASTRO received from staking the ASTRO of a DCA purchase to the recipient of the
+/// purchase.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+pub fn auto_stake_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let stake = PENDING_STAKE.load(deps.storage)?;
+    PENDING_STAKE.remove(deps.storage);
+
+    let balance = query_balance(&deps.querier, &stake.share_token, &env.contract.address)?;
+    let amount = balance.checked_sub(stake.balance)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: stake.share_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: stake.recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "auto_stake_reply"),
+            attr("recipient", stake.recipient),
+            attr("xastro_amount", amount),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+        staking::ConfigResponse as StakingConfigResponse,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
+    use cw_multi_test::{App, AppResponse, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_user_orders, store_cw20_token_code,
+            store_staking_code,
+        },
+    };
+
+    fn create_order(
+        app: &mut App,
+        dca_addr: &Addr,
+        target_asset: AssetInfo,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset,
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: true,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .map_err(|e| e.downcast().unwrap())
+    }
+
+    /// Instantiates a staking contract for the cw20 token, returning its address and the address of
+    /// the share token it pays out
+    fn instantiate_staking(app: &mut App, astro_addr: &Addr) -> (Addr, Addr) {
+        let cw20_token_id = store_cw20_token_code(app);
+        let staking_id = store_staking_code(app);
+
+        let xastro_addr = app
+            .instantiate_contract(
+                cw20_token_id,
+                Addr::unchecked("admin"),
+                &cw20_base::msg::InstantiateMsg {
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: "admin".to_string(),
+                        amount: Uint128::new(1_000_000),
+                    }],
+                    marketing: None,
+                    mint: None,
+                    name: "staked cw20 token".to_string(),
+                    symbol: "xcwT".to_string(),
+                },
+                &[],
+                "mock xastro",
+                None,
+            )
+            .unwrap();
+
+        let staking_addr = app
+            .instantiate_contract(
+                staking_id,
+                Addr::unchecked("admin"),
+                &StakingConfigResponse {
+                    deposit_token_addr: astro_addr.clone(),
+                    share_token_addr: xastro_addr.clone(),
+                },
+                &[],
+                "mock staking",
+                None,
+            )
+            .unwrap();
+
+        // fund the staking contract with the share tokens it pays out
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            xastro_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Transfer {
+                recipient: staking_addr.to_string(),
+                amount: Uint128::new(1_000_000),
+            },
+            &[],
+        )
+        .unwrap();
+
+        (staking_addr, xastro_addr)
+    }
+
+    /// Configures the staking contract that the ASTRO purchased by auto staking orders is staked in
+    fn configure_staking(app: &mut App, dca_addr: &Addr, staking_addr: &Addr) {
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: Some(staking_addr.to_string()),
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_auto_stake_purchase() {
+        let (mut app, dca_addr, astro_addr) = mock_astroport_instantiate(None);
+        let astro = AssetInfo::Token {
+            contract_addr: astro_addr.clone(),
+        };
+
+        // cannot auto stake before the staking contract is configured
+        let res = create_order(&mut app, &dca_addr, astro.clone()).unwrap_err();
+        assert_eq!(res, ContractError::StakingNotConfigured {});
+
+        let (staking_addr, xastro_addr) = instantiate_staking(&mut app, &astro_addr);
+        configure_staking(&mut app, &dca_addr, &staking_addr);
+
+        // cannot auto stake an order that does not purchase ASTRO
+        let res = create_order(
+            &mut app,
+            &dca_addr,
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidAutoStake {});
+
+        create_order(&mut app, &dca_addr, astro.clone()).unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: astro,
+                    },
+                ],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let balance = |app: &App, token: &Addr, address: &Addr| {
+            app.wrap()
+                .query_wasm_smart::<BalanceResponse>(
+                    token,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap()
+                .balance
+        };
+
+        // the user should have received xASTRO for the purchased ASTRO
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert!(!orders[0].total_received.is_zero());
+        assert_eq!(
+            balance(&app, &xastro_addr, &mock_creator().sender),
+            orders[0].total_received
+        );
+        assert_eq!(
+            balance(&app, &astro_addr, &mock_creator().sender),
+            Uint128::new(500_000)
+        );
+        assert!(balance(&app, &xastro_addr, &dca_addr).is_zero());
+        assert!(balance(&app, &astro_addr, &dca_addr).is_zero());
+    }
+
+    #[test]
+    fn cannot_modify_auto_stake_target() {
+        let (mut app, dca_addr, astro_addr) = mock_astroport_instantiate(None);
+        let (staking_addr, _) = instantiate_staking(&mut app, &astro_addr);
+        configure_staking(&mut app, &dca_addr, &staking_addr);
+
+        create_order(
+            &mut app,
+            &dca_addr,
+            AssetInfo::Token {
+                contract_addr: astro_addr,
+            },
+        )
+        .unwrap();
+
+        // the purchases of the order would no longer be ASTRO that can be staked
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::ModifyDcaOrder {
+                    id: 1,
+                    new_initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    new_target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    new_interval: Duration::Time(500),
+                    new_schedule: None,
+                    new_dca_amount: Uint128::new(10_000),
+                    new_first_purchase: None,
+                    user: None,
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap();
+        assert_eq!(res, ContractError::InvalidAutoStake {});
+
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert!(orders[0].auto_stake);
+        assert!(matches!(orders[0].target_asset, AssetInfo::Token { .. }));
+    }
+}
@@ -0,0 +1,225 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::CONFIG};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Blacklists a malicious or deprecated `asset`, so that no DCA order can be created for it and
+/// the orders spending, purchasing or routing through it can no longer be purchased. The existing
+/// orders of the asset can be swept back to their owners by anyone with
+/// [`super::sweep_expired`]. The asset can be removed from the blacklist with
+/// [`super::unblacklist_asset`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to blacklist the asset.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to blacklist.
+pub fn blacklist_asset(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.is_blacklisted_asset(&asset) {
+        return Err(ContractError::AssetBlacklisted { asset });
+    }
+
+    config.blacklisted_assets.push(asset.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "blacklist_asset"),
+        attr("asset", asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item, read_user_orders},
+    };
+
+    #[test]
+    fn does_blacklist_asset() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        let create = ExecuteMsg::CreateDcaOrder {
+            initial_asset: Asset {
+                amount: Uint128::new(100_000),
+                info: uluna.clone(),
+            },
+            target_asset: ujpy.clone(),
+            interval: Duration::Time(500),
+            dca_amount: Uint128::new(10_000),
+            first_purchase: None,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            owner: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            max_purchases: None,
+            pull: false,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+            allow_low_liquidity: false,
+        };
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &create,
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let blacklist = ExecuteMsg::BlacklistAsset {
+            asset: ujpy.clone(),
+        };
+
+        // only the contract owner can blacklist an asset
+        let res = app
+            .execute_contract(
+                Addr::unchecked("someone"),
+                dca_addr.clone(),
+                &blacklist,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        app.execute_contract(mock_creator().sender, dca_addr.clone(), &blacklist, &[])
+            .unwrap();
+        assert_eq!(
+            read_item(&app, &dca_addr, CONFIG).blacklisted_assets,
+            vec![ujpy.clone()]
+        );
+
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &blacklist, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetBlacklisted {
+                asset: ujpy.clone()
+            }
+        );
+
+        // no order can be created for the blacklisted asset
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &create,
+                &coins(100_000, "uluna"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetBlacklisted {
+                asset: ujpy.clone()
+            }
+        );
+
+        // the existing order of the blacklisted asset cannot be purchased
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: uluna,
+                        ask_asset_info: ujpy.clone(),
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetBlacklisted { asset: ujpy }
+        );
+
+        // but anyone can sweep it, refunding the owner of the order
+        let balance = |app: &App| {
+            app.wrap()
+                .query_balance(mock_creator().sender, "uluna")
+                .unwrap()
+                .amount
+        };
+        let before = balance(&app);
+
+        app.execute_contract(
+            Addr::unchecked("someone"),
+            dca_addr.clone(),
+            &ExecuteMsg::SweepExpired {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(balance(&app), before + Uint128::new(100_000));
+        assert!(read_user_orders(&app, dca_addr, &mock_creator().sender).is_empty());
+    }
+}
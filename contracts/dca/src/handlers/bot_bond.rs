@@ -0,0 +1,285 @@
+use cosmwasm_std::{
+    attr, Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
+
+use crate::{
+    error::ContractError,
+    state::{Config, BOTS, CONFIG, MAX_BPS},
+};
+
+/// ## Description
+/// Starts the unbonding period of the bond of the sender as a registered bot, after which the
+/// bond can be withdrawn through [`withdraw_bot_bond`]. The bond can still be slashed until then.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the bot who wants to unbond.
+pub fn unbond_bot(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut stats = BOTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::UnregisteredBot {})?;
+
+    if stats.bond.is_none() {
+        return Err(ContractError::BotNotBonded {});
+    }
+
+    if stats.unbonding_at.is_some() {
+        return Err(ContractError::BotAlreadyUnbonding {});
+    }
+
+    let unbonding_period = CONFIG
+        .load(deps.storage)?
+        .bot_bond
+        .map_or(0, |bot_bond| bot_bond.unbonding_period);
+    let unbonding_at = env.block.time.plus_seconds(unbonding_period).seconds();
+
+    stats.unbonding_at = Some(unbonding_at);
+    BOTS.save(deps.storage, &info.sender, &stats)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "unbond_bot"),
+        attr("bot", info.sender),
+        attr("unbonding_at", unbonding_at.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Sends the bond of the sender back once its unbonding period has passed, removing the sender
+/// from the registered bots along with the stats of its purchases.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the bot who wants to withdraw its bond.
+pub fn withdraw_bot_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let stats = BOTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::UnregisteredBot {})?;
+
+    let bond = stats.bond.ok_or(ContractError::BotNotBonded {})?;
+
+    match stats.unbonding_at {
+        Some(unbonding_at) if unbonding_at <= env.block.time.seconds() => {}
+        _ => return Err(ContractError::BotBondLocked {}),
+    }
+
+    BOTS.remove(deps.storage, &info.sender);
+
+    let mut response = Response::new();
+    if !bond.amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![bond.clone()],
+        });
+    }
+
+    Ok(response.add_attributes(vec![
+        attr("action", "withdraw_bot_bond"),
+        attr("bot", info.sender),
+        attr("bond", bond.amount),
+    ]))
+}
+
+/// ## Description
+/// Slashes the share of the bond of `bot` set by the contract owner to `user`, for a DCA purchase
+/// of the order `id` that the bot attempted through an invalid or worse route. The attempted
+/// purchase is not performed, leaving the order due for another bot.
+///
+/// Returns [`None`] if the bot has no bond to slash, otherwise returns a [`Response`] sending the
+/// slashed bond to the user.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `config` - The [`Config`] of the contract, containing the share of the bond to slash.
+///
+/// * `bot` - The [`Addr`] of the bot that attempted the purchase.
+///
+/// * `user` - The [`Addr`] of the user who owns the order.
+///
+/// * `id` - The [`u64`] id of the order.
+///
+/// * `reason` - The reason the bot is slashed for, added to the attributes of the response.
+pub fn slash_bot(
+    storage: &mut dyn Storage,
+    config: &Config,
+    bot: &Addr,
+    user: &Addr,
+    id: u64,
+    reason: &str,
+) -> StdResult<Option<Response>> {
+    let slash_bps = match &config.bot_bond {
+        Some(bot_bond) => bot_bond.slash_bps,
+        None => return Ok(None),
+    };
+
+    let mut stats = match BOTS.may_load(storage, bot)? {
+        Some(stats) => stats,
+        None => return Ok(None),
+    };
+
+    let bond = match &mut stats.bond {
+        Some(bond) => bond,
+        None => return Ok(None),
+    };
+
+    let slashed = bond.amount.multiply_ratio(slash_bps, MAX_BPS);
+    if slashed.is_zero() {
+        return Ok(None);
+    }
+
+    bond.amount -= slashed;
+    let denom = bond.denom.clone();
+    stats.slashes += 1;
+    BOTS.save(storage, bot, &stats)?;
+
+    Ok(Some(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: user.to_string(),
+                amount: vec![Coin {
+                    denom,
+                    amount: slashed,
+                }],
+            })
+            .add_attributes(vec![
+                attr("action", "slash_bot"),
+                attr("bot", bot),
+                attr("user", user),
+                attr("id", id.to_string()),
+                attr("reason", reason),
+                attr("slashed", slashed),
+            ]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::{BotBond, BotInfo, ExecuteMsg, QueryMsg};
+    use cosmwasm_std::{coin, coins, Addr, Coin};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator},
+    };
+
+    #[test]
+    fn does_bond_and_withdraw_bot_bond() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: Some(BotBond {
+                    bond: coin(50_000, "uluna"),
+                    slash_bps: 1_000,
+                    unbonding_period: 1_000,
+                }),
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let bot = Addr::unchecked("bot_user");
+        app.send_tokens(mock_creator().sender, bot.clone(), &coins(100_000, "uluna"))
+            .unwrap();
+
+        let execute = |app: &mut App, msg: ExecuteMsg, funds: &[Coin]| {
+            app.execute_contract(bot.clone(), dca_addr.clone(), &msg, funds)
+        };
+
+        // the bond must be sent along to register
+        let res = execute(
+            &mut app,
+            ExecuteMsg::RegisterBot {},
+            &coins(10_000, "uluna"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InsufficientBotBond {
+                bond: "50000uluna".to_string()
+            }
+        );
+
+        execute(
+            &mut app,
+            ExecuteMsg::RegisterBot {},
+            &coins(60_000, "uluna"),
+        )
+        .unwrap();
+
+        let query_bots = |app: &App| -> Vec<BotInfo> {
+            app.wrap()
+                .query_wasm_smart(
+                    dca_addr.clone(),
+                    &QueryMsg::Bots {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(query_bots(&app)[0].stats.bond, Some(coin(60_000, "uluna")));
+
+        // the bond is locked until the unbonding period has passed
+        let res = execute(&mut app, ExecuteMsg::WithdrawBotBond {}, &[]).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::BotBondLocked {}
+        );
+
+        execute(&mut app, ExecuteMsg::UnbondBot {}, &[]).unwrap();
+
+        let res = execute(&mut app, ExecuteMsg::WithdrawBotBond {}, &[]).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::BotBondLocked {}
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1_000));
+        execute(&mut app, ExecuteMsg::WithdrawBotBond {}, &[]).unwrap();
+
+        assert_eq!(
+            app.wrap().query_balance(&bot, "uluna").unwrap(),
+            coin(100_000, "uluna")
+        );
+        assert!(query_bots(&app).is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use astroport_dca::dca::CallbackMsg;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+use crate::error::ContractError;
+
+use super::perform_batched_dca_purchase;
+
+/// ## Description
+/// Executes a [`CallbackMsg`] that the contract sent to itself.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns the [`Response`] of the callback.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] which must be from the contract itself.
+///
+/// * `msg` - The [`CallbackMsg`] to execute.
+pub fn callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        CallbackMsg::PerformDcaPurchase { bot, purchase } => perform_batched_dca_purchase(
+            deps,
+            env,
+            MessageInfo {
+                sender: bot,
+                funds: vec![],
+            },
+            purchase,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::{CallbackMsg, ExecuteMsg, PurchaseRequest};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::execute, error::ContractError, tests::mock_creator};
+
+    #[test]
+    fn does_require_contract_sender() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::Callback(CallbackMsg::PerformDcaPurchase {
+                bot: Addr::unchecked("bot_user"),
+                purchase: PurchaseRequest {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+            }),
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::Unauthorized {});
+    }
+}
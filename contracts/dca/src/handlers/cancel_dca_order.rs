@@ -1,13 +1,20 @@
-use astroport::asset::AssetInfo;
-use astroport_dca::dca::DcaInfo;
-use cosmwasm_std::{attr, BankMsg, Coin, DepsMut, MessageInfo, Response};
+use astroport_dca::dca::OrderEvent;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Order, Response};
 
-use crate::{error::ContractError, state::USER_DCA};
+use crate::{
+    error::ContractError, refund_order::refund_order, state::user_dca, stats::record_order_removed,
+};
+
+use super::{
+    notifier::notification_msg, set_operator::get_order_owner, withdraw::withdraw_all_tips,
+};
 
 /// ## Description
 /// Cancels a users DCA purchase so that it will no longer be fulfilled.
 ///
-/// Returns the `initial_asset` back to the user if it was a native token.
+/// Returns the `initial_asset` back to the user if it was a native token or a cw20 token held by
+/// the contract, along with the whole bot tip of the user if `withdraw_tips` is set and it was
+/// the last order of the user.
 ///
 /// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
 /// attributes if the operation was successful.
@@ -17,66 +24,77 @@ use crate::{error::ContractError, state::USER_DCA};
 /// * `info` - A [`MessageInfo`] from the sender who wants to cancel their order.
 ///
 /// * `id` The [`u64`] ID which the user wants to cancel the DCA order for.
+///
+/// * `user` - The address of the user who owns the order, if the sender is an operator cancelling
+/// it on their behalf.
+///
+/// * `withdraw_tips` - Whether to withdraw the bot tip of the user if no orders remain after the
+/// cancellation.
 pub fn cancel_dca_order(
     deps: DepsMut,
     info: MessageInfo,
     id: u64,
+    user: Option<String>,
+    withdraw_tips: bool,
 ) -> Result<Response, ContractError> {
-    let mut funds = Vec::new();
-
-    // remove order from user dca's, and add any native token funds for `initial_asset` into the `funds`.
-    USER_DCA.update(
-        deps.storage,
-        &info.sender,
-        |orders| -> Result<Vec<DcaInfo>, ContractError> {
-            let mut orders = orders.ok_or(ContractError::NonexistentDca {})?;
-
-            let order_position = orders
-                .iter()
-                .position(|order| order.id == id)
-                .ok_or(ContractError::NonexistentDca {})?;
-
-            let removed_order = &orders[order_position];
-            if let AssetInfo::NativeToken { denom } = &removed_order.initial_asset.info {
-                funds.push(BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: vec![Coin {
-                        amount: removed_order.initial_asset.amount,
-                        denom: denom.clone(),
-                    }],
-                })
-            }
+    let (owner, holder) = get_order_owner(deps.as_ref(), &info.sender, user, id)?;
 
-            orders.remove(order_position);
+    // remove order from user dca's, and refund any funds for `initial_asset` held by the contract
+    let removed_order = user_dca()
+        .may_load(deps.storage, (&owner, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
 
-            Ok(orders)
-        },
-    )?;
+    // the deposit of a pooled order is refunded to each depositor as they leave it
+    if removed_order.pooled {
+        return Err(ContractError::PooledOrder {});
+    }
+
+    let mut funds = refund_order(&removed_order, removed_order.initial_asset.amount, &holder)?;
 
-    Ok(Response::new().add_messages(funds).add_attributes(vec![
-        attr("action", "cancel_dca_order"),
-        attr("id", id.to_string()),
-    ]))
+    user_dca().remove(deps.storage, (&owner, id))?;
+    record_order_removed(deps.storage)?;
+    let notification = notification_msg(deps.storage, &owner, id, OrderEvent::Cancelled {})?;
+
+    // refund the bot tip to the user once there is no order left to tip bots for
+    if withdraw_tips
+        && user_dca()
+            .prefix(&owner)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    {
+        funds.extend(withdraw_all_tips(deps.storage, &owner)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(funds)
+        .add_submessages(notification)
+        .add_attributes(vec![
+            attr("action", "cancel_dca_order"),
+            attr("id", id.to_string()),
+        ]))
 }
 
 #[cfg(test)]
 mod tests {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::ExecuteMsg;
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
     use cosmwasm_std::{
         attr, coins,
         testing::{mock_dependencies, mock_env, mock_info},
-        Addr, BankMsg, DepsMut, MessageInfo, Response, Uint128,
+        Addr, BankMsg, DepsMut, MessageInfo, Response, SubMsg, Uint128,
     };
     use cw_multi_test::Executor;
 
     use crate::{
         contract::execute,
         error::ContractError,
-        state::USER_DCA,
+        state::{get_user_orders, USER_CONFIG},
         tests::{
-            app_mock_instantiate, mock_app, mock_creator, read_map, store_cw20_token_code,
-            store_dca_module_code,
+            app_mock_instantiate, mock_app, mock_creator, mock_instantiate, read_user_orders,
+            store_cw20_token_code, store_dca_module_code,
         },
     };
 
@@ -100,8 +118,42 @@ mod tests {
                 initial_asset: asset,
                 target_asset,
                 first_purchase,
-                interval: 60,
+                interval: Duration::Time(60),
                 dca_amount: Uint128::new(500_000),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -131,7 +183,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             mock_creator(),
-            ExecuteMsg::CancelDcaOrder { id: 1 },
+            ExecuteMsg::CancelDcaOrder {
+                id: 1,
+                user: None,
+                withdraw_tips: false,
+            },
         )
         .unwrap();
 
@@ -146,9 +202,7 @@ mod tests {
         );
 
         // check it was removed from storage
-        let orders = USER_DCA
-            .load(&deps.storage, &mock_creator().sender)
-            .unwrap();
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
         assert!(orders.is_empty())
     }
 
@@ -217,9 +271,43 @@ mod tests {
                 target_asset: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
-                interval: 500,
+                interval: Duration::Time(500),
                 dca_amount: Uint128::new(500_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
             &[],
         )
@@ -229,13 +317,17 @@ mod tests {
         app.execute_contract(
             mock_creator().sender,
             dca_addr.clone(),
-            &ExecuteMsg::CancelDcaOrder { id: 1 },
+            &ExecuteMsg::CancelDcaOrder {
+                id: 1,
+                user: None,
+                withdraw_tips: false,
+            },
             &[],
         )
         .unwrap();
 
         // check it was removed from storage
-        let orders = read_map(&app, dca_addr, &mock_creator().sender, USER_DCA);
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
         assert!(orders.is_empty());
     }
 
@@ -248,7 +340,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             mock_creator(),
-            ExecuteMsg::CancelDcaOrder { id: 2 },
+            ExecuteMsg::CancelDcaOrder {
+                id: 2,
+                user: None,
+                withdraw_tips: false,
+            },
         )
         .unwrap_err();
         assert_eq!(res, ContractError::NonexistentDca {});
@@ -270,9 +366,94 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             mock_creator(),
-            ExecuteMsg::CancelDcaOrder { id: 2 },
+            ExecuteMsg::CancelDcaOrder {
+                id: 2,
+                user: None,
+                withdraw_tips: false,
+            },
         )
         .unwrap_err();
         assert_eq!(res, ContractError::NonexistentDca {});
     }
+
+    #[test]
+    fn does_withdraw_tips_on_last_cancel() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![],
+        );
+
+        for _ in 0..2 {
+            create_order(
+                deps.as_mut(),
+                mock_info("creator", &coins(1_000_000, "uluna")),
+                AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                None,
+            );
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &coins(30_000, "uluna")),
+            ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+            },
+        )
+        .unwrap();
+
+        let cancel = |id: u64| ExecuteMsg::CancelDcaOrder {
+            id,
+            user: None,
+            withdraw_tips: true,
+        };
+
+        // the tip is kept while another order remains
+        let res = execute(deps.as_mut(), env.clone(), mock_creator(), cancel(1)).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: mock_creator().sender.into_string(),
+                amount: coins(1_000_000, "uluna")
+            })]
+        );
+
+        // cancelling the last order also withdraws the tip
+        let res = execute(deps.as_mut(), env, mock_creator(), cancel(2)).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: mock_creator().sender.into_string(),
+                    amount: coins(1_000_000, "uluna")
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: mock_creator().sender.into_string(),
+                    amount: coins(30_000, "uluna")
+                })
+            ]
+        );
+
+        let user_config = USER_CONFIG
+            .load(&deps.storage, &mock_creator().sender)
+            .unwrap();
+        assert!(user_config.tip_balance.is_empty());
+    }
 }
@@ -0,0 +1,299 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::OrderEvent;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Order, Response, StdResult};
+
+use crate::{
+    error::ContractError, refund_order::refund_order, state::user_dca, stats::record_order_removed,
+};
+
+use super::{
+    notifier::notification_msg, set_operator::get_order_owner, withdraw::withdraw_all_tips,
+};
+
+/// ## Description
+/// Cancels multiple DCA orders of a user at once, as in
+/// [`cancel_dca_order`](super::cancel_dca_order).
+///
+/// Returns the `initial_asset` of each order back to the user if it was a native token or a cw20
+/// token held by the contract, along with the whole bot tip of the user if `withdraw_tips` is set
+/// and no orders of the user remain.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to cancel their orders.
+///
+/// * `ids` - The [`Option<Vec<u64>>`] IDs of the DCA orders which the user wants to cancel, or
+/// every order of the user if not specified.
+///
+/// * `user` - The address of the user who owns the orders, if the sender is an operator
+/// cancelling them on their behalf.
+///
+/// * `withdraw_tips` - Whether to withdraw the bot tip of the user if no orders remain after the
+/// cancellation.
+pub fn cancel_dca_orders(
+    deps: DepsMut,
+    info: MessageInfo,
+    ids: Option<Vec<u64>>,
+    user: Option<String>,
+    withdraw_tips: bool,
+) -> Result<Response, ContractError> {
+    let owner = match &user {
+        Some(user) => addr_validate_to_lower(deps.api, user)?,
+        None => info.sender.clone(),
+    };
+
+    let ids = match ids {
+        Some(ids) => ids,
+        None => user_dca()
+            .prefix(&owner)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    if ids.is_empty() {
+        return Err(ContractError::NoOrdersProvided {});
+    }
+
+    // remove each order from user dca's, and refund any funds for `initial_asset` held by the
+    // contract
+    let mut funds = Vec::new();
+    let mut notifications = Vec::new();
+    for id in &ids {
+        let (_, holder) = get_order_owner(deps.as_ref(), &info.sender, user.clone(), *id)?;
+
+        let removed_order = user_dca()
+            .may_load(deps.storage, (&owner, *id))?
+            .ok_or(ContractError::NonexistentDca {})?;
+
+        if removed_order.pooled {
+            return Err(ContractError::PooledOrder {});
+        }
+
+        funds.extend(refund_order(
+            &removed_order,
+            removed_order.initial_asset.amount,
+            &holder,
+        )?);
+
+        user_dca().remove(deps.storage, (&owner, *id))?;
+        record_order_removed(deps.storage)?;
+        notifications.extend(notification_msg(
+            deps.storage,
+            &owner,
+            *id,
+            OrderEvent::Cancelled {},
+        )?);
+    }
+
+    // refund the bot tip to the user once there is no order left to tip bots for
+    if withdraw_tips
+        && user_dca()
+            .prefix(&owner)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    {
+        funds.extend(withdraw_all_tips(deps.storage, &owner)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(funds)
+        .add_submessages(notifications)
+        .add_attributes(vec![
+            attr("action", "cancel_dca_orders"),
+            attr(
+                "ids",
+                ids.iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        attr, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        BankMsg, DepsMut, Response, Uint128,
+    };
+
+    use crate::{
+        contract::execute, error::ContractError, state::get_user_orders, tests::mock_creator,
+    };
+
+    fn create_order(deps: DepsMut, denom: &str) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("creator", &coins(1_000_000, denom)),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(1_000_000),
+                    info: AssetInfo::NativeToken {
+                        denom: denom.to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                first_purchase: None,
+                interval: Duration::Time(60),
+                dca_amount: Uint128::new(500_000),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_cancel_given_orders() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut(), "uluna");
+        create_order(deps.as_mut(), "ujpy");
+        create_order(deps.as_mut(), "uluna");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::CancelDcaOrders {
+                ids: Some(vec![1, 3]),
+                user: None,
+                withdraw_tips: false,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attributes(vec![
+                    attr("action", "cancel_dca_orders"),
+                    attr("ids", "1,3")
+                ])
+                .add_messages(vec![
+                    BankMsg::Send {
+                        to_address: mock_creator().sender.into_string(),
+                        amount: coins(1_000_000, "uluna")
+                    },
+                    BankMsg::Send {
+                        to_address: mock_creator().sender.into_string(),
+                        amount: coins(1_000_000, "uluna")
+                    }
+                ])
+        );
+
+        // only the order that was not cancelled should remain
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert_eq!(orders.iter().map(|o| o.id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn does_cancel_all_orders() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut(), "uluna");
+        create_order(deps.as_mut(), "ujpy");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::CancelDcaOrders {
+                ids: None,
+                user: None,
+                withdraw_tips: false,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(res.attributes[1], attr("ids", "1,2"));
+        assert_eq!(res.messages.len(), 2);
+
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn does_error_on_nonexistent_order() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut(), "uluna");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::CancelDcaOrders {
+                ids: Some(vec![1, 2]),
+                user: None,
+                withdraw_tips: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::NonexistentDca {});
+    }
+
+    #[test]
+    fn cannot_cancel_no_orders() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::CancelDcaOrders {
+                ids: None,
+                user: None,
+                withdraw_tips: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::NoOrdersProvided {});
+    }
+}
@@ -0,0 +1,243 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    attr, to_binary, BankMsg, Coin, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    state::{POOLS, PURCHASED_BALANCE},
+};
+
+use super::member_group::get_order_owner_or_member;
+
+/// ## Description
+/// Sends the `target_asset` purchased for a DCA order with escrowed delivery, or of the failed
+/// ICS-20 transfers of an order with IBC delivery, that is held by the contract to the owner of
+/// the order.
+///
+/// The purchased balance can still be claimed after the order has been cancelled or fulfilled.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to claim their purchased assets.
+///
+/// * `id` - The [`u64`] ID of the order to claim the purchased assets of.
+///
+/// * `user` - The address of the user the order is stored under, if the sender is the holder of
+/// the NFT of the order, an operator or a member of the group of the owner claiming on behalf of
+/// the owner.
+pub fn claim_purchased(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    user: Option<String>,
+) -> Result<Response, ContractError> {
+    let (user, owner) = get_order_owner_or_member(deps.as_ref(), &info.sender, user, id)?;
+
+    // the purchases of a pooled order are claimed by each depositor for their shares
+    if POOLS.has(deps.storage, (&user, id)) {
+        return Err(ContractError::PooledOrder {});
+    }
+
+    let balance = PURCHASED_BALANCE
+        .may_load(deps.storage, (&user, id))?
+        .filter(|balance| !balance.amount.is_zero())
+        .ok_or(ContractError::NothingToClaim {})?;
+
+    PURCHASED_BALANCE.remove(deps.storage, (&user, id));
+
+    let claim_msg: CosmosMsg = match &balance.info {
+        AssetInfo::NativeToken { denom } => BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: vec![Coin {
+                amount: balance.amount,
+                denom: denom.clone(),
+            }],
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: owner.to_string(),
+                amount: balance.amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(Response::new().add_message(claim_msg).add_attributes(vec![
+        attr("action", "claim_purchased"),
+        attr("id", id.to_string()),
+        attr("amount", balance.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coin, coins, Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        state::PURCHASED_BALANCE,
+        tests::{mock_astroport_instantiate, mock_creator, read_map, read_user_orders},
+    };
+
+    #[test]
+    fn does_escrow_and_claim_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the purchased asset should be held by the contract
+        let query_balance =
+            |app: &App, addr: &Addr| app.wrap().query_balance(addr, "ukrw").unwrap();
+        assert_eq!(query_balance(&app, &mock_creator().sender), coin(0, "ukrw"));
+        assert_eq!(query_balance(&app, &dca_addr), coin(9_558, "ukrw"));
+
+        let purchased = read_map(
+            &app,
+            dca_addr.clone(),
+            (&mock_creator().sender, 1),
+            PURCHASED_BALANCE,
+        );
+        assert_eq!(
+            purchased,
+            Asset {
+                amount: Uint128::new(9_558),
+                info: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+            }
+        );
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].total_received, Uint128::new(9_558));
+
+        // claiming should send the purchased asset to the user
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimPurchased { id: 1, user: None },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            query_balance(&app, &mock_creator().sender),
+            coin(9_558, "ukrw")
+        );
+        assert_eq!(query_balance(&app, &dca_addr), coin(0, "ukrw"));
+
+        // there should be nothing left to claim
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr,
+                &ExecuteMsg::ClaimPurchased { id: 1, user: None },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+    }
+}
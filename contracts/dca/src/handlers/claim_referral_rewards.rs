@@ -0,0 +1,235 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    attr, to_binary, BankMsg, Coin, CosmosMsg, DepsMut, MessageInfo, Order, Response, StdResult,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{error::ContractError, state::REFERRAL_REWARDS};
+
+/// ## Description
+/// Sends every share of the protocol fees accrued to the sender as the referrer of DCA orders,
+/// with one message for each asset the fees were paid in.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the referrer who wants to claim their rewards.
+pub fn claim_referral_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let rewards = REFERRAL_REWARDS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut claimed = vec![];
+    for (key, reward) in rewards {
+        REFERRAL_REWARDS.remove(deps.storage, (&info.sender, key));
+
+        if reward.amount.is_zero() {
+            continue;
+        }
+
+        messages.push(match &reward.info {
+            AssetInfo::NativeToken { denom } => BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin {
+                    amount: reward.amount,
+                    denom: denom.clone(),
+                }],
+            }
+            .into(),
+            AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount: reward.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        });
+        claimed.push(reward.to_string());
+    }
+
+    if messages.is_empty() {
+        return Err(ContractError::NoReferralRewards {});
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "claim_referral_rewards"),
+        attr("referrer", info.sender),
+        attr("rewards", claimed.join(",")),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator},
+    };
+
+    #[test]
+    fn does_accrue_and_claim_referral_rewards() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: Some(100),
+                fee_collector: Some("collector".to_string()),
+                fee_tiers: None,
+                referral_share: Some(Decimal::percent(25)),
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: Some("referrer".to_string()),
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    ask_asset_info: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the referrer's share of the fee of 100 should be held by the contract
+        let query_balance =
+            |app: &App, addr: &str| app.wrap().query_balance(addr, "uluna").unwrap();
+        assert_eq!(query_balance(&app, "collector"), coin(75, "uluna"));
+        assert_eq!(
+            query_balance(&app, dca_addr.as_str()),
+            coin(90_025, "uluna")
+        );
+
+        app.execute_contract(
+            Addr::unchecked("referrer"),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimReferralRewards {},
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(query_balance(&app, "referrer"), coin(25, "uluna"));
+        assert_eq!(
+            query_balance(&app, dca_addr.as_str()),
+            coin(90_000, "uluna")
+        );
+
+        // there should be nothing left to claim
+        let res = app
+            .execute_contract(
+                Addr::unchecked("referrer"),
+                dca_addr,
+                &ExecuteMsg::ClaimReferralRewards {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NoReferralRewards {}
+        );
+    }
+}
@@ -1,21 +1,119 @@
-use astroport::asset::{Asset, AssetInfo};
-use astroport_dca::dca::DcaInfo;
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport_dca::dca::{
+    CreateDcaOrderParams, CreateDcaOrderResponse, DcaInfo, Delivery, Distribution, Duration,
+    FailurePolicy, OrderDirection, PurchaseHook, Schedule, Strategy, TargetWeight, TipEscalation,
+    TipReplenish, Zap,
+};
 use cosmwasm_std::{
-    attr, DepsMut, Env, MessageInfo, OverflowError, OverflowOperation, Response, StdError, Uint128,
+    attr, to_binary, Addr, Decimal, Deps, DepsMut, Env, MessageInfo, OverflowError,
+    OverflowOperation, Response, StdError, StdResult, Storage, Uint128,
 };
+use cw20::Expiration;
 
 use crate::{
+    build_route::{build_route, validate_route},
     error::ContractError,
+    find_best_route::find_best_route,
     get_token_allowance::get_token_allowance,
-    state::{USER_CONFIG, USER_DCA},
+    jitter::jitter_offset,
+    state::{
+        get_reserved_allowance, user_dca, Pool, PoolShare, CONFIG, MAX_SWAP_TIP_BPS, POOLS,
+        POOL_SHARES, USER_CONFIG,
+    },
+    stats::record_order_created,
+    token_decimals::{query_decimals, scale_by_decimals},
+    validate_liquidity::validate_liquidity,
+    validate_token::validate_token,
 };
 
+use super::{auto_stake::validate_auto_stake, nft::mint_order_nft, zap::validate_zap};
+
 pub struct CreateDcaOrder {
     pub initial_asset: Asset,
     pub target_asset: AssetInfo,
-    pub interval: u64,
+    pub interval: Duration,
+    pub schedule: Option<Schedule>,
+    pub max_delay: Option<u64>,
+    pub jitter: Option<u64>,
     pub dca_amount: Uint128,
+    pub strategy: Strategy,
     pub first_purchase: Option<u64>,
+    pub expires_at: Option<Expiration>,
+    pub max_purchases: Option<u64>,
+    pub max_failures: Option<u32>,
+    pub failure_policy: FailurePolicy,
+    pub swap_tip_bps: Option<u16>,
+    pub tip_escalation: Option<TipEscalation>,
+    pub tip_replenish: Option<TipReplenish>,
+    pub allowed_bots: Vec<String>,
+    pub allow_low_liquidity: bool,
+    pub max_price: Option<Decimal>,
+    pub only_below_price: Option<Decimal>,
+    pub max_price_impact: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+    pub take_profit_price: Option<Decimal>,
+    pub direction: OrderDirection,
+    pub distribution: Option<Distribution>,
+    pub targets: Vec<TargetWeight>,
+    pub pooled: bool,
+    pub max_spread: Option<Decimal>,
+    pub min_receive_per_purchase: Option<Uint128>,
+    pub route: Option<Vec<AssetInfo>>,
+    pub auto_route: bool,
+    pub delivery: Delivery,
+    pub recipient: Option<String>,
+    pub purchase_hooks: Vec<PurchaseHook>,
+    pub auto_stake: bool,
+    pub zap: Option<Zap>,
+    pub referrer: Option<String>,
+    pub owner: Option<String>,
+    pub pull: bool,
+}
+
+impl From<CreateDcaOrderParams> for CreateDcaOrder {
+    fn from(params: CreateDcaOrderParams) -> Self {
+        CreateDcaOrder {
+            initial_asset: params.initial_asset,
+            target_asset: params.target_asset,
+            interval: params.interval,
+            schedule: params.schedule,
+            max_delay: params.max_delay,
+            jitter: params.jitter,
+            dca_amount: params.dca_amount,
+            strategy: params.strategy,
+            first_purchase: params.first_purchase,
+            expires_at: params.expires_at,
+            max_purchases: params.max_purchases,
+            max_failures: params.max_failures,
+            failure_policy: params.failure_policy,
+            swap_tip_bps: params.swap_tip_bps,
+            tip_escalation: params.tip_escalation,
+            tip_replenish: params.tip_replenish,
+            allowed_bots: params.allowed_bots,
+            allow_low_liquidity: params.allow_low_liquidity,
+            max_price: params.max_price,
+            only_below_price: params.only_below_price,
+            max_price_impact: params.max_price_impact,
+            stop_price: params.stop_price,
+            take_profit_price: params.take_profit_price,
+            direction: params.direction,
+            distribution: params.distribution,
+            targets: params.targets,
+            pooled: params.pooled,
+            max_spread: params.max_spread,
+            min_receive_per_purchase: params.min_receive_per_purchase,
+            route: params.route,
+            auto_route: params.auto_route,
+            delivery: params.delivery,
+            recipient: params.recipient,
+            purchase_hooks: params.purchase_hooks,
+            auto_stake: params.auto_stake,
+            zap: params.zap,
+            referrer: params.referrer,
+            owner: params.owner,
+            pull: params.pull,
+        }
+    }
 }
 
 /// ## Description
@@ -38,34 +136,205 @@ pub struct CreateDcaOrder {
 ///
 /// * `target_asset` - The [`AssetInfo`] that is being purchased with `initial_asset`.
 ///
-/// * `interval` - The time in seconds between DCA purchases.
+/// * `interval` - The [`Duration`] in seconds or in blocks between DCA purchases.
+///
+/// * `schedule` - An [`Option<Schedule>`] of the times DCA purchases are performed at instead of
+/// every `interval`, which must then be in seconds.
+///
+/// * `max_delay` - An [`Option<u64>`] representing the delay in the unit of `interval` after which
+/// a DCA purchase is skipped rather than performed late.
+///
+/// * `jitter` - An [`Option<u64>`] representing the window in the unit of `interval` within which
+/// each DCA purchase is pseudo-randomly delayed.
 ///
 /// * `dca_amount` - A [`Uint128`] representing the amount of `initial_asset` to spend each DCA
 /// purchase.
 ///
-/// * `first_purchase` - A [`Option<u64>`] representing the first time the users DCA order should be
-/// processed if specified, otherwise as soon as the order is made it can be processed.
+/// * `first_purchase` - A [`Option<u64>`] representing the first time or block height, in the unit
+/// of `interval`, the users DCA order should be processed if specified, otherwise as soon as the
+/// order is made it can be processed.
+///
+/// * `expires_at` - An [`Option<Expiration>`] after which no more DCA purchases can be performed
+/// for the order, and the remaining funds can be swept back to the user.
+///
+/// * `max_purchases` - An [`Option<u64>`] amount of DCA purchases after which the order is closed,
+/// and the remaining funds are refunded to the user.
+///
+/// * `max_failures` - An [`Option<u32>`] amount of DCA purchases failing in a row after which
+/// `failure_policy` is applied to the order.
+///
+/// * `failure_policy` - The [`FailurePolicy`] that pauses the order or widens its maximum spread
+/// once `max_failures` of its purchases have failed in a row.
+///
+/// * `swap_tip_bps` - An [`Option<u16>`] share of each DCA purchase in basis points paid to the
+/// bot performing it, instead of a tip from the tip balance of the user.
+///
+/// * `tip_escalation` - An optional [`TipEscalation`] tip paid to the bot on top of the tip it
+/// redeems, growing the longer a due DCA purchase goes unperformed.
+///
+/// * `tip_replenish` - An optional [`TipReplenish`] part of each DCA purchase added to the tip
+/// balance of the user while it is below a threshold.
+///
+/// * `allowed_bots` - The [`Vec<String>`] addresses of the only bots that can perform the DCA
+/// purchases of the order, or any bot if empty.
+///
+/// * `allow_low_liquidity` - Whether the order can be created for pairs holding less than the
+/// minimum liquidity set by the contract owner.
+///
+/// * `max_price` - An [`Option<Decimal>`] representing the maximum price of `target_asset` in
+/// `initial_asset` that a DCA purchase can be performed at.
+///
+/// * `only_below_price` - An [`Option<Decimal>`] representing the price of `target_asset` in
+/// `initial_asset` above which a due DCA purchase is skipped and rescheduled.
+///
+/// * `max_price_impact` - An [`Option<Decimal>`] representing the share of the value of a DCA
+/// purchase lost to its swap above which the purchase is skipped and rescheduled.
+///
+/// * `stop_price` - An [`Option<Decimal>`] representing the price of `target_asset` in
+/// `initial_asset` below which the order is cancelled at its next DCA purchase.
+///
+/// * `take_profit_price` - An [`Option<Decimal>`] representing the price of `target_asset` in
+/// `initial_asset` above which the order is closed at its next DCA purchase.
+///
+/// * `direction` - The [`OrderDirection`] telling whether the order accumulates `target_asset` or
+/// distributes `initial_asset`.
+///
+/// * `distribution` - An optional [`Distribution`] order created once the order completes, selling
+/// the `target_asset` it accumulated.
+///
+/// * `targets` - The [`TargetWeight`] assets that each DCA purchase is split across, including
+/// `target_asset`, or empty to only purchase `target_asset`.
+///
+/// * `pooled` - Whether other depositors can join the order, each holding shares of it.
+///
+/// * `max_spread` - An [`Option<Decimal>`] overriding the users maximum spread for the swaps of the
+/// order.
+///
+/// * `min_receive_per_purchase` - An [`Option<Uint128>`] representing the minimum amount of
+/// `target_asset` that must be received from each DCA purchase.
+///
+/// * `route` - An [`Option<Vec<AssetInfo>>`] of the whitelisted intermediate assets to swap through
+/// from `initial_asset` to `target_asset` when performing DCA purchases.
+///
+/// * `auto_route` - Whether DCA purchases should use the best route found by simulating the direct
+/// route and every route through one whitelisted asset, rather than the provided hops.
+///
+/// * `delivery` - The [`Delivery`] of the purchased `target_asset` to the user.
+///
+/// * `recipient` - An [`Option<String>`] address to send the purchased `target_asset` to instead of
+/// the user.
+///
+/// * `purchase_hooks` - A [`Vec<PurchaseHook>`] of the contracts to call with the purchased
+/// `target_asset` after each DCA purchase.
+///
+/// * `auto_stake` - Whether the purchased ASTRO should be staked for xASTRO.
+///
+/// * `zap` - An [`Option<Zap>`] to provide liquidity with each DCA purchase instead of sending the
+/// purchased `target_asset`.
+///
+/// * `referrer` - An [`Option<String>`] address that accrues a share of the protocol fee of each
+/// DCA purchase.
+///
+/// * `owner` - An [`Option<String>`] address that owns the order instead of the sender, receiving
+/// its refunds and purchased assets.
 pub fn create_dca_order(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     order_info: CreateDcaOrder,
 ) -> Result<Response, ContractError> {
-    let CreateDcaOrder {
+    let initial_asset = &order_info.initial_asset;
+    validate_dca_order(
         initial_asset,
-        target_asset,
-        interval,
-        dca_amount,
-        first_purchase,
-    } = order_info;
+        &order_info.target_asset,
+        order_info.dca_amount,
+        &order_info.strategy,
+    )?;
+
+    // check that user has sent the valid tokens to the contract
+    // if native token, they should have included it in the message
+    // otherwise, if cw20 token, they should have provided the correct allowance, unless each
+    // purchase is pulled from their wallet as it is performed
+    match &initial_asset.info {
+        AssetInfo::NativeToken { .. } => initial_asset.assert_sent_native_token_balance(&info)?,
+        AssetInfo::Token { contract_addr } if !order_info.pull => validate_token_allowance(
+            &deps.as_ref(),
+            &env,
+            &info.sender,
+            contract_addr,
+            initial_asset.amount,
+        )?,
+        AssetInfo::Token { .. } => {}
+    }
+
+    store_dca_order(deps, &env, &info.sender, order_info, false)
+}
+
+/// ## Description
+/// Validates that the allowance of the cw20 token `contract_addr` given by `owner` to the contract
+/// covers `amount` on top of the amount reserved by the existing orders of `owner`.
+///
+/// Returns a [`ContractError`] if `contract_addr` is not a cw20 token or the allowance is
+/// insufficient.
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `owner` - The [`Addr`] of the user who owns the orders.
+///
+/// * `contract_addr` - The [`Addr`] of the cw20 token.
+///
+/// * `amount` - A [`Uint128`] representing the amount of the token needed for the new order.
+pub(crate) fn validate_token_allowance(
+    deps: &Deps,
+    env: &Env,
+    owner: &Addr,
+    contract_addr: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    validate_token(
+        deps.api,
+        &deps.querier,
+        &AssetInfo::Token {
+            contract_addr: contract_addr.clone(),
+        },
+    )?;
+
+    // allowance should be greater than the sum of all orders with this initial asset
+    let total_allowance = get_reserved_allowance(deps.storage, owner, contract_addr)?;
+
+    let allowance = get_token_allowance(deps, env, owner, contract_addr)?;
+    if total_allowance + amount > allowance {
+        return Err(ContractError::InvalidTokenDeposit {});
+    }
 
-    // check that user has not previously created dca strategy with this initial_asset
-    let mut orders = USER_DCA
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or_default();
+    Ok(())
+}
 
+/// ## Description
+/// Validates the parameters of a DCA order, ensuring that the assets differ and that the
+/// `initial_asset` amount can be spent in purchases of a non zero `dca_amount`, where the last
+/// purchase spends the remainder, or in the tranches of the order if its `strategy` has tranches.
+///
+/// Returns a [`ContractError`] if the order is invalid.
+/// ## Arguments
+/// * `initial_asset` - The [`Asset`] that is being spent to purchase DCA orders.
+///
+/// * `target_asset` - The [`AssetInfo`] that is being purchased with `initial_asset`.
+///
+/// * `dca_amount` - A [`Uint128`] representing the amount of `initial_asset` to spend each DCA
+/// purchase.
+///
+/// * `strategy` - The [`Strategy`] setting how much of `initial_asset` each DCA purchase spends.
+pub(crate) fn validate_dca_order(
+    initial_asset: &Asset,
+    target_asset: &AssetInfo,
+    dca_amount: Uint128,
+    strategy: &Strategy,
+) -> Result<(), ContractError> {
     // check that assets are not duplicate
-    if initial_asset.info == target_asset {
+    if &initial_asset.info == target_asset {
         return Err(ContractError::DuplicateAsset {});
     }
 
@@ -74,41 +343,229 @@ pub fn create_dca_order(
         return Err(ContractError::DepositTooSmall {});
     }
 
-    // check that initial_asset.amount is divisible by dca_amount
-    let remainder = initial_asset
+    // check that dca_amount is non zero, and that the tranches spend exactly initial_asset.amount
+    initial_asset
         .amount
         .checked_rem(dca_amount)
         .map_err(|e| StdError::DivideByZero { source: e })?;
-    if !remainder.is_zero() {
-        return Err(ContractError::IndivisibleDeposit {});
+    if let Strategy::Tranches { amounts } = strategy {
+        validate_tranches(amounts, initial_asset.amount)?;
     }
 
-    // check that user has sent the valid tokens to the contract
-    // if native token, they should have included it in the message
-    // otherwise, if cw20 token, they should have provided the correct allowance
-    match &initial_asset.info {
-        AssetInfo::NativeToken { .. } => initial_asset.assert_sent_native_token_balance(&info)?,
-        AssetInfo::Token { contract_addr } => {
-            // allowance should be greater than the sum of all orders with this initial asset
-            let total_allowance: Uint128 = orders
-                .iter()
-                .map(|o| match &o.initial_asset.info {
-                    AssetInfo::Token {
-                        contract_addr: o_contract_addr,
-                    } if contract_addr == o_contract_addr => o.initial_asset.amount,
-                    _ => Uint128::zero(),
-                })
-                .sum();
-
-            let allowance = get_token_allowance(&deps.as_ref(), &env, &info.sender, contract_addr)?;
-            if total_allowance + initial_asset.amount > allowance {
-                return Err(ContractError::InvalidTokenDeposit {});
+    Ok(())
+}
+
+/// ## Description
+/// Validates that each of the tranches `amounts` of a DCA order spends something, and that they
+/// sum to the `deposit` remaining in the order.
+///
+/// Returns a [`ContractError::InvalidTranches`] if the tranches are invalid.
+pub(crate) fn validate_tranches(
+    amounts: &[Uint128],
+    deposit: Uint128,
+) -> Result<(), ContractError> {
+    let total = amounts
+        .iter()
+        .try_fold(Uint128::zero(), |total, amount| total.checked_add(*amount))?;
+
+    if amounts.iter().any(|amount| amount.is_zero()) || total != deposit {
+        return Err(ContractError::InvalidTranches {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Validates that `interval` is greater than zero, and that `schedule` is valid and used with an
+/// interval in seconds if it is set, as the last purchase of a scheduled order is a time.
+///
+/// Returns a [`ContractError`] if the interval or schedule is invalid.
+pub(crate) fn validate_interval(
+    interval: &Duration,
+    schedule: &Option<Schedule>,
+) -> Result<(), ContractError> {
+    if interval.value() == 0 {
+        return Err(ContractError::InvalidInterval {});
+    }
+
+    match schedule {
+        Some(schedule) if !schedule.is_valid() || !matches!(interval, Duration::Time(_)) => {
+            Err(ContractError::InvalidSchedule {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Validates the rules of a DCA `order` that depend on the assets it spends and purchases, the
+/// deposit held for it and the amount of each of its purchases, as they are set both when the
+/// order is created and when it is modified.
+///
+/// Returns a [`ContractError`] if the order is invalid.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `owner` - The [`Addr`] of the user who owns the order.
+///
+/// * `order` - The [`DcaInfo`] of the order that is being stored.
+pub(crate) fn validate_order_assets(
+    deps: DepsMut,
+    owner: &Addr,
+    order: &DcaInfo,
+) -> Result<(), ContractError> {
+    let DcaInfo {
+        initial_asset,
+        target_asset,
+        dca_amount,
+        ..
+    } = order;
+
+    // orders cannot be created or modified while the contract is paused
+    let contract_config = CONFIG.may_load(deps.storage)?;
+    if contract_config
+        .as_ref()
+        .map_or(false, |config| config.paused)
+    {
+        return Err(ContractError::ContractPaused {});
+    }
+
+    // a deployment curating its assets only allows orders between the assets it allows, and no
+    // order can be made of a blacklisted asset
+    let order_assets: Vec<&AssetInfo> = [&initial_asset.info, target_asset]
+        .into_iter()
+        .chain(order.targets.iter().map(|target| &target.asset))
+        .collect();
+    if let Some(config) = &contract_config {
+        for asset in order_assets.iter().copied() {
+            if config.is_blacklisted_asset(asset) {
+                return Err(ContractError::AssetBlacklisted {
+                    asset: asset.clone(),
+                });
+            }
+
+            if !config.is_allowed_asset(asset) {
+                return Err(ContractError::AssetNotAllowed {
+                    asset: asset.clone(),
+                });
+            }
+        }
+
+        // the amount a token taking a tax on transfers delivers cannot be split across targets
+        if !order.targets.is_empty() && config.is_fee_on_transfer_token(&initial_asset.info) {
+            return Err(ContractError::InvalidMultiTargetOrder {});
+        }
+    }
+
+    // check that the cw20 assets of the order are real tokens and that its tokenfactory denoms are
+    // well formed, as an order of a contract that is not a token could never be purchased
+    for asset in order_assets {
+        validate_token(deps.api, &deps.querier, asset)?;
+    }
+
+    // dust orders are rejected against minimums in whole units of the initial asset, so that one
+    // minimum fits assets of any decimals
+    if let Some(config) = &contract_config {
+        if config.min_deposit.is_some() || config.min_dca_amount.is_some() {
+            let decimals = query_decimals(deps.storage, &deps.querier, &initial_asset.info)?;
+
+            if let Some(min_deposit) = config.min_deposit {
+                let min = scale_by_decimals(min_deposit, decimals)?;
+                if initial_asset.amount < min {
+                    return Err(ContractError::DepositBelowMinimum { min });
+                }
             }
+
+            if let Some(min_dca_amount) = config.min_dca_amount {
+                let min = scale_by_decimals(min_dca_amount, decimals)?;
+                if *dca_amount < min {
+                    return Err(ContractError::DcaAmountBelowMinimum { min });
+                }
+            }
+        }
+    }
+
+    // the distribution sells the purchased asset for another asset
+    if let Some(distribution) = &order.distribution {
+        if &distribution.target_asset == target_asset {
+            return Err(ContractError::InvalidDistribution {});
+        }
+    }
+
+    // an order with several target assets purchases its target asset among them, and does not
+    // purchase the asset it spends
+    if !order.targets.is_empty()
+        && (order
+            .targets
+            .iter()
+            .all(|target| &target.asset != target_asset)
+            || order
+                .targets
+                .iter()
+                .any(|target| target.asset == initial_asset.info))
+    {
+        return Err(ContractError::InvalidTargets {});
+    }
+
+    // the purchases of an order delivered to another chain are transferred as native tokens
+    if matches!(order.delivery, Delivery::Ibc { .. }) && !target_asset.is_native_token() {
+        return Err(ContractError::InvalidIbcDelivery {});
+    }
+
+    // check that the stored route only swaps through whitelisted assets
+    if let Some(route) = &order.route {
+        let config = CONFIG.load(deps.storage)?;
+        let max_hops = USER_CONFIG
+            .may_load(deps.storage, owner)?
+            .and_then(|c| c.max_hops)
+            .unwrap_or(config.max_hops);
+
+        validate_route(&config, max_hops, &initial_asset.info, target_asset, route)?;
+    }
+
+    // check that the purchased asset is the ASTRO of the staking contract
+    if order.auto_stake {
+        let config = CONFIG.load(deps.storage)?;
+        validate_auto_stake(&deps.querier, &config, target_asset)?;
+    }
+
+    // check that there is a pair of the assets to provide liquidity to, and a generator to stake
+    // the LP tokens in if they are staked
+    if let Some(zap) = &order.zap {
+        let config = CONFIG.load(deps.storage)?;
+        validate_zap(
+            &deps.querier,
+            &config.factory_addr,
+            zap,
+            &initial_asset.info,
+            target_asset,
+        )?;
+    }
+
+    // check that the tip balance is replenished with a whitelisted tip asset held by the contract,
+    // while leaving part of each purchase to be swapped
+    if let Some(TipReplenish { amount, .. }) = &order.tip_replenish {
+        let held = order.escrowed || initial_asset.info.is_native_token();
+        if !held || amount.is_zero() || amount >= dca_amount {
+            return Err(ContractError::InvalidTipReplenish {});
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        if !config.is_whitelisted_fee_asset(&initial_asset.info) {
+            return Err(ContractError::NonWhitelistedTipAsset {
+                asset: initial_asset.info.clone(),
+            });
         }
     }
 
-    let id = USER_CONFIG
-        .update::<_, StdError>(deps.storage, &info.sender, |config| {
+    Ok(())
+}
+
+/// ## Description
+/// Returns the next available order id of `owner`, incrementing the last id stored in the
+/// configuration of `owner`.
+pub(crate) fn next_order_id(storage: &mut dyn Storage, owner: &Addr) -> StdResult<u64> {
+    Ok(USER_CONFIG
+        .update::<_, StdError>(storage, owner, |config| {
             let mut config = config.unwrap_or_default();
 
             config.last_id = config
@@ -118,47 +575,471 @@ pub fn create_dca_order(
 
             Ok(config)
         })?
-        .last_id;
+        .last_id)
+}
+
+/// ## Description
+/// Stores a new DCA order funded by `sender` under the next available order id of its owner, once
+/// the deposit for the order has been validated. The order belongs to `sender`, unless an `owner`
+/// is specified in `order_info`.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `sender` - The [`Addr`] of the user who funded the order.
+///
+/// * `order_info` - The [`CreateDcaOrder`] parameters of the new order.
+///
+/// * `escrowed` - Whether a cw20 `initial_asset` has been deposited into the contract rather than
+/// being spent from the owners allowance.
+pub(crate) fn store_dca_order(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    order_info: CreateDcaOrder,
+    escrowed: bool,
+) -> Result<Response, ContractError> {
+    let CreateDcaOrder {
+        initial_asset,
+        target_asset,
+        interval,
+        schedule,
+        max_delay,
+        jitter,
+        dca_amount,
+        strategy,
+        first_purchase,
+        expires_at,
+        max_purchases,
+        max_failures,
+        failure_policy,
+        swap_tip_bps,
+        tip_escalation,
+        tip_replenish,
+        allowed_bots,
+        allow_low_liquidity,
+        max_price,
+        only_below_price,
+        max_price_impact,
+        stop_price,
+        take_profit_price,
+        direction,
+        distribution,
+        targets,
+        pooled,
+        max_spread,
+        min_receive_per_purchase,
+        route,
+        auto_route,
+        delivery,
+        recipient,
+        purchase_hooks,
+        auto_stake,
+        zap,
+        referrer,
+        owner: order_owner,
+        pull,
+    } = order_info;
+
+    // the order belongs to the specified owner instead of the sender who funded it
+    let owner = &match order_owner {
+        Some(order_owner) => addr_validate_to_lower(deps.api, &order_owner)?,
+        None => sender.clone(),
+    };
+
+    // a cw20 deposit spent from an allowance is taken from the owner of the order at each
+    // purchase, so an order for another owner must deposit the tokens into the contract
+    if owner != sender && !escrowed && !initial_asset.info.is_native_token() {
+        return Err(ContractError::OwnerAllowanceDeposit {});
+    }
+
+    // only a cw20 deposit spent from an allowance can be pulled from the wallet of the owner
+    if pull && (escrowed || initial_asset.info.is_native_token()) {
+        return Err(ContractError::InvalidPullOrder {});
+    }
+
+    validate_interval(&interval, &schedule)?;
+
+    // an order closed before its first purchase could never spend its deposit
+    if max_purchases == Some(0) {
+        return Err(ContractError::InvalidMaxPurchases {});
+    }
+
+    // the failure policy must wait for at least one failure, and widening by nothing would keep
+    // the order failing
+    let widens_nothing = matches!(
+        failure_policy,
+        FailurePolicy::WidenSlippage { step, .. } if step.is_zero()
+    );
+    if max_failures == Some(0) || widens_nothing {
+        return Err(ContractError::InvalidFailurePolicy {});
+    }
+
+    if let Some(swap_tip_bps) = swap_tip_bps {
+        if swap_tip_bps == 0 || swap_tip_bps > MAX_SWAP_TIP_BPS {
+            return Err(ContractError::InvalidSwapTip {
+                max: MAX_SWAP_TIP_BPS,
+            });
+        }
+    }
+
+    if let Some(max_price_impact) = max_price_impact {
+        if max_price_impact.is_zero() || max_price_impact > Decimal::one() {
+            return Err(ContractError::InvalidMaxPriceImpact {});
+        }
+    }
+
+    // an order that cannot be purchased at or above its stop price would be cancelled at every
+    // purchase it could perform
+    if let Some(stop_price) = stop_price {
+        let below_limit = [max_price, only_below_price]
+            .iter()
+            .flatten()
+            .all(|limit| stop_price < *limit);
+        if stop_price.is_zero() || !below_limit {
+            return Err(ContractError::InvalidStopPrice {});
+        }
+    }
+
+    // an order closed both below and at or above the same price could never purchase
+    if let Some(take_profit_price) = take_profit_price {
+        if take_profit_price.is_zero() || stop_price.map_or(false, |stop| take_profit_price <= stop)
+        {
+            return Err(ContractError::InvalidTakeProfitPrice {});
+        }
+    }
+
+    // the distribution sells the purchased balance held by the contract for another asset
+    if let Some(distribution) = &distribution {
+        if direction != OrderDirection::Accumulate
+            || delivery != Delivery::Escrow
+            || distribution.dca_amount.is_zero()
+        {
+            return Err(ContractError::InvalidDistribution {});
+        }
+
+        validate_interval(&distribution.interval, &None)?;
+    }
+
+    // each purchase of an order with several target assets is split across them by weight, and
+    // each target is swapped through the best route found on-chain and sent straight to the
+    // recipient, so the order cannot be priced, routed or delivered by a single target asset
+    if !targets.is_empty() {
+        let mut assets: Vec<&AssetInfo> = vec![];
+        let mut total_weight = Decimal::zero();
+        for target in &targets {
+            if target.weight.is_zero()
+                || target.weight > Decimal::one()
+                || assets.contains(&&target.asset)
+            {
+                return Err(ContractError::InvalidTargets {});
+            }
+
+            assets.push(&target.asset);
+            total_weight = total_weight + target.weight;
+        }
+
+        if targets.len() < 2 || total_weight != Decimal::one() {
+            return Err(ContractError::InvalidTargets {});
+        }
+
+        let priced = [
+            max_price,
+            only_below_price,
+            max_price_impact,
+            stop_price,
+            take_profit_price,
+        ]
+        .iter()
+        .any(Option::is_some);
+        if priced
+            || !auto_route
+            || route.is_some()
+            || min_receive_per_purchase.is_some()
+            || delivery == Delivery::Escrow
+            || matches!(delivery, Delivery::Ibc { .. })
+            || !purchase_hooks.is_empty()
+            || auto_stake
+            || zap.is_some()
+            || distribution.is_some()
+            || strategy == Strategy::ValueAveraging
+        {
+            return Err(ContractError::InvalidMultiTargetOrder {});
+        }
+    }
+
+    // the deposit and purchases of a pooled order belong to its depositors by their shares, so the
+    // contract must hold both, and the order can only end by spending its whole deposit
+    if pooled
+        && (!(escrowed || initial_asset.info.is_native_token())
+            || delivery != Delivery::Escrow
+            || expires_at.is_some()
+            || max_purchases.is_some()
+            || stop_price.is_some()
+            || take_profit_price.is_some()
+            || matches!(strategy, Strategy::Tranches { .. })
+            || distribution.is_some()
+            || !targets.is_empty())
+    {
+        return Err(ContractError::InvalidPooledOrder {});
+    }
+
+    // the purchases of an order with a maximum delay are skipped once they are too late, so
+    // without a first purchase the interval starts from the creation of the order rather than
+    // from the start of the chain
+    let last_purchase = match (first_purchase, &schedule, max_delay) {
+        (Some(first_purchase), ..) => first_purchase,
+        (None, None, Some(_)) => interval
+            .current(&env.block)
+            .saturating_sub(interval.value()),
+        _ => 0,
+    };
+
+    // the purchased asset is sent to the hooks, so it cannot also be held by the contract
+    if !purchase_hooks.is_empty() && delivery == Delivery::Escrow {
+        return Err(ContractError::EscrowedPurchaseHooks {});
+    }
+
+    // the purchased ASTRO is staked and sent to the recipient, so it cannot be held by the contract
+    // or sent to the hooks
+    if auto_stake && (delivery == Delivery::Escrow || !purchase_hooks.is_empty()) {
+        return Err(ContractError::InvalidAutoStake {});
+    }
+
+    // the purchased asset is provided as liquidity along with the unswapped half of each purchase,
+    // so it cannot be held by the contract, sent to the hooks or staked, nor be valued to average
+    if zap.is_some()
+        && (delivery == Delivery::Escrow
+            || !purchase_hooks.is_empty()
+            || auto_stake
+            || strategy == Strategy::ValueAveraging)
+    {
+        return Err(ContractError::InvalidZap {});
+    }
+
+    // the purchased asset is transferred to an address on another chain, so it is not sent
+    // anywhere else on this chain
+    if let Delivery::Ibc {
+        channel_id,
+        to_address,
+        timeout,
+    } = &delivery
+    {
+        if channel_id.is_empty()
+            || to_address.is_empty()
+            || *timeout == 0
+            || recipient.is_some()
+            || !purchase_hooks.is_empty()
+            || auto_stake
+            || zap.is_some()
+        {
+            return Err(ContractError::InvalidIbcDelivery {});
+        }
+    }
+
+    // check that an escalating tip grows by a whitelisted tip asset over a period of the order
+    if let Some(TipEscalation { step, period, max }) = &tip_escalation {
+        if *period == 0 || step.amount.is_zero() || max < &step.amount {
+            return Err(ContractError::InvalidTipEscalation {});
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        if !config.is_whitelisted_fee_asset(&step.info) {
+            return Err(ContractError::NonWhitelistedTipAsset {
+                asset: step.info.clone(),
+            });
+        }
+    }
+
+    let allowed_bots = allowed_bots
+        .iter()
+        .map(|bot| addr_validate_to_lower(deps.api, bot))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let purchase_hooks = purchase_hooks
+        .into_iter()
+        .map(|hook| {
+            Ok(PurchaseHook {
+                contract_addr: addr_validate_to_lower(deps.api, &hook.contract_addr)?.into_string(),
+                msg: hook.msg,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // the user cannot refer their own order to take back a share of the protocol fee
+    let referrer = referrer
+        .map(|referrer| addr_validate_to_lower(deps.api, &referrer))
+        .transpose()?;
+    if referrer.as_ref() == Some(owner) {
+        return Err(ContractError::SelfReferral {});
+    }
+
+    let id = next_order_id(deps.storage, owner)?;
 
-    // store dca order
-    orders.push(DcaInfo {
+    let order = DcaInfo {
         id,
         initial_asset: initial_asset.clone(),
         target_asset: target_asset.clone(),
         interval,
-        last_purchase: first_purchase.unwrap_or_default(),
+        schedule,
+        max_delay,
+        jitter,
+        last_purchase,
         dca_amount,
-    });
+        strategy,
+        escrowed,
+        pull,
+        paused: false,
+        expires_at,
+        max_purchases,
+        max_failures,
+        failure_policy,
+        swap_tip_bps,
+        tip_escalation,
+        tip_replenish,
+        allowed_bots,
+        max_price,
+        only_below_price,
+        max_price_impact,
+        stop_price,
+        take_profit_price,
+        direction,
+        distribution,
+        targets,
+        pooled,
+        max_spread,
+        min_receive_per_purchase,
+        route,
+        auto_route,
+        delivery,
+        recipient: recipient
+            .map(|recipient| addr_validate_to_lower(deps.api, &recipient))
+            .transpose()?,
+        purchase_hooks,
+        auto_stake,
+        zap,
+        referrer,
+        total_spent: Uint128::zero(),
+        total_received: Uint128::zero(),
+        targets_received: vec![],
+        executing: false,
+        jitter_offset: jitter.map_or(0, |window| jitter_offset(env, owner, id, window)),
+        target_value: Uint128::zero(),
+        last_failure: None,
+        consecutive_failures: 0,
+        replenished_tip: Uint128::zero(),
+    };
+    validate_order_assets(deps.branch(), owner, &order)?;
+
+    let contract_config = CONFIG.may_load(deps.storage)?;
+
+    // check that the pairs the order swaps through hold the minimum liquidity, through the stored
+    // route if there is one, otherwise the best route found on-chain, unless the user has opted
+    // out of the check
+    let min_liquidity = contract_config
+        .as_ref()
+        .and_then(|config| config.min_liquidity);
+    if let (Some(config), Some(min_liquidity), false) =
+        (&contract_config, min_liquidity, allow_low_liquidity)
+    {
+        let hops = match &order.route {
+            Some(route) => build_route(&initial_asset.info, route, &target_asset),
+            None => {
+                let max_hops = USER_CONFIG
+                    .may_load(deps.storage, owner)?
+                    .and_then(|c| c.max_hops)
+                    .unwrap_or(config.max_hops);
+
+                find_best_route(
+                    &deps.as_ref(),
+                    config,
+                    max_hops,
+                    &initial_asset.info,
+                    &target_asset,
+                    dca_amount,
+                )?
+            }
+        };
+
+        validate_liquidity(&deps.querier, &config.factory_addr, &hops, min_liquidity)?;
+    }
+
+    user_dca().save(deps.storage, (owner, id), &order)?;
+    record_order_created(deps.storage)?;
+
+    // the owner of a pooled order holds the shares of its initial deposit
+    if pooled {
+        POOLS.save(
+            deps.storage,
+            (owner, id),
+            &Pool {
+                total_shares: initial_asset.amount,
+                output_per_share: Decimal::zero(),
+            },
+        )?;
+        POOL_SHARES.save(
+            deps.storage,
+            (owner, id, owner),
+            &PoolShare {
+                shares: initial_asset.amount,
+                ..PoolShare::default()
+            },
+        )?;
+    }
 
-    USER_DCA.save(deps.storage, &info.sender, &orders)?;
+    let mut response = Response::new()
+        .set_data(to_binary(&CreateDcaOrderResponse { id })?)
+        .add_attributes(vec![
+            attr("action", "create_dca_order"),
+            attr("initial_asset", initial_asset.to_string()),
+            attr("target_asset", target_asset.to_string()),
+            attr("interval", interval.to_string()),
+            attr("dca_amount", dca_amount),
+        ]);
+
+    // mint the NFT representing the order once the cw721 contract has been instantiated, unless
+    // the deposit is spent from the allowance of the user rather than held by the contract, or is
+    // shared by the depositors of a pooled order
+    if let Some(nft_addr) = contract_config.and_then(|config| config.nft_addr) {
+        if (escrowed || initial_asset.info.is_native_token()) && !pooled {
+            let (token_id, mint_msg) = mint_order_nft(deps, &nft_addr, owner, id)?;
+
+            response = response
+                .add_message(mint_msg)
+                .add_attribute("nft_token_id", token_id);
+        }
+    }
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "create_dca_order"),
-        attr("initial_asset", initial_asset.to_string()),
-        attr("target_asset", target_asset.to_string()),
-        attr("interval", interval.to_string()),
-        attr("dca_amount", dca_amount),
-    ]))
+    Ok(response)
 }
 
 #[cfg(test)]
 mod tests {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::{DcaInfo, ExecuteMsg};
+    use astroport_dca::dca::{
+        CreateDcaOrderResponse, DcaInfo, Delivery, Distribution, Duration, ExecuteMsg,
+        FailurePolicy, OrderDirection, PurchaseHook, Schedule, Strategy, TargetWeight,
+        TipEscalation, TipReplenish, Zap,
+    };
     use cosmwasm_std::{
         attr, coins,
         testing::{mock_dependencies, mock_env, mock_info},
-        Addr, DivideByZeroError, Response, StdError, Uint128,
+        to_binary, Addr, Binary, Decimal, DepsMut, DivideByZeroError, Response, StdError,
+        StdResult, Uint128,
     };
     use cw_multi_test::{App, AppResponse, Executor};
 
     use crate::{
         contract::execute,
         error::ContractError,
-        state::USER_DCA,
+        state::{get_user_orders, CONFIG, MAX_SWAP_TIP_BPS},
         tests::{
-            app_mock_instantiate, mock_app, mock_creator, store_cw20_token_code,
-            store_dca_module_code,
+            app_mock_instantiate, mock_app, mock_astroport_instantiate, mock_creator,
+            mock_instantiate, store_cw20_token_code, store_dca_module_code,
         },
     };
 
@@ -183,28 +1064,62 @@ mod tests {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset: target_asset.clone(),
-                interval: 1_000,
+                interval: Duration::Time(1_000),
                 dca_amount: Uint128::new(25_000),
                 first_purchase: Some(18_000),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
 
         assert_eq!(
             res,
-            Response::new().add_attributes(vec![
-                attr("action", "create_dca_order"),
-                attr("initial_asset", initial_asset.to_string()),
-                attr("target_asset", target_asset.to_string()),
-                attr("interval", "1000"),
-                attr("dca_amount", "25000"),
-            ])
+            Response::new()
+                .set_data(to_binary(&CreateDcaOrderResponse { id: 1 }).unwrap())
+                .add_attributes(vec![
+                    attr("action", "create_dca_order"),
+                    attr("initial_asset", initial_asset.to_string()),
+                    attr("target_asset", target_asset.to_string()),
+                    attr("interval", "1000 seconds"),
+                    attr("dca_amount", "25000"),
+                ])
         );
 
         // check that it got added to state
-        let orders = USER_DCA
-            .load(&deps.storage, &mock_creator().sender)
-            .unwrap();
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
 
         assert_eq!(
             orders,
@@ -213,8 +1128,51 @@ mod tests {
                 dca_amount: Uint128::new(25_000),
                 initial_asset,
                 target_asset,
-                interval: 1_000,
-                last_purchase: 18_000
+                interval: Duration::Time(1_000),
+                last_purchase: 18_000,
+                escrowed: false,
+                paused: false,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                total_spent: Uint128::zero(),
+                total_received: Uint128::zero(),
+                targets_received: vec![],
+                executing: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                jitter_offset: 0,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                target_value: Uint128::zero(),
+                max_purchases: None,
+                pull: false,
+                last_failure: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                consecutive_failures: 0,
+                replenished_tip: Uint128::zero(),
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
             }]
         );
     }
@@ -254,77 +1212,1563 @@ mod tests {
                 info: AssetInfo::Token {
                     contract_addr: cw20_addr.clone(),
                 },
-            }],
-        );
+            }],
+        );
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: AssetInfo::Token {
+                contract_addr: cw20_addr.clone(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        // increment allowance
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr,
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: dca_addr.clone().into_string(),
+                amount: initial_asset.amount,
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr,
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_create_order_for_owner() {
+        let mut deps = mock_dependencies();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("funder", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: Some("owner".to_string()),
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        // the order should belong to the owner rather than the funder
+        let orders = get_user_orders(&deps.storage, &Addr::unchecked("owner")).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert!(get_user_orders(&deps.storage, &Addr::unchecked("funder"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn cannot_create_token_order_for_owner_from_allowance() {
+        let mut app = mock_app();
+
+        let cw20_token_id = store_cw20_token_code(&mut app);
+        let dca_module_id = store_dca_module_code(&mut app);
+
+        let cw20_addr = app
+            .instantiate_contract(
+                cw20_token_id,
+                mock_creator().sender,
+                &cw20_base::msg::InstantiateMsg {
+                    decimals: 6,
+                    initial_balances: vec![],
+                    marketing: None,
+                    mint: None,
+                    name: "cw20 token".to_string(),
+                    symbol: "cwT".to_string(),
+                },
+                &[],
+                "mock cw20 token",
+                None,
+            )
+            .unwrap();
+
+        let dca_addr = app_mock_instantiate(
+            &mut app,
+            dca_module_id,
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: dca_addr.clone().into_string(),
+                amount: Uint128::new(100_000),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the deposit would be taken from the owner at each purchase rather than the funder
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr,
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::Token {
+                            contract_addr: cw20_addr,
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(25_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: Some("owner".to_string()),
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::OwnerAllowanceDeposit {}
+        );
+    }
+
+    #[test]
+    fn cannot_create_duplicate_asset() {
+        let mut deps = mock_dependencies();
+
+        let asset = Asset {
+            amount: Uint128::new(25_000),
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: asset.clone(),
+                target_asset: asset.info,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(5_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::DuplicateAsset {});
+    }
+
+    #[test]
+    fn cannot_create_escrowed_order_with_purchase_hooks() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![PurchaseHook {
+                    contract_addr: "vault".to_string(),
+                    msg: Binary::default(),
+                }],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::EscrowedPurchaseHooks {});
+    }
+
+    #[test]
+    fn cannot_create_escrowed_zap_order() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: Some(Zap { stake_lp: false }),
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::InvalidZap {});
+    }
+
+    #[test]
+    fn cannot_create_greater_dca_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: initial_asset.clone(),
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: initial_asset.amount * Uint128::new(2),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::DepositTooSmall {});
+    }
+
+    #[test]
+    fn cannot_create_zero_interval_order() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Height(0),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::InvalidInterval {});
+    }
+
+    #[test]
+    fn cannot_create_invalid_schedule_order() {
+        let mut deps = mock_dependencies();
+
+        let mut create_order = |interval: Duration, schedule: Schedule| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &coins(100_000, "uluna")),
+                ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval,
+                    schedule: Some(schedule),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+            )
+            .unwrap_err()
+        };
+
+        let weekly = Schedule {
+            minute: 0,
+            hour: 14,
+            day_of_week: Some(1),
+            day_of_month: None,
+        };
+
+        // the last purchase of a scheduled order is a time, so the interval must be in seconds
+        assert_eq!(
+            create_order(Duration::Height(1_000), weekly.clone()),
+            ContractError::InvalidSchedule {}
+        );
+        assert_eq!(
+            create_order(
+                Duration::Time(1_000),
+                Schedule {
+                    hour: 24,
+                    ..weekly.clone()
+                }
+            ),
+            ContractError::InvalidSchedule {}
+        );
+        assert_eq!(
+            create_order(
+                Duration::Time(1_000),
+                Schedule {
+                    day_of_month: Some(1),
+                    ..weekly
+                }
+            ),
+            ContractError::InvalidSchedule {}
+        );
+    }
+
+    #[test]
+    fn cannot_create_invalid_tranches_order() {
+        let mut deps = mock_dependencies();
+
+        let mut create_order = |amounts: Vec<u128>| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &coins(100_000, "uluna")),
+                ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    schedule: None,
+                    dca_amount: Uint128::new(10_000),
+                    strategy: Strategy::Tranches {
+                        amounts: amounts.into_iter().map(Uint128::new).collect(),
+                    },
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+            )
+            .unwrap_err()
+        };
+
+        // the tranches must spend exactly the deposit
+        assert_eq!(
+            create_order(vec![10_000, 20_000, 30_000]),
+            ContractError::InvalidTranches {}
+        );
+        assert_eq!(
+            create_order(vec![10_000, 20_000, 30_000, 40_000, 1]),
+            ContractError::InvalidTranches {}
+        );
+        // and each tranche must spend something
+        assert_eq!(
+            create_order(vec![10_000, 0, 30_000, 60_000]),
+            ContractError::InvalidTranches {}
+        );
+    }
+
+    #[test]
+    fn cannot_create_zero_amount_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // does not panic when using size of zero to create order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(0),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::Std(StdError::DivideByZero {
+                source: DivideByZeroError::new("100000")
+            })
+        );
+    }
+
+    #[test]
+    fn cannot_create_zero_max_purchases_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: Some(0),
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidMaxPurchases {});
+    }
+
+    #[test]
+    fn cannot_create_invalid_failure_policy_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: Some(1),
+                failure_policy: FailurePolicy::WidenSlippage {
+                    step: Decimal::zero(),
+                    max_spread: Decimal::percent(10),
+                },
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidFailurePolicy {});
+    }
+
+    #[test]
+    fn cannot_create_excessive_swap_tip_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: Some(MAX_SWAP_TIP_BPS + 1),
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::InvalidSwapTip {
+                max: MAX_SWAP_TIP_BPS
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_create_excessive_price_impact_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: Some(Decimal::percent(101)),
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidMaxPriceImpact {});
+    }
+
+    #[test]
+    fn cannot_create_stop_price_above_max_price_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // every purchase at or below the max price would be below the stop price
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: Some(Decimal::percent(150)),
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: Some(Decimal::percent(150)),
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidStopPrice {});
+    }
+
+    #[test]
+    fn cannot_create_take_profit_below_stop_price_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // every purchase would be below the stop price or above the take profit price
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: Some(Decimal::percent(150)),
+                take_profit_price: Some(Decimal::percent(120)),
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidTakeProfitPrice {});
+    }
+
+    #[test]
+    fn cannot_create_distribution_without_escrow_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // the purchased asset must be held by the contract to be distributed
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: Some(Distribution {
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    dca_amount: Uint128::new(5_000),
+                    interval: Duration::Time(1_000),
+                }),
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidDistribution {});
+    }
+
+    #[test]
+    fn cannot_create_pooled_order_without_escrow() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // the purchases of a pooled order must be held by the contract to be shared
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: true,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidPooledOrder {});
+    }
+
+    #[test]
+    fn cannot_create_ibc_delivery_with_recipient() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // the purchases of an order delivered to another chain cannot be sent to a recipient
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Ibc {
+                    channel_id: "channel-0".to_string(),
+                    to_address: "osmo1recipient".to_string(),
+                    timeout: 600,
+                },
+                recipient: Some("recipient".to_string()),
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidIbcDelivery {});
+    }
+
+    #[test]
+    fn cannot_create_order_of_malformed_tokenfactory_denom() {
+        let mut deps = mock_dependencies();
 
         let initial_asset = Asset {
             amount: Uint128::new(100_000),
-            info: AssetInfo::Token {
-                contract_addr: cw20_addr.clone(),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
             },
         };
         let target_asset = AssetInfo::NativeToken {
-            denom: "uluna".to_string(),
+            denom: "factory/creator".to_string(),
         };
 
-        // increment allowance
-        app.execute_contract(
-            mock_creator().sender,
-            cw20_addr,
-            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-                spender: dca_addr.clone().into_string(),
-                amount: initial_asset.amount,
-                expires: None,
-            },
-            &[],
-        )
-        .unwrap();
-
-        app.execute_contract(
-            mock_creator().sender,
-            dca_addr,
-            &ExecuteMsg::CreateDcaOrder {
+        // a tokenfactory denom must have a subdenom after its creator
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
                 initial_asset,
                 target_asset,
-                interval: 1000,
-                dca_amount: Uint128::new(25_000),
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
-            &[],
         )
-        .unwrap();
+        .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::InvalidTokenFactoryDenom {
+                denom: "factory/creator".to_string(),
+            }
+        );
     }
 
     #[test]
-    fn cannot_create_duplicate_asset() {
+    fn cannot_create_invalid_targets_order() {
         let mut deps = mock_dependencies();
 
-        let asset = Asset {
-            amount: Uint128::new(25_000),
-            info: AssetInfo::NativeToken {
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
                 denom: "uluna".to_string(),
             },
         };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
 
+        // the weights of the targets must sum to one
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_creator(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
             ExecuteMsg::CreateDcaOrder {
-                initial_asset: asset.clone(),
-                target_asset: asset.info,
-                interval: 1_000,
-                dca_amount: Uint128::new(5_000),
+                initial_asset,
+                target_asset: target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: true,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![
+                    TargetWeight {
+                        asset: target_asset,
+                        weight: Decimal::percent(50),
+                    },
+                    TargetWeight {
+                        asset: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        weight: Decimal::percent(40),
+                    },
+                ],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap_err();
-
-        assert_eq!(res, ContractError::DuplicateAsset {});
+        assert_eq!(res, ContractError::InvalidTargets {});
     }
 
     #[test]
-    fn cannot_create_greater_dca_order() {
+    fn cannot_create_invalid_tip_escalation_order() {
         let mut deps = mock_dependencies();
 
         let initial_asset = Asset {
@@ -342,20 +2786,62 @@ mod tests {
             mock_env(),
             mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
             ExecuteMsg::CreateDcaOrder {
-                initial_asset: initial_asset.clone(),
+                initial_asset,
                 target_asset,
-                interval: 1_000,
-                dca_amount: initial_asset.amount * Uint128::new(2),
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: Some(TipEscalation {
+                    step: Asset {
+                        amount: Uint128::new(1_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    period: 0,
+                    max: Uint128::new(5_000),
+                }),
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap_err();
-
-        assert_eq!(res, ContractError::DepositTooSmall {});
+        assert_eq!(res, ContractError::InvalidTipEscalation {});
     }
 
     #[test]
-    fn cannot_create_indivisible_order() {
+    fn cannot_create_invalid_tip_replenish_order() {
         let mut deps = mock_dependencies();
 
         let initial_asset = Asset {
@@ -373,17 +2859,453 @@ mod tests {
             mock_env(),
             mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
             ExecuteMsg::CreateDcaOrder {
-                initial_asset: initial_asset.clone(),
-                target_asset: target_asset.clone(),
-                interval: 1_000,
-                dca_amount: Uint128::new(999),
+                initial_asset,
+                target_asset,
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                // the whole purchase would be kept as tip balance
+                tip_replenish: Some(TipReplenish {
+                    amount: Uint128::new(10_000),
+                    threshold: Uint128::new(50_000),
+                }),
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap_err();
-        assert_eq!(res, ContractError::IndivisibleDeposit {});
+        assert_eq!(res, ContractError::InvalidTipReplenish {});
+    }
+
+    #[test]
+    fn cannot_create_order_of_asset_not_allowed() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        let native = |denom: &str| AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+                config.allowed_assets = vec![native("uluna"), native("ujpy")];
+                config.allowed_assets_only = true;
+                Ok(config)
+            })
+            .unwrap();
+
+        let create = |deps: DepsMut, target_asset: AssetInfo| {
+            execute(
+                deps,
+                env.clone(),
+                mock_info("creator", &coins(100_000, "uluna")),
+                ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: native("uluna"),
+                    },
+                    target_asset,
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+            )
+        };
+
+        let res = create(deps.as_mut(), native("ukrw")).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::AssetNotAllowed {
+                asset: native("ukrw")
+            }
+        );
+
+        create(deps.as_mut(), native("ujpy")).unwrap();
+
+        // every asset is allowed once the allowlist is turned off
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+                config.allowed_assets_only = false;
+                Ok(config)
+            })
+            .unwrap();
+        create(deps.as_mut(), native("ukrw")).unwrap();
+    }
+
+    #[test]
+    fn cannot_create_order_of_invalid_token() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        let create = |app: &mut App, contract_addr: Addr| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::Token { contract_addr },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(100_000, "uluna"),
+            )
+        };
+
+        // neither a missing contract nor a contract that is not a token can be purchased
+        for contract_addr in [Addr::unchecked("not_a_token"), dca_addr.clone()] {
+            let res = create(&mut app, contract_addr.clone()).unwrap_err();
+            assert_eq!(
+                res.downcast::<ContractError>().unwrap(),
+                ContractError::InvalidToken {
+                    contract_addr: contract_addr.into_string()
+                }
+            );
+        }
+
+        create(&mut app, cw20_addr).unwrap();
+    }
+
+    #[test]
+    fn cannot_create_order_below_min_liquidity() {
+        let (mut app, dca_addr, _) = mock_astroport_instantiate(None);
+
+        let set_min_liquidity = |app: &mut App, min_liquidity: u128| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::UpdateConfig {
+                    max_hops: None,
+                    whitelisted_tokens: None,
+                    whitelisted_fee_assets: None,
+                    max_spread: None,
+                    staking_addr: None,
+                    protocol_fee_bps: None,
+                    fee_collector: None,
+                    fee_tiers: None,
+                    referral_share: None,
+                    factory_addr: None,
+                    router_addr: None,
+                    config_timelock: None,
+                    paused: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: None,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: None,
+                    allowed_assets_only: None,
+                    min_liquidity: Some(Uint128::new(min_liquidity)),
+                    fee_on_transfer_tokens: None,
+                    min_deposit: None,
+                    min_dca_amount: None,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        let create = |app: &mut App, allow_low_liquidity: bool| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity,
+                },
+                &coins(100_000, "uluna"),
+            )
+        };
+
+        // the best route swaps through the ujpy pairs, each holding 500_000 of both assets
+        set_min_liquidity(&mut app, 500_001);
+        let res = create(&mut app, false).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InsufficientLiquidity {
+                offer_asset: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                ask_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+            }
+        );
+
+        // power users can opt out of the check
+        create(&mut app, true).unwrap();
+
+        set_min_liquidity(&mut app, 500_000);
+        create(&mut app, false).unwrap();
+    }
+
+    #[test]
+    fn cannot_create_order_below_minimums() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        // minimums of 0.1 and 0.01 LUNA in whole units of its 6 decimals
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+                config.min_deposit = Some(Decimal::from_ratio(1u128, 10u128));
+                config.min_dca_amount = Some(Decimal::from_ratio(1u128, 100u128));
+                Ok(config)
+            })
+            .unwrap();
+
+        let create = |deps: DepsMut, amount: u128, dca_amount: u128| {
+            execute(
+                deps,
+                env.clone(),
+                mock_info("creator", &coins(amount, "uluna")),
+                ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(amount),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(dca_amount),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+            )
+        };
+
+        let res = create(deps.as_mut(), 50_000, 10_000).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::DepositBelowMinimum {
+                min: Uint128::new(100_000)
+            }
+        );
+
+        let res = create(deps.as_mut(), 100_000, 5_000).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::DcaAmountBelowMinimum {
+                min: Uint128::new(10_000)
+            }
+        );
+
+        create(deps.as_mut(), 100_000, 10_000).unwrap();
+
+        // a minimum too large to be scaled into the smallest unit of the asset is an error
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+                config.min_deposit = Some(Decimal::MAX);
+                Ok(config)
+            })
+            .unwrap();
+        let res = create(deps.as_mut(), 100_000, 10_000).unwrap_err();
+        assert!(matches!(res, ContractError::OverflowError(_)));
+    }
+
+    #[test]
+    fn cannot_create_native_pull_order() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
 
-        // does not panic when using size of zero to create order
         let res = execute(
             deps.as_mut(),
             mock_env(),
@@ -391,18 +3313,47 @@ mod tests {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset,
                 target_asset,
-                interval: 1_000,
-                dca_amount: Uint128::new(0),
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: true,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap_err();
-        assert_eq!(
-            res,
-            ContractError::Std(StdError::DivideByZero {
-                source: DivideByZeroError::new("100000")
-            })
-        );
+        assert_eq!(res, ContractError::InvalidPullOrder {});
     }
 
     #[test]
@@ -426,9 +3377,43 @@ mod tests {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset,
                 target_asset,
-                interval: 1_000,
+                interval: Duration::Time(1_000),
                 dca_amount: Uint128::new(25_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap_err();
@@ -496,9 +3481,43 @@ mod tests {
                 &ExecuteMsg::CreateDcaOrder {
                     initial_asset,
                     target_asset,
-                    interval: 1000,
+                    interval: Duration::Time(1000),
                     dca_amount: Uint128::new(25_000),
                     first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
                 &[],
             )
@@ -578,9 +3597,43 @@ mod tests {
                     target_asset: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
-                    interval: 500,
+                    interval: Duration::Time(500),
                     dca_amount: Uint128::new(5_000),
                     first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
                 &[],
             )
@@ -602,6 +3655,83 @@ mod tests {
         create_order(&mut app).unwrap();
     }
 
+    #[test]
+    fn can_create_multiple_orders_same_asset() {
+        let mut deps = mock_dependencies();
+
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+        let mut create_order = |amount: u128, interval: Duration, dca_amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &coins(amount, "uluna")),
+                ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(amount),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: target_asset.clone(),
+                    interval,
+                    schedule,
+                    max_delay,
+                    jitter,
+                    dca_amount: Uint128::new(dca_amount),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+            )
+            .unwrap();
+        };
+
+        // two uluna -> ukrw strategies with different intervals and amounts
+        create_order(100_000, Duration::Time(1_000), 25_000);
+        create_order(50_000, Duration::Height(14_400), 10_000);
+
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(
+            orders
+                .iter()
+                .map(|o| (o.id, o.interval))
+                .collect::<Vec<_>>(),
+            vec![(1, Duration::Time(1_000)), (2, Duration::Height(14_400))]
+        );
+    }
+
     #[test]
     fn does_increment_id() {
         let mut deps = mock_dependencies();
@@ -624,9 +3754,43 @@ mod tests {
                 ExecuteMsg::CreateDcaOrder {
                     initial_asset: initial_asset.clone(),
                     target_asset: target_asset.clone(),
-                    interval: 1_000,
+                    interval: Duration::Time(1_000),
                     dca_amount: Uint128::new(25_000),
                     first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
             )
             .unwrap();
@@ -635,9 +3799,7 @@ mod tests {
         create_order();
 
         // check that it got added to state
-        let orders = USER_DCA
-            .load(&deps.storage, &mock_creator().sender)
-            .unwrap();
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
 
         assert_eq!(
             orders,
@@ -647,16 +3809,102 @@ mod tests {
                     dca_amount: Uint128::new(25_000),
                     initial_asset: initial_asset.clone(),
                     target_asset: target_asset.clone(),
-                    interval: 1_000,
-                    last_purchase: 0
+                    interval: Duration::Time(1_000),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
                 },
                 DcaInfo {
                     id: 2,
                     dca_amount: Uint128::new(25_000),
                     initial_asset,
                     target_asset,
-                    interval: 1_000,
-                    last_purchase: 0
+                    interval: Duration::Time(1_000),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
                 }
             ]
         );
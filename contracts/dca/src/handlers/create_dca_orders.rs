@@ -0,0 +1,264 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::{CreateDcaOrderResponse, CreateDcaOrdersResponse};
+use cosmwasm_std::{attr, from_binary, to_binary, DepsMut, Env, MessageInfo, Response};
+
+use crate::error::ContractError;
+
+use super::create_dca_order::{
+    store_dca_order, validate_dca_order, validate_token_allowance, CreateDcaOrder,
+};
+
+/// ## Description
+/// Creates multiple new DCA orders for a user at once, where each order is created as in
+/// [`create_dca_order`](super::create_dca_order). Either every order is created, or none are.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes of each order if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to create their orders, containing the
+/// total amount of each [`AssetInfo::NativeToken`] spent by the orders.
+///
+/// * `orders` - The [`Vec<CreateDcaOrder>`] parameters of each new order.
+pub fn create_dca_orders(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    orders: Vec<CreateDcaOrder>,
+) -> Result<Response, ContractError> {
+    if orders.is_empty() {
+        return Err(ContractError::NoOrdersProvided {});
+    }
+
+    // validate each order, and sum the amount of each native token spent by the orders
+    let mut native_totals: Vec<Asset> = Vec::new();
+    for order in &orders {
+        validate_dca_order(
+            &order.initial_asset,
+            &order.target_asset,
+            order.dca_amount,
+            &order.strategy,
+        )?;
+
+        if let AssetInfo::NativeToken { .. } = &order.initial_asset.info {
+            match native_totals
+                .iter_mut()
+                .find(|total| total.info == order.initial_asset.info)
+            {
+                Some(total) => {
+                    total.amount = total.amount.checked_add(order.initial_asset.amount)?
+                }
+                None => native_totals.push(order.initial_asset.clone()),
+            }
+        }
+    }
+
+    // check that the native tokens sent cover the orders of each native token exactly
+    for total in &native_totals {
+        total.assert_sent_native_token_balance(&info)?;
+    }
+
+    let mut attributes = vec![
+        attr("action", "create_dca_orders"),
+        attr("orders", orders.len().to_string()),
+    ];
+
+    let mut ids = vec![];
+    for order in orders {
+        // each stored order reserves its allowance, so the allowance must cover the orders before
+        // it in the batch as well, unless the order pulls each purchase as it is performed
+        if let (AssetInfo::Token { contract_addr }, false) = (&order.initial_asset.info, order.pull)
+        {
+            validate_token_allowance(
+                &deps.as_ref(),
+                &env,
+                &info.sender,
+                contract_addr,
+                order.initial_asset.amount,
+            )?;
+        }
+
+        let res = store_dca_order(deps.branch(), &env, &info.sender, order, false)?;
+        attributes.extend(res.attributes);
+
+        if let Some(data) = res.data {
+            ids.push(from_binary::<CreateDcaOrderResponse>(&data)?.id);
+        }
+    }
+
+    Ok(Response::new()
+        .set_data(to_binary(&CreateDcaOrdersResponse { ids })?)
+        .add_attributes(attributes))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        CreateDcaOrderParams, CreateDcaOrdersResponse, Delivery, Duration, ExecuteMsg,
+        FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        attr, coin, from_binary,
+        testing::{mock_dependencies, mock_env, mock_info},
+        StdError, Uint128,
+    };
+
+    use crate::{contract::execute, error::ContractError, state::get_user_orders};
+
+    fn order(amount: u128, denom: &str, target: &str) -> CreateDcaOrderParams {
+        CreateDcaOrderParams {
+            initial_asset: Asset {
+                amount: Uint128::new(amount),
+                info: AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: target.to_string(),
+            },
+            interval: Duration::Time(1_000),
+            dca_amount: Uint128::new(10_000),
+            first_purchase: None,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            owner: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            max_purchases: None,
+            pull: false,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+            allow_low_liquidity: false,
+        }
+    }
+
+    #[test]
+    fn does_create_orders() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[coin(50_000, "uluna"), coin(10_000, "ukrw")]),
+            ExecuteMsg::CreateDcaOrders {
+                orders: vec![
+                    order(20_000, "uluna", "ukrw"),
+                    order(10_000, "ukrw", "uluna"),
+                    order(30_000, "uluna", "ujpy"),
+                ],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.attributes[..2],
+            vec![attr("action", "create_dca_orders"), attr("orders", "3")]
+        );
+        assert_eq!(
+            from_binary::<CreateDcaOrdersResponse>(&res.data.unwrap()).unwrap(),
+            CreateDcaOrdersResponse { ids: vec![1, 2, 3] }
+        );
+
+        let orders = get_user_orders(&deps.storage, &mock_info("creator", &[]).sender).unwrap();
+        assert_eq!(
+            orders
+                .iter()
+                .map(|o| (o.id, o.initial_asset.amount.u128()))
+                .collect::<Vec<_>>(),
+            vec![(1, 20_000), (2, 10_000), (3, 30_000)]
+        );
+    }
+
+    #[test]
+    fn does_require_total_native_sent() {
+        let mut deps = mock_dependencies();
+
+        // only enough uluna was sent for the first order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[coin(20_000, "uluna")]),
+            ExecuteMsg::CreateDcaOrders {
+                orders: vec![
+                    order(20_000, "uluna", "ukrw"),
+                    order(30_000, "uluna", "ujpy"),
+                ],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            res,
+            ContractError::Std(StdError::generic_err(
+                "Native token balance mismatch between the argument and the transferred"
+            ))
+        );
+
+        // no order should have been stored
+        let orders = get_user_orders(&deps.storage, &mock_info("creator", &[]).sender).unwrap();
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn does_validate_every_order() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[coin(25_000, "uluna")]),
+            ExecuteMsg::CreateDcaOrders {
+                orders: vec![
+                    order(20_000, "uluna", "ukrw"),
+                    order(5_000, "uluna", "ujpy"),
+                ],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::DepositTooSmall {});
+    }
+
+    #[test]
+    fn cannot_create_no_orders() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::CreateDcaOrders { orders: vec![] },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::NoOrdersProvided {});
+    }
+}
@@ -0,0 +1,471 @@
+use astroport_dca::dca::{DcaInfo, PurchaseRequest};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::{
+    error::ContractError,
+    find_best_route::find_best_route,
+    state::{
+        order_purchase_key, user_dca, NextPurchaseKey, CONFIG, HEIGHT_UNIT, SCHEDULER_CURSOR,
+        TIME_UNIT, USER_CONFIG,
+    },
+};
+
+use super::perform_dca_purchases;
+
+/// The maximum amount of DCA purchases performed by the chain scheduler at once
+const MAX_SCHEDULED_PURCHASES: u32 = 30;
+/// The maximum amount of entries of the `next_purchase` index read by the chain scheduler at once,
+/// so that the orders that are not due before the due orders, such as orders that expired while
+/// due, cannot hold back the scheduler
+const MAX_SCANNED_ORDERS: usize = 50;
+
+/// The next purchase key, user address and id of an order read by the chain scheduler
+type ScheduleKey = (NextPurchaseKey, Addr, u64);
+
+/// The DCA orders read from the `next_purchase` index by the chain scheduler
+#[derive(Default)]
+struct DueOrders {
+    /// The orders that a purchase can currently be performed for
+    orders: Vec<(ScheduleKey, DcaInfo)>,
+    /// The key of the last order read, whether it was due or not
+    last_scanned: Option<ScheduleKey>,
+    /// The amount of orders read
+    scanned: usize,
+}
+
+/// ## Description
+/// Reads the DCA orders that a purchase can currently be performed for into `due`, like
+/// [`crate::queries::get_ready_orders`] does, starting after the key of `start_after` and up to
+/// the key of `end`, until `due` holds `limit` orders or [`MAX_SCANNED_ORDERS`] orders have been
+/// read.
+fn due_orders(
+    storage: &dyn Storage,
+    env: &Env,
+    start_after: Option<&ScheduleKey>,
+    end: Option<&ScheduleKey>,
+    limit: usize,
+    due: &mut DueOrders,
+) -> StdResult<()> {
+    // the orders with an interval in blocks are indexed before the orders with an interval in
+    // seconds, and each are due up to the current block in their own unit
+    let first = Addr::unchecked("");
+    for now in [
+        (HEIGHT_UNIT, env.block.height),
+        (TIME_UNIT, env.block.time.seconds()),
+    ] {
+        let min = match start_after {
+            Some((key, ..)) if key.0 > now.0 => continue,
+            Some((key, user, id)) if key.0 == now.0 => Bound::exclusive((*key, (user, *id))),
+            _ => Bound::inclusive(((now.0, 0), (&first, 0))),
+        };
+
+        for item in user_dca()
+            .idx
+            .next_purchase
+            .range(storage, Some(min), None, Order::Ascending)
+        {
+            if due.orders.len() >= limit || due.scanned >= MAX_SCANNED_ORDERS {
+                return Ok(());
+            }
+
+            let ((user, id), order) = item?;
+            let key = (order_purchase_key(&order), user, id);
+            if key.0 > now || end.map_or(false, |end| &key > end) {
+                break;
+            }
+
+            due.scanned += 1;
+            due.last_scanned = Some(key.clone());
+
+            let expired = order
+                .expires_at
+                .as_ref()
+                .map_or(false, |expires_at| expires_at.is_expired(&env.block));
+            if order.is_due(&env.block) && !order.paused && !order.executing && !expired {
+                due.orders.push((key, order));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Performs the DCA purchases of up to `limit` due orders without a bot, as called by the
+/// scheduler of a chain supporting native scheduling such as the cron module of Neutron. The
+/// purchases are performed by the contract itself through [`perform_dca_purchases`], so that a
+/// purchase that fails is recorded on its order without reverting the others, and pay no tips.
+///
+/// The orders are taken in the order of their next purchase, starting after the last order read
+/// by the previous call and wrapping around to the first due order, so that orders whose purchases
+/// keep failing do not hold back the other orders. At most [`MAX_SCANNED_ORDERS`] orders are read
+/// at once, whether they are due or not. Orders without a stored route
+/// are routed through the best route found on-chain, and are skipped if there is none.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `limit` - The maximum [`u32`] amount of DCA purchases to perform.
+pub fn sudo_execute_due_purchases(
+    deps: DepsMut,
+    env: Env,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.paused {
+        return Err(ContractError::ContractPaused {});
+    }
+
+    let limit = limit.min(MAX_SCHEDULED_PURCHASES) as usize;
+    let cursor = SCHEDULER_CURSOR.may_load(deps.storage)?;
+
+    let mut due = DueOrders::default();
+    due_orders(deps.storage, &env, cursor.as_ref(), None, limit, &mut due)?;
+
+    // wrap around to the due orders up to the cursor
+    if let Some(cursor) = &cursor {
+        due_orders(deps.storage, &env, None, Some(cursor), limit, &mut due)?;
+    }
+
+    let mut purchases = vec![];
+    for (key, order) in &due.orders {
+        let max_hops = USER_CONFIG
+            .may_load(deps.storage, &key.1)?
+            .and_then(|user_config| user_config.max_hops)
+            .unwrap_or(config.max_hops);
+
+        // the route of an order routed by the bot is found on-chain
+        let hops = match order {
+            DcaInfo {
+                route: None,
+                auto_route: false,
+                ..
+            } => match find_best_route(
+                &deps.as_ref(),
+                &config,
+                max_hops,
+                &order.initial_asset.info,
+                &order.target_asset,
+                order.dca_amount,
+            ) {
+                Ok(hops) => hops,
+                Err(_) => continue,
+            },
+            _ => vec![],
+        };
+
+        purchases.push(PurchaseRequest {
+            user: key.1.to_string(),
+            id: key.2,
+            hops,
+            fee_redeem: vec![],
+            deadline: None,
+            accepted_tips: vec![],
+        });
+    }
+
+    if let Some(key) = &due.last_scanned {
+        SCHEDULER_CURSOR.save(deps.storage, key)?;
+    }
+
+    let attrs = vec![
+        attr("action", "sudo_execute_due_purchases"),
+        attr("purchases", purchases.len().to_string()),
+    ];
+
+    if purchases.is_empty() {
+        return Ok(Response::new().add_attributes(attrs));
+    }
+
+    let contract = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    Ok(perform_dca_purchases(deps, env, contract, purchases)?.add_attributes(attrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy, SudoMsg,
+    };
+    use cosmwasm_std::{coins, Uint128};
+    use cw20::Expiration;
+    use cw_multi_test::{App, Executor};
+
+    use crate::tests::{mock_astroport_instantiate, mock_creator, read_user_orders};
+
+    use super::MAX_SCANNED_ORDERS;
+
+    #[test]
+    fn does_execute_due_purchases() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        for _ in 0..2 {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(20_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(20_000, "uluna"),
+            )
+            .unwrap();
+        }
+
+        // only the first order is purchased within the limit, routed through the pool of the pair
+        app.wasm_sudo(dca_addr.clone(), &SudoMsg::ExecuteDuePurchases { limit: 1 })
+            .unwrap();
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert!(!orders[0].total_received.is_zero());
+        assert!(orders[1].total_spent.is_zero());
+
+        // the next call starts after the first order, which is no longer due
+        app.wasm_sudo(
+            dca_addr.clone(),
+            &SudoMsg::ExecuteDuePurchases { limit: 10 },
+        )
+        .unwrap();
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].total_spent, Uint128::new(10_000));
+        assert_eq!(orders[1].total_spent, Uint128::new(10_000));
+        assert!(!orders[1].total_received.is_zero());
+
+        // no orders are due until their interval has passed
+        let res = app
+            .wasm_sudo(dca_addr, &SudoMsg::ExecuteDuePurchases { limit: 10 })
+            .unwrap();
+        assert!(res.events.iter().any(|event| event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "purchases" && attr.value == "0")));
+    }
+
+    #[test]
+    fn does_execute_due_purchases_in_blocks() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        for interval in [Duration::Height(10), Duration::Time(500)] {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval,
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(30_000, "uluna"),
+            )
+            .unwrap();
+        }
+
+        let execute = |app: &mut App| {
+            app.wasm_sudo(
+                dca_addr.clone(),
+                &SudoMsg::ExecuteDuePurchases { limit: 10 },
+            )
+            .unwrap();
+            read_user_orders(app, dca_addr.clone(), &mock_creator().sender)
+        };
+
+        let orders = execute(&mut app);
+        assert_eq!(orders[0].total_spent, Uint128::new(10_000));
+        assert_eq!(orders[1].total_spent, Uint128::new(10_000));
+
+        // the order in blocks is not due once the interval has passed in seconds
+        app.update_block(|block| block.time = block.time.plus_seconds(500));
+        let orders = execute(&mut app);
+        assert_eq!(orders[0].total_spent, Uint128::new(10_000));
+        assert_eq!(orders[1].total_spent, Uint128::new(20_000));
+
+        // but once it has passed in blocks
+        app.update_block(|block| block.height += 10);
+        let orders = execute(&mut app);
+        assert_eq!(orders[0].total_spent, Uint128::new(20_000));
+        assert_eq!(orders[1].total_spent, Uint128::new(20_000));
+    }
+    #[test]
+    fn does_cap_scanned_orders() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, amount: u128, expires_at: Option<Expiration>| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(amount),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(amount),
+                    first_purchase: None,
+                    expires_at,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(amount, "uluna"),
+            )
+            .unwrap();
+        };
+
+        // the orders expire while they are due, before the order created after them
+        let expires_at = Expiration::AtTime(app.block_info().time.plus_seconds(100));
+        for _ in 0..MAX_SCANNED_ORDERS {
+            create_order(&mut app, 10, Some(expires_at));
+        }
+        create_order(&mut app, 10_000, None);
+        app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+        let execute = |app: &mut App, purchases: &str| {
+            let res = app
+                .wasm_sudo(
+                    dca_addr.clone(),
+                    &SudoMsg::ExecuteDuePurchases { limit: 10 },
+                )
+                .unwrap();
+            assert!(res.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "purchases" && attr.value == purchases)));
+        };
+
+        // the expired orders use up the orders read by the first call
+        execute(&mut app, "0");
+
+        // the next call starts after them
+        execute(&mut app, "1");
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[MAX_SCANNED_ORDERS].total_spent, Uint128::new(10_000));
+    }
+}
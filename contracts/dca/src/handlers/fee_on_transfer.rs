@@ -0,0 +1,366 @@
+use cosmwasm_std::{
+    attr, to_binary, Addr, DepsMut, Env, QuerierWrapper, Response, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    state::{PendingTransfer, CONFIG, PENDING_PURCHASE, PENDING_TRANSFER},
+};
+
+use super::auto_stake::query_balance;
+
+/// The id of the reply to transferring a token taking a tax on transfers into the contract for a
+/// DCA purchase
+pub const FEE_ON_TRANSFER_REPLY_ID: u64 = 5;
+
+/// ## Description
+/// Creates the submessage transferring the `swap_amount` and `provide_amount` of a DCA purchase
+/// spending the token `token`, which takes a tax on transfers, from the allowance of `owner` into
+/// the contract, storing the balance of the contract so that the amount actually received can be
+/// measured once the transfer replies.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns the [`SubMsg`] of the transfer.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `querier` - The [`QuerierWrapper`] used to query the balance of the contract.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `token` - The [`Addr`] of the token taking a tax on transfers.
+///
+/// * `owner` - The [`Addr`] of the user whose allowance the token is spent from.
+///
+/// * `swap_amount` - The [`Uint128`] amount of the token to swap.
+///
+/// * `provide_amount` - The [`Uint128`] amount of the token to provide as liquidity.
+pub(crate) fn pull_fee_on_transfer_token(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    token: &Addr,
+    owner: &Addr,
+    swap_amount: Uint128,
+    provide_amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+    let balance = query_balance(querier, token, &env.contract.address)?;
+    PENDING_TRANSFER.save(
+        storage,
+        &PendingTransfer {
+            token: token.clone(),
+            balance,
+            swap_amount,
+            provide_amount,
+        },
+    )?;
+
+    Ok(SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: owner.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: swap_amount.checked_add(provide_amount)?,
+            })?,
+            funds: vec![],
+        },
+        FEE_ON_TRANSFER_REPLY_ID,
+    ))
+}
+
+/// ## Description
+/// Measures the amount of the token taking a tax on transfers received by the contract for a DCA
+/// purchase, splitting it between the swap and the liquidity provided in the ratio that was
+/// requested, and sends the share to swap to the router, which swaps whatever it receives.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+pub fn fee_on_transfer_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let transfer = PENDING_TRANSFER.load(deps.storage)?;
+    PENDING_TRANSFER.remove(deps.storage);
+
+    let balance = query_balance(&deps.querier, &transfer.token, &env.contract.address)?;
+    let received = balance.checked_sub(transfer.balance)?;
+
+    // the tax is taken from the swap and the liquidity provided alike
+    let requested = transfer.swap_amount.checked_add(transfer.provide_amount)?;
+    let swap_amount = received.multiply_ratio(transfer.swap_amount, requested);
+    let provide_amount = received - swap_amount;
+
+    PENDING_PURCHASE.update(deps.storage, |mut purchase| -> StdResult<_> {
+        purchase.provide_amount = provide_amount;
+        Ok(purchase)
+    })?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: transfer.token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: config.router_addr.to_string(),
+                amount: swap_amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "fee_on_transfer_reply"),
+            attr("requested", requested),
+            attr("received", received),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        factory::PairType,
+        router::{QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+    };
+    use astroport_dca::dca::{
+        Cw20HookMsg, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, to_binary, Addr, Uint128};
+    use cw20::Cw20Coin;
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_item, read_user_orders,
+            store_taxed_token_code,
+        },
+    };
+
+    #[test]
+    fn does_swap_amount_received_of_fee_on_transfer_token() {
+        let (mut app, dca_addr, _) = mock_astroport_instantiate(None);
+        let config = read_item(&app, &dca_addr, CONFIG);
+
+        // a token burning 10% of every transfer
+        let taxed_token_id = store_taxed_token_code(&mut app);
+        let taxed_addr = app
+            .instantiate_contract(
+                taxed_token_id,
+                mock_creator().sender,
+                &cw20_base::msg::InstantiateMsg {
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: mock_creator().sender.into_string(),
+                        amount: Uint128::new(500_000),
+                    }],
+                    marketing: None,
+                    mint: None,
+                    name: "taxed token".to_string(),
+                    symbol: "cwTT".to_string(),
+                },
+                &[],
+                "taxed token",
+                None,
+            )
+            .unwrap();
+        let taxed = AssetInfo::Token {
+            contract_addr: taxed_addr.clone(),
+        };
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        let execute = |app: &mut App, contract: &Addr, msg: &ExecuteMsg| {
+            app.execute_contract(mock_creator().sender, contract.clone(), msg, &[])
+        };
+        let increase_allowance = |app: &mut App, spender: &Addr, amount: u128| {
+            app.execute_contract(
+                mock_creator().sender,
+                taxed_addr.clone(),
+                &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: spender.to_string(),
+                    amount: Uint128::new(amount),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        // a pair of the taxed token, holding 270_000 of it once taxed
+        let pair_res = app
+            .execute_contract(
+                mock_creator().sender,
+                config.factory_addr.clone(),
+                &astroport::factory::ExecuteMsg::CreatePair {
+                    pair_type: PairType::Xyk {},
+                    asset_infos: [taxed.clone(), uluna.clone()],
+                    init_params: None,
+                },
+                &[],
+            )
+            .unwrap();
+        let pair_addr = Addr::unchecked(pair_res.events[2].attributes[0].value.clone());
+
+        increase_allowance(&mut app, &pair_addr, 300_000);
+        app.execute_contract(
+            mock_creator().sender,
+            pair_addr,
+            &astroport::pair::ExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        amount: Uint128::new(300_000),
+                        info: taxed.clone(),
+                    },
+                    Asset {
+                        amount: Uint128::new(300_000),
+                        info: uluna.clone(),
+                    },
+                ],
+                slippage_tolerance: None,
+                auto_stake: None,
+                receiver: None,
+            },
+            &coins(300_000, "uluna"),
+        )
+        .unwrap();
+
+        execute(
+            &mut app,
+            &dca_addr,
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: Some(vec![taxed_addr.to_string()]),
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+        )
+        .unwrap();
+
+        // the taxed token cannot be sent to the contract, as the amount received is unknown
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                taxed_addr.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: dca_addr.to_string(),
+                    amount: Uint128::new(10_000),
+                    msg: to_binary(&Cw20HookMsg::AddBotTip {}).unwrap(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::FeeOnTransferSend {}
+        );
+
+        increase_allowance(&mut app, &dca_addr, 100_000);
+        execute(
+            &mut app,
+            &dca_addr,
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: taxed.clone(),
+                },
+                target_asset: uluna.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        // the contract receives 9_000 of the 10_000 pulled, of which the router receives 8_100
+        let hops = vec![SwapOperation::AstroSwap {
+            offer_asset_info: taxed,
+            ask_asset_info: uluna,
+        }];
+        let simulation: SimulateSwapOperationsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &config.router_addr,
+                &RouterQueryMsg::SimulateSwapOperations {
+                    offer_amount: Uint128::new(8_100),
+                    operations: hops.clone(),
+                },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops,
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(90_000));
+        assert_eq!(orders[0].total_received, simulation.amount);
+    }
+}
@@ -0,0 +1,318 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::IbcLifecycleComplete;
+use cosmwasm_std::{
+    attr, Addr, Coin, DepsMut, Env, Response, Storage, SubMsg, SubMsgResult, Uint128,
+};
+
+use crate::{
+    error::ContractError,
+    ibc_transfer::{transfer_msg, transfer_sequence, Transfer},
+    state::{IbcTransfer, IBC_TRANSFERS, PENDING_IBC_TRANSFER, PURCHASED_BALANCE},
+};
+
+/// The id of the reply to sending the ICS-20 transfer of a DCA purchase with IBC delivery
+pub const IBC_TRANSFER_REPLY_ID: u64 = 7;
+
+/// ## Description
+/// Returns the submessage sending the native `asset` purchased for the DCA order `id` of `user`
+/// to `to_address` over `channel_id` through an ICS-20 transfer timing out after `timeout`
+/// seconds, storing the transfer until the transfer module replies with its sequence.
+///
+/// Returns a [`ContractError::InvalidIbcDelivery`] if `asset` is not a native token.
+pub(crate) fn ibc_transfer_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user: &Addr,
+    id: u64,
+    (channel_id, to_address, timeout): (&str, &str, u64),
+    asset: Asset,
+) -> Result<SubMsg, ContractError> {
+    let denom = match &asset.info {
+        AssetInfo::NativeToken { denom } => denom.clone(),
+        AssetInfo::Token { .. } => return Err(ContractError::InvalidIbcDelivery {}),
+    };
+
+    let msg = transfer_msg(&Transfer {
+        channel_id: channel_id.to_string(),
+        to_address: to_address.to_string(),
+        amount: Coin {
+            denom,
+            amount: asset.amount,
+        },
+        timeout: env.block.time.plus_seconds(timeout),
+        sender: env.contract.address.to_string(),
+    });
+
+    PENDING_IBC_TRANSFER.save(
+        storage,
+        &IbcTransfer {
+            user: user.clone(),
+            id,
+            channel_id: channel_id.to_string(),
+            asset,
+        },
+    )?;
+
+    Ok(SubMsg::reply_always(msg, IBC_TRANSFER_REPLY_ID))
+}
+
+/// ## Description
+/// Holds the asset of a failed ICS-20 transfer in the purchased balance of its order, so that the
+/// user can claim it with [`super::claim_purchased`].
+fn escrow_transfer(storage: &mut dyn Storage, transfer: &IbcTransfer) -> Result<(), ContractError> {
+    PURCHASED_BALANCE.update(
+        storage,
+        (&transfer.user, transfer.id),
+        |balance| -> Result<_, ContractError> {
+            let mut balance = balance.unwrap_or(Asset {
+                info: transfer.asset.info.clone(),
+                amount: Uint128::zero(),
+            });
+            balance.amount = balance.amount.checked_add(transfer.asset.amount)?;
+            Ok(balance)
+        },
+    )?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Stores the ICS-20 transfer of a DCA purchase by the sequence the transfer module replied with,
+/// until ibc-hooks reports its acknowledgement or timeout. The asset of a transfer that could not
+/// be sent is held by the contract for the user to claim instead.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `result` - The [`SubMsgResult`] of sending the transfer.
+pub fn ibc_transfer_reply(deps: DepsMut, result: SubMsgResult) -> Result<Response, ContractError> {
+    let transfer = PENDING_IBC_TRANSFER.load(deps.storage)?;
+    PENDING_IBC_TRANSFER.remove(deps.storage);
+
+    let sequence = result
+        .into_result()
+        .ok()
+        .and_then(|response| response.data)
+        .and_then(|data| transfer_sequence(&data));
+
+    let response = Response::new().add_attributes(vec![
+        attr("action", "ibc_transfer_reply"),
+        attr("user", transfer.user.clone()),
+        attr("id", transfer.id.to_string()),
+    ]);
+
+    match sequence {
+        Some(sequence) => {
+            IBC_TRANSFERS.save(deps.storage, (&transfer.channel_id, sequence), &transfer)?;
+
+            Ok(response.add_attributes(vec![
+                attr("channel", transfer.channel_id),
+                attr("sequence", sequence.to_string()),
+            ]))
+        }
+        None => {
+            escrow_transfer(deps.storage, &transfer)?;
+
+            Ok(response.add_attribute("escrowed", transfer.asset.to_string()))
+        }
+    }
+}
+
+/// ## Description
+/// Settles the ICS-20 transfer of a DCA purchase reported by the ibc-hooks callback of the
+/// transfer. The asset of a transfer that failed to be acknowledged or timed out has been
+/// refunded to the contract, and is held in the purchased balance of its order for the user to
+/// claim.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `msg` - The [`IbcLifecycleComplete`] outcome of the transfer.
+pub fn sudo_ibc_lifecycle_complete(
+    deps: DepsMut,
+    msg: IbcLifecycleComplete,
+) -> Result<Response, ContractError> {
+    let (channel, sequence, success) = match msg {
+        IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            success,
+            ..
+        } => (channel, sequence, success),
+        IbcLifecycleComplete::IbcTimeout { channel, sequence } => (channel, sequence, false),
+    };
+
+    let transfer = IBC_TRANSFERS
+        .may_load(deps.storage, (&channel, sequence))?
+        .ok_or_else(|| ContractError::NonexistentIbcTransfer {
+            channel: channel.clone(),
+            sequence,
+        })?;
+    IBC_TRANSFERS.remove(deps.storage, (&channel, sequence));
+
+    if !success {
+        escrow_transfer(deps.storage, &transfer)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "sudo_ibc_lifecycle_complete"),
+        attr("user", transfer.user),
+        attr("id", transfer.id.to_string()),
+        attr("channel", channel),
+        attr("sequence", sequence.to_string()),
+        attr("success", success.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{IbcLifecycleComplete, SudoMsg};
+    use cosmwasm_std::{Addr, Binary, Reply, SubMsgResponse, SubMsgResult, Uint128};
+
+    use crate::{
+        contract::{reply, sudo},
+        error::ContractError,
+        state::{IbcTransfer, IBC_TRANSFERS, PENDING_IBC_TRANSFER, PURCHASED_BALANCE},
+        tests::mock_instantiate,
+    };
+
+    use super::IBC_TRANSFER_REPLY_ID;
+
+    fn transfer(id: u64) -> IbcTransfer {
+        IbcTransfer {
+            user: Addr::unchecked("user"),
+            id,
+            channel_id: "channel-0".to_string(),
+            asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                amount: Uint128::new(1_000),
+            },
+        }
+    }
+
+    #[test]
+    fn does_store_and_settle_transfers() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        // the transfer module replies with the sequence of the transfer
+        PENDING_IBC_TRANSFER
+            .save(&mut deps.storage, &transfer(1))
+            .unwrap();
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: IBC_TRANSFER_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary(vec![0x08, 0x05])),
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            IBC_TRANSFERS.load(&deps.storage, ("channel-0", 5)).unwrap(),
+            transfer(1)
+        );
+
+        // an acknowledged transfer is settled without holding its asset
+        sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+                channel: "channel-0".to_string(),
+                sequence: 5,
+                ack: "AQ==".to_string(),
+                success: true,
+            }),
+        )
+        .unwrap();
+        assert!(!IBC_TRANSFERS.has(&deps.storage, ("channel-0", 5)));
+        assert!(!PURCHASED_BALANCE.has(&deps.storage, (&Addr::unchecked("user"), 1)));
+
+        // a transfer that timed out is held for the user to claim
+        IBC_TRANSFERS
+            .save(&mut deps.storage, ("channel-0", 6), &transfer(1))
+            .unwrap();
+        sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+                channel: "channel-0".to_string(),
+                sequence: 6,
+            }),
+        )
+        .unwrap();
+        assert!(!IBC_TRANSFERS.has(&deps.storage, ("channel-0", 6)));
+        assert_eq!(
+            PURCHASED_BALANCE
+                .load(&deps.storage, (&Addr::unchecked("user"), 1))
+                .unwrap(),
+            transfer(1).asset
+        );
+
+        // a transfer cannot be settled twice
+        let res = sudo(
+            deps.as_mut(),
+            env,
+            SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+                channel: "channel-0".to_string(),
+                sequence: 6,
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::NonexistentIbcTransfer {
+                channel: "channel-0".to_string(),
+                sequence: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn does_escrow_failed_transfer() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        PENDING_IBC_TRANSFER
+            .save(&mut deps.storage, &transfer(2))
+            .unwrap();
+        reply(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: IBC_TRANSFER_REPLY_ID,
+                result: SubMsgResult::Err("channel is closed".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert!(PENDING_IBC_TRANSFER
+            .may_load(&deps.storage)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            PURCHASED_BALANCE
+                .load(&deps.storage, (&Addr::unchecked("user"), 2))
+                .unwrap(),
+            transfer(2).asset
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use astroport_dca::dca::InterchainController;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{
+    error::ContractError,
+    state::{CONTROLLED_ACCOUNTS, INTERCHAIN_CONTROLLERS},
+};
+
+/// ## Description
+/// Returns whether `controller` is an address on the remote chain of an ibc-go connection, whose
+/// identifiers are of the form `connection-{n}`.
+fn is_valid_controller(controller: &InterchainController) -> bool {
+    let connection = controller
+        .connection_id
+        .strip_prefix("connection-")
+        .map_or(false, |n| n.parse::<u64>().is_ok());
+
+    connection
+        && !controller.address.is_empty()
+        && !controller.address.contains(char::is_whitespace)
+}
+
+/// ## Description
+/// Declares the sender to be an interchain account owned by `controller` on a remote chain, so
+/// that the DCA orders of the sender can be found by their controller. The account itself owns
+/// its orders like any other user, and is the only one able to make the declaration, which front
+/// ends should check against the interchain account registered for the controller on the remote
+/// chain. Removes the declaration if `controller` is not specified.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the interchain account declaring its controller.
+///
+/// * `controller` - The [`InterchainController`] of the account, or [`None`] to remove it.
+pub fn set_interchain_controller(
+    deps: DepsMut,
+    info: MessageInfo,
+    controller: Option<InterchainController>,
+) -> Result<Response, ContractError> {
+    if !controller.as_ref().map_or(true, is_valid_controller) {
+        return Err(ContractError::InvalidInterchainController {});
+    }
+
+    if let Some(previous) = INTERCHAIN_CONTROLLERS.may_load(deps.storage, &info.sender)? {
+        CONTROLLED_ACCOUNTS.remove(
+            deps.storage,
+            (&previous.connection_id, &previous.address, &info.sender),
+        );
+    }
+
+    let attrs = match &controller {
+        Some(controller) => {
+            INTERCHAIN_CONTROLLERS.save(deps.storage, &info.sender, controller)?;
+            CONTROLLED_ACCOUNTS.save(
+                deps.storage,
+                (&controller.connection_id, &controller.address, &info.sender),
+                &true,
+            )?;
+
+            vec![
+                attr("connection_id", controller.connection_id.clone()),
+                attr("controller", controller.address.clone()),
+            ]
+        }
+        None => {
+            INTERCHAIN_CONTROLLERS.remove(deps.storage, &info.sender);
+
+            vec![attr("controller", "none")]
+        }
+    };
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "set_interchain_controller"),
+            attr("user", info.sender),
+        ])
+        .add_attributes(attrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::{ExecuteMsg, InterchainController};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr,
+    };
+
+    use crate::{
+        contract::execute,
+        error::ContractError,
+        state::{CONTROLLED_ACCOUNTS, INTERCHAIN_CONTROLLERS},
+    };
+
+    #[test]
+    fn does_set_interchain_controller() {
+        let mut deps = mock_dependencies();
+        let account = Addr::unchecked("ica");
+        let controller = |connection_id: &str, address: &str| InterchainController {
+            connection_id: connection_id.to_string(),
+            address: address.to_string(),
+        };
+
+        // the controller must be on the remote chain of a connection
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ica", &[]),
+            ExecuteMsg::SetInterchainController {
+                controller: Some(controller("channel-0", "osmo1controller")),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidInterchainController {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ica", &[]),
+            ExecuteMsg::SetInterchainController {
+                controller: Some(controller("connection-0", "osmo1controller")),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            INTERCHAIN_CONTROLLERS
+                .load(&deps.storage, &account)
+                .unwrap(),
+            controller("connection-0", "osmo1controller")
+        );
+        assert!(
+            CONTROLLED_ACCOUNTS.has(&deps.storage, ("connection-0", "osmo1controller", &account))
+        );
+
+        // changing the controller no longer lists the account under the previous one
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ica", &[]),
+            ExecuteMsg::SetInterchainController {
+                controller: Some(controller("connection-1", "juno1controller")),
+            },
+        )
+        .unwrap();
+        assert!(
+            !CONTROLLED_ACCOUNTS.has(&deps.storage, ("connection-0", "osmo1controller", &account))
+        );
+        assert!(
+            CONTROLLED_ACCOUNTS.has(&deps.storage, ("connection-1", "juno1controller", &account))
+        );
+
+        // and removing it lists the account under none
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ica", &[]),
+            ExecuteMsg::SetInterchainController { controller: None },
+        )
+        .unwrap();
+        assert!(!INTERCHAIN_CONTROLLERS.has(&deps.storage, &account));
+        assert!(
+            !CONTROLLED_ACCOUNTS.has(&deps.storage, ("connection-1", "juno1controller", &account))
+        );
+    }
+}
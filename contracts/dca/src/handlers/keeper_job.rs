@@ -0,0 +1,400 @@
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport_dca::dca::{DcaInfo, ExecuteMsg};
+use cosmwasm_std::{attr, coins, to_binary, DepsMut, Env, MessageInfo, Response, WasmMsg};
+
+use crate::{
+    croncat::{Action, CroncatExecuteMsg, Interval, TaskRequest},
+    error::ContractError,
+    state::{user_dca, CONFIG, KEEPER, USER_CONFIG},
+};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Sets the tasks contract of the CronCat automation protocol that keeper jobs of DCA orders are
+/// registered on, or removes it if `keeper` is [`None`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to set the keeper.
+///
+/// * `keeper` - The address of the tasks contract, or [`None`] to remove the keeper.
+pub fn set_keeper(
+    deps: DepsMut,
+    info: MessageInfo,
+    keeper: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let keeper = match keeper {
+        Some(keeper) => {
+            let keeper = addr_validate_to_lower(deps.api, &keeper)?;
+            KEEPER.save(deps.storage, &keeper)?;
+            keeper.into_string()
+        }
+        None => {
+            KEEPER.remove(deps.storage);
+            "none".to_string()
+        }
+    };
+
+    Ok(Response::new().add_attributes(vec![attr("action", "set_keeper"), attr("keeper", keeper)]))
+}
+
+/// ## Description
+/// Registers a recurring task on the keeper that performs the DCA purchases of the order `id` of
+/// `user` every `interval` blocks, so that the purchases do not depend on bots watching the
+/// order. The task is funded with the native `funds` taken from the tip balance of the user,
+/// which pay the agents of the keeper instead of the tips of each purchase.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the user of the order, or the contract owner.
+///
+/// * `id` - The [`u64`] id of the DCA order to register the keeper job of.
+///
+/// * `user` - The user the DCA order is stored under, which is the sender if [`None`].
+///
+/// * `interval` - The [`u64`] amount of blocks between the runs of the job.
+///
+/// * `funds` - The native [`Asset`] taken from the tip balance of the user to fund the job.
+pub fn register_keeper_job(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    user: Option<String>,
+    interval: u64,
+    funds: Asset,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let keeper = KEEPER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoKeeper {})?;
+
+    let user = match user {
+        Some(user) => addr_validate_to_lower(deps.api, &user)?,
+        None => info.sender.clone(),
+    };
+    if user != info.sender && info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the purchases of the job are performed without hops, so the order must route them itself
+    let order = user_dca()
+        .may_load(deps.storage, (&user, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+    if let DcaInfo {
+        route: None,
+        auto_route: false,
+        ..
+    } = order
+    {
+        return Err(ContractError::InvalidKeeperJob {});
+    }
+
+    let denom = match &funds.info {
+        AssetInfo::NativeToken { denom }
+            if interval > 0
+                && !funds.amount.is_zero()
+                && config.is_whitelisted_fee_asset(&funds.info) =>
+        {
+            denom.clone()
+        }
+        _ => return Err(ContractError::InvalidKeeperJob {}),
+    };
+
+    let mut user_config = USER_CONFIG
+        .may_load(deps.storage, &user)?
+        .unwrap_or_default();
+    let balance = user_config
+        .tip_balance
+        .iter_mut()
+        .find(|a| a.info == funds.info)
+        .ok_or(ContractError::InsufficientTipBalance {})?;
+    balance.amount = balance
+        .amount
+        .checked_sub(funds.amount)
+        .map_err(|_| ContractError::InsufficientTipBalance {})?;
+    USER_CONFIG.save(deps.storage, &user, &user_config)?;
+
+    let purchase = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::PerformDcaPurchase {
+            user: user.to_string(),
+            id,
+            hops: vec![],
+            fee_redeem: vec![],
+            deadline: None,
+            accepted_tips: vec![],
+        })?,
+        funds: vec![],
+    };
+
+    let task = WasmMsg::Execute {
+        contract_addr: keeper.to_string(),
+        msg: to_binary(&CroncatExecuteMsg::CreateTask {
+            task: Box::new(TaskRequest {
+                interval: Interval::Block(interval),
+                stop_on_fail: false,
+                actions: vec![Action {
+                    msg: purchase.into(),
+                    gas_limit: None,
+                }],
+            }),
+        })?,
+        funds: coins(funds.amount.u128(), denom),
+    };
+
+    Ok(Response::new().add_message(task).add_attributes(vec![
+        attr("action", "register_keeper_job"),
+        attr("user", user),
+        attr("id", id.to_string()),
+        attr("keeper", keeper),
+        attr("interval", interval.to_string()),
+        attr("funds", funds.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        coins, from_binary, testing::mock_info, to_binary, Addr, CosmosMsg, StdResult, Uint128,
+        WasmMsg,
+    };
+
+    use crate::{
+        contract::execute,
+        croncat::{Action, CroncatExecuteMsg, Interval, TaskRequest},
+        error::ContractError,
+        state::{user_dca, CONFIG, USER_CONFIG},
+        tests::{mock_creator, mock_instantiate},
+    };
+
+    #[test]
+    fn does_register_keeper_job() {
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(1_000),
+                info: uluna.clone(),
+            }],
+            vec![],
+        );
+        let user = Addr::unchecked("user");
+
+        // the owner is set rather than queried from the factory
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.owner = Some(mock_creator().sender);
+                Ok(config)
+            })
+            .unwrap();
+
+        let order = DcaInfo {
+            id: 1,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            last_purchase: 0,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: true,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+        user_dca()
+            .save(&mut deps.storage, (&user, 1), &order)
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(10_000, "uluna")),
+            ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(10_000),
+                    info: uluna.clone(),
+                }],
+            },
+        )
+        .unwrap();
+
+        let register = |user: Option<String>, amount: u128| ExecuteMsg::RegisterKeeperJob {
+            id: 1,
+            user,
+            interval: 100,
+            funds: Asset {
+                amount: Uint128::new(amount),
+                info: uluna.clone(),
+            },
+        };
+
+        // jobs cannot be registered before the owner sets a keeper
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            register(None, 4_000),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::NoKeeper {});
+
+        // which only the owner can set
+        let set_keeper = ExecuteMsg::SetKeeper {
+            keeper: Some("croncat".to_string()),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            set_keeper.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_creator(), set_keeper).unwrap();
+
+        // the user funds the job of their order from their tip balance
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            register(None, 4_000),
+        )
+        .unwrap();
+        let purchase = WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::PerformDcaPurchase {
+                user: "user".to_string(),
+                id: 1,
+                hops: vec![],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            })
+            .unwrap(),
+            funds: vec![],
+        };
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                assert_eq!(contract_addr, "croncat");
+                assert_eq!(funds, &coins(4_000, "uluna"));
+                assert_eq!(
+                    from_binary::<CroncatExecuteMsg>(msg).unwrap(),
+                    CroncatExecuteMsg::CreateTask {
+                        task: Box::new(TaskRequest {
+                            interval: Interval::Block(100),
+                            stop_on_fail: false,
+                            actions: vec![Action {
+                                msg: purchase.into(),
+                                gas_limit: None,
+                            }],
+                        }),
+                    }
+                );
+            }
+            msg => panic!("unexpected message {:?}", msg),
+        }
+        assert_eq!(
+            USER_CONFIG.load(&deps.storage, &user).unwrap().tip_balance[0].amount,
+            Uint128::new(6_000)
+        );
+
+        // the owner can register jobs for the user, but no one else can
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other", &[]),
+            register(Some("user".to_string()), 4_000),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_creator(),
+            register(Some("user".to_string()), 4_000),
+        )
+        .unwrap();
+
+        // and the job cannot take more than the tip balance of the user
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("user", &[]),
+            register(None, 4_000),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InsufficientTipBalance {});
+    }
+}
@@ -0,0 +1,324 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{attr, Addr, Deps, DepsMut, MessageInfo, Response};
+
+use crate::{
+    cw4::{Cw4QueryMsg, MemberResponse, TotalWeightResponse},
+    error::ContractError,
+    state::{MEMBER_GROUPS, OPERATORS},
+};
+
+use super::nft::get_order_holder;
+
+/// ## Description
+/// Returns the user the DCA order `id` managed by `sender` is stored under, along with the owner
+/// of the order, like [`super::set_operator::get_order_owner`] does, while also allowing `sender`
+/// to be a member of the cw4 group set by the owner. Only claims, pauses and resumes are allowed
+/// for the members of the group, which never move funds anywhere but to the owner.
+///
+/// Returns a [`ContractError::Unauthorized`] if `sender` is neither the owner of the order, an
+/// operator of the owner nor a member of the group of the owner.
+pub(crate) fn get_order_owner_or_member(
+    deps: Deps,
+    sender: &Addr,
+    user: Option<String>,
+    id: u64,
+) -> Result<(Addr, Addr), ContractError> {
+    let user = match user {
+        Some(user) => addr_validate_to_lower(deps.api, &user)?,
+        None => sender.clone(),
+    };
+
+    let owner = get_order_holder(deps, &user, id)?.unwrap_or_else(|| user.clone());
+    if &owner != sender
+        && !OPERATORS.has(deps.storage, (&owner, sender))
+        && !is_group_member(deps, &owner, sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok((user, owner))
+}
+
+/// ## Description
+/// Returns whether `sender` is a member of the cw4 group set by `owner`, which is never the case
+/// if the owner has not set a group.
+fn is_group_member(deps: Deps, owner: &Addr, sender: &Addr) -> Result<bool, ContractError> {
+    let group = match MEMBER_GROUPS.may_load(deps.storage, owner)? {
+        Some(group) => group,
+        None => return Ok(false),
+    };
+
+    let member: MemberResponse = deps.querier.query_wasm_smart(
+        group,
+        &Cw4QueryMsg::Member {
+            addr: sender.to_string(),
+            at_height: None,
+        },
+    )?;
+
+    Ok(member.weight.is_some())
+}
+
+/// ## Description
+/// Sets the cw4 `group` whose members can claim the purchases of and pause and resume the DCA
+/// orders of the sender, such as the group of the members of a multisig or DAO contract sending
+/// the message. Any funds claimed by the members are sent to the sender. Removes the group if it
+/// is not specified.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the user who wants to set the group of their orders.
+///
+/// * `group` - The address of the cw4 group contract, or [`None`] to remove the group.
+pub fn set_member_group(
+    deps: DepsMut,
+    info: MessageInfo,
+    group: Option<String>,
+) -> Result<Response, ContractError> {
+    match &group {
+        Some(group) => {
+            let group = addr_validate_to_lower(deps.api, group)?;
+
+            // check that the group answers the queries of a cw4 group
+            deps.querier
+                .query_wasm_smart::<TotalWeightResponse>(
+                    &group,
+                    &Cw4QueryMsg::TotalWeight { at_height: None },
+                )
+                .map_err(|_| ContractError::InvalidMemberGroup {})?;
+
+            MEMBER_GROUPS.save(deps.storage, &info.sender, &group)?;
+        }
+        None => MEMBER_GROUPS.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_member_group"),
+        attr("user", info.sender),
+        attr("group", group.unwrap_or_else(|| "none".to_string())),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_user_orders, store_cw4_group_code,
+            MockCw4GroupInstantiateMsg,
+        },
+    };
+
+    #[test]
+    fn does_allow_members_to_claim_and_pause() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let dao = Addr::unchecked("dao");
+        let member = Addr::unchecked("member");
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        let group_id = store_cw4_group_code(&mut app);
+        let group_addr = app
+            .instantiate_contract(
+                group_id,
+                Addr::unchecked("admin"),
+                &MockCw4GroupInstantiateMsg {
+                    members: vec![member.to_string()],
+                },
+                &[],
+                "mock cw4 group",
+                None,
+            )
+            .unwrap();
+
+        // the dao owns an order escrowing its purchases
+        app.send_tokens(mock_creator().sender, dao.clone(), &coins(20_000, "uluna"))
+            .unwrap();
+        app.execute_contract(
+            dao.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(20_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(20_000, "uluna"),
+        )
+        .unwrap();
+
+        // a contract that does not answer the queries of a cw4 group cannot be the group
+        let res = app
+            .execute_contract(
+                dao.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::SetMemberGroup {
+                    group: Some(dca_addr.to_string()),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidMemberGroup {}
+        );
+
+        // the order cannot be paused by the member before the dao sets its group
+        let pause = ExecuteMsg::PauseDcaOrder {
+            id: 1,
+            user: Some(dao.to_string()),
+        };
+        let res = app
+            .execute_contract(member.clone(), dca_addr.clone(), &pause, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        app.execute_contract(
+            dao.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::SetMemberGroup {
+                group: Some(group_addr.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: dao.to_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: uluna,
+                    ask_asset_info: ujpy,
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the member pauses the order and claims its purchases, which are sent to the dao
+        app.execute_contract(member.clone(), dca_addr.clone(), &pause, &[])
+            .unwrap();
+        assert!(read_user_orders(&app, dca_addr.clone(), &dao)[0].paused);
+
+        app.execute_contract(
+            member.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimPurchased {
+                id: 1,
+                user: Some(dao.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        let received = read_user_orders(&app, dca_addr.clone(), &dao)[0].total_received;
+        assert_eq!(
+            app.wrap().query_balance(&dao, "ujpy").unwrap().amount,
+            received
+        );
+        assert!(app
+            .wrap()
+            .query_balance(&member, "ujpy")
+            .unwrap()
+            .amount
+            .is_zero());
+
+        // but cannot cancel the order
+        let res = app
+            .execute_contract(
+                member,
+                dca_addr.clone(),
+                &ExecuteMsg::CancelDcaOrder {
+                    id: 1,
+                    user: Some(dao.to_string()),
+                    withdraw_tips: false,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        // and neither can anyone outside the group resume it
+        let res = app
+            .execute_contract(
+                Addr::unchecked("outsider"),
+                dca_addr,
+                &ExecuteMsg::ResumeDcaOrder {
+                    id: 1,
+                    user: Some(dao.to_string()),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+    }
+}
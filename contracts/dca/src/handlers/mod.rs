@@ -1,17 +1,103 @@
 mod add_bot_tip;
+mod apply_pending_config;
+mod auto_stake;
+mod blacklist_asset;
+mod bot_bond;
+mod callback;
 mod cancel_dca_order;
+mod cancel_dca_orders;
+mod claim_purchased;
+mod claim_referral_rewards;
 mod create_dca_order;
+mod create_dca_orders;
+mod execute_due_purchases;
+mod fee_on_transfer;
+mod ibc_delivery;
+mod interchain_account;
+mod keeper_job;
+mod member_group;
 mod modify_dca_order;
+mod nft;
+mod notifier;
+mod ownership;
+mod pause_asset;
+mod pause_dca_order;
 mod perform_dca_purchase;
+mod perform_dca_purchase_reply;
+mod perform_dca_purchases;
+mod pooled_order;
+mod prune_archived_orders;
+mod receive;
+mod receive_ibc;
+mod refresh_pair;
+mod register_bot;
+mod resume_dca_order;
+mod set_operator;
+mod set_oracle;
+mod sudo;
+mod sweep_expired;
+mod sweep_surplus;
+mod top_up_dca_order;
+mod transfer_order;
+mod unblacklist_asset;
+mod unpause_asset;
 mod update_config;
 mod update_user_config;
+mod whitelist;
 mod withdraw;
+mod withdraw_from_dca_order;
+mod zap;
 
 pub use add_bot_tip::add_bot_tip;
+pub use apply_pending_config::apply_pending_config;
+pub use auto_stake::{auto_stake_reply, AUTO_STAKE_REPLY_ID};
+pub use blacklist_asset::blacklist_asset;
+pub use bot_bond::{unbond_bot, withdraw_bot_bond};
+pub use callback::callback;
 pub use cancel_dca_order::cancel_dca_order;
+pub use cancel_dca_orders::cancel_dca_orders;
+pub use claim_purchased::claim_purchased;
+pub use claim_referral_rewards::claim_referral_rewards;
 pub use create_dca_order::{create_dca_order, CreateDcaOrder};
+pub use create_dca_orders::create_dca_orders;
+pub use execute_due_purchases::sudo_execute_due_purchases;
+pub use fee_on_transfer::{fee_on_transfer_reply, FEE_ON_TRANSFER_REPLY_ID};
+pub use ibc_delivery::{ibc_transfer_reply, sudo_ibc_lifecycle_complete, IBC_TRANSFER_REPLY_ID};
+pub use interchain_account::set_interchain_controller;
+pub use keeper_job::{register_keeper_job, set_keeper};
+pub use member_group::set_member_group;
 pub use modify_dca_order::{modify_dca_order, ModifyDcaOrderParameters};
-pub use perform_dca_purchase::perform_dca_purchase;
-pub use update_config::update_config;
+pub use nft::{instantiate_nft, instantiate_nft_reply, NFT_INSTANTIATE_REPLY_ID};
+pub use notifier::{notifier_reply, set_notifier, NOTIFIER_REPLY_ID};
+pub use ownership::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+pub use pause_asset::pause_asset;
+pub use pause_dca_order::pause_dca_order;
+pub use perform_dca_purchase::{
+    perform_batched_dca_purchase, perform_dca_purchase, purchase_amounts, purchase_hops,
+    purchase_spend_amount, select_tip, PERFORM_DCA_PURCHASE_REPLY_ID, SPLIT_SWAP_REPLY_ID,
+};
+pub use perform_dca_purchase_reply::{perform_dca_purchase_reply, split_swap_reply};
+pub use perform_dca_purchases::{
+    perform_dca_purchases, perform_dca_purchases_reply, PERFORM_DCA_PURCHASES_REPLY_ID,
+};
+pub use pooled_order::{claim_pool_share, join_pooled_order, leave_pooled_order};
+pub use prune_archived_orders::prune_archived_orders;
+pub use receive::receive;
+pub use receive_ibc::receive_ibc;
+pub use refresh_pair::refresh_pair;
+pub use register_bot::register_bot;
+pub use resume_dca_order::resume_dca_order;
+pub use set_operator::set_operator;
+pub use set_oracle::set_oracle;
+pub use sudo::{sudo_set_paused, sudo_update_owner};
+pub use sweep_expired::sweep_expired;
+pub use sweep_surplus::sweep_surplus;
+pub use top_up_dca_order::top_up_dca_order;
+pub use transfer_order::transfer_order;
+pub use unblacklist_asset::unblacklist_asset;
+pub use unpause_asset::unpause_asset;
+pub use update_config::{update_config, UpdateConfigParameters};
 pub use update_user_config::update_user_config;
+pub use whitelist::{add_hop_asset, add_tip_asset, remove_hop_asset, remove_tip_asset};
 pub use withdraw::withdraw;
+pub use withdraw_from_dca_order::withdraw_from_dca_order;
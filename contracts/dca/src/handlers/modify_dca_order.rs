@@ -1,7 +1,18 @@
 use astroport::asset::{Asset, AssetInfo};
-use cosmwasm_std::{attr, coins, BankMsg, DepsMut, Env, MessageInfo, Response, Uint128};
+use astroport_dca::dca::{Duration, Schedule};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, Uint128};
 
-use crate::{error::ContractError, get_token_allowance::get_token_allowance, state::USER_DCA};
+use crate::{
+    error::ContractError,
+    get_token_allowance::get_token_allowance,
+    refund_order::refund_order,
+    state::{get_reserved_allowance, user_dca},
+};
+
+use super::{
+    create_dca_order::{validate_dca_order, validate_interval, validate_order_assets},
+    set_operator::get_order_owner,
+};
 
 /// Stores a modified dca order new parameters
 pub struct ModifyDcaOrderParameters {
@@ -11,13 +22,18 @@ pub struct ModifyDcaOrderParameters {
     pub new_initial_asset: Asset,
     /// The [`AssetInfo`] that is being purchased with `new_initial_asset`.
     pub new_target_asset: AssetInfo,
-    /// The time in seconds between DCA purchases.
-    pub new_interval: u64,
+    /// The new [`Duration`] in seconds or in blocks between DCA purchases.
+    pub new_interval: Duration,
+    /// The new [`Schedule`] of DCA purchases, which takes precedence over `new_interval` if set.
+    pub new_schedule: Option<Schedule>,
     /// a [`Uint128`] amount of `new_initial_asset` to spend each DCA purchase.
     pub new_dca_amount: Uint128,
     /// An optional parameter that determines if the order's next purchase should be set to
-    /// `new_first_purchase`.
+    /// `new_first_purchase`, in the unit of `new_interval`.
     pub new_first_purchase: Option<u64>,
+    /// The address of the user who owns the order, if the sender is an operator modifying it on
+    /// their behalf.
+    pub user: Option<String>,
 }
 
 /// ## Description
@@ -29,6 +45,11 @@ pub struct ModifyDcaOrderParameters {
 ///
 /// If the user decreases the size of their order, they will be refunded with the difference.
 ///
+/// An operator of the user can modify the order on their behalf, in which case any native tokens
+/// increasing the order are sent by the operator while any refund is sent to the user.
+///
+/// The new order parameters are validated in the same way as when creating an order.
+///
 /// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
 /// attributes if the operation was successful.
 /// ## Arguments
@@ -42,7 +63,7 @@ pub struct ModifyDcaOrderParameters {
 /// * `order_details` - The [`ModifyDcaOrderParameters`] details about the old and new DCA order
 /// parameters.
 pub fn modify_dca_order(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     order_details: ModifyDcaOrderParameters,
@@ -52,21 +73,21 @@ pub fn modify_dca_order(
         new_initial_asset,
         new_target_asset,
         new_interval,
+        new_schedule,
         new_dca_amount,
         new_first_purchase,
+        user,
     } = order_details;
-
-    let mut orders = USER_DCA
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or_default();
+    let (owner, holder) = get_order_owner(deps.as_ref(), &info.sender, user, id)?;
 
     // check that order with given id exists
-    let order_position = orders
-        .iter()
-        .position(|order| order.id == id)
+    let mut order = user_dca()
+        .may_load(deps.storage, (&owner, id))?
         .ok_or(ContractError::NonexistentDca {})?;
 
-    let order = &orders[order_position];
+    if order.pooled {
+        return Err(ContractError::PooledOrder {});
+    }
 
     let should_refund = order.initial_asset.amount > new_initial_asset.amount;
     let asset_difference = Asset {
@@ -84,9 +105,19 @@ pub fn modify_dca_order(
 
     let mut messages = Vec::new();
 
-    // check that user did not set new asset to the old asset target
-    if new_initial_asset.info == new_target_asset {
-        return Err(ContractError::DuplicateAsset {});
+    // check that user did not set new asset to the old asset target, and that the new deposit can
+    // be evenly spent in `new_dca_amount` purchases, or in the tranches of the order
+    validate_dca_order(
+        &new_initial_asset,
+        &new_target_asset,
+        new_dca_amount,
+        &order.strategy,
+    )?;
+    validate_interval(&new_interval, &new_schedule)?;
+
+    // the target assets of an order purchasing several assets are fixed along with their weights
+    if !order.targets.is_empty() && new_target_asset != order.target_asset {
+        return Err(ContractError::InvalidTargets {});
     }
 
     if order.initial_asset.info == new_initial_asset.info {
@@ -100,63 +131,50 @@ pub fn modify_dca_order(
                 AssetInfo::NativeToken { .. } => {
                     asset_difference.assert_sent_native_token_balance(&info)?
                 }
+                AssetInfo::Token { .. } if order.escrowed => {
+                    // escrowed tokens can not be topped up from the users allowance
+                    return Err(ContractError::EscrowedDepositIncrease {});
+                }
+                // the allowance only needs to cover each purchase as it is pulled
+                AssetInfo::Token { .. } if order.pull => {}
                 AssetInfo::Token { contract_addr } => {
                     // allowance should be greater than the sum of all orders with this initial asset
-                    let total_allowance: Uint128 = orders
-                        .iter()
-                        .map(|o| match &o.initial_asset.info {
-                            AssetInfo::Token {
-                                contract_addr: o_contract_addr,
-                            } if contract_addr == o_contract_addr => o.initial_asset.amount,
-                            _ => Uint128::zero(),
-                        })
-                        .sum();
+                    let total_allowance =
+                        get_reserved_allowance(deps.storage, &owner, contract_addr)?;
 
                     let allowance =
-                        get_token_allowance(&deps.as_ref(), &env, &info.sender, contract_addr)?;
+                        get_token_allowance(&deps.as_ref(), &env, &owner, contract_addr)?;
                     if total_allowance + asset_difference.amount > allowance {
                         return Err(ContractError::InvalidTokenDeposit {});
                     }
                 }
             }
         } else {
-            // we need to refund the user with the difference if it is a native token
-            if let AssetInfo::NativeToken { denom } = &new_initial_asset.info {
-                messages.push(BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: coins(asset_difference.amount.u128(), denom),
-                })
-            }
+            // we need to refund the user with the difference if the contract holds the funds
+            messages.extend(refund_order(&order, asset_difference.amount, &holder)?);
         }
     } else {
-        // they are different assets, so we will return the old_initial_asset if it is a native token
-        if let AssetInfo::NativeToken { denom } = &order.initial_asset.info {
-            messages.push(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount: coins(order.initial_asset.amount.u128(), denom),
-            })
-        }
+        // they are different assets, so we will return the old_initial_asset if the contract
+        // holds the funds
+        messages.extend(refund_order(&order, order.initial_asset.amount, &holder)?);
+
+        // the new asset is spent from the users funds or allowance below
+        order.escrowed = false;
 
         // validate that user sent either native tokens or has set allowance for the new token
         match &new_initial_asset.info {
             AssetInfo::NativeToken { .. } => {
-                new_initial_asset.assert_sent_native_token_balance(&info)?
+                new_initial_asset.assert_sent_native_token_balance(&info)?;
+
+                // native tokens are held by the contract, so they cannot be pulled
+                order.pull = false;
             }
+            AssetInfo::Token { .. } if order.pull => {}
             AssetInfo::Token { contract_addr } => {
                 // allowance should be greater than the sum of all orders with this initial asset
-                let total_allowance: Uint128 = orders
-                    .clone()
-                    .iter()
-                    .map(|o| match &o.initial_asset.info {
-                        AssetInfo::Token {
-                            contract_addr: o_contract_addr,
-                        } if contract_addr == o_contract_addr => o.initial_asset.amount,
-                        _ => Uint128::zero(),
-                    })
-                    .sum();
-
-                let allowance =
-                    get_token_allowance(&deps.as_ref(), &env, &info.sender, contract_addr)?;
+                let total_allowance = get_reserved_allowance(deps.storage, &owner, contract_addr)?;
+
+                let allowance = get_token_allowance(&deps.as_ref(), &env, &owner, contract_addr)?;
                 if total_allowance + new_initial_asset.amount > allowance {
                     return Err(ContractError::InvalidTokenDeposit {});
                 }
@@ -165,18 +183,25 @@ pub fn modify_dca_order(
     }
 
     // update order
-    let mut order = &mut orders[order_position];
-
     order.initial_asset = new_initial_asset.clone();
     order.target_asset = new_target_asset.clone();
-    order.interval = new_interval;
     order.dca_amount = new_dca_amount;
 
+    // the last purchase is measured in the unit of the interval, so the schedule restarts from
+    // the current block when the unit changes
     if let Some(new_first_purchase) = new_first_purchase {
         order.last_purchase = new_first_purchase;
+    } else if !order.interval.same_unit(&new_interval) {
+        order.last_purchase = new_interval.current(&env.block);
     }
+    order.interval = new_interval;
+    order.schedule = new_schedule.clone();
+
+    // the modified order is checked against the rules of its features for its new assets and
+    // amounts, in the same way as when creating an order
+    validate_order_assets(deps.branch(), &owner, &order)?;
 
-    USER_DCA.save(deps.storage, &info.sender, &orders)?;
+    user_dca().save(deps.storage, (&owner, id), &order)?;
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "modify_dca_order"),
@@ -192,13 +217,22 @@ pub fn modify_dca_order(
                 None => "none".to_string(),
             },
         ),
+        attr(
+            "new_schedule",
+            match new_schedule {
+                Some(schedule) => schedule.to_string(),
+                None => "none".to_string(),
+            },
+        ),
     ]))
 }
 
 #[cfg(test)]
 mod test {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::{DcaInfo, ExecuteMsg};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
     use cosmwasm_std::{
         attr, coins,
         testing::{mock_dependencies, mock_env, mock_info},
@@ -209,7 +243,7 @@ mod test {
     use crate::{
         contract::execute,
         error::ContractError,
-        state::USER_DCA,
+        state::get_user_orders,
         tests::{
             app_mock_instantiate, mock_app, mock_app_with_balance, mock_creator,
             store_cw20_token_code, store_dca_module_code,
@@ -241,9 +275,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset,
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -257,9 +325,11 @@ mod test {
                 id: 1,
                 new_initial_asset: initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 1_000,
+                new_interval: Duration::Time(1_000),
                 new_dca_amount: Uint128::new(500),
                 new_first_purchase: Some(18_000),
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap();
@@ -271,25 +341,67 @@ mod test {
                 attr("id", "1"),
                 attr("new_initial_asset", initial_asset.to_string()),
                 attr("new_target_asset", new_target_asset.to_string()),
-                attr("new_interval", "1000"),
+                attr("new_interval", "1000 seconds"),
                 attr("new_dca_amount", "500"),
                 attr("new_first_purchase", "18000"),
+                attr("new_schedule", "none"),
             ])
         );
 
         // check state
-        let orders = USER_DCA
-            .load(&deps.storage, &mock_creator().sender)
-            .unwrap();
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
         assert_eq!(
             orders,
             vec![DcaInfo {
                 id: 1,
                 dca_amount: Uint128::new(500),
                 initial_asset,
-                interval: 1_000,
+                interval: Duration::Time(1_000),
                 last_purchase: 18_000,
-                target_asset: new_target_asset
+                target_asset: new_target_asset,
+                escrowed: false,
+                paused: false,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                total_spent: Uint128::zero(),
+                total_received: Uint128::zero(),
+                targets_received: vec![],
+                executing: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                jitter_offset: 0,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                target_value: Uint128::zero(),
+                max_purchases: None,
+                pull: false,
+                last_failure: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                consecutive_failures: 0,
+                replenished_tip: Uint128::zero(),
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
             }]
         );
     }
@@ -325,9 +437,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset,
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -340,9 +486,11 @@ mod test {
                 id: 1,
                 new_initial_asset: new_initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 5_000,
-                new_dca_amount: Uint128::new(1_000),
+                new_interval: Duration::Time(5_000),
+                new_dca_amount: Uint128::new(500),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap();
@@ -355,9 +503,10 @@ mod test {
                     attr("id", "1"),
                     attr("new_initial_asset", new_initial_asset.to_string()),
                     attr("new_target_asset", new_target_asset.to_string()),
-                    attr("new_interval", "5000"),
-                    attr("new_dca_amount", "1000"),
+                    attr("new_interval", "5000 seconds"),
+                    attr("new_dca_amount", "500"),
                     attr("new_first_purchase", "none"),
+                    attr("new_schedule", "none"),
                 ])
                 .add_message(BankMsg::Send {
                     amount: coins(
@@ -400,9 +549,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset,
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -415,9 +598,11 @@ mod test {
                 id: 1,
                 new_initial_asset: new_initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 5_000,
+                new_interval: Duration::Time(5_000),
                 new_dca_amount: Uint128::new(1_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap_err();
@@ -443,9 +628,11 @@ mod test {
                 id: 1,
                 new_initial_asset: new_initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 5_000,
+                new_interval: Duration::Time(5_000),
                 new_dca_amount: Uint128::new(1_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap();
@@ -456,9 +643,10 @@ mod test {
                 attr("id", "1"),
                 attr("new_initial_asset", new_initial_asset.to_string()),
                 attr("new_target_asset", new_target_asset.to_string()),
-                attr("new_interval", "5000"),
+                attr("new_interval", "5000 seconds"),
                 attr("new_dca_amount", "1000"),
                 attr("new_first_purchase", "none"),
+                attr("new_schedule", "none"),
             ])
         );
     }
@@ -537,9 +725,43 @@ mod test {
             &ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset: target_asset.clone(),
-                interval: 1000,
+                interval: Duration::Time(1000),
                 dca_amount: Uint128::new(25_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
             &[],
         )
@@ -559,9 +781,43 @@ mod test {
                 target_asset: AssetInfo::NativeToken {
                     denom: "ukrw".to_string(),
                 },
-                interval: 1_000,
+                interval: Duration::Time(1_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
             &coins(20_000, "uluna"),
         )
@@ -576,9 +832,11 @@ mod test {
                     id: 1,
                     new_initial_asset: new_initial_asset.clone(),
                     new_target_asset: target_asset.clone(),
-                    new_interval: 5_000,
+                    new_interval: Duration::Time(5_000),
                     new_dca_amount: Uint128::new(1_000),
                     new_first_purchase: None,
+                    user: None,
+                    new_schedule: None,
                 },
                 &[],
             )
@@ -608,9 +866,11 @@ mod test {
                 id: 1,
                 new_initial_asset,
                 new_target_asset: target_asset,
-                new_interval: 5_000,
+                new_interval: Duration::Time(5_000),
                 new_dca_amount: Uint128::new(1_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
             &[],
         )
@@ -648,9 +908,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset,
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -663,9 +957,11 @@ mod test {
                 id: 1,
                 new_initial_asset: new_initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 5_000,
+                new_interval: Duration::Time(5_000),
                 new_dca_amount: Uint128::new(1_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap_err();
@@ -688,9 +984,11 @@ mod test {
                 id: 1,
                 new_initial_asset: new_initial_asset.clone(),
                 new_target_asset: new_target_asset.clone(),
-                new_interval: 5_000,
+                new_interval: Duration::Time(5_000),
                 new_dca_amount: Uint128::new(1_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap();
@@ -706,9 +1004,10 @@ mod test {
                     attr("id", "1"),
                     attr("new_initial_asset", new_initial_asset.to_string()),
                     attr("new_target_asset", new_target_asset.to_string()),
-                    attr("new_interval", "5000"),
+                    attr("new_interval", "5000 seconds"),
                     attr("new_dca_amount", "1000"),
                     attr("new_first_purchase", "none"),
+                    attr("new_schedule", "none"),
                 ])
         );
     }
@@ -805,9 +1104,43 @@ mod test {
             &ExecuteMsg::CreateDcaOrder {
                 initial_asset,
                 target_asset: target_asset.clone(),
-                interval: 1000,
+                interval: Duration::Time(1000),
                 dca_amount: Uint128::new(25_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
             &[],
         )
@@ -822,9 +1155,11 @@ mod test {
                     id: 1,
                     new_initial_asset: new_initial_asset.clone(),
                     new_target_asset: target_asset.clone(),
-                    new_interval: 1_000,
+                    new_interval: Duration::Time(1_000),
                     new_dca_amount: Uint128::new(25_000),
                     new_first_purchase: None,
+                    user: None,
+                    new_schedule: None,
                 },
                 &[],
             )
@@ -855,9 +1190,11 @@ mod test {
                 id: 1,
                 new_initial_asset,
                 new_target_asset: target_asset,
-                new_interval: 1_000,
+                new_interval: Duration::Time(1_000),
                 new_dca_amount: Uint128::new(25_000),
                 new_first_purchase: None,
+                user: None,
+                new_schedule: None,
             },
             &[],
         )
@@ -886,9 +1223,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset: initial_asset.clone(),
                 target_asset: target_asset.clone(),
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -901,9 +1272,11 @@ mod test {
                 id: 2,
                 new_initial_asset: initial_asset,
                 new_target_asset: target_asset,
-                new_interval: 1_000,
+                new_interval: Duration::Time(1_000),
                 new_dca_amount: Uint128::new(500),
                 new_first_purchase: Some(18_000),
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap_err();
@@ -936,9 +1309,43 @@ mod test {
             ExecuteMsg::CreateDcaOrder {
                 initial_asset,
                 target_asset: target_asset.clone(),
-                interval: 5_000,
+                interval: Duration::Time(5_000),
                 dca_amount: Uint128::new(1_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
         )
         .unwrap();
@@ -951,12 +1358,100 @@ mod test {
                 id: 1,
                 new_initial_asset,
                 new_target_asset: target_asset,
-                new_interval: 1_000,
+                new_interval: Duration::Time(1_000),
                 new_dca_amount: Uint128::new(500),
                 new_first_purchase: Some(18_000),
+                user: None,
+                new_schedule: None,
             },
         )
         .unwrap_err();
         assert_eq!(res, ContractError::DuplicateAsset {});
     }
+
+    #[test]
+    fn cannot_modify_to_invalid_dca_amount() {
+        let mut deps = mock_dependencies();
+
+        let initial_asset = Asset {
+            amount: Uint128::new(15_000),
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+        let target_asset = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // create order
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(initial_asset.amount.u128(), "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: initial_asset.clone(),
+                target_asset: target_asset.clone(),
+                interval: Duration::Time(5_000),
+                dca_amount: Uint128::new(1_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        let mut modify_order = |new_dca_amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_creator(),
+                ExecuteMsg::ModifyDcaOrder {
+                    id: 1,
+                    new_initial_asset: initial_asset.clone(),
+                    new_target_asset: target_asset.clone(),
+                    new_interval: Duration::Time(1_000),
+                    new_dca_amount: Uint128::new(new_dca_amount),
+                    new_first_purchase: None,
+                    user: None,
+                    new_schedule: None,
+                },
+            )
+            .unwrap_err()
+        };
+
+        // dca amount larger than the deposit
+        assert_eq!(modify_order(30_000), ContractError::DepositTooSmall {});
+    }
 }
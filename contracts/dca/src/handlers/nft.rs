@@ -0,0 +1,346 @@
+use cosmwasm_std::{
+    attr, to_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, SubMsg,
+    SubMsgResult, WasmMsg,
+};
+
+use crate::{
+    cw721::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg, OwnerOfResponse},
+    error::ContractError,
+    state::{CONFIG, ORDER_NFTS},
+};
+
+use super::ownership::get_owner;
+
+/// The id of the reply to instantiating the companion cw721 contract
+pub const NFT_INSTANTIATE_REPLY_ID: u64 = 4;
+
+/// ## Description
+/// Returns the current holder of the NFT minted for the DCA order `id` of `user`, or [`None`] if
+/// no NFT was minted for the order.
+pub(crate) fn get_order_holder(deps: Deps, user: &Addr, id: u64) -> StdResult<Option<Addr>> {
+    let token_id = match ORDER_NFTS.may_load(deps.storage, (user, id))? {
+        Some(token_id) => token_id,
+        None => return Ok(None),
+    };
+
+    // an NFT can only have been minted once the contract is set in the configuration
+    let nft_addr = CONFIG
+        .load(deps.storage)?
+        .nft_addr
+        .ok_or_else(|| StdError::generic_err("nft contract not set"))?;
+
+    let res: OwnerOfResponse = deps.querier.query_wasm_smart(
+        nft_addr,
+        &Cw721QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        },
+    )?;
+
+    deps.api.addr_validate(&res.owner).map(Some)
+}
+
+/// ## Description
+/// Creates the message minting the NFT representing the new DCA order `id` of `user` to `user`
+/// from the companion cw721 contract at `nft_addr`, storing the token id of the NFT for the order.
+///
+/// Returns the token id along with the [`WasmMsg`] minting the NFT.
+pub(crate) fn mint_order_nft(
+    deps: DepsMut,
+    nft_addr: &Addr,
+    user: &Addr,
+    id: u64,
+) -> StdResult<(String, WasmMsg)> {
+    let token_id = format!("{}/{}", user, id);
+    ORDER_NFTS.save(deps.storage, (user, id), &token_id)?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: nft_addr.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: user.to_string(),
+            token_uri: None,
+            extension: None,
+        })?,
+        funds: vec![],
+    };
+
+    Ok((token_id, msg))
+}
+
+/// ## Description
+/// Instantiates the companion cw721 contract from `code_id`, with the contract as its minter.
+/// Once the contract has been instantiated, an NFT is minted for each new DCA order, whose holder
+/// is treated as the owner of the order.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to instantiate the contract.
+///
+/// * `code_id` - The [`u64`] code id of the cw721 contract.
+///
+/// * `name` - The name of the NFT collection.
+///
+/// * `symbol` - The symbol of the NFT collection.
+pub fn instantiate_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code_id: u64,
+    name: String,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.nft_addr.is_some() {
+        return Err(ContractError::NftAlreadyInstantiated {});
+    }
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                admin: Some(info.sender.to_string()),
+                code_id,
+                msg: to_binary(&Cw721InstantiateMsg {
+                    name,
+                    symbol,
+                    minter: env.contract.address.to_string(),
+                })?,
+                funds: vec![],
+                label: "Astroport DCA positions".to_string(),
+            },
+            NFT_INSTANTIATE_REPLY_ID,
+        ))
+        .add_attributes(vec![
+            attr("action", "instantiate_nft"),
+            attr("code_id", code_id.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Stores the address of the companion cw721 contract once it has been instantiated.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `result` - The [`SubMsgResult`] of instantiating the cw721 contract.
+pub fn instantiate_nft_reply(
+    deps: DepsMut,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let response = result.into_result().map_err(StdError::generic_err)?;
+
+    let nft_addr = response
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == "_contract_address")
+        .map(|attr| deps.api.addr_validate(&attr.value))
+        .transpose()?
+        .ok_or_else(|| StdError::generic_err("missing nft contract address"))?;
+
+    CONFIG.update::<_, StdError>(deps.storage, |mut config| {
+        config.nft_addr = Some(nft_addr.clone());
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "instantiate_nft_reply"),
+        attr("nft_addr", nft_addr),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{attr, coin, coins, Addr, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_item, store_cw721_code,
+            MockCw721ExecuteMsg,
+        },
+    };
+
+    #[test]
+    fn does_treat_nft_holder_as_order_owner() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let cw721_id = store_cw721_code(&mut app);
+
+        let instantiate_msg = ExecuteMsg::InstantiateNft {
+            code_id: cw721_id,
+            name: "DCA positions".to_string(),
+            symbol: "DCA".to_string(),
+        };
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("random"),
+                dca_addr.clone(),
+                &instantiate_msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &instantiate_msg,
+            &[],
+        )
+        .unwrap();
+        let nft_addr = read_item(&app, &dca_addr, CONFIG).nft_addr.unwrap();
+
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &instantiate_msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NftAlreadyInstantiated {}
+        );
+
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(100_000, "uluna"),
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("nft_token_id", "creator/1"))));
+
+        // sell the position by transferring its NFT
+        app.execute_contract(
+            mock_creator().sender,
+            nft_addr,
+            &MockCw721ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "creator/1".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let cancel = |user: Option<&str>| ExecuteMsg::CancelDcaOrder {
+            id: 1,
+            user: user.map(|user| user.to_string()),
+            withdraw_tips: false,
+        };
+
+        // the user who created the order no longer owns it
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &cancel(None), &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        // the holder of the NFT should receive the amount withdrawn from the order
+        app.execute_contract(
+            Addr::unchecked("buyer"),
+            dca_addr.clone(),
+            &ExecuteMsg::WithdrawFromDcaOrder {
+                id: 1,
+                amount: Uint128::new(40_000),
+                user: Some("creator".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance("buyer", "uluna").unwrap(),
+            coin(40_000, "uluna")
+        );
+
+        // the holder of the NFT should receive the refund of the order
+        app.execute_contract(
+            Addr::unchecked("buyer"),
+            dca_addr,
+            &cancel(Some("creator")),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance("buyer", "uluna").unwrap(),
+            coin(100_000, "uluna")
+        );
+    }
+}
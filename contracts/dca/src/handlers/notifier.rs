@@ -0,0 +1,427 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::{NotifierMsg, OrderEvent};
+use cosmwasm_std::{
+    attr, to_binary, Addr, DepsMut, MessageInfo, ReplyOn, Response, StdResult, Storage, SubMsg,
+    SubMsgResult, WasmMsg,
+};
+
+use crate::{error::ContractError, state::NOTIFIERS};
+
+/// The id of the reply to a failed notification of the notifier of a user
+pub const NOTIFIER_REPLY_ID: u64 = 8;
+
+/// The gas available to the notifier of a user for each notification
+const NOTIFIER_GAS_LIMIT: u64 = 100_000;
+
+/// ## Description
+/// Returns the submessage notifying the notifier of `user` of `event` of the DCA order `id`, if
+/// the user has set a notifier. The notification is limited to [`NOTIFIER_GAS_LIMIT`] and only
+/// replies if it fails, so that a failing notifier never reverts the event it is notified of.
+pub(crate) fn notification_msg(
+    storage: &dyn Storage,
+    user: &Addr,
+    id: u64,
+    event: OrderEvent,
+) -> StdResult<Option<SubMsg>> {
+    let notifier = match NOTIFIERS.may_load(storage, user)? {
+        Some(notifier) => notifier,
+        None => return Ok(None),
+    };
+
+    Ok(Some(SubMsg {
+        id: NOTIFIER_REPLY_ID,
+        msg: WasmMsg::Execute {
+            contract_addr: notifier.into_string(),
+            msg: to_binary(&NotifierMsg::DcaOrderEvent {
+                user: user.to_string(),
+                id,
+                event,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        gas_limit: Some(NOTIFIER_GAS_LIMIT),
+        reply_on: ReplyOn::Error,
+    }))
+}
+
+/// ## Description
+/// Ignores a notification of the notifier of a user that failed, which is reverted on its own.
+///
+/// Returns a [`Response`] with the error of the notification as an attribute.
+/// ## Params
+/// * `result` - The [`SubMsgResult`] of the notification.
+pub fn notifier_reply(result: SubMsgResult) -> Result<Response, ContractError> {
+    let err = match result {
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+        SubMsgResult::Err(err) => err,
+    };
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "notification_failed"),
+        attr("error", err),
+    ]))
+}
+
+/// ## Description
+/// Sets the `notifier` contract that is called whenever a DCA order of the sender is purchased,
+/// fails to be purchased in a batch of purchases, completes or is removed otherwise, so that other
+/// contracts can act on the events of the orders without polling. Removes the notifier if it is
+/// not specified.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the user who wants to set the notifier of their orders.
+///
+/// * `notifier` - The address of the notifier contract, or [`None`] to remove the notifier.
+pub fn set_notifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    notifier: Option<String>,
+) -> Result<Response, ContractError> {
+    let notifier = match notifier {
+        Some(notifier) => {
+            let notifier = addr_validate_to_lower(deps.api, &notifier)?;
+            NOTIFIERS.save(deps.storage, &info.sender, &notifier)?;
+            notifier.into_string()
+        }
+        None => {
+            NOTIFIERS.remove(deps.storage, &info.sender);
+            "none".to_string()
+        }
+    };
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_notifier"),
+        attr("user", info.sender),
+        attr("notifier", notifier),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, PurchaseRequest, Strategy,
+    };
+    use cosmwasm_std::{attr, coins, Addr, Decimal, Empty, Uint128};
+    use cw20::Expiration;
+    use cw_multi_test::{App, AppResponse, Executor};
+
+    use crate::tests::{
+        mock_astroport_instantiate, mock_creator, read_user_orders, store_notifier_code,
+    };
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn notifications(res: &AppResponse) -> Vec<String> {
+        res.events
+            .iter()
+            .filter(|e| e.attributes.contains(&attr("action", "dca_notification")))
+            .filter_map(|e| {
+                e.attributes
+                    .iter()
+                    .find(|a| a.key == "event")
+                    .map(|a| a.value.clone())
+            })
+            .collect()
+    }
+
+    fn create_order(app: &mut App, dca_addr: &Addr) {
+        create_order_with(app, dca_addr, None, None, false)
+    }
+
+    fn create_order_with(
+        app: &mut App,
+        dca_addr: &Addr,
+        stop_price: Option<Decimal>,
+        expires_at: Option<Expiration>,
+        pooled: bool,
+    ) {
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(20_000),
+                    info: native("uluna"),
+                },
+                target_asset: native("ujpy"),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: match pooled {
+                    true => Delivery::Escrow,
+                    false => Delivery::Immediate,
+                },
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(20_000, "uluna"),
+        )
+        .unwrap();
+    }
+
+    fn set_notifier(app: &mut App, dca_addr: &Addr) {
+        let notifier_id = store_notifier_code(app);
+        let notifier_addr = app
+            .instantiate_contract(
+                notifier_id,
+                Addr::unchecked("admin"),
+                &Empty {},
+                &[],
+                "mock notifier",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::SetNotifier {
+                notifier: Some(notifier_addr.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_notify_order_events() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        set_notifier(&mut app, &dca_addr);
+
+        create_order(&mut app, &dca_addr);
+        let purchase = ExecuteMsg::PerformDcaPurchase {
+            user: mock_creator().sender.into_string(),
+            id: 1,
+            hops: vec![SwapOperation::AstroSwap {
+                offer_asset_info: native("uluna"),
+                ask_asset_info: native("ujpy"),
+            }],
+            fee_redeem: vec![],
+            deadline: None,
+            accepted_tips: vec![],
+        };
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &purchase,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(notifications(&res), vec!["executed"]);
+
+        // the last purchase of the deposit completes the order
+        app.update_block(|block| block.time = block.time.plus_seconds(500));
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &purchase,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(notifications(&res), vec!["executed", "completed"]);
+
+        create_order(&mut app, &dca_addr);
+        let cancel = ExecuteMsg::CancelDcaOrder {
+            id: 2,
+            user: None,
+            withdraw_tips: false,
+        };
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &cancel, &[])
+            .unwrap();
+        assert_eq!(notifications(&res), vec!["cancelled"]);
+
+        // a notifier that fails does not revert the event it is notified of
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::SetNotifier {
+                notifier: Some("not_a_contract".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        create_order(&mut app, &dca_addr);
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr,
+                &ExecuteMsg::CancelDcaOrder {
+                    id: 3,
+                    user: None,
+                    withdraw_tips: false,
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.events.iter().any(|e| e
+            .attributes
+            .contains(&attr("action", "notification_failed"))));
+    }
+
+    #[test]
+    fn does_notify_orders_removed_without_completing() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        set_notifier(&mut app, &dca_addr);
+
+        // an order stopped at its stop price
+        create_order_with(
+            &mut app,
+            &dca_addr,
+            Some(Decimal::from_ratio(1_000_000u128, 1u128)),
+            None,
+            false,
+        );
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: native("uluna"),
+                        ask_asset_info: native("ujpy"),
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("action", "stop_dca_order"))));
+        assert_eq!(notifications(&res), vec!["cancelled"]);
+
+        // an order swept once it has expired
+        let expires_at = Expiration::AtTime(app.block_info().time.plus_seconds(100));
+        create_order_with(&mut app, &dca_addr, None, Some(expires_at), false);
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        let res = app
+            .execute_contract(
+                Addr::unchecked("random"),
+                dca_addr.clone(),
+                &ExecuteMsg::SweepExpired {
+                    user: mock_creator().sender.into_string(),
+                    id: 2,
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(notifications(&res), vec!["cancelled"]);
+
+        // a pooled order removed once its last depositor leaves it
+        create_order_with(&mut app, &dca_addr, None, None, true);
+        app.send_tokens(
+            mock_creator().sender,
+            Addr::unchecked("depositor"),
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("depositor"),
+            dca_addr.clone(),
+            &ExecuteMsg::JoinPooledOrder {
+                user: mock_creator().sender.into_string(),
+                id: 3,
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+        let leave = ExecuteMsg::LeavePooledOrder {
+            user: mock_creator().sender.into_string(),
+            id: 3,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked("depositor"), dca_addr.clone(), &leave, &[])
+            .unwrap();
+        assert!(notifications(&res).is_empty());
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr, &leave, &[])
+            .unwrap();
+        assert_eq!(notifications(&res), vec!["cancelled"]);
+    }
+
+    #[test]
+    fn does_not_notify_rejected_purchases() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        set_notifier(&mut app, &dca_addr);
+        create_order(&mut app, &dca_addr);
+
+        // the last hop is not the target asset, so the purchase is rejected before its swap
+        let res = app
+            .execute_contract(
+                Addr::unchecked("random"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchases {
+                    purchases: vec![PurchaseRequest {
+                        user: mock_creator().sender.into_string(),
+                        id: 1,
+                        hops: vec![SwapOperation::AstroSwap {
+                            offer_asset_info: native("uluna"),
+                            ask_asset_info: native("ukrw"),
+                        }],
+                        fee_redeem: vec![],
+                        deadline: None,
+                        accepted_tips: vec![],
+                    }],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(notifications(&res).is_empty());
+
+        // without a failure being recorded on the order
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert_eq!(orders[0].last_failure, None);
+    }
+}
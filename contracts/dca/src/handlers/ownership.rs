@@ -0,0 +1,292 @@
+use astroport::{
+    common::{
+        claim_ownership as astroport_claim_ownership,
+        drop_ownership_proposal as astroport_drop_ownership_proposal,
+        propose_new_owner as astroport_propose_new_owner,
+    },
+    querier::query_factory_config,
+};
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, QuerierWrapper, Response, StdError, StdResult,
+};
+
+use crate::{
+    error::ContractError,
+    state::{Config, CONFIG, OWNERSHIP_PROPOSAL},
+};
+
+/// ## Description
+/// Returns the owner of the contract, which is the owner of the factory contract until the
+/// ownership of the contract has been transferred.
+pub(crate) fn get_owner(querier: &QuerierWrapper, config: &Config) -> StdResult<Addr> {
+    match &config.owner {
+        Some(owner) => Ok(owner.clone()),
+        None => Ok(query_factory_config(querier, config.factory_addr.clone())?.owner),
+    }
+}
+
+/// ## Description
+/// Proposes `owner` as the new owner of the contract, who has `expires_in` seconds to claim the
+/// ownership with [`claim_ownership`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the current owner of the contract.
+///
+/// * `owner` - The address of the proposed new owner.
+///
+/// * `expires_in` - The amount of seconds the proposal can be claimed for.
+pub fn propose_new_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    expires_in: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_owner = get_owner(&deps.querier, &config)?;
+
+    Ok(astroport_propose_new_owner(
+        deps,
+        info,
+        env,
+        owner,
+        expires_in,
+        current_owner,
+        OWNERSHIP_PROPOSAL,
+    )?)
+}
+
+/// ## Description
+/// Removes the pending proposal of a new owner of the contract.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the current owner of the contract.
+pub fn drop_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_owner = get_owner(&deps.querier, &config)?;
+
+    Ok(astroport_drop_ownership_proposal(
+        deps,
+        info,
+        current_owner,
+        OWNERSHIP_PROPOSAL,
+    )?)
+}
+
+/// ## Description
+/// Transfers the ownership of the contract to the sender if they are the proposed new owner and
+/// the proposal has not expired.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the proposed new owner of the contract.
+pub fn claim_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    Ok(astroport_claim_ownership(
+        deps,
+        info,
+        env,
+        OWNERSHIP_PROPOSAL,
+        |deps, new_owner| {
+            CONFIG.update::<_, StdError>(deps.storage, |mut config| {
+                config.owner = Some(new_owner);
+                Ok(config)
+            })?;
+
+            Ok(())
+        },
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::{ExecuteMsg, QueryMsg};
+    use cosmwasm_std::Addr;
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        state::ConfigResponse,
+        tests::{mock_astroport_instantiate, mock_creator},
+    };
+
+    fn update_config(app: &mut App, dca_addr: &Addr, sender: &str) -> Result<(), ContractError> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: Some(2),
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    }
+
+    #[test]
+    fn does_transfer_ownership() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let factory_owner = mock_creator().sender;
+
+        // only the owner can propose a new owner
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            dca_addr.clone(),
+            &ExecuteMsg::ProposeNewOwner {
+                owner: "new_owner".to_string(),
+                expires_in: 100,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        app.execute_contract(
+            factory_owner.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::ProposeNewOwner {
+                owner: "new_owner".to_string(),
+                expires_in: 100,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Config {})
+            .unwrap();
+        assert_eq!(
+            config.ownership_proposal.unwrap().owner,
+            Addr::unchecked("new_owner")
+        );
+
+        // only the proposed owner can claim the ownership
+        app.execute_contract(
+            Addr::unchecked("someone"),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+
+        // the factory owner stays the owner until the ownership is claimed
+        update_config(&mut app, &dca_addr, "new_owner").unwrap_err();
+
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimOwnership {},
+            &[],
+        )
+        .unwrap();
+
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Config {})
+            .unwrap();
+        assert_eq!(config.config.owner, Some(Addr::unchecked("new_owner")));
+        assert_eq!(config.ownership_proposal, None);
+
+        update_config(&mut app, &dca_addr, "new_owner").unwrap();
+        assert_eq!(
+            update_config(&mut app, &dca_addr, factory_owner.as_str()).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+    }
+
+    #[test]
+    fn cannot_claim_expired_ownership() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::ProposeNewOwner {
+                owner: "new_owner".to_string(),
+                expires_in: 100,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| block.time = block.time.plus_seconds(101));
+
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+
+        // a dropped proposal can no longer be claimed either
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::ProposeNewOwner {
+                owner: "new_owner".to_string(),
+                expires_in: 100,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::DropOwnershipProposal {},
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            dca_addr,
+            &ExecuteMsg::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+    }
+}
@@ -0,0 +1,179 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::CONFIG};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Pauses the DCA purchases of every order spending or purchasing `asset`, while the orders of
+/// other assets can still be purchased. The purchases can be resumed with
+/// [`super::unpause_asset`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to pause the asset.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to pause.
+pub fn pause_asset(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.is_paused_asset(&asset) {
+        return Err(ContractError::AssetPaused { asset });
+    }
+
+    config.paused_assets.push(asset.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "pause_asset"),
+        attr("asset", asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    #[test]
+    fn does_pause_asset() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let pause = ExecuteMsg::PauseAsset {
+            asset: ujpy.clone(),
+        };
+
+        // only the contract owner can pause an asset
+        let res = app
+            .execute_contract(Addr::unchecked("someone"), dca_addr.clone(), &pause, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        app.execute_contract(mock_creator().sender, dca_addr.clone(), &pause, &[])
+            .unwrap();
+        assert_eq!(
+            read_item(&app, &dca_addr, CONFIG).paused_assets,
+            vec![ujpy.clone()]
+        );
+
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &pause, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetPaused {
+                asset: ujpy.clone()
+            }
+        );
+
+        // the order purchasing the paused asset cannot be purchased
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: ujpy.clone(),
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetPaused { asset: ujpy }
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::user_dca};
+
+use super::member_group::get_order_owner_or_member;
+
+/// ## Description
+/// Pauses a DCA order of a user, so that no DCA purchases can be performed for it until it is
+/// resumed with [`super::resume_dca_order`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to pause their order.
+///
+/// * `id` - The [`u64`] ID of the order to pause.
+///
+/// * `user` - The address of the user who owns the order, if the sender is an operator or a
+/// member of the group of the owner pausing it on their behalf.
+pub fn pause_dca_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    user: Option<String>,
+) -> Result<Response, ContractError> {
+    let (owner, _) = get_order_owner_or_member(deps.as_ref(), &info.sender, user, id)?;
+
+    user_dca().update(deps.storage, (&owner, id), |order| {
+        let mut order = order.ok_or(ContractError::NonexistentDca {})?;
+
+        if order.paused {
+            return Err(ContractError::OrderPaused {});
+        }
+
+        order.paused = true;
+
+        Ok(order)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "pause_dca_order"),
+        attr("id", id.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        attr, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Response, Uint128,
+    };
+
+    use crate::{
+        contract::execute, error::ContractError, state::get_user_orders, tests::mock_creator,
+    };
+
+    #[test]
+    fn does_pause_and_resume_order() {
+        let mut deps = mock_dependencies();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: Some(18_000),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PauseDcaOrder { id: 1, user: None },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attributes(vec![attr("action", "pause_dca_order"), attr("id", "1")])
+        );
+
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert!(orders[0].paused);
+
+        // can not pause a paused order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PauseDcaOrder { id: 1, user: None },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::OrderPaused {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::ResumeDcaOrder { id: 1, user: None },
+        )
+        .unwrap();
+
+        // the schedule of the order should be kept
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert!(!orders[0].paused);
+        assert_eq!(orders[0].last_purchase, 18_000);
+
+        // can not resume an active order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::ResumeDcaOrder { id: 1, user: None },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::OrderNotPaused {});
+    }
+
+    #[test]
+    fn does_error_on_invalid_id() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PauseDcaOrder { id: 1, user: None },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::NonexistentDca {});
+    }
+}
@@ -1,22 +1,453 @@
 use astroport::{
     asset::{addr_validate_to_lower, Asset, AssetInfo},
-    router::{ExecuteMsg as RouterExecuteMsg, SwapOperation},
+    router::SwapOperation,
+};
+use astroport_dca::dca::{
+    DcaInfo, Delivery, OrderEvent, PurchaseRequest, Strategy, SwapBackendType,
 };
-use astroport_dca::dca::DcaInfo;
 use cosmwasm_std::{
-    attr, to_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
-    Uint128, WasmMsg,
+    attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Order, QuerierWrapper, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 
 use crate::{
+    build_route::build_route,
     error::ContractError,
-    state::{CONFIG, USER_CONFIG, USER_DCA},
+    find_best_route::{find_best_route, find_split_routes},
+    get_swap_simulation::get_swap_simulation,
+    get_token_allowance::get_token_allowance,
+    jitter::jitter_offset,
+    oracle::consult_oracle,
+    pool_type::{is_xyk_route, swap_params},
+    price_impact::get_price_impact,
+    refund_order::refund_order,
+    state::{
+        get_reserved_allowance, user_dca, Config, PendingPurchase, PendingSplit, UserConfig, BOTS,
+        CONFIG, MAX_BPS, ORACLES, PENDING_PURCHASE, PENDING_SPLIT, PROTOCOL_FEES, REFERRAL_REWARDS,
+        USER_CONFIG,
+    },
+    stats::record_order_removed,
+    swap_backend::{swap_backend, Swap},
+};
+
+use super::{
+    auto_stake::{query_balance, query_staking_config},
+    bot_bond::slash_bot,
+    fee_on_transfer::pull_fee_on_transfer_token,
+    nft::get_order_holder,
+    notifier::notification_msg,
+    zap::split_zap_amount,
 };
 
+/// The id of the reply to the router swap of a DCA purchase
+pub const PERFORM_DCA_PURCHASE_REPLY_ID: u64 = 1;
+/// The id of the reply to each router swap of a DCA purchase split across several routes
+pub const SPLIT_SWAP_REPLY_ID: u64 = 6;
+
+/// ## Description
+/// Skips the due DCA purchase of `order` for `reason`, rescheduling the order to its next interval
+/// without spending its deposit or paying the tip of the bot.
+fn skip_purchase(
+    deps: DepsMut,
+    env: &Env,
+    user: &Addr,
+    id: u64,
+    mut order: DcaInfo,
+    reason: &str,
+) -> Result<Response, ContractError> {
+    order.last_purchase = order.interval.current(&env.block);
+    order.jitter_offset = order
+        .jitter
+        .map_or(0, |window| jitter_offset(env, user, id, window));
+    user_dca().save(deps.storage, (user, id), &order)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "skip_dca_purchase"),
+        attr("user", user),
+        attr("id", id.to_string()),
+        attr("reason", reason),
+    ]))
+}
+
+/// ## Description
+/// Closes `order` at its due DCA purchase for `reason` once the simulated `price` has crossed the
+/// stop or take profit price of the order, refunding the remaining deposit held by the contract to
+/// the holder of the order without paying the tip of the bot.
+fn stop_order(
+    deps: DepsMut,
+    user: &Addr,
+    id: u64,
+    order: DcaInfo,
+    price: Decimal,
+    reason: &str,
+) -> Result<Response, ContractError> {
+    // the funds are refunded to the holder of the NFT of the order if one was minted for it
+    let owner = get_order_holder(deps.as_ref(), user, id)?.unwrap_or_else(|| user.clone());
+    let refund = refund_order(&order, order.initial_asset.amount, &owner)?;
+
+    user_dca().remove(deps.storage, (user, id))?;
+    record_order_removed(deps.storage)?;
+    let notification = notification_msg(deps.storage, user, id, OrderEvent::Cancelled {})?;
+
+    Ok(Response::new()
+        .add_messages(refund)
+        .add_submessages(notification)
+        .add_attributes(vec![
+            attr("action", "stop_dca_order"),
+            attr("user", user),
+            attr("id", id.to_string()),
+            attr("reason", reason),
+            attr("price", price.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Returns the protocol fee taken from `amount` spent in a DCA purchase for `user`, discounted by
+/// the fee tier reached by the xASTRO balance of the user.
+fn get_protocol_fee(
+    querier: &QuerierWrapper,
+    config: &Config,
+    user: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    // the xASTRO balance is only queried when it can discount a fee
+    if config.protocol_fee_bps == 0 || config.fee_tiers.is_empty() {
+        return Ok(config.protocol_fee(amount, Uint128::zero()));
+    }
+
+    let (_, staking_config) = query_staking_config(querier, config)?;
+    let xastro_balance = query_balance(querier, &staking_config.share_token_addr, user)?;
+
+    Ok(config.protocol_fee(amount, xastro_balance))
+}
+
+/// ## Description
+/// Returns the tip for `hops` hops redeemed from the first asset of the tip balance of the user,
+/// in the priority set by the user, that is one of `accepted_tips` and covers the tip.
+pub fn select_tip(
+    config: &Config,
+    user_config: &UserConfig,
+    accepted_tips: &[AssetInfo],
+    hops: u32,
+) -> Result<Vec<Asset>, ContractError> {
+    user_config
+        .prioritized_tip_balance()
+        .into_iter()
+        .filter(|balance| accepted_tips.contains(&balance.info))
+        .find_map(|balance| {
+            let fee = config
+                .whitelisted_fee_assets
+                .iter()
+                .find(|fee| fee.info == balance.info && !fee.amount.is_zero())?;
+            let amount = fee.amount.checked_mul(Uint128::from(hops)).ok()?;
+
+            (balance.amount >= amount).then(|| Asset {
+                info: balance.info,
+                amount,
+            })
+        })
+        .map(|tip| vec![tip])
+        .ok_or(ContractError::InsufficientTipBalance {})
+}
+
+/// ## Description
+/// Creates the messages paying `tip` from the tip balance of `user` to `bot`, where the cw20 tokens
+/// deposited into the contract are paid first, and the rest of the tip is transferred from the
+/// allowance of the user.
+fn tip_payment_msgs(
+    user_config: &mut UserConfig,
+    user: &Addr,
+    bot: &Addr,
+    tip: Asset,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    match tip.info {
+        AssetInfo::NativeToken { denom } => messages.push(
+            BankMsg::Send {
+                to_address: bot.to_string(),
+                amount: vec![Coin {
+                    amount: tip.amount,
+                    denom,
+                }],
+            }
+            .into(),
+        ),
+        AssetInfo::Token { contract_addr } => {
+            let deposited = user_config.take_deposited_tip(
+                &AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                tip.amount,
+            );
+            let from_allowance = tip.amount - deposited;
+
+            if !deposited.is_zero() {
+                messages.push(
+                    WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient: bot.to_string(),
+                            amount: deposited,
+                        })?,
+                        funds: vec![],
+                    }
+                    .into(),
+                );
+            }
+
+            if !from_allowance.is_zero() {
+                messages.push(
+                    WasmMsg::Execute {
+                        contract_addr: contract_addr.into_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: user.to_string(),
+                            recipient: bot.to_string(),
+                            amount: from_allowance,
+                        })?,
+                        funds: vec![],
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    Ok(messages)
+}
+
+/// ## Description
+/// Creates a message sending `amount` of the `initial_asset` of `order` to `recipient`, from the
+/// contract if it holds the funds of the order, otherwise from the allowance of `user`.
+fn spend_order_msg(
+    order: &DcaInfo,
+    user: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(match &order.initial_asset.info {
+        AssetInfo::NativeToken { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                amount,
+                denom: denom.clone(),
+            }],
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&match order.escrowed {
+                true => Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                },
+                false => Cw20ExecuteMsg::TransferFrom {
+                    owner: user.to_string(),
+                    recipient: recipient.to_string(),
+                    amount,
+                },
+            })?,
+        }
+        .into(),
+    })
+}
+
+/// ## Description
+/// Returns the bot assigned to the DCA purchases of `order` that were due in `epoch`, rotating by
+/// the id of the order and the epoch through the allowed bots of the order, or the registered bots
+/// if the order allows any bot.
+///
+/// Returns [`None`] if there is no bot to assign the purchases to.
+fn assigned_bot(storage: &dyn Storage, order: &DcaInfo, epoch: u64) -> StdResult<Option<Addr>> {
+    let bots = match order.allowed_bots.is_empty() {
+        true => BOTS
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+        false => order.allowed_bots.clone(),
+    };
+
+    if bots.is_empty() {
+        return Ok(None);
+    }
+
+    let index = order.id.wrapping_add(epoch) % bots.len() as u64;
+    Ok(bots.into_iter().nth(index as usize))
+}
+
+/// Describes how the amount spent in a DCA purchase is split
+pub struct PurchaseAmounts {
+    /// The protocol fee taken from the amount spent
+    pub protocol_fee: Uint128,
+    /// The share of the amount spent paid to the bot by an order with a swap tip
+    pub swap_tip: Uint128,
+    /// The part of the amount spent kept to replenish a low tip balance of the user
+    pub replenished_tip: Uint128,
+    /// The amount swapped for the target asset
+    pub swap_amount: Uint128,
+    /// The amount of a zapping order kept to provide liquidity along with the target asset
+    pub provide_amount: Uint128,
+}
+
+/// ## Description
+/// Returns the hop route of a DCA purchase of `order`, which is the route stored on the order if
+/// there is one, or the best route found on-chain if the order is auto routed, otherwise the
+/// `hops` provided by the sender. The first middle hop of the route to a token that is not
+/// whitelisted is returned along with it, if there is one.
+///
+/// Returns a [`ContractError`] if the route is empty or has more hops than the user allows.
+pub fn purchase_hops(
+    deps: &Deps,
+    config: &Config,
+    user_config: &UserConfig,
+    order: Option<&DcaInfo>,
+    hops: Vec<SwapOperation>,
+) -> Result<(Vec<SwapOperation>, Option<String>), ContractError> {
+    let max_hops = user_config.max_hops.unwrap_or(config.max_hops);
+
+    let hops = match order {
+        Some(DcaInfo {
+            route: Some(route),
+            initial_asset,
+            target_asset,
+            ..
+        }) => build_route(&initial_asset.info, route, target_asset),
+        Some(DcaInfo {
+            auto_route: true,
+            initial_asset,
+            target_asset,
+            dca_amount,
+            ..
+        }) => find_best_route(
+            deps,
+            config,
+            max_hops,
+            &initial_asset.info,
+            target_asset,
+            *dca_amount,
+        )?,
+        _ => hops,
+    };
+
+    // validate hops is at least one
+    if hops.is_empty() {
+        return Err(ContractError::EmptyHopRoute {});
+    }
+
+    // validate hops does not exceed max_hops
+    let hops_len = hops.len() as u32;
+    if hops_len > max_hops {
+        return Err(ContractError::MaxHopsAssertion { hops: hops_len });
+    }
+
+    // validate that all middle hops (last hop excluded) are whitelisted tokens for the ask_denom or ask_asset
+    let middle_hops = &hops[..hops.len() - 1];
+    let invalid_hop = middle_hops.iter().find_map(|swap| {
+        let (token, whitelisted) = match swap {
+            // only a whitelisted native token
+            SwapOperation::NativeSwap { ask_denom, .. } => (
+                ask_denom.to_string(),
+                config.whitelisted_tokens.iter().any(|token| match token {
+                    AssetInfo::NativeToken { denom } => ask_denom == denom,
+                    AssetInfo::Token { .. } => false,
+                }),
+            ),
+            SwapOperation::AstroSwap { ask_asset_info, .. } => (
+                ask_asset_info.to_string(),
+                config.is_whitelisted_asset(ask_asset_info),
+            ),
+        };
+
+        (!whitelisted).then(|| token)
+    });
+
+    Ok((hops, invalid_hop))
+}
+
+/// ## Description
+/// Returns the amount of the initial asset spent in the next DCA purchase of `order` through
+/// `hops`, along with the target value of a value averaging order after the purchase.
+///
+/// The last purchase of an order spends whatever remains of its deposit if it is less than
+/// dca_amount, while a value averaging order spends whatever brings the value of the target asset
+/// received up to its target path, valued at the simulated price of dca_amount, and spends nothing
+/// if the value is already on the path, and an order of tranches spends the first of its remaining
+/// tranches.
+pub fn purchase_spend_amount(
+    deps: &Deps,
+    config: &Config,
+    order: &DcaInfo,
+    hops: &[SwapOperation],
+) -> Result<(Uint128, Uint128), ContractError> {
+    let target_value = order.target_value.checked_add(order.dca_amount)?;
+    let spend_amount = match &order.strategy {
+        Strategy::Fixed => order.dca_amount.min(order.initial_asset.amount),
+        Strategy::ValueAveraging => {
+            let simulated_amount =
+                get_swap_simulation(deps, &config.router_addr, order.dca_amount, hops.to_vec())?;
+
+            let value = match simulated_amount.is_zero() {
+                true => target_value,
+                false => order
+                    .total_received
+                    .multiply_ratio(order.dca_amount, simulated_amount),
+            };
+
+            target_value
+                .saturating_sub(value)
+                .min(order.initial_asset.amount)
+        }
+        Strategy::Tranches { amounts } => amounts.first().copied().unwrap_or_default(),
+    };
+
+    Ok((spend_amount, target_value))
+}
+
+/// ## Description
+/// Splits `spend_amount` spent in a DCA purchase of `order` for `user` into the protocol fee, the
+/// share paid to the bot and the part kept to replenish a low tip balance of the user, purchasing
+/// with the rest, where a zapping order only swaps half of it, keeping the rest to provide
+/// liquidity.
+pub fn purchase_amounts(
+    querier: &QuerierWrapper,
+    config: &Config,
+    user_config: &UserConfig,
+    order: &DcaInfo,
+    user: &Addr,
+    spend_amount: Uint128,
+) -> Result<PurchaseAmounts, ContractError> {
+    let protocol_fee = get_protocol_fee(querier, config, user, spend_amount)?;
+    let swap_tip = order.swap_tip_bps.map_or(Uint128::zero(), |bps| {
+        spend_amount.multiply_ratio(bps, MAX_BPS)
+    });
+    let replenished_tip = match &order.tip_replenish {
+        Some(replenish) if user_config.tip(&order.initial_asset.info) < replenish.threshold => {
+            replenish.amount.min(spend_amount - protocol_fee - swap_tip)
+        }
+        _ => Uint128::zero(),
+    };
+    let purchase_amount = spend_amount - protocol_fee - swap_tip - replenished_tip;
+    let (swap_amount, provide_amount) = match order.zap {
+        Some(_) => split_zap_amount(purchase_amount),
+        None => (purchase_amount, Uint128::zero()),
+    };
+
+    Ok(PurchaseAmounts {
+        protocol_fee,
+        swap_tip,
+        replenished_tip,
+        swap_amount,
+        provide_amount,
+    })
+}
+
 /// ## Description
 /// Performs a DCA purchase on behalf of another user using the hop route specified.
 ///
+/// A bonded bot specifying a hop route through a non-whitelisted token, or returning less than the
+/// best route found on-chain, is slashed to the user through [`slash_bot`] instead.
+///
 /// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
 /// attributes if the operation was successful.
 /// ## Params
@@ -27,24 +458,90 @@ use crate::{
 /// * `info` - A [`MessageInfo`] from the bot who is performing a DCA purchase on behalf of another
 /// user, who will be rewarded with a uusd tip.
 ///
-/// * `user` - The address of the user as a [`String`] who is having a DCA purchase fulfilled.
+/// * `purchase` - The [`PurchaseRequest`] describing the purchase, containing:
+///
+///     * `user` - The address of the user as a [`String`] who is having a DCA purchase fulfilled.
+///
+///     * `id` - A [`u64`] representing the ID of the DCA order for the user
+///
+///     * `hops` - A [`Vec<SwapOperation>`] of the hop operations to complete in the swap to
+///     purchase the target asset. This is ignored if the order has a stored `route` or is auto
+///     routed.
 ///
-/// * `id` - A [`u64`] representing the ID of the DCA order for the user
+///     * `fee_redeem` - A [`Vec<Asset>`] of the fees redeemed by the sender for processing the
+///     DCA order.
 ///
-/// * `hops` - A [`Vec<SwapOperation>`] of the hop operations to complete in the swap to purchase
-/// the target asset.
+///     * `deadline` - An [`Option<u64>`] time in seconds after which the purchase can no longer be
+///     performed.
 ///
-/// * `fee_redeem` - A [`Vec<Asset>`] of the fees redeemed by the sender for processing the DCA
-/// order.
+///     * `accepted_tips` - A [`Vec<AssetInfo>`] of the tip assets the sender accepts, from which
+///     the tip is chosen by the priority of the user if `fee_redeem` is empty.
 pub fn perform_dca_purchase(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    user: String,
-    id: u64,
-    hops: Vec<SwapOperation>,
-    fee_redeem: Vec<Asset>,
+    purchase: PurchaseRequest,
+) -> Result<Response, ContractError> {
+    execute_dca_purchase(deps, env, info, purchase, &mut false)
+}
+
+/// ## Description
+/// Performs a DCA purchase of a batch of purchases through [`perform_dca_purchase`], ignoring the
+/// purchase if it is rejected by the checks that come before its swap, such as a purchase that is
+/// not due yet, an invalid hop route or a bot that is not allowed to perform it. Only the
+/// purchases that failed after passing every check are recorded as failures of their orders, so
+/// that no one can record failures on an order on purpose.
+///
+/// Returns a [`ContractError`] if the purchase failed after passing its checks, otherwise returns
+/// the [`Response`] of the purchase, or a [`Response`] with the error that rejected the purchase
+/// as an attribute.
+pub fn perform_batched_dca_purchase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    purchase: PurchaseRequest,
+) -> Result<Response, ContractError> {
+    let (user, id) = (purchase.user.clone(), purchase.id);
+
+    // nothing is stored by a purchase before it has passed its checks
+    let mut checked = false;
+    match execute_dca_purchase(deps, env, info, purchase, &mut checked) {
+        Err(err) if !checked => Ok(Response::new().add_attributes(vec![
+            attr("action", "reject_dca_purchase"),
+            attr("user", user),
+            attr("id", id.to_string()),
+            attr("error", err.to_string()),
+        ])),
+        result => result,
+    }
+}
+
+/// ## Description
+/// Performs the DCA purchase of [`perform_dca_purchase`], setting `checked` once the purchase has
+/// passed every check and starts to be stored.
+fn execute_dca_purchase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    purchase: PurchaseRequest,
+    checked: &mut bool,
 ) -> Result<Response, ContractError> {
+    let PurchaseRequest {
+        user,
+        id,
+        hops,
+        fee_redeem,
+        deadline,
+        accepted_tips,
+    } = purchase;
+
+    // check that the bot's deadline for the purchase has not passed
+    if let Some(deadline) = deadline {
+        if env.block.time.seconds() > deadline {
+            return Err(ContractError::DeadlinePassed {});
+        }
+    }
+
     // validate user address
     let user_address = addr_validate_to_lower(deps.api, &user)?;
 
@@ -54,46 +551,71 @@ pub fn perform_dca_purchase(
         .unwrap_or_default();
     let contract_config = CONFIG.load(deps.storage)?;
 
-    // validate hops is at least one
-    if hops.is_empty() {
-        return Err(ContractError::EmptyHopRoute {});
+    if contract_config.paused {
+        return Err(ContractError::ContractPaused {});
     }
 
-    // validate hops does not exceed max_hops
-    let hops_len = hops.len() as u32;
-    if hops_len > user_config.max_hops.unwrap_or(contract_config.max_hops) {
-        return Err(ContractError::MaxHopsAssertion { hops: hops_len });
+    // a purchase scheduled by the chain through [`super::sudo_execute_due_purchases`] is performed
+    // by the contract itself, which is not a bot and is paid no tips
+    let scheduled = info.sender == env.contract.address;
+
+    if contract_config.registered_bots_only && !scheduled && !BOTS.has(deps.storage, &info.sender) {
+        return Err(ContractError::UnregisteredBot {});
     }
 
-    // validate that all middle hops (last hop excluded) are whitelisted tokens for the ask_denom or ask_asset
-    let middle_hops = &hops[..hops.len() - 1];
-    for swap in middle_hops {
-        match swap {
-            SwapOperation::NativeSwap { ask_denom, .. } => {
-                if !contract_config
-                    .whitelisted_tokens
-                    .iter()
-                    .any(|token| match token {
-                        AssetInfo::NativeToken { denom } => ask_denom == denom,
-                        AssetInfo::Token { .. } => false,
-                    })
-                {
-                    // not a whitelisted native token
-                    return Err(ContractError::InvalidHopRoute {
-                        token: ask_denom.to_string(),
-                    });
-                }
-            }
-            SwapOperation::AstroSwap { ask_asset_info, .. } => {
-                if !contract_config.is_whitelisted_asset(ask_asset_info) {
-                    return Err(ContractError::InvalidHopRoute {
-                        token: ask_asset_info.to_string(),
-                    });
-                }
-            }
+    // load the user dca order, which is checked to exist once the hops have been validated
+    let order = user_dca().may_load(deps.storage, (&user_address, id))?;
+
+    // check that the sender is allowed to perform the purchases of the order
+    if let (Some(order), false) = (&order, scheduled) {
+        if !order.allowed_bots.is_empty() && !order.allowed_bots.contains(&info.sender) {
+            return Err(ContractError::BotNotAllowed {});
         }
     }
 
+    // a bot routing the purchase of an order itself has its bond slashed for an invalid or worse
+    // route, once the purchase is otherwise known to be valid
+    let bonded = matches!(
+        &order,
+        Some(DcaInfo {
+            route: None,
+            auto_route: false,
+            ..
+        })
+    ) && contract_config.bot_bond.is_some()
+        && BOTS
+            .may_load(deps.storage, &info.sender)?
+            .map_or(false, |stats| stats.bond.is_some());
+
+    let (hops, invalid_hop) = purchase_hops(
+        &deps.as_ref(),
+        &contract_config,
+        &user_config,
+        order.as_ref(),
+        hops,
+    )?;
+    let hops_len = hops.len() as u32;
+    if let (Some(token), false) = (&invalid_hop, bonded) {
+        return Err(ContractError::InvalidHopRoute {
+            token: token.clone(),
+        });
+    }
+
+    // an order paying a share of each purchase to the bot does not redeem the tip balance
+    let swap_tip_bps = order.as_ref().and_then(|order| order.swap_tip_bps);
+    if swap_tip_bps.is_some() && !fee_redeem.is_empty() {
+        return Err(ContractError::SwapTipNotRedeemable {});
+    }
+
+    // leave the choice of tip to the contract if the bot did not request one, redeeming the tip
+    // for every hop from the first accepted asset in the priority of the user that covers it
+    let fee_redeem = if fee_redeem.is_empty() && !accepted_tips.is_empty() && swap_tip_bps.is_none()
+    {
+        select_tip(&contract_config, &user_config, &accepted_tips, hops_len)?
+    } else {
+        fee_redeem
+    };
+
     // validate that fee_redeem is a valid combination
     let requested_fee_hops: Uint128 = fee_redeem
         .iter()
@@ -135,7 +657,7 @@ pub fn perform_dca_purchase(
     let mut messages: Vec<CosmosMsg> = Vec::new();
 
     // validate purchaser has enough funds to pay the sender
-    for fee_asset in fee_redeem {
+    for fee_asset in fee_redeem.clone() {
         let mut user_balance = user_config
             .tip_balance
             .iter_mut()
@@ -151,393 +673,859 @@ pub fn perform_dca_purchase(
         user_balance.amount = new_balance;
 
         // add tip payment to messages
-        let tip_payment_message = match fee_asset.info {
-            AssetInfo::NativeToken { denom } => BankMsg::Send {
-                to_address: info.clone().sender.to_string(),
-                amount: vec![Coin {
-                    amount: fee_asset.amount,
-                    denom,
-                }],
-            }
-            .into(),
-            AssetInfo::Token { contract_addr } => WasmMsg::Execute {
-                contract_addr: contract_addr.into_string(),
-                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                    owner: user_address.clone().into_string(),
-                    recipient: info.sender.clone().into_string(),
-                    amount: fee_asset.amount,
-                })?,
-                funds: vec![],
-            }
-            .into(),
-        };
+        messages.extend(tip_payment_msgs(
+            &mut user_config,
+            &user_address,
+            &info.sender,
+            fee_asset,
+        )?);
+    }
+
+    // check that the user dca order exists and update it
+    let mut order = order.ok_or(ContractError::NonexistentDca {})?;
 
-        messages.push(tip_payment_message);
+    // check that a purchase is not already being performed for the order
+    if order.executing {
+        return Err(ContractError::PurchaseInProgress {});
     }
 
-    // retrieve max_spread from user config, or default to contract set max_spread
-    let max_spread = user_config.max_spread.unwrap_or(contract_config.max_spread);
+    // check that the order has not been paused by the user
+    if order.paused {
+        return Err(ContractError::OrderPaused {});
+    }
 
-    // load user dca orders and update the relevant one
-    USER_DCA.update(
-        deps.storage,
-        &user_address,
-        |orders| -> Result<Vec<DcaInfo>, ContractError> {
-            let mut orders = orders.ok_or(ContractError::NonexistentDca {})?;
+    // check that the purchases of neither end of the route, nor any of the target assets of the
+    // order, have been paused by the contract owner
+    let order_assets = [order.initial_asset.info.clone(), order.target_asset.clone()]
+        .into_iter()
+        .chain(order.targets.iter().map(|target| target.asset.clone()));
+    for asset in order_assets.clone() {
+        if contract_config.is_paused_asset(&asset) {
+            return Err(ContractError::AssetPaused { asset });
+        }
+    }
 
-            let order_idx = orders
-                .iter()
-                .position(|order| order.id == id)
-                .ok_or(ContractError::NonexistentDca {})?;
+    // check that neither the order nor its route touches a blacklisted asset, in which case the
+    // order can only be swept back to its owner
+    if let Some(asset) = order_assets
+        .chain(hops.iter().map(|hop| hop.get_target_asset_info()))
+        .find(|asset| contract_config.is_blacklisted_asset(asset))
+    {
+        return Err(ContractError::AssetBlacklisted { asset });
+    }
 
-            let mut order = &mut orders[order_idx];
+    // check that the order has not expired
+    if let Some(expires_at) = &order.expires_at {
+        if expires_at.is_expired(&env.block) {
+            return Err(ContractError::OrderExpired {});
+        }
+    }
 
-            // check that it has been long enough between dca purchases
-            if order.last_purchase + order.interval > env.block.time.seconds() {
-                return Err(ContractError::PurchaseTooEarly {});
-            }
+    // check that it has been long enough between dca purchases, and that the latest scheduled
+    // purchase has not been skipped for being too late
+    if !order.is_due(&env.block) {
+        return match order.latest_purchase(order.interval.current(&env.block)) {
+            Some(_) => Err(ContractError::PurchaseSkipped {}),
+            None => Err(ContractError::PurchaseTooEarly {}),
+        };
+    }
 
-            // check that last hop is target asset
-            let last_hop = hops.last().ok_or(ContractError::EmptyHopRoute {})?;
-            if last_hop.get_target_asset_info() != order.target_asset {
-                return Err(ContractError::TargetAssetAssertion {});
-            }
+    // the delay of the purchase after it was due, which is recorded on the stats of the bot
+    let delay = order.overdue(&env.block).unwrap_or(0);
 
-            // subtract dca_amount from order and update last_purchase time
-            order.initial_asset.amount = order
-                .initial_asset
-                .amount
-                .checked_sub(order.dca_amount)
-                .map_err(|_| ContractError::InsufficientBalance {})?;
-            order.last_purchase = env.block.time.seconds();
+    // within the fairness window after the purchase was due, only the bot assigned to it can
+    // perform it, so that a single bot cannot take the tip of every purchase
+    if let (Some(window), Some(overdue), false) = (
+        contract_config.fairness_window,
+        order.overdue(&env.block),
+        scheduled,
+    ) {
+        if overdue < window {
+            let due = order.interval.current(&env.block) - overdue;
+            if let Some(assigned) = assigned_bot(deps.storage, &order, due / window)? {
+                if assigned != info.sender {
+                    return Err(ContractError::NotAssignedBot {
+                        bot: assigned.into_string(),
+                    });
+                }
+            }
+        }
+    }
 
-            // add funds and router message to response
-            if let AssetInfo::Token { contract_addr } = &order.initial_asset.info {
-                // send a TransferFrom request to the token to the router
-                messages.push(
-                    WasmMsg::Execute {
-                        contract_addr: contract_addr.to_string(),
-                        funds: vec![],
-                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                            owner: user_address.to_string(),
-                            recipient: contract_config.router_addr.to_string(),
-                            amount: order.dca_amount,
-                        })?,
-                    }
-                    .into(),
-                );
+    // pay the bot the tip of an overdue purchase that has grown on top of the tip it redeemed, as
+    // far as the tip balance of the user covers it
+    let mut tip = fee_redeem;
+    if let (Some(escalated_tip), false) = (order.escalated_tip(&env.block), scheduled) {
+        let amount = match user_config
+            .tip_balance
+            .iter_mut()
+            .find(|a| a.info == escalated_tip.info)
+        {
+            Some(balance) => {
+                let amount = balance.amount.min(escalated_tip.amount);
+                balance.amount -= amount;
+                amount
             }
+            None => Uint128::zero(),
+        };
 
-            // if it is a native token, we need to send the funds
-            let funds = match &order.initial_asset.info {
-                AssetInfo::NativeToken { denom } => vec![Coin {
-                    amount: order.dca_amount,
-                    denom: denom.clone(),
-                }],
-                AssetInfo::Token { .. } => vec![],
+        if !amount.is_zero() {
+            let escalated_tip = Asset {
+                info: escalated_tip.info,
+                amount,
             };
+            messages.extend(tip_payment_msgs(
+                &mut user_config,
+                &user_address,
+                &info.sender,
+                escalated_tip.clone(),
+            )?);
+
+            match tip.iter_mut().find(|a| a.info == escalated_tip.info) {
+                Some(redeemed) => redeemed.amount = redeemed.amount.checked_add(amount)?,
+                None => tip.push(escalated_tip),
+            }
+        }
+    }
 
-            // tell the router to perform swap operations
-            messages.push(
-                WasmMsg::Execute {
-                    contract_addr: contract_config.router_addr.to_string(),
-                    funds,
-                    msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
-                        operations: hops,
-                        minimum_receive: None,
-                        to: Some(user_address.clone().into_string()),
-                        max_spread: Some(max_spread),
-                    })?,
+    // check that last hop is target asset
+    let last_hop = hops.last().ok_or(ContractError::EmptyHopRoute {})?;
+    if last_hop.get_target_asset_info() != order.target_asset {
+        return Err(ContractError::TargetAssetAssertion {});
+    }
+
+    // slash a bonded bot to the user instead of purchasing through a non-whitelisted token, or
+    // through a route returning less than the best route found on-chain
+    if bonded {
+        let reason = match invalid_hop {
+            Some(_) => Some("invalid_route"),
+            None => {
+                let returned = get_swap_simulation(
+                    &deps.as_ref(),
+                    &contract_config.router_addr,
+                    order.dca_amount,
+                    hops.clone(),
+                );
+                let best = find_best_route(
+                    &deps.as_ref(),
+                    &contract_config,
+                    user_config.max_hops.unwrap_or(contract_config.max_hops),
+                    &order.initial_asset.info,
+                    &order.target_asset,
+                    order.dca_amount,
+                )
+                .ok()
+                .and_then(|best_route| {
+                    get_swap_simulation(
+                        &deps.as_ref(),
+                        &contract_config.router_addr,
+                        order.dca_amount,
+                        best_route,
+                    )
+                    .ok()
+                });
+
+                match (returned, best) {
+                    (Ok(returned), Some(best)) if returned < best => Some("worse_route"),
+                    _ => None,
                 }
-                .into(),
-            );
+            }
+        };
 
-            // remove order if it was fulfilled
-            if order.initial_asset.amount.is_zero() {
-                orders.remove(order_idx);
+        if let Some(reason) = reason {
+            if let Some(response) = slash_bot(
+                deps.storage,
+                &contract_config,
+                &info.sender,
+                &user_address,
+                id,
+                reason,
+            )? {
+                return Ok(response);
             }
+        }
 
-            Ok(orders)
-        },
-    )?;
+        if let Some(token) = invalid_hop {
+            return Err(ContractError::InvalidHopRoute { token });
+        }
+    }
 
-    // save new config
-    USER_CONFIG.save(deps.storage, &user_address, &user_config)?;
+    let (spend_amount, target_value) =
+        purchase_spend_amount(&deps.as_ref(), &contract_config, &order, &hops)?;
+    if order.strategy == Strategy::ValueAveraging {
+        order.target_value = target_value;
+    }
+    if spend_amount.is_zero() {
+        return skip_purchase(deps, &env, &user_address, id, order, "target_value_reached");
+    }
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "perform_dca_purchase"),
-        attr("user", user_address),
-        attr("id", id.to_string()),
-    ]))
-}
+    // an order pulling each purchase from the wallet of the user is skipped while the allowance
+    // not reserved by the other orders of the user, or the balance of the user, does not cover it
+    if let (AssetInfo::Token { contract_addr }, true) = (&order.initial_asset.info, order.pull) {
+        let reserved = get_reserved_allowance(deps.storage, &user_address, contract_addr)?;
+        let allowance = get_token_allowance(&deps.as_ref(), &env, &user_address, contract_addr)?;
+        if allowance.saturating_sub(reserved) < spend_amount {
+            return skip_purchase(
+                deps,
+                &env,
+                &user_address,
+                id,
+                order,
+                "insufficient_allowance",
+            );
+        }
 
-#[cfg(test)]
-mod test {
-    use std::str::FromStr;
+        let balance = query_balance(&deps.querier, contract_addr, &user_address)?;
+        if balance < spend_amount {
+            return skip_purchase(deps, &env, &user_address, id, order, "insufficient_balance");
+        }
+    }
 
-    use astroport::{
-        asset::{Asset, AssetInfo},
-        factory::{PairConfig, PairType},
-        router::SwapOperation,
+    let PurchaseAmounts {
+        protocol_fee,
+        swap_tip,
+        replenished_tip,
+        swap_amount,
+        provide_amount,
+    } = match scheduled {
+        // an order paying a share of each purchase to the bot swaps that share as well when the
+        // purchase is scheduled by the chain
+        true => purchase_amounts(
+            &deps.querier,
+            &contract_config,
+            &user_config,
+            &DcaInfo {
+                swap_tip_bps: None,
+                ..order.clone()
+            },
+            &user_address,
+            spend_amount,
+        )?,
+        false => purchase_amounts(
+            &deps.querier,
+            &contract_config,
+            &user_config,
+            &order,
+            &user_address,
+            spend_amount,
+        )?,
     };
-    use astroport_dca::dca::{DcaInfo, ExecuteMsg, InstantiateMsg};
-    use cosmwasm_std::{
-        attr, coin, coins,
-        testing::{mock_dependencies, mock_env, mock_info},
-        to_binary, Addr, BankMsg, CosmosMsg, Decimal, DivideByZeroError, Response, StdError,
-        Uint128, WasmMsg,
-    };
-    use cw20::{BalanceResponse, Cw20Coin};
-    use cw_multi_test::{App, Executor};
 
-    use crate::{
-        contract::execute,
-        error::ContractError,
-        state::{Config, UserConfig, CONFIG, USER_CONFIG, USER_DCA},
-        tests::{
-            mock_app_with_balance, mock_creator, mock_instantiate, read_map,
-            store_astroport_pair_code, store_cw20_token_code, store_dca_module_code,
-            store_factory_code, store_router_code,
-        },
+    // the oracle guarding the purchase, if the contract owner has set a maximum deviation and an
+    // oracle for the pair of the order
+    let oracle = match contract_config.max_oracle_deviation {
+        Some(max_deviation) => ORACLES
+            .may_load(
+                deps.storage,
+                (
+                    order.initial_asset.info.to_string(),
+                    order.target_asset.to_string(),
+                ),
+            )?
+            .map(|oracle_addr| (oracle_addr, max_deviation)),
+        None => None,
     };
 
-    fn instantiate(max_hops: Option<u32>) -> (App, Addr, Addr) {
-        let admin = Addr::unchecked("admin");
+    // the routes that a purchase losing too much of its value through a single route is split
+    // across instead
+    let mut split = None;
+
+    if order.max_price.is_some()
+        || order.only_below_price.is_some()
+        || order.max_price_impact.is_some()
+        || order.stop_price.is_some()
+        || order.take_profit_price.is_some()
+        || oracle.is_some()
+    {
+        let simulated_amount = get_swap_simulation(
+            &deps.as_ref(),
+            &contract_config.router_addr,
+            swap_amount,
+            hops.clone(),
+        )?;
+
+        // a route that returns nothing is priced above any limit
+        let price = match simulated_amount.is_zero() {
+            true => Decimal::MAX,
+            false => Decimal::from_ratio(swap_amount, simulated_amount),
+        };
 
-        let mut app = mock_app_with_balance(vec![
-            (mock_creator().sender, coins(500_000, "uluna")),
-            (
-                admin.clone(),
-                vec![
-                    coin(1_000_000, "uluna"),
-                    coin(1_500_000, "ujpy"),
-                    coin(1_000_000, "ukrw"),
-                ],
-            ),
-        ]);
+        // close the order once the simulated price has fallen below its stop price or risen
+        // above its take profit price
+        if let Some(stop_price) = order.stop_price {
+            if price < stop_price {
+                return stop_order(deps, &user_address, id, order, price, "stop_loss");
+            }
+        }
+        if let Some(take_profit_price) = order.take_profit_price {
+            if price > take_profit_price {
+                return stop_order(deps, &user_address, id, order, price, "take_profit");
+            }
+        }
 
-        let cw20_token_id = store_cw20_token_code(&mut app);
-        let dca_module_id = store_dca_module_code(&mut app);
-        let astroport_pair_id = store_astroport_pair_code(&mut app);
-        let factory_id = store_factory_code(&mut app);
-        let router_id = store_router_code(&mut app);
+        // skip the purchase while the simulated price is above the threshold of the order
+        if let Some(only_below_price) = order.only_below_price {
+            if price > only_below_price {
+                return skip_purchase(
+                    deps,
+                    &env,
+                    &user_address,
+                    id,
+                    order,
+                    "price_above_threshold",
+                );
+            }
+        }
 
-        // instantiate cw20 token
-        let cw20_addr = app
-            .instantiate_contract(
-                cw20_token_id,
-                mock_creator().sender,
-                &cw20_base::msg::InstantiateMsg {
-                    decimals: 6,
-                    initial_balances: vec![
-                        Cw20Coin {
-                            address: admin.clone().into_string(),
-                            amount: Uint128::new(1_000_000),
-                        },
-                        Cw20Coin {
-                            address: mock_creator().sender.into_string(),
-                            amount: Uint128::new(500_000),
-                        },
-                    ],
-                    marketing: None,
-                    mint: None,
-                    name: "cw20 token".to_string(),
-                    symbol: "cwT".to_string(),
-                },
-                &[],
-                "cw20 mock contract",
-                None,
-            )
-            .unwrap();
+        // split the purchase across several routes through the router while the simulated swap
+        // loses more of its value to the fees and slippage of its pairs than the order allows,
+        // such as a large purchase from a thin pool, or skip it if no split stays within the limit
+        if let Some(max_price_impact) = order.max_price_impact {
+            let price_impact = get_price_impact(
+                &deps.as_ref(),
+                &contract_config,
+                swap_amount,
+                &hops,
+                simulated_amount,
+            )?;
+            if price_impact.map_or(false, |price_impact| price_impact > max_price_impact) {
+                if contract_config.swap_backend == SwapBackendType::Router
+                    && !contract_config.is_fee_on_transfer_token(&order.initial_asset.info)
+                {
+                    split = find_split_routes(
+                        &deps.as_ref(),
+                        &contract_config,
+                        user_config.max_hops.unwrap_or(contract_config.max_hops),
+                        &order.initial_asset.info,
+                        &order.target_asset,
+                        swap_amount,
+                        max_price_impact,
+                    )?;
+                }
 
-        // instantiate random whitelisted contract
-        let cw20_whitelist_addr = app
-            .instantiate_contract(
-                cw20_token_id,
-                mock_creator().sender,
-                &cw20_base::msg::InstantiateMsg {
-                    decimals: 6,
-                    initial_balances: vec![],
-                    marketing: None,
-                    mint: None,
-                    name: "cw20 whitelisted token".to_string(),
-                    symbol: "cwWT".to_string(),
-                },
-                &[],
-                "cw20 mock whitelisted contract",
-                None,
-            )
-            .unwrap();
+                if split.is_none() {
+                    return skip_purchase(
+                        deps,
+                        &env,
+                        &user_address,
+                        id,
+                        order,
+                        "price_impact_too_high",
+                    );
+                }
+            }
+        }
 
-        // instantiate factory
-        let factory_addr = app
-            .instantiate_contract(
-                factory_id,
-                mock_creator().sender,
-                &astroport::factory::InstantiateMsg {
-                    fee_address: None,
-                    generator_address: None,
-                    owner: mock_creator().sender.into_string(),
-                    pair_configs: vec![PairConfig {
-                        pair_type: PairType::Xyk {},
-                        is_disabled: false,
-                        is_generator_disabled: true,
-                        maker_fee_bps: 30,
-                        total_fee_bps: 30,
-                        code_id: astroport_pair_id,
-                    }],
-                    token_code_id: cw20_token_id,
-                    whitelist_code_id: 100,
+        // check that the simulated price of the target asset does not exceed the max price of the
+        // order
+        if let Some(max_price) = order.max_price {
+            if price > max_price {
+                return Err(ContractError::PriceAboveLimit { price, max_price });
+            }
+        }
+
+        // check that the simulated amount does not deviate too far from the amount the oracle
+        // prices the swap at, where an oracle pricing it at nothing deviates above any limit
+        if let Some((oracle_addr, max_deviation)) = oracle {
+            let oracle_amount = consult_oracle(
+                &deps.querier,
+                &oracle_addr,
+                order.initial_asset.info.clone(),
+                swap_amount,
+            )?;
+
+            let deviation = match oracle_amount.is_zero() {
+                true => Decimal::MAX,
+                false => {
+                    let difference = match simulated_amount > oracle_amount {
+                        true => simulated_amount - oracle_amount,
+                        false => oracle_amount - simulated_amount,
+                    };
+                    Decimal::from_ratio(difference, oracle_amount)
+                }
+            };
+            if deviation > max_deviation {
+                return Err(ContractError::OracleDeviation {
+                    deviation,
+                    max_deviation,
+                });
+            }
+        }
+    }
+
+    // split the purchase of an order with several target assets across them by their weights, the
+    // last target receiving the remainder, each swapped through the best route found on-chain
+    let mut targets = vec![];
+    if !order.targets.is_empty() {
+        // a token taking a tax on transfers is pulled into the contract as a single amount
+        if contract_config.is_fee_on_transfer_token(&order.initial_asset.info) {
+            return Err(ContractError::InvalidMultiTargetOrder {});
+        }
+
+        let max_hops = user_config.max_hops.unwrap_or(contract_config.max_hops);
+        let mut remaining = swap_amount;
+        let mut routes = vec![];
+        for (i, target) in order.targets.iter().enumerate() {
+            let amount = match i + 1 == order.targets.len() {
+                true => remaining,
+                false => swap_amount * target.weight,
+            };
+            remaining = remaining.checked_sub(amount)?;
+            if amount.is_zero() {
+                continue;
+            }
+
+            let hops = find_best_route(
+                &deps.as_ref(),
+                &contract_config,
+                max_hops,
+                &order.initial_asset.info,
+                &target.asset,
+                amount,
+            )?;
+            routes.push((hops, amount));
+            targets.push(target.asset.clone());
+        }
+
+        if !routes.is_empty() {
+            split = Some(routes);
+        }
+    }
+
+    *checked = true;
+
+    // subtract the amount spent from order and update last_purchase to the time or height of the
+    // block
+    order.initial_asset.amount = order
+        .initial_asset
+        .amount
+        .checked_sub(spend_amount)
+        .map_err(|_| ContractError::InsufficientBalance {})?;
+    if let Strategy::Tranches { amounts } = &mut order.strategy {
+        amounts.remove(0);
+    }
+    order.last_purchase = order.interval.current(&env.block);
+    order.jitter_offset = order
+        .jitter
+        .map_or(0, |window| jitter_offset(&env, &user_address, id, window));
+    order.total_spent = order.total_spent.checked_add(spend_amount)?;
+
+    // add funds and swap message to response
+    let backend = swap_backend(&contract_config.swap_backend);
+    let deposit_addr = backend.deposit_addr(&env, &contract_config);
+    let mut pull_msg = None;
+    if let (AssetInfo::Token { contract_addr }, false, true) = (
+        &order.initial_asset.info,
+        order.escrowed,
+        contract_config.is_fee_on_transfer_token(&order.initial_asset.info),
+    ) {
+        // only the router swaps the amount it received rather than the amount requested
+        if contract_config.swap_backend != SwapBackendType::Router {
+            return Err(ContractError::FeeOnTransferSwapBackend {});
+        }
+
+        // a token taking a tax on transfers is pulled into the contract first, so that only the
+        // amount actually received is sent to the router and provided as liquidity
+        pull_msg = Some(pull_fee_on_transfer_token(
+            deps.storage,
+            &deps.querier,
+            &env,
+            contract_addr,
+            &user_address,
+            swap_amount,
+            provide_amount,
+        )?);
+    } else if let AssetInfo::Token { contract_addr } = &order.initial_asset.info {
+        // send the escrowed tokens held by the contract to the deposit address of the swap
+        // backend, unless they are swapped from the contract, otherwise send a TransferFrom
+        // request to the token to the deposit address. The tokens of a split purchase are sent
+        // along with each of its swaps instead
+        let transfer_msg = match order.escrowed {
+            _ if split.is_some() => None,
+            true if deposit_addr == env.contract.address => None,
+            true => Some(Cw20ExecuteMsg::Transfer {
+                recipient: deposit_addr.to_string(),
+                amount: swap_amount,
+            }),
+            false => Some(Cw20ExecuteMsg::TransferFrom {
+                owner: user_address.to_string(),
+                recipient: deposit_addr.to_string(),
+                amount: swap_amount,
+            }),
+        };
+
+        if let Some(transfer_msg) = transfer_msg {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&transfer_msg)?,
+                }
+                .into(),
+            );
+        }
+
+        // the half of a zapping order provided as liquidity must be held by the contract
+        if !order.escrowed && !provide_amount.is_zero() {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                        owner: user_address.to_string(),
+                        recipient: env.contract.address.to_string(),
+                        amount: provide_amount,
+                    })?,
+                }
+                .into(),
+            );
+        }
+    }
+
+    // send the protocol fee to the fee collector and record it, keeping the share of the referrer
+    // of the order in the contract until they claim it
+    if let (Some(fee_collector), false) = (&contract_config.fee_collector, protocol_fee.is_zero()) {
+        let referral_reward = match &order.referrer {
+            Some(_) => protocol_fee * contract_config.referral_share,
+            None => Uint128::zero(),
+        };
+        let collected_fee = protocol_fee - referral_reward;
+
+        if !collected_fee.is_zero() {
+            messages.push(spend_order_msg(
+                &order,
+                &user_address,
+                fee_collector,
+                collected_fee,
+            )?);
+
+            PROTOCOL_FEES.update(
+                deps.storage,
+                order.initial_asset.info.to_string(),
+                |fees| -> StdResult<_> {
+                    let mut fees = fees.unwrap_or(Asset {
+                        info: order.initial_asset.info.clone(),
+                        amount: Uint128::zero(),
+                    });
+                    fees.amount = fees.amount.checked_add(collected_fee)?;
+                    Ok(fees)
                 },
-                &[],
-                "astroport factory",
-                None,
-            )
-            .unwrap();
+            )?;
+        }
 
-        // instantiate router
-        let router_addr = app
-            .instantiate_contract(
-                router_id,
-                mock_creator().sender,
-                &astroport::router::InstantiateMsg {
-                    astroport_factory: factory_addr.clone().into_string(),
+        if let (Some(referrer), false) = (&order.referrer, referral_reward.is_zero()) {
+            // tokens spent from the allowance of the user must be moved into the contract
+            if let AssetInfo::Token { .. } = &order.initial_asset.info {
+                if !order.escrowed {
+                    messages.push(spend_order_msg(
+                        &order,
+                        &user_address,
+                        &env.contract.address,
+                        referral_reward,
+                    )?);
+                }
+            }
+
+            REFERRAL_REWARDS.update(
+                deps.storage,
+                (referrer, order.initial_asset.info.to_string()),
+                |rewards| -> StdResult<_> {
+                    let mut rewards = rewards.unwrap_or(Asset {
+                        info: order.initial_asset.info.clone(),
+                        amount: Uint128::zero(),
+                    });
+                    rewards.amount = rewards.amount.checked_add(referral_reward)?;
+                    Ok(rewards)
                 },
-                &[],
-                "astroport router",
-                None,
-            )
-            .unwrap();
+            )?;
+        }
+    }
 
-        // instantiate dca module
-        let dca_addr = app
-            .instantiate_contract(
-                dca_module_id,
-                mock_creator().sender,
-                &InstantiateMsg {
-                    factory_addr: factory_addr.clone().into_string(),
-                    max_hops: max_hops.unwrap_or(4),
-                    max_spread: "0.05".to_string(),
-                    router_addr: router_addr.into_string(),
-                    whitelisted_fee_assets: vec![
-                        Asset {
-                            amount: Uint128::new(15_000),
-                            info: AssetInfo::NativeToken {
-                                denom: "uluna".to_string(),
-                            },
-                        },
-                        Asset {
-                            amount: Uint128::new(15_000),
-                            info: AssetInfo::Token {
-                                contract_addr: cw20_addr.clone(),
-                            },
-                        },
-                    ],
-                    whitelisted_tokens: vec![
-                        AssetInfo::Token {
-                            contract_addr: cw20_whitelist_addr,
-                        },
-                        AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
+    // pay the share of the purchase to the bot, recording it as part of the tip of the purchase
+    if !swap_tip.is_zero() {
+        messages.push(spend_order_msg(
+            &order,
+            &user_address,
+            &info.sender,
+            swap_tip,
+        )?);
+
+        tip.push(Asset {
+            info: order.initial_asset.info.clone(),
+            amount: swap_tip,
+        });
+    }
+
+    // the replenished tip is already held by the contract, as only orders with a native or
+    // escrowed deposit replenish the tip balance
+    if !replenished_tip.is_zero() {
+        user_config.add_held_tip(&Asset {
+            info: order.initial_asset.info.clone(),
+            amount: replenished_tip,
+        })?;
+        order.replenished_tip = order.replenished_tip.checked_add(replenished_tip)?;
+    }
+
+    // retrieve max_spread from the order, then the user config, or default to contract set
+    // max_spread
+    let max_spread = order
+        .max_spread
+        .or(user_config.max_spread)
+        .unwrap_or(contract_config.max_spread);
+
+    // a route through a stable or concentrated liquidity pool bounds its slippage by the amount it
+    // is simulated to return, as their spread does not measure the slippage of a swap. A split
+    // purchase bounds the slippage of all of its swaps together, while each swap of a purchase
+    // split across several target assets bounds its own
+    let mut target_params = vec![];
+    for (hops, amount) in split.iter().flatten().filter(|_| !targets.is_empty()) {
+        let xyk = is_xyk_route(
+            deps.storage,
+            &deps.querier,
+            &contract_config.factory_addr,
+            hops,
+        )?;
+        let simulated = match xyk {
+            true => None,
+            false => Some(get_swap_simulation(
+                &deps.as_ref(),
+                &contract_config.router_addr,
+                *amount,
+                hops.clone(),
+            )?),
+        };
+        target_params.push(swap_params(max_spread, None, simulated));
+    }
+
+    let routes = match targets.is_empty() {
+        true => split
+            .clone()
+            .unwrap_or_else(|| vec![(hops.clone(), swap_amount)]),
+        false => vec![],
+    };
+    let mut xyk = true;
+    for (hops, _) in &routes {
+        xyk &= is_xyk_route(
+            deps.storage,
+            &deps.querier,
+            &contract_config.factory_addr,
+            hops,
+        )?;
+    }
+    let simulated = match xyk {
+        true => None,
+        false => {
+            let mut simulated = Uint128::zero();
+            for (hops, amount) in &routes {
+                simulated = simulated.checked_add(get_swap_simulation(
+                    &deps.as_ref(),
+                    &contract_config.router_addr,
+                    *amount,
+                    hops.clone(),
+                )?)?;
+            }
+            Some(simulated)
+        }
+    };
+    let (max_spread, minimum_receive) =
+        swap_params(max_spread, order.min_receive_per_purchase, simulated);
+
+    // send the purchased asset to the recipient of the order or the user, or hold it in the
+    // contract until it is claimed, sent to the purchase hooks of the order, staked, provided as
+    // liquidity or transferred to another chain
+    let recipient = match order.recipient.clone() {
+        Some(recipient) => recipient,
+        None => get_order_holder(deps.as_ref(), &user_address, id)?
+            .unwrap_or_else(|| user_address.clone()),
+    };
+    let to = match order.delivery {
+        Delivery::Immediate
+            if order.purchase_hooks.is_empty() && !order.auto_stake && order.zap.is_none() =>
+        {
+            Some(recipient.to_string())
+        }
+        _ => None,
+    };
+
+    // tell the swap backend to perform the swap, replying on success to record the amount
+    // received by the user. Each swap of a split purchase replies with the amount it received
+    // instead, which are checked against the minimum receive together once all have replied
+    let mut swap_msgs = vec![];
+    match split {
+        None => swap_msgs.push(SubMsg::reply_on_success(
+            backend.swap_msg(
+                deps.storage,
+                &deps.querier,
+                &contract_config,
+                Swap {
+                    offer: Asset {
+                        info: order.initial_asset.info.clone(),
+                        amount: swap_amount,
+                    },
+                    hops,
+                    max_spread,
+                    minimum_receive,
+                    to,
+                },
+            )?,
+            PERFORM_DCA_PURCHASE_REPLY_ID,
+        )),
+        Some(split) => {
+            PENDING_SPLIT.save(
+                deps.storage,
+                &PendingSplit {
+                    remaining: split.len() as u32,
+                    received: Uint128::zero(),
+                    minimum_receive,
+                    targets,
+                    outputs: vec![],
+                },
+            )?;
+
+            for (i, (hops, amount)) in split.into_iter().enumerate() {
+                // the router swaps all of the tokens it holds, so the tokens of each swap are
+                // only sent to it right before the swap
+                if let AssetInfo::Token { contract_addr } = &order.initial_asset.info {
+                    let transfer_msg = match order.escrowed {
+                        true => Cw20ExecuteMsg::Transfer {
+                            recipient: deposit_addr.to_string(),
+                            amount,
                         },
-                        AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
+                        false => Cw20ExecuteMsg::TransferFrom {
+                            owner: user_address.to_string(),
+                            recipient: deposit_addr.to_string(),
+                            amount,
                         },
-                    ],
-                },
-                &[],
-                "dca module",
-                None,
-            )
-            .unwrap();
+                    };
+                    swap_msgs.push(SubMsg::new(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        funds: vec![],
+                        msg: to_binary(&transfer_msg)?,
+                    }));
+                }
 
-        // create some pools
-        let mut create_pool = |assets: [AssetInfo; 2]| {
-            let pair_res = app
-                .execute_contract(
-                    mock_creator().sender,
-                    factory_addr.clone(),
-                    &astroport::factory::ExecuteMsg::CreatePair {
-                        pair_type: PairType::Xyk {},
-                        asset_infos: [assets[0].clone(), assets[1].clone()],
-                        init_params: None,
-                    },
-                    &[],
-                )
-                .unwrap();
-
-            let pair_addr = Addr::unchecked(pair_res.events[2].attributes[0].value.clone());
-
-            // add some liquidity to the pools
-            let mut funds = vec![];
-            for asset in &assets {
-                match asset {
-                    AssetInfo::NativeToken { denom } => funds.push(coin(500_000, denom)),
-                    AssetInfo::Token { contract_addr } => {
-                        app.execute_contract(
-                            admin.clone(),
-                            contract_addr.clone(),
-                            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-                                spender: pair_addr.clone().into_string(),
-                                amount: Uint128::new(500_000),
-                                expires: None,
+                swap_msgs.push(SubMsg::reply_on_success(
+                    backend.swap_msg(
+                        deps.storage,
+                        &deps.querier,
+                        &contract_config,
+                        Swap {
+                            offer: Asset {
+                                info: order.initial_asset.info.clone(),
+                                amount,
                             },
-                            &[],
-                        )
-                        .unwrap();
-                    }
-                }
+                            hops,
+                            max_spread: target_params
+                                .get(i)
+                                .map_or(max_spread, |(max_spread, _)| *max_spread),
+                            minimum_receive: target_params
+                                .get(i)
+                                .and_then(|(_, minimum_receive)| *minimum_receive),
+                            to: to.clone(),
+                        },
+                    )?,
+                    SPLIT_SWAP_REPLY_ID,
+                ));
             }
+        }
+    }
 
-            app.execute_contract(
-                admin.clone(),
-                pair_addr,
-                &astroport::pair::ExecuteMsg::ProvideLiquidity {
-                    assets: [
-                        Asset {
-                            amount: Uint128::new(500_000),
-                            info: assets[0].clone(),
-                        },
-                        Asset {
-                            amount: Uint128::new(500_000),
-                            info: assets[1].clone(),
-                        },
-                    ],
-                    slippage_tolerance: None,
-                    auto_stake: None,
-                    receiver: None,
-                },
-                &funds,
-            )
-            .unwrap();
-        };
+    // lock the order until the swap replies, and store the updated order, which is removed in the
+    // reply if it has been fulfilled
+    order.executing = true;
+    user_dca().save(deps.storage, (&user_address, id), &order)?;
+    PENDING_PURCHASE.save(
+        deps.storage,
+        &PendingPurchase {
+            user: user_address.clone(),
+            id,
+            bot: info.sender,
+            tip,
+            amount_in: spend_amount,
+            provide_amount,
+            delay,
+        },
+    )?;
 
-        create_pool([
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ujpy".to_string(),
-            },
-        ]);
-        create_pool([
-            AssetInfo::NativeToken {
-                denom: "ujpy".to_string(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
-            },
-        ]);
-        create_pool([
-            AssetInfo::Token {
-                contract_addr: cw20_addr.clone(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ujpy".to_string(),
-            },
-        ]);
+    // save new config
+    USER_CONFIG.save(deps.storage, &user_address, &user_config)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(pull_msg)
+        .add_attributes(vec![
+            attr("action", "perform_dca_purchase"),
+            attr("user", user_address),
+            attr("id", id.to_string()),
+            attr("recipient", recipient),
+            attr("protocol_fee", protocol_fee),
+        ])
+        .add_attributes((routes.len() > 1).then(|| attr("splits", routes.len().to_string())))
+        .add_submessages(swap_msgs))
+}
 
-        (app, dca_addr, cw20_addr)
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use astroport::{
+        asset::{Asset, AssetInfo, PairInfo},
+        factory::PairType,
+        router::{QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+        staking::ConfigResponse as StakingConfigResponse,
+    };
+    use astroport_dca::dca::{
+        BotBond, BotInfo, Cw20HookMsg, DcaInfo, Delivery, Duration, ExecuteMsg, FailurePolicy,
+        FeeTier, OrderDirection, PurchaseHook, PurchaseQueryInfo, PurchaseRecord, QueryMsg,
+        Strategy, SwapBackendType, TargetWeight, TipEscalation, TipReplenish,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        to_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal, DepsMut, DivideByZeroError,
+        Empty, Event, Reply, Response, StdError, StdResult, Storage, SubMsg, SubMsgResponse,
+        SubMsgResult, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{BalanceResponse, Expiration};
+    use cw_multi_test::{App, AppResponse, Executor};
+
+    use crate::{
+        contract::{execute, reply},
+        error::ContractError,
+        query_pair::pair_key,
+        state::{user_dca, Config, UserConfig, BOTS, CONFIG, PAIRS, USER_CONFIG},
+        tests::{
+            mock_astroport_instantiate, mock_creator, mock_instantiate, read_item, read_map,
+            read_user_orders, store_purchase_hook_code, store_staking_code,
+        },
+    };
+
+    use super::PERFORM_DCA_PURCHASE_REPLY_ID;
+
+    // caches the pairs of `hops` as pools of `pair_type`, so that purchases made without an
+    // Astroport factory can resolve the pool type of each hop
+    fn cache_pairs(storage: &mut dyn Storage, hops: &[SwapOperation], pair_type: PairType) {
+        for hop in hops {
+            if let SwapOperation::AstroSwap {
+                offer_asset_info,
+                ask_asset_info,
+            } = hop
+            {
+                let asset_infos = [offer_asset_info.clone(), ask_asset_info.clone()];
+                PAIRS
+                    .save(
+                        storage,
+                        pair_key(&asset_infos),
+                        &PairInfo {
+                            asset_infos,
+                            contract_addr: Addr::unchecked("pair"),
+                            liquidity_token: Addr::unchecked("lp_token"),
+                            pair_type: pair_type.clone(),
+                        },
+                    )
+                    .unwrap();
+            }
+        }
     }
 
     const NORMAL_ORDER_INTERVAL: u64 = 500;
@@ -562,9 +1550,43 @@ mod test {
                     info: initial_info,
                 },
                 target_asset: target_info,
-                interval: NORMAL_ORDER_INTERVAL,
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
                 dca_amount: Uint128::new(10_000),
                 first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
             funds,
         )
@@ -590,7 +1612,7 @@ mod test {
 
     #[test]
     fn can_perform_native_purchase() {
-        let (mut app, dca_addr, ..) = instantiate(None);
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
         create_normal_order(
             &mut app,
@@ -638,6 +1660,8 @@ mod test {
                         denom: "uluna".to_string(),
                     },
                 }],
+                deadline: Some(app.block_info().time.seconds()),
+                accepted_tips: vec![],
             },
             &[],
         )
@@ -657,7 +1681,7 @@ mod test {
         );
 
         // should have added to bot balance
-        let bot_balance = app.wrap().query_balance(bot_user, "uluna").unwrap();
+        let bot_balance = app.wrap().query_balance(bot_user.clone(), "uluna").unwrap();
         assert_eq!(bot_balance, coin(30_000, "uluna"));
 
         // should have bought the target token
@@ -669,10 +1693,10 @@ mod test {
         assert_eq!(user_balance, coin(9_558, "ukrw"));
 
         // should have updated dca order
-        let user_dca_orders = read_map(&app, dca_addr, &mock_creator().sender, USER_DCA);
+        let user_dca_orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
         let expected_orders = vec![DcaInfo {
             id: 1,
-            interval: NORMAL_ORDER_INTERVAL,
+            interval: Duration::Time(NORMAL_ORDER_INTERVAL),
             dca_amount: Uint128::new(10_000),
             initial_asset: Asset {
                 amount: Uint128::new(90_000),
@@ -684,53 +1708,114 @@ mod test {
             target_asset: AssetInfo::NativeToken {
                 denom: "ukrw".to_string(),
             },
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::new(10_000),
+            total_received: Uint128::new(9_558),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
         }];
         assert_eq!(user_dca_orders, expected_orders);
+
+        // should have recorded the purchase
+        let purchase_history: Vec<PurchaseQueryInfo> = app
+            .wrap()
+            .query_wasm_smart(
+                dca_addr,
+                &QueryMsg::PurchaseHistory {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            purchase_history,
+            vec![PurchaseQueryInfo {
+                seq: 1,
+                record: PurchaseRecord {
+                    timestamp: app.block_info().time.seconds(),
+                    amount_in: Uint128::new(10_000),
+                    amount_out: Uint128::new(9_558),
+                    outputs: vec![],
+                    bot: bot_user,
+                    tip: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                },
+            }]
+        );
     }
 
     #[test]
-    fn can_perform_token_purchase() {
-        let (mut app, dca_addr, cw20_addr) = instantiate(None);
+    fn does_purchase_order_by_id() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        // increase allowance for order
-        app.execute_contract(
-            mock_creator().sender,
-            cw20_addr.clone(),
-            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-                spender: dca_addr.clone().into_string(),
-                amount: Uint128::new(100_000),
-                expires: None,
-            },
-            &[],
-        )
-        .unwrap();
+        // create two orders for the same assets
+        for _ in 0..2 {
+            create_normal_order(
+                &mut app,
+                dca_addr.clone(),
+                AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+            );
+        }
 
-        create_normal_order(
-            &mut app,
-            dca_addr.clone(),
-            AssetInfo::Token {
-                contract_addr: cw20_addr.clone(),
-            },
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-        );
-
-        add_tip_balance(&mut app, dca_addr.clone());
-
-        let bot_user = Addr::unchecked("bot_user");
-
-        // perform purchase
         app.execute_contract(
-            bot_user.clone(),
+            Addr::unchecked("bot_user"),
             dca_addr.clone(),
             &ExecuteMsg::PerformDcaPurchase {
                 user: mock_creator().sender.into_string(),
-                id: 1,
+                id: 2,
                 hops: vec![
                     SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::Token {
-                            contract_addr: cw20_addr.clone(),
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
                         },
                         ask_asset_info: AssetInfo::NativeToken {
                             denom: "ujpy".to_string(),
@@ -741,160 +1826,400 @@ mod test {
                             denom: "ujpy".to_string(),
                         },
                         ask_asset_info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
+                            denom: "ukrw".to_string(),
                         },
                     },
                 ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
-                    info: AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
             },
             &[],
         )
         .unwrap();
 
-        // should have removed from tip balance
-        let user_config = read_map(&app, dca_addr.clone(), &mock_creator().sender, USER_CONFIG);
-        // 150,000 start - 30,000 fee redeem
+        // only the second order should have been purchased
+        let user_dca_orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
         assert_eq!(
-            user_config.tip_balance,
-            vec![Asset {
-                amount: Uint128::new(120_000),
-                info: AssetInfo::NativeToken {
-                    denom: "uluna".to_string()
-                }
-            }]
+            user_dca_orders
+                .iter()
+                .map(|o| (o.id, o.initial_asset.amount, o.last_purchase))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, Uint128::new(100_000), 0),
+                (2, Uint128::new(90_000), app.block_info().time.seconds()),
+            ]
         );
+    }
 
-        // should have added to bot balance
-        let bot_balance = app.wrap().query_balance(bot_user, "uluna").unwrap();
-        assert_eq!(bot_balance, coin(30_000, "uluna"));
+    #[test]
+    fn does_purchase_by_block_height() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        // should have spent the initial asset
-        let user_balance: cw20::BalanceResponse = app
-            .wrap()
-            .query_wasm_smart(
-                cw20_addr.clone(),
-                &cw20_base::msg::QueryMsg::Balance {
-                    address: mock_creator().sender.into_string(),
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
                 },
-            )
-            .unwrap();
-        // 500_000 - 10_000 = 490_000 left in balance
-        assert_eq!(user_balance.balance, Uint128::new(490_000));
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Height(10),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
 
-        // should have reduced from allowance
-        let dca_allowance: cw20::AllowanceResponse = app
-            .wrap()
-            .query_wasm_smart(
-                cw20_addr.clone(),
-                &cw20_base::msg::QueryMsg::Allowance {
-                    owner: mock_creator().sender.into_string(),
-                    spender: dca_addr.clone().into_string(),
+        let purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
-            .unwrap();
-        assert_eq!(dca_allowance.allowance, Uint128::new(90_000));
+            .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
 
-        // should have bought the target asset
-        let user_balance = app
-            .wrap()
-            .query_balance(mock_creator().sender, "uluna")
-            .unwrap();
-        // 500_000 starting balance - 150_000 tip + 9_558 from swap
-        assert_eq!(user_balance, coin(350_000 + 9_558, "uluna"));
+        purchase(&mut app).unwrap();
 
-        // should have updated dca order
-        let user_dca_orders = read_map(&app, dca_addr, &mock_creator().sender, USER_DCA);
-        let expected_orders = vec![DcaInfo {
-            id: 1,
-            interval: NORMAL_ORDER_INTERVAL,
-            dca_amount: Uint128::new(10_000),
-            initial_asset: Asset {
-                amount: Uint128::new(90_000),
-                info: AssetInfo::Token {
-                    contract_addr: cw20_addr,
-                },
-            },
-            last_purchase: app.block_info().time.seconds(),
-            target_asset: AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-        }];
-        assert_eq!(user_dca_orders, expected_orders);
+        // the last purchase should be the height of the block it was performed in
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].last_purchase, app.block_info().height);
+
+        // passing time should not make the order purchasable before enough blocks have passed
+        app.update_block(|block| {
+            block.time = block.time.plus_seconds(86_400);
+            block.height += 9;
+        });
+        assert_eq!(
+            purchase(&mut app).unwrap_err(),
+            ContractError::PurchaseTooEarly {}
+        );
+
+        app.update_block(|block| block.height += 1);
+        purchase(&mut app).unwrap();
     }
 
     #[test]
-    fn does_error_if_empty_hops() {
-        let (mut app, dca_addr, ..) = instantiate(None);
+    fn does_delay_purchase_by_jitter() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        create_normal_order(
-            &mut app,
+        app.execute_contract(
+            mock_creator().sender,
             dca_addr.clone(),
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: Some(500),
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
-        );
-
-        add_tip_balance(&mut app, dca_addr.clone());
-
-        let bot_user = Addr::unchecked("bot_user");
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
 
-        // perform purchase
-        let res = app
-            .execute_contract(
-                bot_user,
-                dca_addr,
+        let purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
                     id: 1,
-                    hops: vec![],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(30_000),
-                        info: AssetInfo::NativeToken {
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
                     }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
-            .unwrap_err();
+            .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
 
+        purchase(&mut app).unwrap();
+
+        // the next purchase is delayed by an offset of up to the jitter window
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        let order = &orders[0];
+        assert!(order.jitter_offset <= 500);
         assert_eq!(
-            res.downcast::<ContractError>().unwrap(),
-            ContractError::EmptyHopRoute {}
+            order.next_purchase(),
+            app.block_info().time.seconds() + 1_000 + order.jitter_offset
+        );
+
+        let delay = 1_000 + order.jitter_offset;
+        app.update_block(|block| block.time = block.time.plus_seconds(delay - 1));
+        assert_eq!(
+            purchase(&mut app).unwrap_err(),
+            ContractError::PurchaseTooEarly {}
         );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1));
+        purchase(&mut app).unwrap();
     }
 
     #[test]
-    fn does_error_if_too_many_hops() {
-        let (mut app, dca_addr, ..) = instantiate(Some(2));
+    fn does_skip_late_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        create_normal_order(
-            &mut app,
+        app.execute_contract(
+            mock_creator().sender,
             dca_addr.clone(),
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: Some(100),
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
+
+        // an order with a maximum delay can be purchased from its creation
+        purchase(&mut app).unwrap();
+
+        // the next purchase is more than the maximum delay late, so it is skipped
+        app.update_block(|block| block.time = block.time.plus_seconds(1_200));
+        assert_eq!(
+            purchase(&mut app).unwrap_err(),
+            ContractError::PurchaseSkipped {}
         );
 
-        add_tip_balance(&mut app, dca_addr.clone());
+        // the purchase after it can be performed on time
+        app.update_block(|block| block.time = block.time.plus_seconds(800));
+        purchase(&mut app).unwrap();
+    }
 
-        let bot_user = Addr::unchecked("bot_user");
+    #[test]
+    fn does_send_purchase_to_recipient() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: Some("cold_wallet".to_string()),
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
 
-        // perform purchase
         let res = app
             .execute_contract(
-                bot_user,
+                Addr::unchecked("bot_user"),
                 dca_addr,
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
@@ -916,147 +2241,374 @@ mod test {
                                 denom: "ukrw".to_string(),
                             },
                         },
-                        SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::NativeToken {
-                                denom: "ukrw".to_string(),
-                            },
-                            ask_asset_info: AssetInfo::NativeToken {
-                                denom: "ujpy".to_string(),
-                            },
-                        },
                     ],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(30_000),
-                        info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
-                        },
-                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
-            .unwrap_err();
+            .unwrap();
 
-        assert_eq!(
-            res.downcast::<ContractError>().unwrap(),
-            ContractError::MaxHopsAssertion { hops: 3 }
-        );
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("recipient", "cold_wallet"))));
+
+        // the recipient should have received the purchase instead of the user
+        let balance = |addr: &str| app.wrap().query_balance(addr, "ukrw").unwrap();
+        assert_eq!(balance("cold_wallet"), coin(9_558, "ukrw"));
+        assert_eq!(balance(mock_creator().sender.as_str()), coin(0, "ukrw"));
     }
 
     #[test]
-    fn does_error_if_non_whitelisted_hop() {
-        let (mut app, dca_addr, cw20_addr) = instantiate(None);
+    fn cannot_purchase_while_paused() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        create_normal_order(
-            &mut app,
+        let create_order = |app: &mut App| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(100_000, "uluna"),
+            )
+        };
+        create_order(&mut app).unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
             dca_addr.clone(),
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: Some(true),
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
             },
-            AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
-            },
-        );
-
-        add_tip_balance(&mut app, dca_addr.clone());
-
-        let bot_user = Addr::unchecked("bot_user");
+            &[],
+        )
+        .unwrap();
 
-        // perform purchase
         let res = app
             .execute_contract(
-                bot_user.clone(),
+                Addr::unchecked("bot_user"),
                 dca_addr.clone(),
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
                     id: 1,
-                    hops: vec![
-                        SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::NativeToken {
-                                denom: "uluna".to_string(),
-                            },
-                            ask_asset_info: AssetInfo::NativeToken {
-                                denom: "ujpy".to_string(),
-                            },
-                        },
-                        SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::NativeToken {
-                                denom: "ujpy".to_string(),
-                            },
-                            ask_asset_info: AssetInfo::Token {
-                                contract_addr: cw20_addr.clone(),
-                            },
-                        },
-                        SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::Token {
-                                contract_addr: cw20_addr.clone(),
-                            },
-                            ask_asset_info: AssetInfo::NativeToken {
-                                denom: "ukrw".to_string(),
-                            },
-                        },
-                    ],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(30_000),
-                        info: AssetInfo::NativeToken {
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
                     }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
             .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::ContractPaused {}
+        );
 
+        // new orders cannot be created either
+        let res = create_order(&mut app).unwrap_err();
         assert_eq!(
             res.downcast::<ContractError>().unwrap(),
-            ContractError::InvalidHopRoute {
-                token: cw20_addr.into_string()
-            }
+            ContractError::ContractPaused {}
         );
 
-        // should also error for native swap
+        // but existing orders can still be cancelled
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CancelDcaOrder {
+                id: 1,
+                user: None,
+                withdraw_tips: false,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(&dca_addr, "uluna").unwrap(),
+            coin(0, "uluna")
+        );
+    }
+
+    #[test]
+    fn does_take_protocol_fee() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: Some(100),
+                fee_collector: Some("collector".to_string()),
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
         let res = app
             .execute_contract(
-                bot_user,
-                dca_addr,
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
                     id: 1,
-                    hops: vec![
-                        SwapOperation::NativeSwap {
-                            offer_denom: "uluna".to_string(),
-                            ask_denom: "ugbp".to_string(),
-                        },
-                        SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::NativeToken {
-                                denom: "ugbp".to_string(),
-                            },
-                            ask_asset_info: AssetInfo::NativeToken {
-                                denom: "ukrw".to_string(),
-                            },
-                        },
-                    ],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(30_000),
-                        info: AssetInfo::NativeToken {
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
                     }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
-            .unwrap_err();
+            .unwrap();
+
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("protocol_fee", "100"))));
 
+        // the fee should have been sent to the collector, with the rest of the dca_amount swapped
         assert_eq!(
-            res.downcast::<ContractError>().unwrap(),
-            ContractError::InvalidHopRoute {
-                token: "ugbp".to_string()
-            }
+            app.wrap().query_balance("collector", "uluna").unwrap(),
+            coin(100, "uluna")
+        );
+        assert_eq!(
+            app.wrap().query_balance(&dca_addr, "uluna").unwrap(),
+            coin(90_000, "uluna")
+        );
+
+        let fees: Vec<Asset> = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::ProtocolFees {})
+            .unwrap();
+        assert_eq!(
+            fees,
+            vec![Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                amount: Uint128::new(100),
+            }]
         );
     }
 
     #[test]
-    fn does_check_tip_is_divisible() {
-        let (mut app, dca_addr, ..) = instantiate(None);
+    fn does_discount_protocol_fee_for_xastro_holders() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        // the creator holds 500_000 of the cw20 token, which is used as the xASTRO of the staking
+        let staking_addr = app
+            .instantiate_contract(
+                store_staking_code(&mut app),
+                Addr::unchecked("admin"),
+                &StakingConfigResponse {
+                    deposit_token_addr: Addr::unchecked("astro"),
+                    share_token_addr: cw20_addr,
+                },
+                &[],
+                "mock staking",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: Some(staking_addr.into_string()),
+                protocol_fee_bps: Some(100),
+                fee_collector: Some("collector".to_string()),
+                fee_tiers: Some(vec![
+                    FeeTier {
+                        min_xastro: Uint128::new(1_000_000),
+                        discount: Decimal::one(),
+                    },
+                    FeeTier {
+                        min_xastro: Uint128::new(100_000),
+                        discount: Decimal::percent(50),
+                    },
+                ]),
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
 
         create_normal_order(
             &mut app,
@@ -1065,19 +2617,126 @@ mod test {
                 denom: "uluna".to_string(),
             },
             AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
+                denom: "ujpy".to_string(),
             },
         );
 
-        add_tip_balance(&mut app, dca_addr.clone());
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
 
-        let bot_user = Addr::unchecked("bot_user");
+        // only the tier reached by the balance of the user should halve the fee of 100
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("protocol_fee", "50"))));
+        assert_eq!(
+            app.wrap().query_balance("collector", "uluna").unwrap(),
+            coin(50, "uluna")
+        );
+    }
+
+    #[test]
+    fn does_call_purchase_hooks() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let hook_id = store_purchase_hook_code(&mut app);
+        let hooks = ["vault", "notifier"].map(|label| {
+            app.instantiate_contract(
+                hook_id,
+                Addr::unchecked("admin"),
+                &Empty {},
+                &[],
+                label,
+                None,
+            )
+            .unwrap()
+        });
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: hooks
+                    .iter()
+                    .map(|hook| PurchaseHook {
+                        contract_addr: hook.to_string(),
+                        msg: to_binary(&Empty {}).unwrap(),
+                    })
+                    .collect(),
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
 
-        // perform purchase
         let res = app
             .execute_contract(
-                bot_user,
-                dca_addr,
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
                     id: 1,
@@ -1099,35 +2758,55 @@ mod test {
                             },
                         },
                     ],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(35_000),
-                        info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
-                        },
-                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
-            .unwrap_err();
+            .unwrap();
 
-        assert_eq!(
-            res.downcast::<ContractError>().unwrap(),
-            ContractError::IndivisibleDeposit {}
-        );
+        // both hooks should have been called with the purchase
+        let hook_calls = res
+            .events
+            .iter()
+            .filter(|e| e.attributes.contains(&attr("action", "dca_purchase_hook")))
+            .count();
+        assert_eq!(hook_calls, 2);
+
+        // only the first hook should have received the purchased asset
+        let balance = |addr: &Addr| app.wrap().query_balance(addr, "ukrw").unwrap();
+        assert_eq!(balance(&hooks[0]), coin(9_558, "ukrw"));
+        assert_eq!(balance(&hooks[1]), coin(0, "ukrw"));
+        assert_eq!(balance(&mock_creator().sender), coin(0, "ukrw"));
+        assert_eq!(balance(&dca_addr), coin(0, "ukrw"));
     }
 
     #[test]
-    fn does_check_requested_fee_whitelisted() {
-        let (mut app, dca_addr, ..) = instantiate(None);
+    fn can_perform_token_purchase() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
 
-        create_normal_order(
-            &mut app,
+        // increase allowance for order
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: dca_addr.clone().into_string(),
+                amount: Uint128::new(100_000),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        create_normal_order(
+            &mut app,
             dca_addr.clone(),
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
+            AssetInfo::Token {
+                contract_addr: cw20_addr.clone(),
             },
             AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
+                denom: "uluna".to_string(),
             },
         );
 
@@ -1136,17 +2815,243 @@ mod test {
         let bot_user = Addr::unchecked("bot_user");
 
         // perform purchase
-        let res = app
-            .execute_contract(
-                bot_user,
-                dca_addr,
+        app.execute_contract(
+            bot_user.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::Token {
+                            contract_addr: cw20_addr.clone(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // should have removed from tip balance
+        let user_config = read_map(&app, dca_addr.clone(), &mock_creator().sender, USER_CONFIG);
+        // 150,000 start - 30,000 fee redeem
+        assert_eq!(
+            user_config.tip_balance,
+            vec![Asset {
+                amount: Uint128::new(120_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string()
+                }
+            }]
+        );
+
+        // should have added to bot balance
+        let bot_balance = app.wrap().query_balance(bot_user, "uluna").unwrap();
+        assert_eq!(bot_balance, coin(30_000, "uluna"));
+
+        // should have spent the initial asset
+        let user_balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &cw20_base::msg::QueryMsg::Balance {
+                    address: mock_creator().sender.into_string(),
+                },
+            )
+            .unwrap();
+        // 500_000 - 10_000 = 490_000 left in balance
+        assert_eq!(user_balance.balance, Uint128::new(490_000));
+
+        // should have reduced from allowance
+        let dca_allowance: cw20::AllowanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &cw20_base::msg::QueryMsg::Allowance {
+                    owner: mock_creator().sender.into_string(),
+                    spender: dca_addr.clone().into_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(dca_allowance.allowance, Uint128::new(90_000));
+
+        // should have bought the target asset
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap();
+        // 500_000 starting balance - 150_000 tip + 9_558 from swap
+        assert_eq!(user_balance, coin(350_000 + 9_558, "uluna"));
+
+        // should have updated dca order
+        let user_dca_orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        let expected_orders = vec![DcaInfo {
+            id: 1,
+            interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+            dca_amount: Uint128::new(10_000),
+            initial_asset: Asset {
+                amount: Uint128::new(90_000),
+                info: AssetInfo::Token {
+                    contract_addr: cw20_addr,
+                },
+            },
+            last_purchase: app.block_info().time.seconds(),
+            target_asset: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::new(10_000),
+            total_received: Uint128::new(9_558),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        }];
+        assert_eq!(user_dca_orders, expected_orders);
+    }
+
+    #[test]
+    fn does_pull_token_purchases() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        let increase_allowance = |app: &mut App, amount: u128| {
+            app.execute_contract(
+                mock_creator().sender,
+                cw20_addr.clone(),
+                &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                    spender: dca_addr.clone().into_string(),
+                    amount: Uint128::new(amount),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        // the allowance does not need to cover the deposit of the order
+        increase_allowance(&mut app, 5_000);
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr.clone(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                strategy: Strategy::Fixed,
+                first_purchase: None,
+                expires_at: None,
+                max_purchases: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                pull: true,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let perform_purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
                 &ExecuteMsg::PerformDcaPurchase {
                     user: mock_creator().sender.into_string(),
                     id: 1,
                     hops: vec![
                         SwapOperation::AstroSwap {
-                            offer_asset_info: AssetInfo::NativeToken {
-                                denom: "uluna".to_string(),
+                            offer_asset_info: AssetInfo::Token {
+                                contract_addr: cw20_addr.clone(),
                             },
                             ask_asset_info: AssetInfo::NativeToken {
                                 denom: "ujpy".to_string(),
@@ -1157,69 +3062,119 @@ mod test {
                                 denom: "ujpy".to_string(),
                             },
                             ask_asset_info: AssetInfo::NativeToken {
-                                denom: "ukrw".to_string(),
+                                denom: "uluna".to_string(),
                             },
                         },
                     ],
-                    fee_redeem: vec![Asset {
-                        amount: Uint128::new(30_000),
-                        info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
                 &[],
             )
-            .unwrap_err();
+            .unwrap()
+        };
 
-        assert_eq!(
-            res.downcast::<ContractError>().unwrap(),
-            ContractError::NonWhitelistedTipAsset {
-                asset: AssetInfo::NativeToken {
-                    denom: "ujpy".to_string()
-                }
-            }
-        );
-    }
+        // the purchase is skipped while the allowance does not cover it
+        let res = perform_purchase(&mut app);
+        assert!(res.events.iter().any(|e| e
+            .attributes
+            .contains(&attr("reason", "insufficient_allowance"))));
 
-    #[test]
-    fn does_not_panic_for_zero_asset() {
-        let mut deps = mock_dependencies();
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(100_000));
 
-        let cw20_addr = Addr::unchecked("cw20_addr");
+        // and pulled from the wallet of the user once it does
+        increase_allowance(&mut app, 5_000);
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        perform_purchase(&mut app);
 
-        CONFIG
-            .save(
-                &mut deps.storage,
-                &Config {
-                    router_addr: Addr::unchecked("router"),
-                    factory_addr: Addr::unchecked("factory"),
-                    max_hops: 4,
-                    max_spread: Decimal::from_str("0.05").unwrap(),
-                    whitelisted_fee_assets: vec![Asset {
-                        amount: Uint128::new(0),
-                        info: AssetInfo::Token {
-                            contract_addr: cw20_addr.clone(),
-                        },
-                    }],
-                    whitelisted_tokens: vec![AssetInfo::NativeToken {
-                        denom: "ujpy".to_string(),
-                    }],
+        let user_balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &cw20_base::msg::QueryMsg::Balance {
+                    address: mock_creator().sender.into_string(),
                 },
             )
             .unwrap();
+        assert_eq!(user_balance.balance, Uint128::new(490_000));
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(90_000));
+    }
+
+    #[test]
+    fn can_perform_escrowed_token_purchase() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        // deposit the tokens for the order directly into the contract
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: dca_addr.clone().into_string(),
+                amount: Uint128::new(100_000),
+                msg: to_binary(&Cw20HookMsg::CreateDcaOrder {
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        // perform purchase
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
                 user: mock_creator().sender.into_string(),
                 id: 1,
                 hops: vec![
                     SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
+                        offer_asset_info: AssetInfo::Token {
+                            contract_addr: cw20_addr.clone(),
                         },
                         ask_asset_info: AssetInfo::NativeToken {
                             denom: "ujpy".to_string(),
@@ -1230,367 +3185,3416 @@ mod test {
                             denom: "ujpy".to_string(),
                         },
                         ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
+                            denom: "uluna".to_string(),
                         },
                     },
                 ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
-                    info: AssetInfo::Token {
-                        contract_addr: cw20_addr,
-                    },
-                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
             },
+            &[],
         )
-        .unwrap_err();
+        .unwrap();
+
+        // should have spent the escrowed tokens held by the contract
+        let dca_balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &cw20_base::msg::QueryMsg::Balance {
+                    address: dca_addr.clone().into_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(dca_balance.balance, Uint128::new(90_000));
+
+        // should have bought the target asset
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap();
+        // 500_000 starting balance - 150_000 tip + 9_558 from swap
+        assert_eq!(user_balance, coin(350_000 + 9_558, "uluna"));
 
+        let user_dca_orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
         assert_eq!(
-            res,
-            ContractError::Std(StdError::DivideByZero {
-                source: DivideByZeroError {
-                    operand: "30000".to_string()
-                }
-            })
+            user_dca_orders[0].initial_asset.amount,
+            Uint128::new(90_000)
         );
+        assert!(user_dca_orders[0].escrowed);
     }
 
     #[test]
-    fn does_check_tip_redeem_size() {
-        let mut deps = mock_dependencies();
+    fn does_check_max_price() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        CONFIG
-            .save(
-                &mut deps.storage,
-                &Config {
-                    router_addr: Addr::unchecked("router"),
-                    factory_addr: Addr::unchecked("factory"),
-                    max_hops: 4,
-                    max_spread: Decimal::from_str("0.05").unwrap(),
-                    whitelisted_fee_assets: vec![Asset {
-                        amount: Uint128::new(15_000),
+        let create_order = |app: &mut App, max_price: Decimal| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
                         info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                    }],
-                    whitelisted_tokens: vec![
-                        AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: Some(max_price),
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(50_000, "uluna"),
+            )
+            .unwrap();
+        };
+
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
                         },
-                        AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
                         },
                     ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
+        };
+
+        // dca amount of 10_000 would return 9_558, a price of ~1.046
+        create_order(&mut app, Decimal::one());
+        create_order(&mut app, Decimal::from_ratio(11u128, 10u128));
+
+        let res = perform_purchase(&mut app, 1).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::PriceAboveLimit {
+                price: Decimal::from_ratio(10_000u128, 9_558u128),
+                max_price: Decimal::one(),
+            }
+        );
+
+        perform_purchase(&mut app, 2).unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
             .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: vec![
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
+    #[test]
+    fn does_skip_purchase_above_price() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, only_below_price: Decimal| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
+                        info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
                     },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
-                        },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
                     },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: Some(only_below_price),
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(50_000, "uluna"),
+            )
+            .unwrap();
+        };
+
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
                         },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ugbp".to_string(),
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
                         },
-                    },
-                ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(60_000),
-                    info: AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                }],
-            },
-        )
-        .unwrap_err();
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+        };
 
-        // should error because we wanted to do 15_000 * 4 = 60_000 fee redeem for a 15_000 * 3 swap
+        // dca amount of 10_000 would return 9_558, a price of ~1.046
+        create_order(&mut app, Decimal::one());
+        create_order(&mut app, Decimal::from_ratio(11u128, 10u128));
+
+        // the purchase above the threshold is skipped without spending the order
+        let res = perform_purchase(&mut app, 1).unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("action", "skip_dca_purchase"))));
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(50_000));
+        assert_eq!(orders[0].last_purchase, app.block_info().time.seconds());
+
+        // and rescheduled to the next interval
+        let res = perform_purchase(&mut app, 1).unwrap_err();
         assert_eq!(
-            res,
-            ContractError::RedeemTipTooLarge {
-                requested: Uint128::new(4),
-                performed: Uint128::new(3)
-            }
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::PurchaseTooEarly {}
         );
+
+        perform_purchase(&mut app, 2).unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
     }
 
     #[test]
-    fn does_error_if_not_enough_balance() {
-        let mut deps = mock_dependencies();
+    fn does_skip_purchase_above_price_impact() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
 
-        CONFIG
-            .save(
-                &mut deps.storage,
-                &Config {
-                    router_addr: Addr::unchecked("router"),
-                    factory_addr: Addr::unchecked("factory"),
-                    max_hops: 4,
-                    max_spread: Decimal::from_str("0.05").unwrap(),
-                    whitelisted_fee_assets: vec![Asset {
-                        amount: Uint128::new(15_000),
+        let create_order = |app: &mut App, max_price_impact: Decimal| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
                         info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                    }],
-                    whitelisted_tokens: vec![AssetInfo::NativeToken {
-                        denom: "ujpy".to_string(),
-                    }],
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: Some(max_price_impact),
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
+                &coins(50_000, "uluna"),
             )
             .unwrap();
+        };
 
-        USER_CONFIG
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &UserConfig {
-                    last_id: 0,
-                    max_hops: None,
-                    max_spread: None,
-                    tip_balance: vec![Asset {
-                        info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
                         },
-                        amount: Uint128::new(25_000),
-                    }],
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
-            .unwrap();
+        };
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: vec![
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                    },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ugbp".to_string(),
-                        },
-                    },
-                ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
-                    info: AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                }],
-            },
-        )
-        .unwrap_err();
+        // dca amount of 10_000 is worth 10_000 at the ratio of the reserves, but would return
+        // 9_558, a price impact of 4.42%
+        create_order(&mut app, Decimal::percent(4));
+        create_order(&mut app, Decimal::percent(5));
 
-        assert_eq!(res, ContractError::InsufficientTipBalance {});
-    }
+        // the purchase above the maximum price impact is skipped without spending the order
+        let res = perform_purchase(&mut app, 1).unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("action", "skip_dca_purchase"))));
 
-    #[test]
-    fn can_purchase_with_token_fee() {
-        let (mut app, dca_addr, cw20_addr) = instantiate(None);
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(50_000));
+        assert_eq!(orders[0].last_purchase, app.block_info().time.seconds());
 
-        create_normal_order(
-            &mut app,
-            dca_addr.clone(),
-            AssetInfo::NativeToken {
-                denom: "uluna".to_string(),
-            },
-            AssetInfo::NativeToken {
-                denom: "ukrw".to_string(),
-            },
+        // and rescheduled to the next interval
+        let res = perform_purchase(&mut app, 1).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::PurchaseTooEarly {}
         );
 
-        // add bot tips
-        app.execute_contract(
-            mock_creator().sender,
-            cw20_addr.clone(),
-            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-                spender: dca_addr.clone().into_string(),
-                amount: Uint128::new(45_000),
-                expires: None,
-            },
-            &[],
-        )
-        .unwrap();
+        perform_purchase(&mut app, 2).unwrap();
 
-        app.execute_contract(
-            mock_creator().sender,
-            dca_addr.clone(),
-            &ExecuteMsg::AddBotTip {
-                assets: vec![Asset {
-                    amount: Uint128::new(45_000),
-                    info: AssetInfo::Token {
-                        contract_addr: cw20_addr.clone(),
-                    },
-                }],
-            },
-            &[],
-        )
-        .unwrap();
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
 
-        // should succeed
-        app.execute_contract(
-            Addr::unchecked("bot_addr"),
-            dca_addr,
-            &ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: vec![
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
+    #[test]
+    fn does_stop_order_below_stop_price() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, stop_price: Decimal| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
+                        info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                    },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
-                        },
                     },
-                ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
-                    info: AssetInfo::Token {
-                        contract_addr: cw20_addr.clone(),
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
                     },
-                }],
-            },
-            &[],
-        )
-        .unwrap();
-
-        // should have sent the funds to the bot addr
-        let res: BalanceResponse = app
-            .wrap()
-            .query_wasm_smart(
-                cw20_addr.into_string(),
-                &cw20::Cw20QueryMsg::Balance {
-                    address: "bot_addr".to_string(),
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: Some(stop_price),
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
+                &coins(50_000, "uluna"),
             )
             .unwrap();
-        assert_eq!(res.balance, Uint128::new(30_000));
-    }
-
-    #[test]
-    fn does_error_if_purchase_too_early() {
-        let (mut deps, env) = mock_instantiate(
-            Addr::unchecked("factory"),
-            Addr::unchecked("router"),
-            vec![Asset {
-                amount: Uint128::new(15_000),
-                info: AssetInfo::NativeToken {
-                    denom: "uluna".to_string(),
-                },
-            }],
-            vec![AssetInfo::NativeToken {
-                denom: "ujpy".to_string(),
-            }],
-        );
+        };
 
-        USER_CONFIG
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &UserConfig {
-                    last_id: 1,
-                    max_hops: None,
-                    max_spread: None,
-                    tip_balance: vec![Asset {
-                        amount: Uint128::new(45_000),
-                        info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
                         },
-                    }],
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
+        };
+
+        // dca amount of 10_000 would return 9_558, a price of ~1.046
+        create_order(&mut app, Decimal::percent(105));
+        create_order(&mut app, Decimal::percent(104));
+
+        let balance_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
             .unwrap();
 
-        USER_DCA
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &vec![DcaInfo {
-                    id: 1,
-                    dca_amount: Uint128::new(10_000),
+        // the order with a stop price above the simulated price is cancelled and refunded
+        let res = perform_purchase(&mut app, 1).unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("action", "stop_dca_order"))));
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, 2);
+
+        let balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap();
+        assert_eq!(balance.amount, balance_before.amount + Uint128::new(50_000));
+
+        // while the order with a stop price below it is purchased as usual
+        perform_purchase(&mut app, 2).unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
+
+    #[test]
+    fn does_close_order_above_take_profit_price() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, take_profit_price: Decimal| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
                     initial_asset: Asset {
+                        amount: Uint128::new(50_000),
                         info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                        amount: Uint128::new(100_000),
                     },
                     target_asset: AssetInfo::NativeToken {
                         denom: "ukrw".to_string(),
                     },
-                    interval: 500,
-                    last_purchase: env.block.time.seconds(),
-                }],
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: Some(take_profit_price),
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(50_000, "uluna"),
             )
             .unwrap();
+        };
 
-        // should fail when purchasing
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: vec![
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                    },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
                         },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ukrw".to_string(),
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
                         },
-                    },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+        };
+
+        // dca amount of 10_000 would return 9_558, a price of ~1.046
+        create_order(&mut app, Decimal::percent(104));
+        create_order(&mut app, Decimal::percent(105));
+
+        let balance_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap();
+
+        // the order with a take profit price below the simulated price is closed and refunded
+        let res = perform_purchase(&mut app, 1).unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("reason", "take_profit"))));
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, 2);
+
+        let balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap();
+        assert_eq!(balance.amount, balance_before.amount + Uint128::new(50_000));
+
+        // while the order with a take profit price above it is purchased as usual
+        perform_purchase(&mut app, 2).unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
+
+    #[test]
+    fn does_split_purchase_above_price_impact() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let config = read_item(&app, &dca_addr, CONFIG);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        // a direct uluna-ukrw pool alongside the route through ujpy
+        let admin = Addr::unchecked("admin");
+        let pair_res = app
+            .execute_contract(
+                admin.clone(),
+                config.factory_addr.clone(),
+                &astroport::factory::ExecuteMsg::CreatePair {
+                    pair_type: PairType::Xyk {},
+                    asset_infos: [uluna.clone(), ukrw.clone()],
+                    init_params: None,
+                },
+                &[],
+            )
+            .unwrap();
+        let pair_addr = Addr::unchecked(pair_res.events[2].attributes[0].value.clone());
+        app.execute_contract(
+            admin,
+            pair_addr,
+            &astroport::pair::ExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        amount: Uint128::new(500_000),
+                        info: uluna.clone(),
+                    },
+                    Asset {
+                        amount: Uint128::new(500_000),
+                        info: ukrw.clone(),
+                    },
+                ],
+                slippage_tolerance: None,
+                auto_stake: None,
+                receiver: None,
+            },
+            &[coin(500_000, "uluna"), coin(500_000, "ukrw")],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(50_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ukrw.clone(),
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: Some(Decimal::percent(2)),
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(50_000, "uluna"),
+        )
+        .unwrap();
+
+        // the 10_000 would return 9_774 through the direct pool, a price impact of 2.26%, so it is
+        // split in half across the direct pool and the route through ujpy, returning 4_936 and
+        // 4_873 for a price impact of 1.91%
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: uluna,
+                        ask_asset_info: ukrw,
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.contains(&attr("splits", "2"))));
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_809, "ukrw"));
+
+        // the amounts received by both swaps are recorded as one purchase
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(40_000));
+        assert_eq!(orders[0].total_received, Uint128::new(9_809));
+        assert!(!orders[0].executing);
+    }
+
+    #[test]
+    fn does_split_purchase_across_targets() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(50_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: true,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![
+                    TargetWeight {
+                        asset: ujpy.clone(),
+                        weight: Decimal::percent(70),
+                    },
+                    TargetWeight {
+                        asset: ukrw.clone(),
+                        weight: Decimal::percent(30),
+                    },
+                ],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(50_000, "uluna"),
+        )
+        .unwrap();
+
+        let balance = |app: &App, denom: &str| -> Uint128 {
+            app.wrap()
+                .query_balance(mock_creator().sender, denom)
+                .unwrap()
+                .amount
+        };
+        let (jpy_before, krw_before) = (balance(&app, "ujpy"), balance(&app, "ukrw"));
+
+        // the hops provided are ignored, as each target is swapped through its own route
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let jpy_received = balance(&app, "ujpy") - jpy_before;
+        let krw_received = balance(&app, "ukrw") - krw_before;
+        assert!(!jpy_received.is_zero() && !krw_received.is_zero());
+
+        // the amount received of each target is recorded, with the order counting its target
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(40_000));
+        assert_eq!(orders[0].total_received, jpy_received);
+        assert_eq!(
+            orders[0].targets_received,
+            vec![
+                Asset {
+                    info: ujpy,
+                    amount: jpy_received,
+                },
+                Asset {
+                    info: ukrw,
+                    amount: krw_received,
+                },
+            ]
+        );
+        assert!(!orders[0].executing);
+
+        let history: Vec<PurchaseQueryInfo> = app
+            .wrap()
+            .query_wasm_smart(
+                &dca_addr,
+                &QueryMsg::PurchaseHistory {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(history[0].record.outputs, orders[0].targets_received);
+    }
+
+    #[test]
+    fn does_average_value() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let hops = vec![SwapOperation::AstroSwap {
+            offer_asset_info: uluna.clone(),
+            ask_asset_info: ujpy.clone(),
+        }];
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(30_000),
+                    info: uluna,
+                },
+                target_asset: ujpy,
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                strategy: Strategy::ValueAveraging,
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(30_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform_purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: hops.clone(),
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap()
+        };
+
+        // the first purchase spends dca_amount, as nothing has been received yet
+        perform_purchase(&mut app);
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender).remove(0);
+        assert_eq!(order.total_spent, Uint128::new(10_000));
+        assert_eq!(order.target_value, Uint128::new(10_000));
+
+        // the purchase raised the price, so the received asset is worth more than was spent and
+        // the second purchase only spends the rest of the target path of 20_000
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        let simulation: SimulateSwapOperationsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                read_item(&app, &dca_addr, CONFIG).router_addr,
+                &RouterQueryMsg::SimulateSwapOperations {
+                    offer_amount: Uint128::new(10_000),
+                    operations: hops.clone(),
+                },
+            )
+            .unwrap();
+        let value = order
+            .total_received
+            .multiply_ratio(10_000u128, simulation.amount);
+        assert!(value > Uint128::new(10_000));
+
+        perform_purchase(&mut app);
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender).remove(0);
+        assert_eq!(
+            order.total_spent,
+            Uint128::new(10_000) + (Uint128::new(20_000) - value)
+        );
+        assert_eq!(order.target_value, Uint128::new(20_000));
+        assert_eq!(
+            order.initial_asset.amount,
+            Uint128::new(30_000) - order.total_spent
+        );
+    }
+
+    #[test]
+    fn does_purchase_tranches() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(5_000),
+                strategy: Strategy::Tranches {
+                    amounts: vec![Uint128::new(5_000), Uint128::new(25_000)],
+                },
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(30_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform_purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap()
+        };
+
+        // the first purchase spends the first tranche
+        perform_purchase(&mut app);
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].total_spent, Uint128::new(5_000));
+        assert_eq!(
+            orders[0].strategy,
+            Strategy::Tranches {
+                amounts: vec![Uint128::new(25_000)]
+            }
+        );
+
+        // and the second purchase spends the rest, fulfilling the order
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        perform_purchase(&mut app);
+        assert!(read_user_orders(&app, dca_addr, &mock_creator().sender).is_empty());
+    }
+
+    #[test]
+    fn does_purchase_remainder() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(25_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                strategy: Strategy::Fixed,
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(25_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform_purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        // the first two purchases spend the full dca amount
+        for spent in [10_000, 20_000] {
+            perform_purchase(&mut app);
+            let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+            assert_eq!(orders[0].total_spent, Uint128::new(spent));
+            app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        }
+
+        // and the last purchase spends the remainder, fulfilling the order
+        perform_purchase(&mut app);
+        assert!(read_user_orders(&app, dca_addr, &mock_creator().sender).is_empty());
+    }
+
+    #[test]
+    fn does_close_order_after_max_purchases() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                strategy: Strategy::Fixed,
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_purchases: Some(2),
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(30_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform_purchase = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        perform_purchase(&mut app);
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+
+        // the second purchase closes the order, refunding the remaining deposit
+        let query_balance = |app: &App| {
+            app.wrap()
+                .query_balance(mock_creator().sender, "uluna")
+                .unwrap()
+                .amount
+        };
+        let balance = query_balance(&app);
+        perform_purchase(&mut app);
+        assert!(read_user_orders(&app, dca_addr, &mock_creator().sender).is_empty());
+        assert_eq!(query_balance(&app), balance + Uint128::new(10_000));
+    }
+
+    #[test]
+    fn does_check_min_receive_per_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, min_receive: u128| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: Some(Uint128::new(min_receive)),
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(50_000, "uluna"),
+            )
+            .unwrap();
+        };
+
+        let perform_purchase = |app: &mut App, id: u64| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+        };
+
+        // dca amount of 10_000 would return 9_558
+        create_order(&mut app, 10_000);
+        create_order(&mut app, 9_558);
+
+        // the router should refuse to return less than the minimum amount
+        perform_purchase(&mut app, 1).unwrap_err();
+
+        perform_purchase(&mut app, 2).unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
+
+    #[test]
+    fn can_perform_purchase_with_stored_route() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let create_order = |app: &mut App, route: Vec<AssetInfo>| {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(50_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: Some(route),
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(50_000, "uluna"),
+            )
+        };
+
+        // can not route through a non-whitelisted asset
+        let res = create_order(
+            &mut app,
+            vec![AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidHopRoute {
+                token: "uusd".to_string()
+            }
+        );
+
+        create_order(
+            &mut app,
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        )
+        .unwrap();
+
+        // the stored route is used, so no hops need to be provided
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
+
+    #[test]
+    fn can_perform_purchase_with_auto_route() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(50_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: true,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(50_000, "uluna"),
+        )
+        .unwrap();
+
+        // there is no direct uluna-ukrw pool, so the route through ujpy should be found, even if
+        // the provided hops are invalid
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr,
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    ask_asset_info: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+    }
+
+    #[test]
+    fn does_error_if_empty_hops() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let bot_user = Addr::unchecked("bot_user");
+
+        // perform purchase
+        let res = app
+            .execute_contract(
+                bot_user,
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::EmptyHopRoute {}
+        );
+    }
+
+    #[test]
+    fn does_error_if_too_many_hops() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(Some(2));
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let bot_user = Addr::unchecked("bot_user");
+
+        // perform purchase
+        let res = app
+            .execute_contract(
+                bot_user,
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::MaxHopsAssertion { hops: 3 }
+        );
+    }
+
+    #[test]
+    fn does_error_if_non_whitelisted_hop() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let bot_user = Addr::unchecked("bot_user");
+
+        // perform purchase
+        let res = app
+            .execute_contract(
+                bot_user.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::Token {
+                                contract_addr: cw20_addr.clone(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::Token {
+                                contract_addr: cw20_addr.clone(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidHopRoute {
+                token: cw20_addr.into_string()
+            }
+        );
+
+        // should also error for native swap
+        let res = app
+            .execute_contract(
+                bot_user,
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::NativeSwap {
+                            offer_denom: "uluna".to_string(),
+                            ask_denom: "ugbp".to_string(),
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ugbp".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidHopRoute {
+                token: "ugbp".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn does_check_tip_is_divisible() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let bot_user = Addr::unchecked("bot_user");
+
+        // perform purchase
+        let res = app
+            .execute_contract(
+                bot_user,
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(35_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::IndivisibleDeposit {}
+        );
+    }
+
+    #[test]
+    fn does_check_requested_fee_whitelisted() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let bot_user = Addr::unchecked("bot_user");
+
+        // perform purchase
+        let res = app
+            .execute_contract(
+                bot_user,
+                dca_addr,
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NonWhitelistedTipAsset {
+                asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string()
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn does_not_panic_for_zero_asset() {
+        let mut deps = mock_dependencies();
+
+        let cw20_addr = Addr::unchecked("cw20_addr");
+
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    router_addr: Addr::unchecked("router"),
+                    factory_addr: Addr::unchecked("factory"),
+                    max_hops: 4,
+                    max_spread: Decimal::from_str("0.05").unwrap(),
+                    whitelisted_fee_assets: vec![Asset {
+                        amount: Uint128::new(0),
+                        info: AssetInfo::Token {
+                            contract_addr: cw20_addr.clone(),
+                        },
+                    }],
+                    whitelisted_tokens: vec![AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    }],
+                    staking_addr: None,
+                    protocol_fee_bps: 0,
+                    fee_collector: None,
+                    fee_tiers: vec![],
+                    referral_share: Decimal::zero(),
+                    owner: None,
+                    config_timelock: 0,
+                    paused: false,
+                    paused_assets: vec![],
+                    nft_addr: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: false,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: vec![],
+                    allowed_assets_only: false,
+                    blacklisted_assets: vec![],
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: vec![],
+                    min_deposit: None,
+                    min_dca_amount: None,
+                    swap_backend: SwapBackendType::Router,
+                },
+            )
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr,
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            res,
+            ContractError::Std(StdError::DivideByZero {
+                source: DivideByZeroError {
+                    operand: "30000".to_string()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn does_check_tip_redeem_size() {
+        let mut deps = mock_dependencies();
+
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    router_addr: Addr::unchecked("router"),
+                    factory_addr: Addr::unchecked("factory"),
+                    max_hops: 4,
+                    max_spread: Decimal::from_str("0.05").unwrap(),
+                    whitelisted_fee_assets: vec![Asset {
+                        amount: Uint128::new(15_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    whitelisted_tokens: vec![
+                        AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    ],
+                    staking_addr: None,
+                    protocol_fee_bps: 0,
+                    fee_collector: None,
+                    fee_tiers: vec![],
+                    referral_share: Decimal::zero(),
+                    owner: None,
+                    config_timelock: 0,
+                    paused: false,
+                    paused_assets: vec![],
+                    nft_addr: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: false,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: vec![],
+                    allowed_assets_only: false,
+                    blacklisted_assets: vec![],
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: vec![],
+                    min_deposit: None,
+                    min_dca_amount: None,
+                    swap_backend: SwapBackendType::Router,
+                },
+            )
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ugbp".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(60_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        // should error because we wanted to do 15_000 * 4 = 60_000 fee redeem for a 15_000 * 3 swap
+        assert_eq!(
+            res,
+            ContractError::RedeemTipTooLarge {
+                requested: Uint128::new(4),
+                performed: Uint128::new(3)
+            }
+        );
+    }
+
+    #[test]
+    fn does_error_if_not_enough_balance() {
+        let mut deps = mock_dependencies();
+
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    router_addr: Addr::unchecked("router"),
+                    factory_addr: Addr::unchecked("factory"),
+                    max_hops: 4,
+                    max_spread: Decimal::from_str("0.05").unwrap(),
+                    whitelisted_fee_assets: vec![Asset {
+                        amount: Uint128::new(15_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    whitelisted_tokens: vec![AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    }],
+                    staking_addr: None,
+                    protocol_fee_bps: 0,
+                    fee_collector: None,
+                    fee_tiers: vec![],
+                    referral_share: Decimal::zero(),
+                    owner: None,
+                    config_timelock: 0,
+                    paused: false,
+                    paused_assets: vec![],
+                    nft_addr: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: false,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: vec![],
+                    allowed_assets_only: false,
+                    blacklisted_assets: vec![],
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: vec![],
+                    min_deposit: None,
+                    min_dca_amount: None,
+                    swap_backend: SwapBackendType::Router,
+                },
+            )
+            .unwrap();
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 0,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(25_000),
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ugbp".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::InsufficientTipBalance {});
+    }
+
+    #[test]
+    fn can_purchase_with_token_fee() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        // add bot tips
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: dca_addr.clone().into_string(),
+                amount: Uint128::new(45_000),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(45_000),
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr.clone(),
+                    },
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // should succeed
+        app.execute_contract(
+            Addr::unchecked("bot_addr"),
+            dca_addr,
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr.clone(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // should have sent the funds to the bot addr
+        let res: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.into_string(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: "bot_addr".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.balance, Uint128::new(30_000));
+    }
+
+    #[test]
+    fn does_error_if_purchase_too_early() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: env.block.time.seconds(),
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::PurchaseTooEarly {});
+    }
+
+    #[test]
+    fn does_error_if_order_paused() {
+        let (mut deps, _env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: true,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::OrderPaused {});
+    }
+
+    #[test]
+    fn does_error_if_purchase_in_progress() {
+        let (mut deps, _env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: true,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::PurchaseInProgress {});
+    }
+
+    #[test]
+    fn does_error_if_deadline_passed() {
+        let (mut deps, _env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: Some(mock_env().block.time.seconds() - 1),
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::DeadlinePassed {});
+    }
+
+    #[test]
+    fn does_error_if_order_expired() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: Some(Expiration::AtTime(env.block.time)),
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::OrderExpired {});
+    }
+
+    #[test]
+    fn does_not_panic_if_dca_too_big() {
+        let (mut deps, ..) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(5_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ukrw".to_string(),
+                        },
+                    },
+                ],
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::InsufficientBalance {});
+    }
+
+    #[test]
+    fn does_error_if_not_target_hop() {
+        let (mut deps, ..) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(100_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    },
+                    SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ugbp".to_string(),
+                        },
+                    },
                 ],
                 fee_redeem: vec![Asset {
                     amount: Uint128::new(30_000),
@@ -1598,15 +6602,233 @@ mod test {
                         denom: "uluna".to_string(),
                     },
                 }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::TargetAssetAssertion {});
+    }
+
+    #[test]
+    fn does_delete_order_if_fulfilled() {
+        let (mut deps, ..) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &mock_creator().sender,
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(10_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        // should fail when purchasing
+        let hops = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+            },
+        ];
+
+        // the pairs of the hops are constant product pools
+        cache_pairs(&mut deps.storage, &hops, PairType::Xyk {});
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bot_addr", &[]),
+            ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: hops.clone(),
+                fee_redeem: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                }],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+        )
+        .unwrap();
+
+        let expected_msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+            amount: coins(30_000, "uluna"),
+            to_address: "bot_addr".to_string(),
+        }
+        .into()];
+        let expected_swap = SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: "router".to_string(),
+                funds: coins(10_000, "uluna"),
+                msg: to_binary(&astroport::router::ExecuteMsg::ExecuteSwapOperations {
+                    operations: hops,
+                    minimum_receive: None,
+                    to: Some(mock_creator().sender.into_string()),
+                    max_spread: Some(Decimal::from_str("0.05").unwrap()),
+                })
+                .unwrap(),
+            },
+            PERFORM_DCA_PURCHASE_REPLY_ID,
+        );
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_messages(expected_msgs)
+                .add_submessage(expected_swap)
+                .add_attributes(vec![
+                    attr("action", "perform_dca_purchase"),
+                    attr("user", mock_creator().sender.into_string()),
+                    attr("id", "1"),
+                    attr("recipient", mock_creator().sender.into_string()),
+                    attr("protocol_fee", "0"),
+                ])
+        );
+
+        // the order should be removed once the swap replies
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: PERFORM_DCA_PURCHASE_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![Event::new("wasm").add_attribute("return_amount", "9558")],
+                    data: None,
+                }),
             },
         )
-        .unwrap_err();
+        .unwrap();
 
-        assert_eq!(res, ContractError::PurchaseTooEarly {});
+        assert_eq!(
+            res.events,
+            vec![Event::new("dca_purchase").add_attributes(vec![
+                attr("schema_version", "1"),
+                attr("user", mock_creator().sender.into_string()),
+                attr("order_id", "1"),
+                attr("sequence", "1"),
+                attr("amount_in", "10000"),
+                attr("amount_out", "9558"),
+                attr(
+                    "price",
+                    Decimal::from_ratio(10_000u128, 9_558u128).to_string()
+                ),
+                attr("bot", "bot_addr"),
+                attr("tip", "30000uluna"),
+            ])]
+        );
+
+        assert!(user_dca()
+            .may_load(&deps.storage, (&mock_creator().sender, 1))
+            .unwrap()
+            .is_none());
     }
 
     #[test]
-    fn does_not_panic_if_dca_too_big() {
+    fn does_bound_slippage_of_stable_route() {
         let (mut deps, ..) = mock_instantiate(
             Addr::unchecked("factory"),
             Addr::unchecked("router"),
@@ -1635,38 +6857,468 @@ mod test {
                             denom: "uluna".to_string(),
                         },
                     }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
+                    id: 1,
+                    dca_amount: Uint128::new(10_000),
+                    initial_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(10_000),
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    last_purchase: 0,
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
                 },
             )
             .unwrap();
 
-        USER_DCA
+        let hops = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+            },
+        ];
+
+        let perform = |deps: DepsMut| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("bot_addr", &[]),
+                ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: hops.clone(),
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+            )
+        };
+
+        // a pool type the contract does not know the swap parameters of is rejected
+        cache_pairs(
+            &mut deps.storage,
+            &hops,
+            PairType::Custom("weighted".to_string()),
+        );
+        let res = perform(deps.as_mut()).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::UnsupportedPoolType {
+                pair_type: PairType::Custom("weighted".to_string()).to_string()
+            }
+        );
+
+        // a route through a stable pool must receive the simulated amount less the max spread
+        cache_pairs(&mut deps.storage, &hops, PairType::Stable {});
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "router" => {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&SimulateSwapOperationsResponse {
+                        amount: Uint128::new(9_000),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
+
+        let res = perform(deps.as_mut()).unwrap();
+        assert_eq!(
+            res.messages.last().unwrap().msg,
+            WasmMsg::Execute {
+                contract_addr: "router".to_string(),
+                funds: coins(10_000, "uluna"),
+                msg: to_binary(&astroport::router::ExecuteMsg::ExecuteSwapOperations {
+                    operations: hops.clone(),
+                    minimum_receive: Some(Uint128::new(8_550)),
+                    to: Some(mock_creator().sender.into_string()),
+                    max_spread: Some(Decimal::percent(50)),
+                })
+                .unwrap(),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn does_swap_with_pair_backend() {
+        let (mut deps, ..) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            vec![AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            }],
+        );
+
+        USER_CONFIG
             .save(
                 &mut deps.storage,
                 &mock_creator().sender,
-                &vec![DcaInfo {
+                &UserConfig {
+                    last_id: 1,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(45_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    tip_priority: vec![],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&mock_creator().sender, 1),
+                &DcaInfo {
                     id: 1,
                     dca_amount: Uint128::new(10_000),
                     initial_asset: Asset {
                         info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                        amount: Uint128::new(5_000),
+                        amount: Uint128::new(10_000),
                     },
                     target_asset: AssetInfo::NativeToken {
                         denom: "ukrw".to_string(),
                     },
-                    interval: 500,
+                    interval: Duration::Time(500),
                     last_purchase: 0,
-                }],
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::zero(),
+                    total_received: Uint128::zero(),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
             )
             .unwrap();
 
-        // should fail when purchasing
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
+        let hops = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+            },
+        ];
+
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.swap_backend = SwapBackendType::Pair;
+                Ok(config)
+            })
+            .unwrap();
+
+        let perform = |deps: DepsMut, hops: Vec<SwapOperation>| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("bot_addr", &[]),
+                ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops,
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+            )
+        };
+
+        // a pair can only swap a single hop
+        cache_pairs(&mut deps.storage, &hops, PairType::Xyk {});
+        let res = perform(deps.as_mut(), hops).unwrap_err();
+        assert_eq!(res, ContractError::InvalidSwapBackendRoute {});
+
+        let hops = vec![SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        }];
+        cache_pairs(&mut deps.storage, &hops, PairType::Xyk {});
+
+        let res = perform(deps.as_mut(), hops).unwrap();
+        assert_eq!(
+            res.messages.last().unwrap().msg,
+            WasmMsg::Execute {
+                contract_addr: "pair".to_string(),
+                funds: coins(10_000, "uluna"),
+                msg: to_binary(&astroport::pair::ExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        amount: Uint128::new(10_000),
+                    },
+                    belief_price: None,
+                    max_spread: Some(Decimal::from_str("0.05").unwrap()),
+                    to: Some(mock_creator().sender.into_string()),
+                })
+                .unwrap(),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn does_select_tip_by_priority() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        let cw20_tip = AssetInfo::Token {
+            contract_addr: cw20_addr,
+        };
+        let luna_tip = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        // the user prefers to tip with the token, which they have not deposited
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateUserConfig {
+                max_hops: None,
+                max_spread: None,
+                tip_priority: vec![cw20_tip.clone(), luna_tip.clone()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let perform = |app: &mut App, accepted_tips: Vec<AssetInfo>| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "ujpy".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "ukrw".to_string(),
+                            },
+                        },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips,
+                },
+                &[],
+            )
+        };
+
+        // a bot only accepting the token cannot be tipped
+        let res = perform(&mut app, vec![cw20_tip.clone()]).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::InsufficientTipBalance {}
+        );
+
+        // otherwise the tip for both hops falls back to the next asset in the priority
+        perform(&mut app, vec![cw20_tip, luna_tip]).unwrap();
+
+        let bot_balance = app.wrap().query_balance("bot_user", "uluna").unwrap();
+        assert_eq!(bot_balance, coin(30_000, "uluna"));
+
+        let user_config = read_map(&app, dca_addr, &mock_creator().sender, USER_CONFIG);
+        assert_eq!(
+            user_config.tip_balance,
+            vec![Asset {
+                amount: Uint128::new(120_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string()
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn does_pay_deposited_token_tip() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        );
+
+        // deposit the tip into the contract rather than allowing it to be transferred
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: dca_addr.to_string(),
+                amount: Uint128::new(30_000),
+                msg: to_binary(&Cw20HookMsg::AddBotTip {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
                 user: mock_creator().sender.into_string(),
                 id: 1,
                 hops: vec![
@@ -1689,235 +7341,884 @@ mod test {
                 ],
                 fee_redeem: vec![Asset {
                     amount: Uint128::new(30_000),
-                    info: AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr.clone(),
                     },
                 }],
+                deadline: None,
+                accepted_tips: vec![],
             },
+            &[],
         )
-        .unwrap_err();
+        .unwrap();
 
-        assert_eq!(res, ContractError::InsufficientBalance {});
+        // the tip should have been paid by the contract without any allowance
+        let bot_balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &cw20_base::msg::QueryMsg::Balance {
+                    address: "bot_user".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(bot_balance.balance, Uint128::new(30_000));
+
+        let user_config = read_map(&app, dca_addr, &mock_creator().sender, USER_CONFIG);
+        assert_eq!(
+            user_config.deposited_tip_balance,
+            vec![Asset {
+                amount: Uint128::zero(),
+                info: AssetInfo::Token {
+                    contract_addr: cw20_addr
+                }
+            }]
+        );
     }
 
     #[test]
-    fn does_error_if_not_target_hop() {
-        let (mut deps, ..) = mock_instantiate(
-            Addr::unchecked("factory"),
-            Addr::unchecked("router"),
+    fn does_pay_swap_tip() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: Some(100),
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform = |app: &mut App, fee_redeem: Vec<Asset>| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem,
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+        };
+
+        // the tip balance cannot be redeemed for the order
+        let res = perform(
+            &mut app,
             vec![Asset {
                 amount: Uint128::new(15_000),
                 info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
             }],
-            vec![AssetInfo::NativeToken {
-                denom: "ujpy".to_string(),
-            }],
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::SwapTipNotRedeemable {}
         );
 
-        USER_CONFIG
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &UserConfig {
-                    last_id: 1,
-                    max_hops: None,
-                    max_spread: None,
-                    tip_balance: vec![Asset {
-                        amount: Uint128::new(45_000),
-                        info: AssetInfo::NativeToken {
+        // the bot should be paid 1% of the purchase without any tip balance
+        let res = perform(&mut app, vec![]).unwrap();
+        assert!(res.events.iter().any(
+            |e| e.ty == "wasm-dca_purchase" && e.attributes.contains(&attr("tip", "100uluna"))
+        ));
+
+        let bot_balance = app.wrap().query_balance("bot_user", "uluna").unwrap();
+        assert_eq!(bot_balance, coin(100, "uluna"));
+
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.initial_asset.amount, Uint128::new(90_000));
+        assert_eq!(order.total_spent, Uint128::new(10_000));
+    }
+
+    #[test]
+    fn does_restrict_order_to_allowed_bots() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec!["keeper".to_string()],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform = |app: &mut App, bot: &str| {
+            app.execute_contract(
+                Addr::unchecked(bot),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
                     }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
-            .unwrap();
+        };
 
-        USER_DCA
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &vec![DcaInfo {
-                    id: 1,
-                    dca_amount: Uint128::new(10_000),
-                    initial_asset: Asset {
+        // only the keeper of the user can perform the purchases of the order
+        let res = perform(&mut app, "bot_user").unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::BotNotAllowed {}
+        );
+
+        perform(&mut app, "keeper").unwrap();
+
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.allowed_bots, vec![Addr::unchecked("keeper")]);
+        assert_eq!(order.total_spent, Uint128::new(10_000));
+    }
+
+    #[test]
+    fn does_escalate_tip_of_overdue_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: Some(app.block_info().time.seconds()),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: Some(TipEscalation {
+                    step: Asset {
+                        amount: Uint128::new(1_000),
                         info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
-                        amount: Uint128::new(100_000),
                     },
-                    target_asset: AssetInfo::NativeToken {
-                        denom: "ukrw".to_string(),
-                    },
-                    interval: 500,
-                    last_purchase: 0,
-                }],
-            )
-            .unwrap();
+                    period: 100,
+                    max: Uint128::new(5_000),
+                }),
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
 
-        // should fail when purchasing
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_creator(),
-            ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: vec![
-                    SwapOperation::AstroSwap {
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        // the purchase is overdue by three periods of the escalation
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL + 350));
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
                         offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
                         ask_asset_info: AssetInfo::NativeToken {
                             denom: "ujpy".to_string(),
                         },
-                    },
-                    SwapOperation::AstroSwap {
-                        offer_asset_info: AssetInfo::NativeToken {
-                            denom: "ujpy".to_string(),
-                        },
-                        ask_asset_info: AssetInfo::NativeToken {
-                            denom: "ugbp".to_string(),
+                    }],
+                    fee_redeem: vec![Asset {
+                        amount: Uint128::new(15_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
                         },
-                    },
-                ],
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
+                    }],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.ty == "wasm-dca_purchase"
+                && e.attributes.contains(&attr("tip", "18000uluna"))));
+
+        // the bot should be paid the redeemed tip and the escalation from the tip balance
+        let bot_balance = app.wrap().query_balance("bot_user", "uluna").unwrap();
+        assert_eq!(bot_balance, coin(18_000, "uluna"));
+
+        let user_config = read_map(&app, dca_addr, &mock_creator().sender, USER_CONFIG);
+        assert_eq!(user_config.tip_balance[0].amount, Uint128::new(132_000));
+    }
+    #[test]
+    fn does_replenish_tip_from_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
                     info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
-                }],
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: Some(TipReplenish {
+                    amount: Uint128::new(500),
+                    threshold: Uint128::new(500),
+                }),
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
+            &coins(100_000, "uluna"),
         )
-        .unwrap_err();
+        .unwrap();
+
+        let perform = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        };
 
-        assert_eq!(res, ContractError::TargetAssetAssertion {});
-    }
+        // the empty tip balance is replenished from the first purchase
+        perform(&mut app);
 
-    #[test]
-    fn does_delete_order_if_fulfilled() {
-        let (mut deps, ..) = mock_instantiate(
-            Addr::unchecked("factory"),
-            Addr::unchecked("router"),
+        let user_config = read_map(&app, dca_addr.clone(), &mock_creator().sender, USER_CONFIG);
+        assert_eq!(
+            user_config.tip_balance,
             vec![Asset {
-                amount: Uint128::new(15_000),
+                amount: Uint128::new(500),
                 info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
-            }],
-            vec![AssetInfo::NativeToken {
+            }]
+        );
+
+        // the tip balance is no longer below the threshold at the next purchase
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        perform(&mut app);
+
+        let user_config = read_map(&app, dca_addr.clone(), &mock_creator().sender, USER_CONFIG);
+        assert_eq!(user_config.tip_balance[0].amount, Uint128::new(500));
+
+        // the replenished tip is part of the amount spent, and is kept by the contract
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender)[0].clone();
+        assert_eq!(order.initial_asset.amount, Uint128::new(80_000));
+        assert_eq!(order.total_spent, Uint128::new(20_000));
+
+        let dca_balance = app.wrap().query_balance(dca_addr, "uluna").unwrap();
+        assert_eq!(dca_balance, coin(80_500, "uluna"));
+    }
+
+    #[test]
+    fn does_track_registered_bot() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            AssetInfo::NativeToken {
                 denom: "ujpy".to_string(),
-            }],
+            },
         );
+        add_tip_balance(&mut app, dca_addr.clone());
 
-        USER_CONFIG
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &UserConfig {
-                    last_id: 1,
-                    max_hops: None,
-                    max_spread: None,
-                    tip_balance: vec![Asset {
-                        amount: Uint128::new(45_000),
-                        info: AssetInfo::NativeToken {
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: Some(true),
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let perform = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
                             denom: "uluna".to_string(),
                         },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
                     }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
             )
-            .unwrap();
+        };
 
-        USER_DCA
-            .save(
-                &mut deps.storage,
-                &mock_creator().sender,
-                &vec![DcaInfo {
-                    id: 1,
-                    dca_amount: Uint128::new(10_000),
-                    initial_asset: Asset {
-                        info: AssetInfo::NativeToken {
-                            denom: "uluna".to_string(),
-                        },
-                        amount: Uint128::new(10_000),
-                    },
-                    target_asset: AssetInfo::NativeToken {
-                        denom: "ukrw".to_string(),
-                    },
-                    interval: 500,
-                    last_purchase: 0,
-                }],
+        // only registered bots can perform purchases
+        let res = perform(&mut app).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::UnregisteredBot {}
+        );
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::RegisterBot {},
+            &[],
+        )
+        .unwrap();
+
+        // the first purchase is due from whenever the order was made, while the second purchase
+        // is performed 120 seconds after it was due
+        perform(&mut app).unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL + 120));
+        perform(&mut app).unwrap();
+
+        let bots: Vec<BotInfo> = app
+            .wrap()
+            .query_wasm_smart(
+                dca_addr,
+                &QueryMsg::Bots {
+                    start_after: None,
+                    limit: None,
+                },
             )
             .unwrap();
+        assert_eq!(bots.len(), 1);
+        assert_eq!(bots[0].bot, Addr::unchecked("bot_user"));
+        assert_eq!(bots[0].stats.purchases, 2);
+        assert_eq!(bots[0].stats.failures, 0);
+        assert_eq!(bots[0].stats.total_delay, 120);
+        assert_eq!(bots[0].average_delay, 60);
+    }
 
-        // should fail when purchasing
-        let hops = vec![
-            SwapOperation::AstroSwap {
-                offer_asset_info: AssetInfo::NativeToken {
-                    denom: "uluna".to_string(),
-                },
-                ask_asset_info: AssetInfo::NativeToken {
-                    denom: "ujpy".to_string(),
-                },
+    #[test]
+    fn does_slash_bonded_bot_for_invalid_or_worse_route() {
+        let (mut app, dca_addr, cw20_addr) = mock_astroport_instantiate(None);
+
+        create_normal_order(
+            &mut app,
+            dca_addr.clone(),
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
             },
-            SwapOperation::AstroSwap {
-                offer_asset_info: AssetInfo::NativeToken {
-                    denom: "ujpy".to_string(),
-                },
-                ask_asset_info: AssetInfo::NativeToken {
-                    denom: "ukrw".to_string(),
+            AssetInfo::NativeToken {
+                denom: "ujpy".to_string(),
+            },
+        );
+        add_tip_balance(&mut app, dca_addr.clone());
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: Some(BotBond {
+                    bond: coin(50_000, "uluna"),
+                    slash_bps: 1_000,
+                    unbonding_period: 1_000,
+                }),
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let bot = Addr::unchecked("bot_user");
+        app.send_tokens(mock_creator().sender, bot.clone(), &coins(50_000, "uluna"))
+            .unwrap();
+        app.execute_contract(
+            bot.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::RegisterBot {},
+            &coins(50_000, "uluna"),
+        )
+        .unwrap();
+
+        let swap = |offer: AssetInfo, ask: AssetInfo| SwapOperation::AstroSwap {
+            offer_asset_info: offer,
+            ask_asset_info: ask,
+        };
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+        let cw20 = AssetInfo::Token {
+            contract_addr: cw20_addr,
+        };
+
+        let perform = |app: &mut App, hops: Vec<SwapOperation>| {
+            app.execute_contract(
+                bot.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops,
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
                 },
+                &[],
+            )
+            .unwrap()
+        };
+        let slash_reason = |res: &AppResponse| {
+            res.events
+                .iter()
+                .flat_map(|event| &event.attributes)
+                .find(|attr| attr.key == "reason")
+                .map(|attr| attr.value.clone())
+        };
+        let query_balance = |app: &App| {
+            app.wrap()
+                .query_balance(mock_creator().sender, "uluna")
+                .unwrap()
+                .amount
+        };
+
+        // a route through a token that is not whitelisted slashes 10% of the bond to the user
+        let balance = query_balance(&app);
+        let res = perform(
+            &mut app,
+            vec![
+                swap(uluna.clone(), ujpy.clone()),
+                swap(ujpy.clone(), cw20.clone()),
+                swap(cw20, ujpy.clone()),
+            ],
+        );
+        assert_eq!(slash_reason(&res), Some("invalid_route".to_string()));
+        assert_eq!(query_balance(&app), balance + Uint128::new(5_000));
+
+        // so does a route returning less than the direct route
+        let res = perform(
+            &mut app,
+            vec![
+                swap(uluna.clone(), ujpy.clone()),
+                swap(ujpy.clone(), ukrw.clone()),
+                swap(ukrw, ujpy.clone()),
+            ],
+        );
+        assert_eq!(slash_reason(&res), Some("worse_route".to_string()));
+        assert_eq!(query_balance(&app), balance + Uint128::new(9_500));
+
+        // neither purchase was performed, leaving the order due for the best route
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender)[0].clone();
+        assert!(order.total_spent.is_zero());
+
+        let res = perform(&mut app, vec![swap(uluna, ujpy)]);
+        assert_eq!(slash_reason(&res), None);
+
+        let stats = read_map(&app, dca_addr.clone(), &bot, BOTS);
+        assert_eq!(stats.bond, Some(coin(40_500, "uluna")));
+        assert_eq!(stats.slashes, 2);
+        assert_eq!(stats.purchases, 1);
+
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.total_spent, Uint128::new(10_000));
+    }
+
+    #[test]
+    fn does_assign_purchase_to_bot_within_fairness_window() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: Some(100),
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
             },
-        ];
+            &[],
+        )
+        .unwrap();
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_info("bot_addr", &[]),
-            ExecuteMsg::PerformDcaPurchase {
-                user: mock_creator().sender.into_string(),
-                id: 1,
-                hops: hops.clone(),
-                fee_redeem: vec![Asset {
-                    amount: Uint128::new(30_000),
+        let bots = [Addr::unchecked("bot_a"), Addr::unchecked("bot_b")];
+        for bot in &bots {
+            app.execute_contract(
+                bot.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::RegisterBot {},
+                &[],
+            )
+            .unwrap();
+        }
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
                     info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
-                }],
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(NORMAL_ORDER_INTERVAL),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: Some(app.block_info().time.seconds()),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
             },
+            &coins(100_000, "uluna"),
         )
         .unwrap();
 
-        let expected_msgs: Vec<CosmosMsg> = vec![
-            BankMsg::Send {
-                amount: coins(30_000, "uluna"),
-                to_address: "bot_addr".to_string(),
-            }
-            .into(),
-            WasmMsg::Execute {
-                contract_addr: "router".to_string(),
-                funds: coins(10_000, "uluna"),
-                msg: to_binary(&astroport::router::ExecuteMsg::ExecuteSwapOperations {
-                    operations: hops,
-                    minimum_receive: None,
-                    to: Some(mock_creator().sender.into_string()),
-                    max_spread: Some(Decimal::from_str("0.05").unwrap()),
-                })
-                .unwrap(),
-            }
-            .into(),
-        ];
+        let perform = |app: &mut App, bot: &Addr| {
+            app.execute_contract(
+                bot.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+        };
+
+        // the purchase is assigned by rotating through the registered bots by the order id and
+        // the epoch the purchase was due in
+        app.update_block(|block| block.time = block.time.plus_seconds(NORMAL_ORDER_INTERVAL));
+        let epoch = app.block_info().time.seconds() / 100;
+        let assigned = &bots[((1 + epoch) % 2) as usize];
+        let other = &bots[((2 + epoch) % 2) as usize];
 
+        let res = perform(&mut app, other).unwrap_err();
         assert_eq!(
-            res,
-            Response::new()
-                .add_messages(expected_msgs)
-                .add_attributes(vec![
-                    attr("action", "perform_dca_purchase"),
-                    attr("user", mock_creator().sender.into_string()),
-                    attr("id", "1"),
-                ])
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NotAssignedBot {
+                bot: assigned.to_string()
+            }
         );
+
+        // the purchase opens to every bot once the window has passed
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        perform(&mut app, other).unwrap();
+
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.total_spent, Uint128::new(10_000));
     }
 }
@@ -0,0 +1,737 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::{
+    ArchivedOrder, CreateDcaOrderResponse, DcaHookMsg, DcaInfo, Delivery, Distribution,
+    FailurePolicy, OrderDirection, OrderEvent, PurchaseRecord, Strategy,
+};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Addr, Decimal, DepsMut, Env, Event, Response, StdError,
+    StdResult, SubMsgResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    refund_order::refund_order,
+    state::{
+        get_purchase_count, user_dca, ARCHIVED_ORDERS, BOTS, CONFIG, PENDING_PURCHASE,
+        PENDING_SPLIT, PURCHASED_BALANCE, PURCHASE_HISTORY,
+    },
+    stats::{record_order_removed, record_purchase_stats},
+};
+
+use super::{
+    auto_stake::auto_stake,
+    create_dca_order::{store_dca_order, validate_dca_order, CreateDcaOrder},
+    ibc_delivery::ibc_transfer_msg,
+    nft::get_order_holder,
+    notifier::notification_msg,
+    pooled_order::record_pool_purchase,
+    zap::zap_msgs,
+};
+
+/// The version of the attributes of the [`purchase_event`], which is increased whenever they are
+/// changed so that indexers can tell the versions apart
+const PURCHASE_EVENT_SCHEMA_VERSION: &str = "1";
+
+/// ## Description
+/// Returns the `dca_purchase` event describing the purchase `record` performed for the order `id`
+/// of `user`, which is emitted as a `wasm-dca_purchase` event.
+fn purchase_event(user: &Addr, id: u64, seq: u64, record: &PurchaseRecord) -> Event {
+    // a purchase that returns nothing is priced above any limit
+    let price = match record.amount_out.is_zero() {
+        true => Decimal::MAX,
+        false => Decimal::from_ratio(record.amount_in, record.amount_out),
+    };
+    let tip = record
+        .tip
+        .iter()
+        .map(|asset| asset.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Event::new("dca_purchase").add_attributes(vec![
+        attr("schema_version", PURCHASE_EVENT_SCHEMA_VERSION),
+        attr("user", user),
+        attr("order_id", id.to_string()),
+        attr("sequence", seq.to_string()),
+        attr("amount_in", record.amount_in),
+        attr("amount_out", record.amount_out),
+        attr("price", price.to_string()),
+        attr("bot", &record.bot),
+        attr("tip", tip),
+    ])
+}
+
+/// ## Description
+/// Returns the `order_completed` event describing the final stats of `order` of `user` once it has
+/// performed its last purchase, which is emitted as a `wasm-order_completed` event.
+fn completed_event(user: &Addr, order: &DcaInfo, purchases: u64) -> Event {
+    Event::new("order_completed").add_attributes(vec![
+        attr("user", user),
+        attr("order_id", order.id.to_string()),
+        attr("purchases", purchases.to_string()),
+        attr("total_spent", order.total_spent),
+        attr("total_received", order.total_received),
+        attr("remaining", order.initial_asset.amount),
+    ])
+}
+
+/// ## Description
+/// Creates the distribution order following `order` of `user` once it has completed, which sells
+/// the purchased balance of the order held by the contract as described by `distribution`, for
+/// the holder of the NFT of the order if one was minted for it.
+///
+/// Returns [`None`] if there is no purchased balance to distribute, or if the distribution order
+/// cannot be created, such as for a blacklisted asset, in which case the purchased balance is left
+/// for the user to claim. Otherwise returns the [`Response`] of creating the order.
+fn start_distribution(
+    mut deps: DepsMut,
+    env: &Env,
+    user: &Addr,
+    order: &DcaInfo,
+    distribution: &Distribution,
+) -> StdResult<Option<Response>> {
+    let balance = match PURCHASED_BALANCE.may_load(deps.storage, (user, order.id))? {
+        Some(balance) if !balance.amount.is_zero() => balance,
+        _ => return Ok(None),
+    };
+    let owner = get_order_holder(deps.as_ref(), user, order.id)?.unwrap_or_else(|| user.clone());
+
+    let escrowed = !balance.info.is_native_token();
+    let order_info = CreateDcaOrder {
+        initial_asset: balance.clone(),
+        target_asset: distribution.target_asset.clone(),
+        interval: distribution.interval,
+        schedule: None,
+        max_delay: None,
+        jitter: None,
+        dca_amount: distribution.dca_amount.min(balance.amount),
+        strategy: Strategy::Fixed,
+        first_purchase: None,
+        expires_at: None,
+        max_purchases: None,
+        max_failures: None,
+        failure_policy: FailurePolicy::Pause,
+        swap_tip_bps: order.swap_tip_bps,
+        tip_escalation: None,
+        tip_replenish: None,
+        allowed_bots: order
+            .allowed_bots
+            .iter()
+            .map(|bot| bot.to_string())
+            .collect(),
+        allow_low_liquidity: false,
+        max_price: None,
+        only_below_price: None,
+        max_price_impact: None,
+        stop_price: None,
+        take_profit_price: None,
+        direction: OrderDirection::Distribute,
+        distribution: None,
+        targets: vec![],
+        pooled: false,
+        max_spread: order.max_spread,
+        min_receive_per_purchase: None,
+        route: None,
+        auto_route: false,
+        delivery: Delivery::Immediate,
+        recipient: order
+            .recipient
+            .as_ref()
+            .map(|recipient| recipient.to_string()),
+        purchase_hooks: vec![],
+        auto_stake: false,
+        zap: None,
+        referrer: order.referrer.as_ref().map(|referrer| referrer.to_string()),
+        owner: None,
+        pull: false,
+    };
+
+    let created = validate_dca_order(
+        &order_info.initial_asset,
+        &order_info.target_asset,
+        order_info.dca_amount,
+        &order_info.strategy,
+    )
+    .and_then(|_| store_dca_order(deps.branch(), env, &owner, order_info, escrowed));
+
+    match created {
+        Ok(response) => {
+            PURCHASED_BALANCE.remove(deps.storage, (user, order.id));
+            Ok(Some(response))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// ## Description
+/// Records the amount of the target asset received from the router swap of a DCA purchase on the
+/// order it was performed for and in its purchase history, and unlocks the order for the next
+/// purchase, archiving the order if it has been fulfilled or has performed `max_purchases`
+/// purchases, in which case the remaining funds are refunded.
+///
+/// If the order has escrowed delivery, the amount received is added to the purchased balance the
+/// user can claim. Otherwise, the purchase hooks of the order are called with the amount received,
+/// or the amount received is staked if the order is auto staked, or provided as liquidity along
+/// with the unswapped half of the purchase if the order zaps.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes and a `dca_purchase` event describing the purchase, along with an `order_completed`
+/// event if the order was archived, if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `result` - The [`SubMsgResult`] of the router swap.
+pub fn perform_dca_purchase_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let response = result.into_result().map_err(StdError::generic_err)?;
+    let return_amount = get_return_amount(&response.events)?;
+
+    record_purchase(deps, env, return_amount, vec![])
+}
+
+/// ## Description
+/// Adds the amount of the target asset received from one of the swaps of a DCA purchase split
+/// across several routes to the amount received by the purchase. Once every swap has replied, the
+/// total amount received is checked against the minimum receive of the whole purchase and
+/// recorded like the swap of a purchase that is not split through [`perform_dca_purchase_reply`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `result` - The [`SubMsgResult`] of the router swap.
+pub fn split_swap_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let response = result.into_result().map_err(StdError::generic_err)?;
+    let return_amount = get_return_amount(&response.events)?;
+
+    let mut split = PENDING_SPLIT.load(deps.storage)?;
+    split.received = split.received.checked_add(return_amount)?;
+    split.remaining -= 1;
+
+    // each swap of a purchase split across several target assets purchases the next target
+    if let Some(target) = split.targets.get(split.outputs.len()) {
+        split.outputs.push(Asset {
+            info: target.clone(),
+            amount: return_amount,
+        });
+    }
+
+    if split.remaining > 0 {
+        PENDING_SPLIT.save(deps.storage, &split)?;
+        return Ok(Response::new().add_attributes(vec![
+            attr("action", "split_swap_reply"),
+            attr("return_amount", return_amount),
+        ]));
+    }
+
+    PENDING_SPLIT.remove(deps.storage);
+    if let Some(minimum_receive) = split.minimum_receive {
+        if split.received < minimum_receive {
+            return Err(ContractError::SplitMinimumReceive {
+                received: split.received,
+                minimum_receive,
+            });
+        }
+    }
+
+    // the amount received of the target asset of an order with several target assets is what its
+    // total received counts, while the amount of every target is recorded along with it
+    let received = match split.targets.is_empty() {
+        true => split.received,
+        false => {
+            let order_target = PENDING_PURCHASE
+                .load(deps.storage)
+                .and_then(|purchase| user_dca().load(deps.storage, (&purchase.user, purchase.id)))?
+                .target_asset;
+            split
+                .outputs
+                .iter()
+                .filter(|output| output.info == order_target)
+                .map(|output| output.amount)
+                .sum()
+        }
+    };
+
+    record_purchase(deps, env, received, split.outputs)
+}
+
+/// ## Description
+/// Records `return_amount` of the target asset received by the DCA purchase being performed, as
+/// described in [`perform_dca_purchase_reply`], along with the `outputs` received of each target
+/// asset if the order has several.
+fn record_purchase(
+    mut deps: DepsMut,
+    env: Env,
+    return_amount: Uint128,
+    outputs: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let purchase = PENDING_PURCHASE.load(deps.storage)?;
+    PENDING_PURCHASE.remove(deps.storage);
+    let (user_address, id) = (purchase.user, purchase.id);
+
+    let mut order = user_dca().load(deps.storage, (&user_address, id))?;
+    order.total_received = order.total_received.checked_add(return_amount)?;
+    for output in &outputs {
+        match order
+            .targets_received
+            .iter_mut()
+            .find(|received| received.info == output.info)
+        {
+            Some(received) => received.amount = received.amount.checked_add(output.amount)?,
+            None => order.targets_received.push(output.clone()),
+        }
+    }
+    order.executing = false;
+    order.consecutive_failures = 0;
+
+    // hold the purchased asset for the user to claim
+    if order.delivery == Delivery::Escrow {
+        PURCHASED_BALANCE.update(
+            deps.storage,
+            (&user_address, id),
+            |balance| -> Result<_, ContractError> {
+                let mut balance = balance.unwrap_or(Asset {
+                    info: order.target_asset.clone(),
+                    amount: Uint128::zero(),
+                });
+                balance.amount = balance.amount.checked_add(return_amount)?;
+                Ok(balance)
+            },
+        )?;
+
+        // split the purchased asset of a pooled order among the shares of its depositors
+        if order.pooled {
+            record_pool_purchase(deps.storage, &user_address, id, return_amount)?;
+        }
+    }
+
+    // call the purchase hooks of the order, sending the purchased asset to the first hook, on
+    // behalf of the holder of the NFT of the order if one was minted for it
+    let owner =
+        get_order_holder(deps.as_ref(), &user_address, id)?.unwrap_or_else(|| user_address.clone());
+    let recipient = order.recipient.clone().unwrap_or_else(|| owner.clone());
+    let purchased = Asset {
+        info: order.target_asset.clone(),
+        amount: return_amount,
+    };
+    let hook_msgs = order
+        .purchase_hooks
+        .iter()
+        .enumerate()
+        .map(|(i, hook)| {
+            let hook_msg = to_binary(&DcaHookMsg::DcaPurchase {
+                user: user_address.to_string(),
+                id,
+                recipient: recipient.to_string(),
+                asset: purchased.clone(),
+                msg: hook.msg.clone(),
+            })?;
+
+            Ok(match (&purchased.info, i) {
+                (AssetInfo::Token { contract_addr }, 0) => WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: hook.contract_addr.clone(),
+                        amount: return_amount,
+                        msg: hook_msg,
+                    })?,
+                    funds: vec![],
+                },
+                (AssetInfo::NativeToken { denom }, 0) => WasmMsg::Execute {
+                    contract_addr: hook.contract_addr.clone(),
+                    msg: hook_msg,
+                    funds: coins(return_amount.u128(), denom),
+                },
+                _ => WasmMsg::Execute {
+                    contract_addr: hook.contract_addr.clone(),
+                    msg: hook_msg,
+                    funds: vec![],
+                },
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // stake the purchased ASTRO, sending the xASTRO received to the recipient
+    let stake_msg = match order.auto_stake {
+        true => Some(auto_stake(
+            deps.branch(),
+            &env,
+            recipient.clone(),
+            return_amount,
+        )?),
+        false => None,
+    };
+
+    // provide liquidity with the purchased asset and the unswapped half of the purchase
+    let zap_msgs = match &order.zap {
+        Some(zap) => {
+            let config = CONFIG.load(deps.storage)?;
+
+            zap_msgs(
+                deps.storage,
+                &deps.querier,
+                &config.factory_addr,
+                zap,
+                Asset {
+                    info: order.initial_asset.info.clone(),
+                    amount: purchase.provide_amount,
+                },
+                purchased.clone(),
+                &owner,
+                &recipient,
+            )?
+        }
+        None => vec![],
+    };
+
+    // send the purchased asset to the address of the order on another chain
+    let transfer_msg = match &order.delivery {
+        Delivery::Ibc {
+            channel_id,
+            to_address,
+            timeout,
+        } if !return_amount.is_zero() => Some(ibc_transfer_msg(
+            deps.storage,
+            &env,
+            &user_address,
+            id,
+            (channel_id, to_address, *timeout),
+            purchased.clone(),
+        )?),
+        _ => None,
+    };
+
+    // record the purchase and its delay on the stats of the bot if it is registered
+    if let Some(mut stats) = BOTS.may_load(deps.storage, &purchase.bot)? {
+        stats.purchases += 1;
+        stats.total_delay = stats.total_delay.saturating_add(purchase.delay);
+        BOTS.save(deps.storage, &purchase.bot, &stats)?;
+    }
+
+    // record the purchase after the last one performed for the order
+    let seq = get_purchase_count(deps.storage, &user_address, id)? + 1;
+    let record = PurchaseRecord {
+        timestamp: env.block.time.seconds(),
+        amount_in: purchase.amount_in,
+        amount_out: return_amount,
+        outputs,
+        bot: purchase.bot,
+        tip: purchase.tip,
+    };
+    PURCHASE_HISTORY.save(deps.storage, (&user_address, id, seq), &record)?;
+    record_purchase_stats(
+        deps.storage,
+        record.timestamp,
+        (&order.initial_asset.info, record.amount_in),
+        (&order.target_asset, record.amount_out),
+        &record.tip,
+    )?;
+    let event = purchase_event(&user_address, id, seq, &record);
+
+    // notify the notifier of the user of the purchase, and of the order completing
+    let mut notifications = vec![];
+    notifications.extend(notification_msg(
+        deps.storage,
+        &user_address,
+        id,
+        OrderEvent::Executed {
+            amount_in: record.amount_in,
+            amount_out: record.amount_out,
+        },
+    )?);
+
+    // close the order once it has performed its maximum amount of purchases, refunding the
+    // remaining funds to the holder of the NFT of the order if one was minted for it
+    let closed = order.max_purchases.map_or(false, |max| seq >= max);
+    let refund = match closed && !order.initial_asset.amount.is_zero() {
+        true => refund_order(&order, order.initial_asset.amount, &owner)?,
+        false => None,
+    };
+
+    // archive the order with its final stats if it was fulfilled or closed, otherwise store the
+    // updated order
+    let mut events = vec![event];
+    let mut distribution = None;
+    if order.initial_asset.amount.is_zero() || closed {
+        user_dca().remove(deps.storage, (&user_address, id))?;
+        record_order_removed(deps.storage)?;
+
+        // an accumulating order is followed by the distribution of what it has purchased
+        if let Some(order_distribution) = &order.distribution {
+            distribution = start_distribution(
+                deps.branch(),
+                &env,
+                &user_address,
+                &order,
+                order_distribution,
+            )?;
+        }
+
+        events.push(completed_event(&user_address, &order, seq));
+        notifications.extend(notification_msg(
+            deps.storage,
+            &user_address,
+            id,
+            OrderEvent::Completed {},
+        )?);
+        ARCHIVED_ORDERS.save(
+            deps.storage,
+            (&user_address, id),
+            &ArchivedOrder {
+                order,
+                purchases: seq,
+                completed_at: env.block.time.seconds(),
+            },
+        )?;
+    } else {
+        user_dca().save(deps.storage, (&user_address, id), &order)?;
+    }
+
+    let mut response = Response::new()
+        .add_messages(hook_msgs)
+        .add_submessages(stake_msg)
+        .add_messages(zap_msgs)
+        .add_submessages(transfer_msg)
+        .add_messages(refund)
+        .add_submessages(notifications)
+        .add_events(events)
+        .add_attributes(vec![
+            attr("action", "perform_dca_purchase_reply"),
+            attr("user", user_address),
+            attr("id", id.to_string()),
+            attr("return_amount", return_amount),
+        ]);
+
+    // the distribution order is reported along with the NFT minted for it, if any
+    if let Some(distribution) = distribution {
+        if let Some(data) = distribution.data {
+            let CreateDcaOrderResponse { id } = from_binary(&data)?;
+            response = response.add_attribute("distribution_id", id.to_string());
+        }
+        response = response.add_submessages(distribution.messages);
+    }
+
+    Ok(response)
+}
+
+/// ## Description
+/// Returns the `return_amount` of the last swap performed by the Astroport pairs, which is the
+/// amount of the target asset sent to the user.
+fn get_return_amount(events: &[Event]) -> Result<Uint128, ContractError> {
+    let return_amount = events
+        .iter()
+        .filter(|e| e.ty == "wasm")
+        .flat_map(|e| e.attributes.iter())
+        .filter(|a| a.key == "return_amount")
+        .last()
+        .ok_or(ContractError::MissingReturnAmount {})?;
+
+    Ok(return_amount
+        .value
+        .parse::<u128>()
+        .map(Uint128::new)
+        .map_err(|_| StdError::parse_err("Uint128", "return_amount is not a valid amount"))?)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        DcaQueryInfo, Delivery, Distribution, Duration, ExecuteMsg, FailurePolicy, OrderDirection,
+        QueryMsg, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Event, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator, read_user_orders},
+    };
+
+    use super::get_return_amount;
+
+    #[test]
+    fn does_use_last_return_amount() {
+        // a two hop swap, where the last swap returns the amount sent to the user
+        let events = vec![
+            Event::new("wasm")
+                .add_attribute("action", "swap")
+                .add_attribute("return_amount", "9900"),
+            Event::new("transfer").add_attribute("amount", "9900ujpy"),
+            Event::new("wasm")
+                .add_attribute("action", "swap")
+                .add_attribute("return_amount", "9558"),
+        ];
+
+        assert_eq!(get_return_amount(&events).unwrap(), Uint128::new(9_558));
+    }
+
+    #[test]
+    fn does_error_without_return_amount() {
+        let events = vec![Event::new("wasm").add_attribute("action", "swap")];
+
+        assert_eq!(
+            get_return_amount(&events).unwrap_err(),
+            ContractError::MissingReturnAmount {}
+        );
+    }
+
+    #[test]
+    fn does_start_distribution_once_completed() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        // a single purchase accumulating ukrw, which is then sold for ujpy
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(10_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: ukrw.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: Some(Distribution {
+                    target_asset: ujpy.clone(),
+                    dca_amount: Uint128::new(5_000),
+                    interval: Duration::Time(1_000),
+                }),
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            ask_asset_info: ujpy.clone(),
+                        },
+                        SwapOperation::AstroSwap {
+                            offer_asset_info: ujpy.clone(),
+                            ask_asset_info: ukrw.clone(),
+                        },
+                    ],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.events.iter().any(|e| e
+            .attributes
+            .iter()
+            .any(|a| a.key == "distribution_id" && a.value == "2")));
+
+        // the purchased balance is moved into the distribution order instead of being claimable
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, 2);
+        assert_eq!(
+            orders[0].initial_asset,
+            Asset {
+                amount: Uint128::new(9_558),
+                info: ukrw,
+            }
+        );
+        assert_eq!(orders[0].target_asset, ujpy);
+        assert_eq!(orders[0].dca_amount, Uint128::new(5_000));
+        assert_eq!(orders[0].direction, OrderDirection::Distribute);
+
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::ClaimPurchased { id: 1, user: None },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+
+        // and the orders of the user can be told apart by their direction
+        let query_orders = |direction: OrderDirection| -> Vec<DcaQueryInfo> {
+            app.wrap()
+                .query_wasm_smart(
+                    &dca_addr,
+                    &QueryMsg::UserDcaOrders {
+                        user: mock_creator().sender.into_string(),
+                        direction: Some(direction),
+                    },
+                )
+                .unwrap()
+        };
+        assert!(query_orders(OrderDirection::Accumulate).is_empty());
+        assert_eq!(query_orders(OrderDirection::Distribute).len(), 1);
+    }
+}
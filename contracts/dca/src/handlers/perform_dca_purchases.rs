@@ -0,0 +1,628 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::{
+    CallbackMsg, DcaInfo, ExecuteMsg, FailurePolicy, OrderEvent, PurchaseFailure, PurchaseRequest,
+};
+use cosmwasm_std::{
+    attr, to_binary, Addr, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult, SubMsg,
+    SubMsgResult, WasmMsg,
+};
+
+use crate::{
+    error::ContractError,
+    state::{user_dca, PendingBatch, BOTS, CONFIG, PENDING_BATCH, USER_CONFIG},
+};
+
+use super::notifier::notification_msg;
+
+/// The id of the reply to each DCA purchase performed through [`perform_dca_purchases`]
+pub const PERFORM_DCA_PURCHASES_REPLY_ID: u64 = 2;
+
+/// ## Description
+/// Performs DCA purchases for multiple orders at once on behalf of the bot. Each purchase is
+/// executed as a [`CallbackMsg::PerformDcaPurchase`] submessage, so that a purchase that fails is
+/// reverted on its own while the other purchases are still performed.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the bot who is performing the DCA purchases, who will be
+/// rewarded with the tips of each purchase.
+///
+/// * `purchases` - The [`Vec<PurchaseRequest>`] of the DCA purchases to perform.
+pub fn perform_dca_purchases(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    purchases: Vec<PurchaseRequest>,
+) -> Result<Response, ContractError> {
+    if purchases.is_empty() {
+        return Err(ContractError::NoOrdersProvided {});
+    }
+
+    // every purchase replies, so that a failure can be recorded on the order it was performed for
+    PENDING_BATCH.save(
+        deps.storage,
+        &PendingBatch {
+            bot: info.sender.clone(),
+            orders: purchases.iter().map(|p| (p.user.clone(), p.id)).collect(),
+        },
+    )?;
+
+    let messages = purchases
+        .into_iter()
+        .map(|purchase| {
+            Ok(SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::PerformDcaPurchase {
+                        bot: info.sender.clone(),
+                        purchase,
+                    }))?,
+                },
+                PERFORM_DCA_PURCHASES_REPLY_ID,
+            ))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attributes(vec![attr("action", "perform_dca_purchases")]))
+}
+
+/// ## Description
+/// Applies the failure policy of `order` once `max_failures` of its purchases have failed in a row,
+/// widening its maximum spread by the step of the policy, or pausing it once the maximum spread
+/// cannot be widened further.
+///
+/// Returns the `failure_limit_reached` event describing the policy applied, which is emitted as a
+/// `wasm-failure_limit_reached` event, or [`None`] if the order has not reached `max_failures`.
+fn apply_failure_policy(deps: Deps, user: &Addr, order: &mut DcaInfo) -> StdResult<Option<Event>> {
+    match order.max_failures {
+        Some(max_failures) if order.consecutive_failures >= max_failures => {}
+        _ => return Ok(None),
+    }
+
+    // the current maximum spread of the order falls back to that of the user, then the contract
+    let widened_spread = match &order.failure_policy {
+        FailurePolicy::WidenSlippage { step, max_spread } => {
+            let current = match order.max_spread {
+                Some(current) => current,
+                None => match USER_CONFIG
+                    .may_load(deps.storage, user)?
+                    .and_then(|config| config.max_spread)
+                {
+                    Some(current) => current,
+                    None => CONFIG.load(deps.storage)?.max_spread,
+                },
+            };
+
+            (current < *max_spread).then(|| (current + *step).min(*max_spread))
+        }
+        FailurePolicy::Pause => None,
+    };
+
+    let event = Event::new("failure_limit_reached").add_attributes(vec![
+        attr("user", user),
+        attr("order_id", order.id.to_string()),
+        attr("failures", order.consecutive_failures.to_string()),
+    ]);
+
+    order.consecutive_failures = 0;
+    Ok(Some(match widened_spread {
+        Some(max_spread) => {
+            order.max_spread = Some(max_spread);
+            event.add_attributes(vec![
+                attr("policy", "widen_slippage"),
+                attr("max_spread", max_spread.to_string()),
+            ])
+        }
+        None => {
+            order.paused = true;
+            event.add_attribute("policy", "pause")
+        }
+    }))
+}
+
+/// ## Description
+/// Handles a DCA purchase of [`perform_dca_purchases`], recording the error of a purchase that
+/// failed on its order instead of reverting the other purchases.
+///
+/// Returns a [`Response`] with the error of the purchase as an attribute if it failed, along with a
+/// `failure_limit_reached` event if the failure policy of the order was applied.
+/// ## Params
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `result` - The [`SubMsgResult`] of the DCA purchase.
+pub fn perform_dca_purchases_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    // the purchases of the batch reply in the order they were performed
+    let mut batch = PENDING_BATCH.load(deps.storage)?;
+    let (user, id) = batch.orders.remove(0);
+    match batch.orders.is_empty() {
+        true => PENDING_BATCH.remove(deps.storage),
+        false => PENDING_BATCH.save(deps.storage, &batch)?,
+    }
+
+    let err = match result {
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+        SubMsgResult::Err(err) => err,
+    };
+
+    // record the failure on the stats of the bot if it is registered
+    if let Some(mut stats) = BOTS.may_load(deps.storage, &batch.bot)? {
+        stats.failures += 1;
+        BOTS.save(deps.storage, &batch.bot, &stats)?;
+    }
+
+    // record the failure on the order, unless the purchase failed for an order that does not
+    // exist, applying the failure policy of the order once too many purchases have failed in a row
+    let mut events = vec![];
+    let mut notification = None;
+    if let Ok(user_address) = addr_validate_to_lower(deps.api, &user) {
+        if let Some(mut order) = user_dca().may_load(deps.storage, (&user_address, id))? {
+            order.last_failure = Some(PurchaseFailure {
+                error: err.clone(),
+                height: env.block.height,
+                bot: batch.bot,
+            });
+            order.consecutive_failures = order.consecutive_failures.saturating_add(1);
+            events.extend(apply_failure_policy(
+                deps.as_ref(),
+                &user_address,
+                &mut order,
+            )?);
+
+            user_dca().save(deps.storage, (&user_address, id), &order)?;
+
+            notification = notification_msg(
+                deps.storage,
+                &user_address,
+                id,
+                OrderEvent::Failed { error: err.clone() },
+            )?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_submessages(notification)
+        .add_events(events)
+        .add_attributes(vec![
+            attr("action", "perform_dca_purchase_failed"),
+            attr("user", user),
+            attr("id", id.to_string()),
+            attr("error", err),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        BotInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, PurchaseRequest,
+        QueryMsg, Strategy,
+    };
+    use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+    use cw_multi_test::{App, AppResponse, Executor};
+
+    use crate::tests::{mock_astroport_instantiate, mock_creator, read_user_orders};
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    #[test]
+    fn does_isolate_failed_purchases() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        // the router refuses to return less than the minimum receive, so the purchases of the second
+        // order fail in their swap
+        for min_receive in [None, Some(Uint128::new(10_000))] {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: native("uluna"),
+                    },
+                    target_asset: native("ukrw"),
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: min_receive,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(100_000, "uluna"),
+            )
+            .unwrap();
+        }
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(150_000),
+                    info: native("uluna"),
+                }],
+            },
+            &coins(150_000, "uluna"),
+        )
+        .unwrap();
+
+        let bot_user = Addr::unchecked("bot_user");
+        app.execute_contract(
+            bot_user.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::RegisterBot {},
+            &[],
+        )
+        .unwrap();
+
+        let res = app
+            .execute_contract(
+                bot_user.clone(),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchases {
+                    purchases: vec![
+                        PurchaseRequest {
+                            user: mock_creator().sender.into_string(),
+                            id: 1,
+                            hops: vec![
+                                SwapOperation::AstroSwap {
+                                    offer_asset_info: native("uluna"),
+                                    ask_asset_info: native("ujpy"),
+                                },
+                                SwapOperation::AstroSwap {
+                                    offer_asset_info: native("ujpy"),
+                                    ask_asset_info: native("ukrw"),
+                                },
+                            ],
+                            fee_redeem: vec![Asset {
+                                amount: Uint128::new(30_000),
+                                info: native("uluna"),
+                            }],
+                            deadline: None,
+                            accepted_tips: vec![],
+                        },
+                        PurchaseRequest {
+                            user: mock_creator().sender.into_string(),
+                            id: 2,
+                            hops: vec![
+                                SwapOperation::AstroSwap {
+                                    offer_asset_info: native("uluna"),
+                                    ask_asset_info: native("ujpy"),
+                                },
+                                SwapOperation::AstroSwap {
+                                    offer_asset_info: native("ujpy"),
+                                    ask_asset_info: native("ukrw"),
+                                },
+                            ],
+                            fee_redeem: vec![Asset {
+                                amount: Uint128::new(15_000),
+                                info: native("uluna"),
+                            }],
+                            deadline: None,
+                            accepted_tips: vec![],
+                        },
+                    ],
+                },
+                &[],
+            )
+            .unwrap();
+
+        // the failed purchase should be recorded
+        assert!(res.events.iter().any(|e| e
+            .attributes
+            .iter()
+            .any(|a| a.key == "action" && a.value == "perform_dca_purchase_failed")));
+
+        // only the tip of the successful purchase should have been paid
+        let bot_balance = app.wrap().query_balance(bot_user.clone(), "uluna").unwrap();
+        assert_eq!(bot_balance, coin(30_000, "uluna"));
+
+        // both purchases should be recorded on the stats of the registered bot
+        let bots: Vec<BotInfo> = app
+            .wrap()
+            .query_wasm_smart(
+                dca_addr.clone(),
+                &QueryMsg::Bots {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!((bots[0].stats.purchases, bots[0].stats.failures), (1, 1));
+
+        let user_balance = app
+            .wrap()
+            .query_balance(mock_creator().sender, "ukrw")
+            .unwrap();
+        assert_eq!(user_balance, coin(9_558, "ukrw"));
+
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert_eq!(
+            orders
+                .iter()
+                .map(|o| (o.id, o.initial_asset.amount.u128()))
+                .collect::<Vec<_>>(),
+            vec![(1, 90_000), (2, 100_000)]
+        );
+
+        // and the failure should be recorded on the order it was performed for
+        assert_eq!(orders[0].last_failure, None);
+        let failure = orders[1].last_failure.clone().unwrap();
+        assert_eq!(failure.bot, bot_user);
+        assert_eq!(failure.height, app.block_info().height);
+    }
+
+    #[test]
+    fn does_apply_failure_policy() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: native("uluna"),
+                },
+                target_asset: native("ukrw"),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: Some(Uint128::new(10_000)),
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: Some(1),
+                failure_policy: FailurePolicy::WidenSlippage {
+                    step: Decimal::percent(1),
+                    max_spread: Decimal::percent(6),
+                },
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        // the router refuses to return less than the minimum receive, so every purchase fails
+        let perform_failing = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchases {
+                    purchases: vec![PurchaseRequest {
+                        user: mock_creator().sender.into_string(),
+                        id: 1,
+                        hops: vec![
+                            SwapOperation::AstroSwap {
+                                offer_asset_info: native("uluna"),
+                                ask_asset_info: native("ujpy"),
+                            },
+                            SwapOperation::AstroSwap {
+                                offer_asset_info: native("ujpy"),
+                                ask_asset_info: native("ukrw"),
+                            },
+                        ],
+                        fee_redeem: vec![],
+                        deadline: None,
+                        accepted_tips: vec![],
+                    }],
+                },
+                &[],
+            )
+            .unwrap()
+        };
+
+        // the maximum spread of the contract should be widened by the step of the policy
+        let res = perform_failing(&mut app);
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.ty == "wasm-failure_limit_reached"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "policy" && a.value == "widen_slippage")));
+
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender)[0].clone();
+        assert_eq!(order.max_spread, Some(Decimal::percent(6)));
+        assert_eq!(order.consecutive_failures, 0);
+        assert!(!order.paused);
+
+        // once the maximum spread of the policy is reached the order should be paused instead
+        let res = perform_failing(&mut app);
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.ty == "wasm-failure_limit_reached"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "policy" && a.value == "pause")));
+
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.max_spread, Some(Decimal::percent(6)));
+        assert!(order.paused);
+    }
+
+    #[test]
+    fn does_ignore_rejected_purchases() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: native("uluna"),
+                },
+                target_asset: native("ukrw"),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: Some(1),
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![Addr::unchecked("allowed_bot")],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform = |app: &mut App, bot: &str| {
+            app.execute_contract(
+                Addr::unchecked(bot),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchases {
+                    purchases: vec![PurchaseRequest {
+                        user: mock_creator().sender.into_string(),
+                        id: 1,
+                        hops: vec![
+                            SwapOperation::AstroSwap {
+                                offer_asset_info: native("uluna"),
+                                ask_asset_info: native("ujpy"),
+                            },
+                            SwapOperation::AstroSwap {
+                                offer_asset_info: native("ujpy"),
+                                ask_asset_info: native("ukrw"),
+                            },
+                        ],
+                        fee_redeem: vec![],
+                        deadline: None,
+                        accepted_tips: vec![],
+                    }],
+                },
+                &[],
+            )
+            .unwrap()
+        };
+        let is_rejected = |res: &AppResponse| {
+            res.events.iter().any(|e| {
+                e.attributes
+                    .iter()
+                    .any(|a| a.key == "action" && a.value == "reject_dca_purchase")
+            })
+        };
+
+        // a bot that is not allowed to perform the purchases of the order cannot fail them
+        assert!(is_rejected(&perform(&mut app, "bot_user")));
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender)[0].clone();
+        assert_eq!(order.consecutive_failures, 0);
+        assert!(!order.paused);
+        assert!(order.total_spent.is_zero());
+
+        // and neither can a purchase that is not due yet
+        assert!(!is_rejected(&perform(&mut app, "allowed_bot")));
+        assert!(is_rejected(&perform(&mut app, "allowed_bot")));
+        let order = read_user_orders(&app, dca_addr, &mock_creator().sender)[0].clone();
+        assert_eq!(order.consecutive_failures, 0);
+        assert!(!order.paused);
+        assert_eq!(order.total_spent, Uint128::new(10_000));
+    }
+}
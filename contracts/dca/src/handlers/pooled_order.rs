@@ -0,0 +1,545 @@
+use astroport::asset::{addr_validate_to_lower, AssetInfo};
+use astroport_dca::dca::OrderEvent;
+use cosmwasm_std::{
+    attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, MessageInfo, Response,
+    StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    refund_order::refund_order,
+    state::{user_dca, PoolShare, POOLS, POOL_SHARES, PURCHASED_BALANCE},
+    stats::record_order_removed,
+};
+
+use super::notifier::notification_msg;
+
+/// ## Description
+/// Adds the native tokens sent by the sender to the pooled DCA order `id` of `user`, minting
+/// shares of the order to the sender for the deposit. Cw20 tokens are added through
+/// [`astroport_dca::dca::Cw20HookMsg::JoinPooledOrder`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the depositor joining the order, containing the native
+/// `initial_asset` of the order.
+///
+/// * `user` - The address of the user the order is stored under.
+///
+/// * `id` - The [`u64`] ID of the pooled order to join.
+pub fn join_pooled_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let order = user_dca()
+        .may_load(deps.storage, (&user, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+
+    let denom = match &order.initial_asset.info {
+        AssetInfo::NativeToken { denom } => denom,
+        AssetInfo::Token { .. } => return Err(ContractError::InvalidTokenDeposit {}),
+    };
+    let amount = info
+        .funds
+        .iter()
+        .find(|coin| &coin.denom == denom)
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    join_pool(deps.storage, &user, id, &info.sender, amount)
+}
+
+/// ## Description
+/// Adds `amount` of the `initial_asset` of the pooled DCA order `id` of `user` deposited by
+/// `depositor` to the order, once the deposit has been received, minting shares in proportion to
+/// the deposit remaining in the order so that earlier depositors keep their share of it.
+///
+/// Returns a [`ContractError`] if the order is not pooled, or the deposit is too small to mint a
+/// share, otherwise returns a [`Response`] with the specified attributes.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `user` - The [`Addr`] of the user the order is stored under.
+///
+/// * `id` - The [`u64`] ID of the pooled order to join.
+///
+/// * `depositor` - The [`Addr`] of the depositor joining the order.
+///
+/// * `amount` - The [`Uint128`] amount of the `initial_asset` deposited.
+pub(crate) fn join_pool(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    id: u64,
+    depositor: &Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut order = user_dca()
+        .may_load(storage, (user, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+    let mut pool = POOLS
+        .may_load(storage, (user, id))?
+        .ok_or(ContractError::NotPooledOrder {})?;
+
+    // the deposit remaining in the order changes while a purchase is being performed
+    if order.executing {
+        return Err(ContractError::PurchaseInProgress {});
+    }
+
+    if amount.is_zero() || order.initial_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let shares = amount.multiply_ratio(pool.total_shares, order.initial_asset.amount);
+    if shares.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    // the purchases owed to the shares already held are settled before any are added
+    let mut share = POOL_SHARES
+        .may_load(storage, (user, id, depositor))?
+        .unwrap_or_default();
+    share.settle(&pool)?;
+    share.shares = share.shares.checked_add(shares)?;
+    pool.total_shares = pool.total_shares.checked_add(shares)?;
+    order.initial_asset.amount = order.initial_asset.amount.checked_add(amount)?;
+
+    POOL_SHARES.save(storage, (user, id, depositor), &share)?;
+    POOLS.save(storage, (user, id), &pool)?;
+    user_dca().save(storage, (user, id), &order)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "join_pooled_order"),
+        attr("user", user),
+        attr("id", id.to_string()),
+        attr("depositor", depositor),
+        attr("amount", amount),
+        attr("shares", shares),
+    ]))
+}
+
+/// ## Description
+/// Sends the `target_asset` purchased for the pooled DCA order `id` of `user` that is owed to the
+/// shares of the sender, which can still be claimed after the order has been fulfilled.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the depositor claiming their share of the purchases.
+///
+/// * `user` - The address of the user the order is stored under.
+///
+/// * `id` - The [`u64`] ID of the pooled order to claim from.
+pub fn claim_pool_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let mut share = load_pool_share(deps.storage, &user, id, &info.sender)?;
+
+    let claim_msg = take_claimable(deps.storage, &user, id, &info.sender, &mut share)?
+        .ok_or(ContractError::NothingToClaim {})?;
+    POOL_SHARES.save(deps.storage, (&user, id, &info.sender), &share)?;
+
+    Ok(Response::new().add_message(claim_msg).add_attributes(vec![
+        attr("action", "claim_pool_share"),
+        attr("user", user),
+        attr("id", id.to_string()),
+        attr("depositor", info.sender),
+    ]))
+}
+
+/// ## Description
+/// Burns the shares of the sender in the pooled DCA order `id` of `user`, sending back the share
+/// of the remaining deposit of the order owed to them along with the purchased `target_asset`
+/// they have not claimed yet. The order is removed once its last shares are burned.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the depositor leaving the order.
+///
+/// * `user` - The address of the user the order is stored under.
+///
+/// * `id` - The [`u64`] ID of the pooled order to leave.
+pub fn leave_pooled_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let mut share = load_pool_share(deps.storage, &user, id, &info.sender)?;
+    let mut pool = POOLS.load(deps.storage, (&user, id))?;
+
+    let mut messages = vec![];
+    let mut notification = None;
+
+    // the remaining deposit of the order is refunded by the share of its shares that is burned,
+    // while a fulfilled order has nothing left to refund
+    let mut refunded = Uint128::zero();
+    if let Some(mut order) = user_dca().may_load(deps.storage, (&user, id))? {
+        if order.executing {
+            return Err(ContractError::PurchaseInProgress {});
+        }
+
+        refunded = order
+            .initial_asset
+            .amount
+            .multiply_ratio(share.shares, pool.total_shares);
+        messages.extend(refund_order(&order, refunded, &info.sender)?);
+
+        order.initial_asset.amount -= refunded;
+        match pool.total_shares == share.shares {
+            true => {
+                user_dca().remove(deps.storage, (&user, id))?;
+                record_order_removed(deps.storage)?;
+                notification = notification_msg(deps.storage, &user, id, OrderEvent::Cancelled {})?;
+            }
+            false => user_dca().save(deps.storage, (&user, id), &order)?,
+        }
+    }
+
+    messages.extend(take_claimable(
+        deps.storage,
+        &user,
+        id,
+        &info.sender,
+        &mut share,
+    )?);
+
+    pool.total_shares -= share.shares;
+    POOLS.save(deps.storage, (&user, id), &pool)?;
+    POOL_SHARES.remove(deps.storage, (&user, id, &info.sender));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(notification)
+        .add_attributes(vec![
+            attr("action", "leave_pooled_order"),
+            attr("user", user),
+            attr("id", id.to_string()),
+            attr("depositor", info.sender),
+            attr("shares", share.shares),
+            attr("refunded", refunded),
+        ]))
+}
+
+/// ## Description
+/// Returns the shares of the pooled DCA order `id` of `user` held by `depositor`, settled against
+/// the purchases performed since they were last settled.
+///
+/// Returns a [`ContractError::NotPooledOrder`] if the order is not pooled, or a
+/// [`ContractError::NoPoolShares`] if the depositor does not hold any shares of it.
+fn load_pool_share(
+    storage: &dyn Storage,
+    user: &Addr,
+    id: u64,
+    depositor: &Addr,
+) -> Result<PoolShare, ContractError> {
+    let pool = POOLS
+        .may_load(storage, (user, id))?
+        .ok_or(ContractError::NotPooledOrder {})?;
+    let mut share = POOL_SHARES
+        .may_load(storage, (user, id, depositor))?
+        .ok_or(ContractError::NoPoolShares {})?;
+    share.settle(&pool)?;
+
+    Ok(share)
+}
+
+/// ## Description
+/// Takes the purchased `target_asset` that `share` can claim out of the purchased balance of the
+/// pooled DCA order `id` of `user`, returning the message sending it to `depositor` if there is
+/// any to claim.
+fn take_claimable(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    id: u64,
+    depositor: &Addr,
+    share: &mut PoolShare,
+) -> StdResult<Option<CosmosMsg>> {
+    let mut balance = match PURCHASED_BALANCE.may_load(storage, (user, id))? {
+        Some(balance) => balance,
+        None => return Ok(None),
+    };
+
+    // the rounding of the purchases owed to each share never owes more than the balance holds
+    let amount = share.claimable.min(balance.amount);
+    share.claimable = Uint128::zero();
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    balance.amount -= amount;
+    PURCHASED_BALANCE.save(storage, (user, id), &balance)?;
+
+    Ok(Some(match balance.info {
+        AssetInfo::NativeToken { denom } => BankMsg::Send {
+            to_address: depositor.to_string(),
+            amount: vec![Coin { amount, denom }],
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: depositor.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    }))
+}
+
+/// ## Description
+/// Adds the `amount` of the `target_asset` received by a DCA purchase of the DCA order `id` of
+/// `user` to the amount purchased for each of its shares, if the order is pooled.
+pub(crate) fn record_pool_purchase(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    id: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut pool = match POOLS.may_load(storage, (user, id))? {
+        Some(pool) if !pool.total_shares.is_zero() => pool,
+        _ => return Ok(()),
+    };
+
+    pool.output_per_share = pool.output_per_share + Decimal::from_ratio(amount, pool.total_shares);
+    POOLS.save(storage, (user, id), &pool)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, PoolShareResponse, QueryMsg,
+        Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator, read_user_orders},
+    };
+
+    #[test]
+    fn does_share_purchases_of_pooled_order() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let depositor = Addr::unchecked("depositor");
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(10_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: true,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        let pool_share = |app: &App, depositor: &Addr| -> PoolShareResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &dca_addr,
+                    &QueryMsg::PoolShare {
+                        user: mock_creator().sender.into_string(),
+                        id: 1,
+                        depositor: depositor.to_string(),
+                    },
+                )
+                .unwrap()
+        };
+
+        // the depositor doubles the deposit of the order, minting as many shares as the creator
+        app.send_tokens(
+            mock_creator().sender,
+            depositor.clone(),
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+        app.execute_contract(
+            depositor.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::JoinPooledOrder {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+        assert_eq!(
+            pool_share(&app, &depositor),
+            PoolShareResponse {
+                shares: Uint128::new(10_000),
+                total_shares: Uint128::new(20_000),
+                deposit: Uint128::new(10_000),
+                claimable: Uint128::zero(),
+            }
+        );
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: uluna,
+                    ask_asset_info: ujpy,
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // each depositor is owed half of the purchase made for the whole pool
+        let received =
+            read_user_orders(&app, dca_addr.clone(), &mock_creator().sender)[0].total_received;
+        assert_eq!(
+            pool_share(&app, &mock_creator().sender).claimable,
+            received / Uint128::new(2)
+        );
+
+        app.execute_contract(
+            depositor.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::ClaimPoolShare {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(&depositor, "ujpy").unwrap().amount,
+            received / Uint128::new(2)
+        );
+
+        // the purchases of the pool cannot be claimed for the creator alone
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::ClaimPurchased { id: 1, user: None },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::PooledOrder {}
+        );
+
+        // and leaving the order refunds half of the deposit remaining in it
+        app.execute_contract(
+            depositor.clone(),
+            dca_addr.clone(),
+            &ExecuteMsg::LeavePooledOrder {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap()
+                .query_balance(&depositor, "uluna")
+                .unwrap()
+                .amount,
+            Uint128::new(5_000)
+        );
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(5_000));
+        assert_eq!(
+            pool_share(&app, &depositor),
+            PoolShareResponse {
+                shares: Uint128::zero(),
+                total_shares: Uint128::new(10_000),
+                deposit: Uint128::zero(),
+                claimable: Uint128::zero(),
+            }
+        );
+
+        let res = app
+            .execute_contract(
+                depositor,
+                dca_addr,
+                &ExecuteMsg::ClaimPoolShare {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NoPoolShares {}
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+
+use crate::{
+    error::ContractError,
+    state::{ARCHIVED_ORDERS, CONFIG},
+};
+
+use super::ownership::get_owner;
+
+/// The default amount of archived orders removed in a call
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of archived orders that can be removed in a call
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Removes the archived DCA orders of any user that were completed more than `retention` seconds
+/// ago, up to `limit` orders at a time so that the pruning fits within the gas limit of a block.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to prune the archived orders.
+///
+/// * `retention` - The [`u64`] amount of seconds that archived orders are kept for after they
+/// were completed.
+///
+/// * `limit` - An optional [`u32`] amount of archived orders to remove.
+pub fn prune_archived_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    retention: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let now = env.block.time.seconds();
+
+    let expired = ARCHIVED_ORDERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, archived)| {
+                archived.completed_at.saturating_add(retention) < now
+            })
+        })
+        .take(limit)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (user, id) in &expired {
+        ARCHIVED_ORDERS.remove(deps.storage, (user, *id));
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "prune_archived_orders"),
+        attr("pruned", expired.len().to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        ArchivedOrder, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, QueryMsg,
+        Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator},
+    };
+
+    #[test]
+    fn does_archive_and_prune_completed_orders() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(10_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                strategy: Strategy::Fixed,
+                first_purchase: None,
+                expires_at: None,
+                max_purchases: None,
+                max_price: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                pull: false,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        // the only purchase of the order completes it
+        let res = app
+            .execute_contract(
+                Addr::unchecked("bot_user"),
+                dca_addr.clone(),
+                &ExecuteMsg::PerformDcaPurchase {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                    hops: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                        ask_asset_info: AssetInfo::NativeToken {
+                            denom: "ujpy".to_string(),
+                        },
+                    }],
+                    fee_redeem: vec![],
+                    deadline: None,
+                    accepted_tips: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.events.iter().any(|e| e.ty == "wasm-order_completed"));
+
+        let query_archived = |app: &App| -> Vec<ArchivedOrder> {
+            app.wrap()
+                .query_wasm_smart(
+                    dca_addr.clone(),
+                    &QueryMsg::ArchivedOrders {
+                        user: mock_creator().sender.into_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+
+        let archived = query_archived(&app);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].purchases, 1);
+        assert_eq!(archived[0].order.total_spent, Uint128::new(10_000));
+        assert_eq!(archived[0].completed_at, app.block_info().time.seconds());
+
+        let prune = |app: &mut App, sender: &str| {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                dca_addr.clone(),
+                &ExecuteMsg::PruneArchivedOrders {
+                    retention: 1_000,
+                    limit: None,
+                },
+                &[],
+            )
+        };
+
+        // only the contract owner can prune archived orders
+        let res = prune(&mut app, "someone").unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        // the order is kept until the retention has passed
+        prune(&mut app, mock_creator().sender.as_str()).unwrap();
+        assert_eq!(query_archived(&app).len(), 1);
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1_001));
+        prune(&mut app, mock_creator().sender.as_str()).unwrap();
+        assert!(query_archived(&app).is_empty());
+    }
+}
@@ -0,0 +1,544 @@
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport_dca::dca::Cw20HookMsg;
+use cosmwasm_std::{from_binary, DepsMut, Env, MessageInfo, Response};
+use cw20::Cw20ReceiveMsg;
+
+use crate::{
+    error::ContractError,
+    state::{user_dca, CONFIG},
+};
+
+use super::{
+    add_bot_tip::deposit_bot_tip,
+    create_dca_order::{store_dca_order, validate_dca_order, CreateDcaOrder},
+    pooled_order::join_pool,
+    top_up_dca_order::increase_dca_order,
+};
+
+/// ## Description
+/// Handles the cw20 tokens sent to the contract through [`cw20::Cw20ExecuteMsg::Send`], executing
+/// the [`Cw20HookMsg`] contained in the message.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the cw20 token contract that received the tokens.
+///
+/// * `cw20_msg` - The [`Cw20ReceiveMsg`] containing the user who sent the tokens, the amount sent
+/// and the [`Cw20HookMsg`] to execute.
+pub fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+
+    // the amount of a token taking a tax on transfers received through a send cannot be measured,
+    // so such tokens can only be spent from an allowance
+    let token = AssetInfo::Token {
+        contract_addr: info.sender.clone(),
+    };
+    if CONFIG
+        .may_load(deps.storage)?
+        .map_or(false, |config| config.is_fee_on_transfer_token(&token))
+    {
+        return Err(ContractError::FeeOnTransferSend {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateDcaOrder {
+            target_asset,
+            interval,
+            schedule,
+            max_delay,
+            jitter,
+            dca_amount,
+            strategy,
+            first_purchase,
+            expires_at,
+            max_purchases,
+            max_failures,
+            failure_policy,
+            swap_tip_bps,
+            tip_escalation,
+            tip_replenish,
+            allowed_bots,
+            allow_low_liquidity,
+            max_price,
+            only_below_price,
+            max_price_impact,
+            stop_price,
+            take_profit_price,
+            direction,
+            distribution,
+            targets,
+            pooled,
+            max_spread,
+            min_receive_per_purchase,
+            route,
+            auto_route,
+            delivery,
+            recipient,
+            purchase_hooks,
+            auto_stake,
+            zap,
+            referrer,
+            owner,
+        } => {
+            let order_info = CreateDcaOrder {
+                initial_asset: Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: info.sender,
+                    },
+                    amount: cw20_msg.amount,
+                },
+                target_asset,
+                interval,
+                schedule,
+                max_delay,
+                jitter,
+                dca_amount,
+                strategy,
+                first_purchase,
+                expires_at,
+                max_purchases,
+                max_failures,
+                failure_policy,
+                swap_tip_bps,
+                tip_escalation,
+                tip_replenish,
+                allowed_bots,
+                allow_low_liquidity,
+                max_price,
+                only_below_price,
+                max_price_impact,
+                stop_price,
+                take_profit_price,
+                direction,
+                distribution,
+                targets,
+                pooled,
+                max_spread,
+                min_receive_per_purchase,
+                route,
+                auto_route,
+                delivery,
+                recipient,
+                purchase_hooks,
+                auto_stake,
+                zap,
+                referrer,
+                owner,
+                pull: false,
+            };
+
+            // the tokens have already been transferred to the contract, so the order is escrowed
+            validate_dca_order(
+                &order_info.initial_asset,
+                &order_info.target_asset,
+                order_info.dca_amount,
+                &order_info.strategy,
+            )?;
+            store_dca_order(deps, &env, &sender, order_info, true)
+        }
+        Cw20HookMsg::TopUpDcaOrder { id } => {
+            let order = user_dca()
+                .may_load(deps.storage, (&sender, id))?
+                .ok_or(ContractError::NonexistentDca {})?;
+
+            // only escrowed orders of the sent token can be topped up with the received tokens
+            match &order.initial_asset.info {
+                AssetInfo::Token { contract_addr }
+                    if order.escrowed && contract_addr == &info.sender =>
+                {
+                    increase_dca_order(deps.storage, &sender, id, cw20_msg.amount)
+                }
+                _ => Err(ContractError::InvalidTokenDeposit {}),
+            }
+        }
+        Cw20HookMsg::JoinPooledOrder { user, id } => {
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            let order = user_dca()
+                .may_load(deps.storage, (&user, id))?
+                .ok_or(ContractError::NonexistentDca {})?;
+
+            // only orders of the sent token can be joined with the received tokens
+            match &order.initial_asset.info {
+                AssetInfo::Token { contract_addr } if contract_addr == &info.sender => {
+                    join_pool(deps.storage, &user, id, &sender, cw20_msg.amount)
+                }
+                _ => Err(ContractError::InvalidTokenDeposit {}),
+            }
+        }
+        Cw20HookMsg::AddBotTip {} => deposit_bot_tip(
+            deps,
+            &sender,
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            },
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Cw20HookMsg, DcaInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection,
+        Strategy,
+    };
+    use cosmwasm_std::{to_binary, Addr, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{
+            app_mock_instantiate, mock_app, mock_creator, read_user_orders, store_cw20_token_code,
+            store_dca_module_code,
+        },
+    };
+
+    fn instantiate() -> (App, Addr, Addr) {
+        let mut app = mock_app();
+
+        let cw20_token_id = store_cw20_token_code(&mut app);
+        let dca_module_id = store_dca_module_code(&mut app);
+
+        let cw20_addr = app
+            .instantiate_contract(
+                cw20_token_id,
+                Addr::unchecked("admin"),
+                &cw20_base::msg::InstantiateMsg {
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: mock_creator().sender.into_string(),
+                        amount: Uint128::new(100_000),
+                    }],
+                    marketing: None,
+                    mint: None,
+                    name: "cw20 token".to_string(),
+                    symbol: "cwT".to_string(),
+                },
+                &[],
+                "mock cw20 token",
+                None,
+            )
+            .unwrap();
+
+        let dca_addr = app_mock_instantiate(
+            &mut app,
+            dca_module_id,
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+        );
+
+        (app, dca_addr, cw20_addr)
+    }
+
+    #[test]
+    fn does_create_escrowed_order() {
+        let (mut app, dca_addr, cw20_addr) = instantiate();
+
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: dca_addr.to_string(),
+                amount: Uint128::new(100_000),
+                msg: to_binary(&Cw20HookMsg::CreateDcaOrder {
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(25_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the contract should hold the deposited tokens
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw20_addr,
+                &Cw20QueryMsg::Balance {
+                    address: dca_addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::new(100_000));
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(
+            orders,
+            vec![DcaInfo {
+                id: 1,
+                initial_asset: Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: cw20_addr.clone(),
+                    },
+                    amount: Uint128::new(100_000),
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                last_purchase: 0,
+                dca_amount: Uint128::new(25_000),
+                escrowed: true,
+                paused: false,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                total_spent: Uint128::zero(),
+                total_received: Uint128::zero(),
+                targets_received: vec![],
+                executing: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                jitter_offset: 0,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                target_value: Uint128::zero(),
+                max_purchases: None,
+                pull: false,
+                last_failure: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                consecutive_failures: 0,
+                replenished_tip: Uint128::zero(),
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+            }]
+        );
+
+        // cancelling the order should return the escrowed tokens
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CancelDcaOrder {
+                id: 1,
+                user: None,
+                withdraw_tips: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw20_addr,
+                &Cw20QueryMsg::Balance {
+                    address: mock_creator().sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::new(100_000));
+        assert!(read_user_orders(&app, dca_addr, &mock_creator().sender).is_empty());
+    }
+
+    #[test]
+    fn does_create_indivisible_escrowed_order() {
+        let (mut app, dca_addr, cw20_addr) = instantiate();
+
+        app.execute_contract(
+            mock_creator().sender,
+            cw20_addr,
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: dca_addr.to_string(),
+                amount: Uint128::new(100_000),
+                msg: to_binary(&Cw20HookMsg::CreateDcaOrder {
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    dca_amount: Uint128::new(999),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let orders = read_user_orders(&app, dca_addr, &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(100_000));
+        assert_eq!(orders[0].dca_amount, Uint128::new(999));
+    }
+
+    #[test]
+    fn does_top_up_escrowed_order() {
+        let (mut app, dca_addr, cw20_addr) = instantiate();
+
+        let send = |app: &mut App, amount: u128, msg: Cw20HookMsg| {
+            app.execute_contract(
+                mock_creator().sender,
+                cw20_addr.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: dca_addr.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&msg).unwrap(),
+                },
+                &[],
+            )
+        };
+
+        send(
+            &mut app,
+            50_000,
+            Cw20HookMsg::CreateDcaOrder {
+                target_asset: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        send(&mut app, 25_000, Cw20HookMsg::TopUpDcaOrder { id: 1 }).unwrap();
+
+        let orders = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender);
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(75_000));
+
+        // errors on a nonexistent order
+        let res = send(&mut app, 25_000, Cw20HookMsg::TopUpDcaOrder { id: 2 }).unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NonexistentDca {}
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::Cw20HookMsg;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+use crate::error::ContractError;
+
+use super::create_dca_order::{store_dca_order, validate_dca_order, CreateDcaOrder};
+
+/// ## Description
+/// Handles the native tokens transferred to the contract over IBC with an ibc-hooks memo, which
+/// executes the [`Cw20HookMsg`] contained in the memo along with the tokens received.
+///
+/// The sender of the message is an intermediary address that ibc-hooks derives from the channel
+/// and the sender on the other chain, which nobody controls, so only the
+/// [`Cw20HookMsg::CreateDcaOrder`] hook of an order for an `owner` on this chain can be executed,
+/// creating the order from the single coin received. Any refunds of the order are sent to the
+/// owner rather than the sender.
+///
+/// Returns a [`ContractError`] as a failure, in which case the tokens are refunded over IBC,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the intermediary address of the IBC sender, containing the
+/// tokens received.
+///
+/// * `msg` - The [`Cw20HookMsg`] to execute.
+pub fn receive_ibc(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20HookMsg,
+) -> Result<Response, ContractError> {
+    // an ics-20 transfer sends a single coin
+    let initial_asset = match info.funds.as_slice() {
+        [coin] if !coin.amount.is_zero() => Asset {
+            info: AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            },
+            amount: coin.amount,
+        },
+        _ => return Err(ContractError::InvalidIbcFunds {}),
+    };
+
+    match msg {
+        Cw20HookMsg::CreateDcaOrder {
+            target_asset,
+            interval,
+            schedule,
+            max_delay,
+            jitter,
+            dca_amount,
+            strategy,
+            first_purchase,
+            expires_at,
+            max_purchases,
+            max_failures,
+            failure_policy,
+            swap_tip_bps,
+            tip_escalation,
+            tip_replenish,
+            allowed_bots,
+            allow_low_liquidity,
+            max_price,
+            only_below_price,
+            max_price_impact,
+            stop_price,
+            take_profit_price,
+            direction,
+            distribution,
+            targets,
+            pooled,
+            max_spread,
+            min_receive_per_purchase,
+            route,
+            auto_route,
+            delivery,
+            recipient,
+            purchase_hooks,
+            auto_stake,
+            zap,
+            referrer,
+            owner: Some(owner),
+        } => {
+            let order_info = CreateDcaOrder {
+                initial_asset,
+                target_asset,
+                interval,
+                schedule,
+                max_delay,
+                jitter,
+                dca_amount,
+                strategy,
+                first_purchase,
+                expires_at,
+                max_purchases,
+                max_failures,
+                failure_policy,
+                swap_tip_bps,
+                tip_escalation,
+                tip_replenish,
+                allowed_bots,
+                allow_low_liquidity,
+                max_price,
+                only_below_price,
+                max_price_impact,
+                stop_price,
+                take_profit_price,
+                direction,
+                distribution,
+                targets,
+                pooled,
+                max_spread,
+                min_receive_per_purchase,
+                route,
+                auto_route,
+                delivery,
+                recipient,
+                purchase_hooks,
+                auto_stake,
+                zap,
+                referrer,
+                owner: Some(owner),
+                pull: false,
+            };
+
+            validate_dca_order(
+                &order_info.initial_asset,
+                &order_info.target_asset,
+                order_info.dca_amount,
+                &order_info.strategy,
+            )?;
+            store_dca_order(deps, &env, &info.sender, order_info, false)
+        }
+        // the orders and tips of the intermediary address could never be managed or withdrawn
+        _ => Err(ContractError::InvalidIbcHook {}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Cw20HookMsg, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        coin,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::execute, error::ContractError, state::get_user_orders};
+
+    fn hook_msg(owner: Option<String>) -> Cw20HookMsg {
+        Cw20HookMsg::CreateDcaOrder {
+            target_asset: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            dca_amount: Uint128::new(25_000),
+            first_purchase: None,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            owner,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            max_purchases: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+            allow_low_liquidity: false,
+        }
+    }
+
+    #[test]
+    fn does_create_order_from_ibc_transfer() {
+        let mut deps = mock_dependencies();
+        let denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("intermediary", &[coin(100_000, denom)]),
+            ExecuteMsg::ReceiveIbc(hook_msg(Some("owner".to_string()))),
+        )
+        .unwrap();
+
+        // the order spends the coin received and belongs to the owner rather than the sender
+        let orders = get_user_orders(&deps.storage, &Addr::unchecked("owner")).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(
+            orders[0].initial_asset,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                },
+                amount: Uint128::new(100_000),
+            }
+        );
+        assert!(
+            get_user_orders(&deps.storage, &Addr::unchecked("intermediary"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn cannot_create_order_from_ibc_transfer_without_owner() {
+        let mut deps = mock_dependencies();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("intermediary", &[coin(100_000, "ibc/atom")]),
+            ExecuteMsg::ReceiveIbc(hook_msg(None)),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidIbcHook {});
+
+        // nor from anything but a single coin
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(
+                "intermediary",
+                &[coin(100_000, "ibc/atom"), coin(100_000, "ibc/osmo")],
+            ),
+            ExecuteMsg::ReceiveIbc(hook_msg(Some("owner".to_string()))),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidIbcFunds {});
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("intermediary", &[coin(100_000, "ibc/atom")]),
+            ExecuteMsg::ReceiveIbc(Cw20HookMsg::AddBotTip {}),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidIbcHook {});
+    }
+}
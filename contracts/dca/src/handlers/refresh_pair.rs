@@ -0,0 +1,108 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{attr, DepsMut, Response};
+
+use crate::{
+    error::ContractError,
+    query_pair::{pair_key, query_factory_pair},
+    state::{CONFIG, PAIRS},
+};
+
+/// ## Description
+/// Resolves the Astroport pair of `asset_infos` from the factory again, replacing its cached
+/// address and pool type, or removing it if the factory no longer has a pair of the assets. This
+/// can be called by anyone, so that a pair replaced in the factory is not swapped through by DCA
+/// purchases after it was cached.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `asset_infos` - The [`AssetInfo`] of both assets of the pair to refresh.
+pub fn refresh_pair(deps: DepsMut, asset_infos: [AssetInfo; 2]) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let key = pair_key(&asset_infos);
+    let pair = match query_factory_pair(&deps.querier, &config.factory_addr, &asset_infos) {
+        Ok(pair) => {
+            PAIRS.save(deps.storage, key, &pair)?;
+            pair.contract_addr.into_string()
+        }
+        Err(_) => {
+            PAIRS.remove(deps.storage, key);
+            "none".to_string()
+        }
+    };
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "refresh_pair"),
+        attr(
+            "asset_infos",
+            format!("{}-{}", asset_infos[0], asset_infos[1]),
+        ),
+        attr("pair", pair),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{AssetInfo, PairInfo},
+        factory::QueryMsg as FactoryQueryMsg,
+    };
+    use astroport_dca::dca::ExecuteMsg;
+    use cosmwasm_std::{Addr, Attribute};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        query_pair::pair_key,
+        state::{CONFIG, PAIRS},
+        tests::{mock_astroport_instantiate, read_item, read_map},
+    };
+
+    #[test]
+    fn does_refresh_pair() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let native = |denom: &str| AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+
+        // anyone can refresh a pair
+        let refresh = |app: &mut App, asset_infos: [AssetInfo; 2]| -> Vec<Attribute> {
+            let res = app
+                .execute_contract(
+                    Addr::unchecked("someone"),
+                    dca_addr.clone(),
+                    &ExecuteMsg::RefreshPair { asset_infos },
+                    &[],
+                )
+                .unwrap();
+
+            res.events[1].attributes.clone()
+        };
+
+        let asset_infos = [native("uluna"), native("ujpy")];
+        let attrs = refresh(&mut app, asset_infos.clone());
+
+        let pair: PairInfo = app
+            .wrap()
+            .query_wasm_smart(
+                read_item(&app, &dca_addr, CONFIG).factory_addr,
+                &FactoryQueryMsg::Pair {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap();
+        assert!(attrs.contains(&Attribute::new("pair", pair.contract_addr.as_str())));
+
+        // the pair is cached under the same key for both orders of its assets
+        let key = pair_key(&[native("ujpy"), native("uluna")]);
+        assert_eq!(key, pair_key(&asset_infos));
+        assert_eq!(read_map(&app, dca_addr.clone(), key, PAIRS), pair);
+
+        // a pair missing from the factory is not cached
+        let attrs = refresh(&mut app, [native("uluna"), native("uusd")]);
+        assert!(attrs.contains(&Attribute::new("pair", "none")));
+    }
+}
@@ -0,0 +1,146 @@
+use astroport_dca::dca::BotStats;
+use cosmwasm_std::{attr, Coin, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use crate::{
+    error::ContractError,
+    state::{BOTS, CONFIG},
+};
+
+/// ## Description
+/// Registers the sender as a bot performing DCA purchases, from which point the purchases it
+/// performs, the purchases it fails and its delay in performing due purchases are tracked so that
+/// users can tell reliable bots apart.
+///
+/// If the contract owner has set a bot bond, at least the bond must be sent along, which is held
+/// by the contract until the bot withdraws it and can be slashed in the meantime.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the bot who wants to register, along with its bond.
+pub fn register_bot(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    if BOTS.has(deps.storage, &info.sender) {
+        return Err(ContractError::BotAlreadyRegistered {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the whole amount of the bond denom that was sent is held as the bond of the bot
+    let bond = match config.bot_bond {
+        Some(bot_bond) => {
+            let amount = info
+                .funds
+                .iter()
+                .find(|coin| coin.denom == bot_bond.bond.denom)
+                .map_or(Uint128::zero(), |coin| coin.amount);
+
+            if amount < bot_bond.bond.amount {
+                return Err(ContractError::InsufficientBotBond {
+                    bond: format!("{}{}", bot_bond.bond.amount, bot_bond.bond.denom),
+                });
+            }
+
+            Some(Coin {
+                denom: bot_bond.bond.denom,
+                amount,
+            })
+        }
+        None => None,
+    };
+
+    BOTS.save(
+        deps.storage,
+        &info.sender,
+        &BotStats {
+            registered_at: env.block.time.seconds(),
+            purchases: 0,
+            failures: 0,
+            total_delay: 0,
+            bond: bond.clone(),
+            unbonding_at: None,
+            slashes: 0,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_bot"),
+        attr("bot", info.sender),
+        attr(
+            "bond",
+            bond.map_or_else(String::new, |bond| bond.amount.to_string()),
+        ),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport_dca::dca::{BotInfo, BotStats, ExecuteMsg, QueryMsg};
+    use cosmwasm_std::{from_binary, testing::mock_info, Addr};
+
+    use crate::{
+        contract::{execute, query},
+        error::ContractError,
+        tests::mock_instantiate,
+    };
+
+    #[test]
+    fn does_register_bot() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot_user", &[]),
+            ExecuteMsg::RegisterBot {},
+        )
+        .unwrap();
+
+        let res: Vec<BotInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Bots {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![BotInfo {
+                bot: Addr::unchecked("bot_user"),
+                stats: BotStats {
+                    registered_at: env.block.time.seconds(),
+                    purchases: 0,
+                    failures: 0,
+                    total_delay: 0,
+                    bond: None,
+                    unbonding_at: None,
+                    slashes: 0,
+                },
+                average_delay: 0,
+            }]
+        );
+
+        // a bot cannot register twice, which would reset its stats
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("bot_user", &[]),
+            ExecuteMsg::RegisterBot {},
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::BotAlreadyRegistered {});
+    }
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::user_dca};
+
+use super::member_group::get_order_owner_or_member;
+
+/// ## Description
+/// Resumes a paused DCA order of a user, so that DCA purchases can be performed for it again.
+///
+/// The schedule of the order is kept, so a purchase can be performed immediately if the interval
+/// has passed while the order was paused.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to resume their order.
+///
+/// * `id` - The [`u64`] ID of the order to resume.
+///
+/// * `user` - The address of the user who owns the order, if the sender is an operator or a
+/// member of the group of the owner resuming it on their behalf.
+pub fn resume_dca_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    user: Option<String>,
+) -> Result<Response, ContractError> {
+    let (owner, _) = get_order_owner_or_member(deps.as_ref(), &info.sender, user, id)?;
+
+    user_dca().update(deps.storage, (&owner, id), |order| {
+        let mut order = order.ok_or(ContractError::NonexistentDca {})?;
+
+        if !order.paused {
+            return Err(ContractError::OrderNotPaused {});
+        }
+
+        order.paused = false;
+
+        Ok(order)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "resume_dca_order"),
+        attr("id", id.to_string()),
+    ]))
+}
@@ -0,0 +1,219 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{attr, Addr, Deps, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::OPERATORS};
+
+use super::nft::get_order_holder;
+
+/// ## Description
+/// Returns the user the DCA order `id` managed by `sender` is stored under, which is `user` if
+/// specified, otherwise `sender` itself, along with the owner of the order. The owner is the
+/// holder of the NFT of the order if one was minted for it, otherwise the user.
+///
+/// Returns a [`ContractError::Unauthorized`] if `sender` is neither the owner of the order nor an
+/// operator of the owner.
+pub(crate) fn get_order_owner(
+    deps: Deps,
+    sender: &Addr,
+    user: Option<String>,
+    id: u64,
+) -> Result<(Addr, Addr), ContractError> {
+    let user = match user {
+        Some(user) => addr_validate_to_lower(deps.api, &user)?,
+        None => sender.clone(),
+    };
+
+    let owner = get_order_holder(deps, &user, id)?.unwrap_or_else(|| user.clone());
+    if &owner != sender && !OPERATORS.has(deps.storage, (&owner, sender)) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok((user, owner))
+}
+
+/// ## Description
+/// Allows or disallows `operator` to modify, pause, resume and cancel the DCA orders of the sender
+/// on their behalf. Operators can never withdraw funds, and any funds refunded by their changes
+/// are sent to the sender.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the user who wants to set an operator of their orders.
+///
+/// * `operator` - The address of the operator.
+///
+/// * `allowed` - Whether the operator is allowed to manage the orders of the sender.
+pub fn set_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+
+    match allowed {
+        true => OPERATORS.save(deps.storage, (&info.sender, &operator), &true)?,
+        false => OPERATORS.remove(deps.storage, (&info.sender, &operator)),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_operator"),
+        attr("user", info.sender),
+        attr("operator", operator),
+        attr("allowed", allowed.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        BankMsg, CosmosMsg, Uint128,
+    };
+
+    use crate::{contract::execute, error::ContractError, state::get_user_orders};
+
+    #[test]
+    fn does_allow_operator_to_manage_orders() {
+        let mut deps = mock_dependencies();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        let set_operator = |allowed| ExecuteMsg::SetOperator {
+            operator: "operator".to_string(),
+            allowed,
+        };
+        let pause = ExecuteMsg::PauseDcaOrder {
+            id: 1,
+            user: Some("creator".to_string()),
+        };
+        let cancel = ExecuteMsg::CancelDcaOrder {
+            id: 1,
+            user: Some("creator".to_string()),
+            withdraw_tips: false,
+        };
+
+        // the operator cannot manage the orders before being allowed to
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("operator", &[]),
+            pause.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            set_operator(true),
+        )
+        .unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info("operator", &[]), pause).unwrap();
+        let orders = get_user_orders(&deps.storage, &mock_info("creator", &[]).sender).unwrap();
+        assert!(orders[0].paused);
+
+        // once disallowed, the operator can no longer manage the orders
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            set_operator(false),
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("operator", &[]),
+            cancel.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Unauthorized {});
+
+        // the funds of an order cancelled by an operator are refunded to the user
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            set_operator(true),
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("operator", &[]),
+            cancel,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "creator".to_string(),
+                amount: coins(100_000, "uluna"),
+            })
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use astroport::asset::{addr_validate_to_lower, AssetInfo};
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{
+    error::ContractError,
+    state::{CONFIG, ORACLES},
+};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Sets the price oracle guarding the DCA purchases spending `initial_asset` to purchase
+/// `target_asset`, or removes it if `oracle_addr` is [`None`]. The oracle is only consulted once
+/// a maximum oracle deviation has been set in the contract configuration.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to set the oracle.
+///
+/// * `initial_asset` - The [`AssetInfo`] spent by the DCA orders guarded by the oracle.
+///
+/// * `target_asset` - The [`AssetInfo`] purchased by the DCA orders guarded by the oracle.
+///
+/// * `oracle_addr` - The address of the oracle pricing `initial_asset` in `target_asset`, or
+/// [`None`] to remove the oracle of the pair.
+pub fn set_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    initial_asset: AssetInfo,
+    target_asset: AssetInfo,
+    oracle_addr: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = (initial_asset.to_string(), target_asset.to_string());
+    let oracle = match oracle_addr {
+        Some(oracle_addr) => {
+            let oracle_addr = addr_validate_to_lower(deps.api, &oracle_addr)?;
+            ORACLES.save(deps.storage, key, &oracle_addr)?;
+            oracle_addr.into_string()
+        }
+        None => {
+            ORACLES.remove(deps.storage, key);
+            "none".to_string()
+        }
+    };
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_oracle"),
+        attr("initial_asset", initial_asset.to_string()),
+        attr("target_asset", target_asset.to_string()),
+        attr("oracle", oracle),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::{QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_item, store_oracle_code,
+            MockOracleExecuteMsg, MockOracleInstantiateMsg,
+        },
+    };
+
+    #[test]
+    fn does_guard_purchase_with_oracle() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let hops = vec![SwapOperation::AstroSwap {
+            offer_asset_info: uluna.clone(),
+            ask_asset_info: ujpy.clone(),
+        }];
+
+        // the oracle prices the purchase twice as high as the pool does
+        let simulation: SimulateSwapOperationsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                read_item(&app, &dca_addr, CONFIG).router_addr,
+                &RouterQueryMsg::SimulateSwapOperations {
+                    offer_amount: Uint128::new(10_000),
+                    operations: hops.clone(),
+                },
+            )
+            .unwrap();
+        let pool_price = Decimal::from_ratio(simulation.amount, 10_000u128);
+
+        let oracle_id = store_oracle_code(&mut app);
+        let oracle_addr = app
+            .instantiate_contract(
+                oracle_id,
+                Addr::unchecked("admin"),
+                &MockOracleInstantiateMsg {
+                    price: pool_price + pool_price,
+                },
+                &[],
+                "mock oracle",
+                None,
+            )
+            .unwrap();
+
+        let set_oracle = ExecuteMsg::SetOracle {
+            initial_asset: uluna.clone(),
+            target_asset: ujpy.clone(),
+            oracle_addr: Some(oracle_addr.to_string()),
+        };
+
+        // only the contract owner can set an oracle
+        let res = app
+            .execute_contract(
+                Addr::unchecked("someone"),
+                dca_addr.clone(),
+                &set_oracle,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        app.execute_contract(mock_creator().sender, dca_addr.clone(), &set_oracle, &[])
+            .unwrap();
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                max_hops: None,
+                whitelisted_tokens: None,
+                whitelisted_fee_assets: None,
+                max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: Some(Decimal::percent(5)),
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: uluna,
+                },
+                target_asset: ujpy,
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let perform = ExecuteMsg::PerformDcaPurchase {
+            user: mock_creator().sender.into_string(),
+            id: 1,
+            hops,
+            fee_redeem: vec![],
+            deadline: None,
+            accepted_tips: vec![],
+        };
+
+        // the pool returns half of what the oracle expects
+        let res = app
+            .execute_contract(Addr::unchecked("bot_user"), dca_addr.clone(), &perform, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::OracleDeviation {
+                deviation: Decimal::percent(50),
+                max_deviation: Decimal::percent(5),
+            }
+        );
+
+        // the purchase goes through once the oracle agrees with the pool
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            oracle_addr,
+            &MockOracleExecuteMsg::SetPrice { price: pool_price },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(Addr::unchecked("bot_user"), dca_addr, &perform, &[])
+            .unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{attr, DepsMut, Response};
+
+use crate::{
+    error::ContractError,
+    state::{CONFIG, OWNERSHIP_PROPOSAL},
+};
+
+/// ## Description
+/// Pauses or resumes the creation of DCA orders and DCA purchases on behalf of the chain.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `paused` - Whether the contract should be paused.
+pub fn sudo_set_paused(deps: DepsMut, paused: bool) -> Result<Response, ContractError> {
+    CONFIG.update::<_, ContractError>(deps.storage, |mut config| {
+        config.paused = paused;
+
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "sudo_set_paused"),
+        attr("paused", paused.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Transfers the ownership of the contract to `owner` on behalf of the chain, removing any
+/// pending proposal of a new owner.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `owner` - The address of the new owner of the contract.
+pub fn sudo_update_owner(deps: DepsMut, owner: String) -> Result<Response, ContractError> {
+    let owner = addr_validate_to_lower(deps.api, &owner)?;
+
+    CONFIG.update::<_, ContractError>(deps.storage, |mut config| {
+        config.owner = Some(owner.clone());
+
+        Ok(config)
+    })?;
+    OWNERSHIP_PROPOSAL.remove(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "sudo_update_owner"),
+        attr("owner", owner),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::common::OwnershipProposal;
+    use astroport_dca::dca::SudoMsg;
+    use cosmwasm_std::{testing::mock_env, Addr};
+
+    use crate::{
+        contract::sudo,
+        state::{CONFIG, OWNERSHIP_PROPOSAL},
+        tests::mock_instantiate,
+    };
+
+    #[test]
+    fn does_pause_and_resume() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        sudo(deps.as_mut(), env.clone(), SudoMsg::Pause {}).unwrap();
+        assert!(CONFIG.load(&deps.storage).unwrap().paused);
+
+        sudo(deps.as_mut(), env, SudoMsg::Resume {}).unwrap();
+        assert!(!CONFIG.load(&deps.storage).unwrap().paused);
+    }
+
+    #[test]
+    fn does_update_owner() {
+        let (mut deps, _) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+
+        OWNERSHIP_PROPOSAL
+            .save(
+                deps.as_mut().storage,
+                &OwnershipProposal {
+                    owner: Addr::unchecked("proposed"),
+                    ttl: 100,
+                },
+            )
+            .unwrap();
+
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::UpdateOwner {
+                owner: "governance".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().owner,
+            Some(Addr::unchecked("governance"))
+        );
+        assert_eq!(OWNERSHIP_PROPOSAL.may_load(&deps.storage).unwrap(), None);
+    }
+}
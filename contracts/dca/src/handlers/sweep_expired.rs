@@ -0,0 +1,417 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::OrderEvent;
+use cosmwasm_std::{attr, DepsMut, Env, Response};
+
+use crate::{
+    error::ContractError,
+    refund_order::refund_order,
+    state::{user_dca, CONFIG},
+    stats::record_order_removed,
+};
+
+use super::{nft::get_order_holder, notifier::notification_msg};
+
+/// ## Description
+/// Removes an expired DCA order of a user, or an order spending or purchasing an asset that has
+/// been blacklisted by the contract owner, refunding the remaining funds held by the contract for
+/// the `initial_asset` back to the user.
+///
+/// This can be called by anyone, so that abandoned orders do not remain in the contract and the
+/// funds of orders of a blacklisted asset can be recovered. Pooled orders cannot be swept, as their
+/// deposit belongs to each of their depositors, who recover it by leaving the order.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `user` - The address of the user as a [`String`] who owns the expired order.
+///
+/// * `id` - The [`u64`] ID of the expired order.
+pub fn sweep_expired(
+    deps: DepsMut,
+    env: Env,
+    user: String,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+
+    let order = user_dca()
+        .may_load(deps.storage, (&user_address, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+
+    // the deposit of a pooled order is refunded to each depositor as they leave it
+    if order.pooled {
+        return Err(ContractError::PooledOrder {});
+    }
+
+    let expired = order
+        .expires_at
+        .as_ref()
+        .map_or(false, |expires_at| expires_at.is_expired(&env.block));
+    let blacklisted = CONFIG.may_load(deps.storage)?.map_or(false, |config| {
+        config.is_blacklisted_asset(&order.initial_asset.info)
+            || config.is_blacklisted_asset(&order.target_asset)
+            || order
+                .targets
+                .iter()
+                .any(|target| config.is_blacklisted_asset(&target.asset))
+    });
+
+    if !expired && !blacklisted {
+        return Err(ContractError::OrderNotExpired {});
+    }
+
+    // the funds are refunded to the holder of the NFT of the order if one was minted for it
+    let owner =
+        get_order_holder(deps.as_ref(), &user_address, id)?.unwrap_or_else(|| user_address.clone());
+    let refund = refund_order(&order, order.initial_asset.amount, &owner)?;
+
+    user_dca().remove(deps.storage, (&user_address, id))?;
+    record_order_removed(deps.storage)?;
+    let notification = notification_msg(deps.storage, &user_address, id, OrderEvent::Cancelled {})?;
+
+    Ok(Response::new()
+        .add_messages(refund)
+        .add_submessages(notification)
+        .add_attributes(vec![
+            attr("action", "sweep_expired"),
+            attr("user", user_address),
+            attr("id", id.to_string()),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+        TargetWeight,
+    };
+    use cosmwasm_std::{
+        attr, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, BankMsg, CosmosMsg, Decimal, Response, StdResult, Uint128,
+    };
+    use cw20::Expiration;
+
+    use crate::{
+        contract::execute,
+        error::ContractError,
+        state::{get_user_orders, user_dca, Pool, PoolShare, CONFIG, POOLS, POOL_SHARES},
+        tests::{mock_creator, mock_instantiate},
+    };
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn order(targets: Vec<TargetWeight>) -> DcaInfo {
+        DcaInfo {
+            id: 1,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: native("uluna"),
+            },
+            target_asset: native("ukrw"),
+            interval: Duration::Time(1_000),
+            last_purchase: 0,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets,
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        }
+    }
+
+    #[test]
+    fn does_sweep_expired_order() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: Some(Expiration::AtTime(env.block.time.plus_seconds(10_000))),
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+
+        let sweep = ExecuteMsg::SweepExpired {
+            user: mock_creator().sender.into_string(),
+            id: 1,
+        };
+
+        // can not sweep before the order has expired
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot_user", &[]),
+            sweep.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::OrderNotExpired {});
+
+        env.block.time = env.block.time.plus_seconds(10_000);
+
+        let res = execute(deps.as_mut(), env, mock_info("bot_user", &[]), sweep).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attributes(vec![
+                    attr("action", "sweep_expired"),
+                    attr("user", "creator"),
+                    attr("id", "1"),
+                ])
+                .add_message(BankMsg::Send {
+                    to_address: mock_creator().sender.into_string(),
+                    amount: coins(100_000, "uluna")
+                })
+        );
+
+        assert!(get_user_orders(&deps.storage, &mock_creator().sender)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn does_sweep_order_of_blacklisted_target() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+        let user = Addr::unchecked("user");
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&user, 1),
+                &order(vec![
+                    TargetWeight {
+                        asset: native("ukrw"),
+                        weight: Decimal::percent(50),
+                    },
+                    TargetWeight {
+                        asset: native("ujpy"),
+                        weight: Decimal::percent(50),
+                    },
+                ]),
+            )
+            .unwrap();
+        let sweep = ExecuteMsg::SweepExpired {
+            user: user.to_string(),
+            id: 1,
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot_user", &[]),
+            sweep.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::OrderNotExpired {});
+
+        // an order purchasing a blacklisted asset among its targets can be swept
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.blacklisted_assets = vec![native("ujpy")];
+                Ok(config)
+            })
+            .unwrap();
+        let res = execute(deps.as_mut(), env, mock_info("bot_user", &[]), sweep).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user".to_string(),
+                amount: coins(30_000, "uluna"),
+            })
+        );
+        assert!(get_user_orders(&deps.storage, &user).unwrap().is_empty());
+    }
+
+    #[test]
+    fn does_not_sweep_pooled_order() {
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+        let user = Addr::unchecked("user");
+        let mut pooled_order = order(vec![]);
+        pooled_order.pooled = true;
+        pooled_order.delivery = Delivery::Escrow;
+        user_dca()
+            .save(&mut deps.storage, (&user, 1), &pooled_order)
+            .unwrap();
+        POOLS
+            .save(
+                &mut deps.storage,
+                (&user, 1),
+                &Pool {
+                    total_shares: Uint128::new(30_000),
+                    output_per_share: Decimal::zero(),
+                },
+            )
+            .unwrap();
+        POOL_SHARES
+            .save(
+                &mut deps.storage,
+                (&user, 1, &user),
+                &PoolShare {
+                    shares: Uint128::new(30_000),
+                    ..PoolShare::default()
+                },
+            )
+            .unwrap();
+
+        // a second depositor joins the pool before its target asset is blacklisted
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("depositor", &coins(10_000, "uluna")),
+            ExecuteMsg::JoinPooledOrder {
+                user: user.to_string(),
+                id: 1,
+            },
+        )
+        .unwrap();
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.blacklisted_assets = vec![native("ukrw")];
+                Ok(config)
+            })
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot_user", &[]),
+            ExecuteMsg::SweepExpired {
+                user: user.to_string(),
+                id: 1,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::PooledOrder {});
+
+        // the depositor recovers their deposit by leaving the order instead
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("depositor", &[]),
+            ExecuteMsg::LeavePooledOrder {
+                user: user.to_string(),
+                id: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "depositor".to_string(),
+                amount: coins(10_000, "uluna"),
+            })
+        );
+        assert_eq!(
+            user_dca()
+                .load(&deps.storage, (&user, 1))
+                .unwrap()
+                .initial_asset
+                .amount,
+            Uint128::new(30_000)
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, StdResult};
+
+use crate::{error::ContractError, reconciliation::reconcile, state::CONFIG};
+
+use super::{ownership::get_owner, zap::transfer_msg};
+
+/// ## Description
+/// Sends the surplus of each of `assets` held by the contract over the liabilities it tracks for
+/// the asset to the fee collector, such as the dust left by rounding, refunds that were not
+/// tracked or tokens taking a tax on transfers. The surplus is reconciled again when sweeping, so
+/// that the funds owed to users and bots are never swept.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to sweep the surplus.
+///
+/// * `assets` - The [`AssetInfo`]s to sweep the surplus of.
+pub fn sweep_surplus(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<AssetInfo>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let fee_collector = config
+        .fee_collector
+        .ok_or(ContractError::FeeCollectorNotSet {})?;
+
+    let swept = reconcile(deps.as_ref(), &env, Some(assets))?
+        .into_iter()
+        .filter(|reconciliation| !reconciliation.surplus.is_zero())
+        .map(|reconciliation| Asset {
+            info: reconciliation.info,
+            amount: reconciliation.surplus,
+        })
+        .collect::<Vec<_>>();
+
+    if swept.is_empty() {
+        return Err(ContractError::NoSurplus {});
+    }
+
+    let messages = swept
+        .iter()
+        .map(|asset| transfer_msg(&asset.info, asset.amount, &fee_collector))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "sweep_surplus"),
+        attr("fee_collector", fee_collector),
+        attr(
+            "swept",
+            swept
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::ExecuteMsg;
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_info, MOCK_CONTRACT_ADDR},
+        Addr, BankMsg, CosmosMsg, StdResult, Uint128,
+    };
+
+    use crate::{
+        contract::execute,
+        error::ContractError,
+        state::{UserConfig, CONFIG, PURCHASED_BALANCE, USER_CONFIG},
+        tests::{mock_creator, mock_instantiate},
+    };
+
+    #[test]
+    fn does_sweep_surplus() {
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![],
+            vec![],
+        );
+        let sweep = ExecuteMsg::SweepSurplus {
+            assets: vec![uluna.clone(), uluna.clone()],
+        };
+
+        // the owner is set rather than queried from the factory
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.owner = Some(mock_creator().sender);
+                Ok(config)
+            })
+            .unwrap();
+
+        // the contract holds a tip balance and a purchase escrowed for the user, along with dust
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &Addr::unchecked("user"),
+                &UserConfig {
+                    tip_balance: vec![Asset {
+                        amount: Uint128::new(15_000),
+                        info: uluna.clone(),
+                    }],
+                    ..UserConfig::default()
+                },
+            )
+            .unwrap();
+        PURCHASED_BALANCE
+            .save(
+                &mut deps.storage,
+                (&Addr::unchecked("user"), 1),
+                &Asset {
+                    amount: Uint128::new(1_000),
+                    info: uluna.clone(),
+                },
+            )
+            .unwrap();
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(16_123, "uluna"));
+
+        // only the owner can sweep the surplus
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            sweep.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Unauthorized {});
+
+        // to a fee collector
+        let res = execute(deps.as_mut(), env.clone(), mock_creator(), sweep.clone()).unwrap_err();
+        assert_eq!(res, ContractError::FeeCollectorNotSet {});
+
+        CONFIG
+            .update(&mut deps.storage, |mut config| -> StdResult<_> {
+                config.fee_collector = Some(Addr::unchecked("collector"));
+                Ok(config)
+            })
+            .unwrap();
+
+        // the surplus of an asset listed twice is swept once
+        let res = execute(deps.as_mut(), env.clone(), mock_creator(), sweep.clone()).unwrap();
+        assert_eq!(
+            res.messages
+                .into_iter()
+                .map(|sub_msg| sub_msg.msg)
+                .collect::<Vec<_>>(),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "collector".to_string(),
+                amount: coins(123, "uluna"),
+            })]
+        );
+
+        // leaving nothing over the liabilities to sweep
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(16_000, "uluna"));
+        let res = execute(deps.as_mut(), env, mock_creator(), sweep).unwrap_err();
+        assert_eq!(res, ContractError::NoSurplus {});
+    }
+}
@@ -0,0 +1,290 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::Strategy;
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, Storage, Uint128};
+
+use crate::{
+    error::ContractError,
+    get_token_allowance::get_token_allowance,
+    state::{get_reserved_allowance, user_dca},
+};
+
+use super::create_dca_order::validate_tranches;
+
+/// ## Description
+/// Adds `amount` of the `initial_asset` to an existing DCA order of a user, extending the amount
+/// of DCA purchases that will be performed without changing the schedule of the order.
+///
+/// If the `initial_asset` is a native token, the user needs to send `amount` in the message. If it
+/// is a cw20 token spent from the users allowance, the allowance needs to have been increased.
+/// Escrowed cw20 orders are topped up by sending the tokens through
+/// [`astroport_dca::dca::Cw20HookMsg::TopUpDcaOrder`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to top up their order, containing the
+/// [`AssetInfo::NativeToken`] if the `initial_asset` is a native token.
+///
+/// * `id` - The [`u64`] ID of the order to top up.
+///
+/// * `amount` - The [`Uint128`] amount of the `initial_asset` to add to the order.
+pub fn top_up_dca_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let order = user_dca()
+        .may_load(deps.storage, (&info.sender, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+
+    // check that user has sent the valid tokens to the contract
+    match &order.initial_asset.info {
+        AssetInfo::NativeToken { .. } => Asset {
+            info: order.initial_asset.info.clone(),
+            amount,
+        }
+        .assert_sent_native_token_balance(&info)?,
+        AssetInfo::Token { .. } if order.escrowed => {
+            // escrowed tokens must be sent to the contract through the cw20 receive hook
+            return Err(ContractError::EscrowedDepositIncrease {});
+        }
+        // the allowance only needs to cover each purchase as it is pulled
+        AssetInfo::Token { .. } if order.pull => {}
+        AssetInfo::Token { contract_addr } => {
+            // allowance should be greater than the sum of all orders with this initial asset
+            let total_allowance =
+                get_reserved_allowance(deps.storage, &info.sender, contract_addr)?;
+
+            let allowance = get_token_allowance(&deps.as_ref(), &env, &info.sender, contract_addr)?;
+            if total_allowance + amount > allowance {
+                return Err(ContractError::InvalidTokenDeposit {});
+            }
+        }
+    }
+
+    increase_dca_order(deps.storage, &info.sender, id, amount)
+}
+
+/// ## Description
+/// Increases the `initial_asset` amount of a DCA order by `amount`, once the deposit has been
+/// validated.
+///
+/// Returns a [`ContractError`] if the order does not exist or the new amount is not a valid
+/// deposit for the order, otherwise returns a [`Response`] with the specified attributes.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `owner` - The [`Addr`] of the user who owns the order.
+///
+/// * `id` - The [`u64`] ID of the order to top up.
+///
+/// * `amount` - The [`Uint128`] amount of the `initial_asset` to add to the order.
+pub(crate) fn increase_dca_order(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let order = user_dca().update(storage, (owner, id), |order| {
+        let mut order = order.ok_or(ContractError::NonexistentDca {})?;
+
+        // a deposit to a pooled order mints shares of it through joining the order instead
+        if order.pooled {
+            return Err(ContractError::PooledOrder {});
+        }
+
+        order.initial_asset.amount = order.initial_asset.amount.checked_add(amount)?;
+
+        // check that the new initial_asset.amount is still spent exactly by the tranches
+        if let Strategy::Tranches { amounts } = &order.strategy {
+            validate_tranches(amounts, order.initial_asset.amount)?;
+        }
+
+        Ok(order)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "top_up_dca_order"),
+        attr("id", id.to_string()),
+        attr("amount", amount),
+        attr("new_initial_asset", order.initial_asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        attr, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        DepsMut, Response, StdError, Uint128,
+    };
+
+    use crate::{
+        contract::execute, error::ContractError, state::get_user_orders, tests::mock_creator,
+    };
+
+    fn create_order(deps: DepsMut) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: Some(18_000),
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_top_up_native() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(50_000, "uluna")),
+            ExecuteMsg::TopUpDcaOrder {
+                id: 1,
+                amount: Uint128::new(50_000),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            Response::new().add_attributes(vec![
+                attr("action", "top_up_dca_order"),
+                attr("id", "1"),
+                attr("amount", "50000"),
+                attr("new_initial_asset", "150000uluna"),
+            ])
+        );
+
+        // the schedule of the order should be unchanged
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(150_000));
+        assert_eq!(orders[0].last_purchase, 18_000);
+    }
+
+    #[test]
+    fn does_top_up_indivisible() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(10_000, "uluna")),
+            ExecuteMsg::TopUpDcaOrder {
+                id: 1,
+                amount: Uint128::new(10_000),
+            },
+        )
+        .unwrap();
+
+        // the remainder should be spent in a final, smaller purchase
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(110_000));
+    }
+
+    #[test]
+    fn does_require_native_sent() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::TopUpDcaOrder {
+                id: 1,
+                amount: Uint128::new(25_000),
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            res,
+            ContractError::Std(StdError::generic_err(
+                "Native token balance mismatch between the argument and the transferred"
+            ))
+        );
+
+        // errors on a nonexistent order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(25_000, "uluna")),
+            ExecuteMsg::TopUpDcaOrder {
+                id: 2,
+                amount: Uint128::new(25_000),
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(res, ContractError::NonexistentDca {});
+    }
+}
@@ -0,0 +1,334 @@
+use astroport::asset::{addr_validate_to_lower, Asset};
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Order, Response, StdError, StdResult};
+
+use crate::{
+    error::ContractError,
+    state::{user_dca, ORDER_NFTS, PURCHASED_BALANCE, PURCHASE_HISTORY, USER_CONFIG},
+};
+
+use super::create_dca_order::next_order_id;
+
+/// ## Description
+/// Transfers a DCA order of the sender to `new_owner`, moving the remaining deposit, the purchase
+/// history and any escrowed purchases of the order to the next order id of `new_owner`.
+///
+/// Bot tips are deposited for all the orders of a user rather than for a single order, so they
+/// are kept by the sender, except for the tip balance replenished by the order itself through its
+/// `tip_replenish`, which is moved to `new_owner` as far as it has not been spent on tips.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to transfer their order.
+///
+/// * `id` - The [`u64`] ID of the order to transfer.
+///
+/// * `new_owner` - The address of the user the order is transferred to.
+pub fn transfer_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    let new_owner = addr_validate_to_lower(deps.api, &new_owner)?;
+    if new_owner == info.sender {
+        return Err(ContractError::SelfTransfer {});
+    }
+
+    let mut order = user_dca()
+        .may_load(deps.storage, (&info.sender, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+
+    // the depositors of a pooled order find it under the address of its owner
+    if order.pooled {
+        return Err(ContractError::PooledOrder {});
+    }
+
+    if ORDER_NFTS.has(deps.storage, (&info.sender, id)) {
+        return Err(ContractError::TokenizedOrderTransfer {});
+    }
+
+    // the deposit of an allowance based order is held by the sender, so it cannot be moved
+    if !order.escrowed && !order.initial_asset.info.is_native_token() {
+        return Err(ContractError::AllowanceOrderTransfer {});
+    }
+
+    if order.referrer.as_ref() == Some(&new_owner) {
+        return Err(ContractError::SelfReferral {});
+    }
+
+    let new_id = next_order_id(deps.storage, &new_owner)?;
+
+    // the tip balance replenished from the deposit of the order funds its purchases for the new
+    // owner, while the rest of the tip balance of the sender stays with them
+    if !order.replenished_tip.is_zero() {
+        let mut sender_config = USER_CONFIG
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        order.replenished_tip =
+            sender_config.take_held_tip(&order.initial_asset.info, order.replenished_tip);
+        USER_CONFIG.save(deps.storage, &info.sender, &sender_config)?;
+
+        let replenished_tip = Asset {
+            info: order.initial_asset.info.clone(),
+            amount: order.replenished_tip,
+        };
+        USER_CONFIG.update::<_, StdError>(deps.storage, &new_owner, |config| {
+            let mut config = config.unwrap_or_default();
+            config.add_held_tip(&replenished_tip)?;
+            Ok(config)
+        })?;
+    }
+
+    user_dca().remove(deps.storage, (&info.sender, id))?;
+    order.id = new_id;
+    user_dca().save(deps.storage, (&new_owner, new_id), &order)?;
+
+    let history = PURCHASE_HISTORY
+        .prefix((&info.sender, id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (sequence, record) in history {
+        PURCHASE_HISTORY.remove(deps.storage, (&info.sender, id, sequence));
+        PURCHASE_HISTORY.save(deps.storage, (&new_owner, new_id, sequence), &record)?;
+    }
+
+    if let Some(purchased) = PURCHASED_BALANCE.may_load(deps.storage, (&info.sender, id))? {
+        PURCHASED_BALANCE.remove(deps.storage, (&info.sender, id));
+        PURCHASED_BALANCE.save(deps.storage, (&new_owner, new_id), &purchased)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "transfer_order"),
+        attr("id", id.to_string()),
+        attr("new_owner", new_owner),
+        attr("new_id", new_id.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, PurchaseRecord, Strategy,
+    };
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, DepsMut, Uint128,
+    };
+
+    use crate::{
+        contract::execute,
+        error::ContractError,
+        state::{get_user_orders, user_dca, PURCHASED_BALANCE, PURCHASE_HISTORY, USER_CONFIG},
+    };
+
+    fn create_order(deps: DepsMut, user: &str) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(user, &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Escrow,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_transfer_order() {
+        let mut deps = mock_dependencies();
+
+        let creator = Addr::unchecked("creator");
+        let new_owner = Addr::unchecked("new_owner");
+        create_order(deps.as_mut(), "creator");
+        create_order(deps.as_mut(), "new_owner");
+
+        let record = PurchaseRecord {
+            timestamp: 1_000,
+            amount_in: Uint128::new(25_000),
+            amount_out: Uint128::new(20_000),
+            outputs: vec![],
+            bot: Addr::unchecked("bot"),
+            tip: vec![],
+        };
+        let purchased = Asset {
+            amount: Uint128::new(20_000),
+            info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        };
+        PURCHASE_HISTORY
+            .save(deps.as_mut().storage, (&creator, 1, 1), &record)
+            .unwrap();
+        PURCHASED_BALANCE
+            .save(deps.as_mut().storage, (&creator, 1), &purchased)
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::TransferOrder {
+                id: 1,
+                new_owner: "creator".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::SelfTransfer {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::TransferOrder {
+                id: 1,
+                new_owner: "new_owner".to_string(),
+            },
+        )
+        .unwrap();
+
+        // the order should have been moved to the next id of the new owner
+        assert!(get_user_orders(&deps.storage, &creator).unwrap().is_empty());
+        let orders = get_user_orders(&deps.storage, &new_owner).unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[1].id, 2);
+        assert_eq!(orders[1].initial_asset.amount, Uint128::new(100_000));
+
+        // along with its purchase history and escrowed purchases
+        assert_eq!(
+            PURCHASE_HISTORY
+                .may_load(&deps.storage, (&creator, 1, 1))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            PURCHASE_HISTORY
+                .load(&deps.storage, (&new_owner, 2, 1))
+                .unwrap(),
+            record
+        );
+        assert_eq!(
+            PURCHASED_BALANCE
+                .may_load(&deps.storage, (&creator, 1))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            PURCHASED_BALANCE
+                .load(&deps.storage, (&new_owner, 2))
+                .unwrap(),
+            purchased
+        );
+    }
+
+    #[test]
+    fn does_transfer_replenished_tip() {
+        let mut deps = mock_dependencies();
+
+        let creator = Addr::unchecked("creator");
+        let new_owner = Addr::unchecked("new_owner");
+        create_order(deps.as_mut(), "creator");
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let uusd = AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        };
+
+        // the order replenished 3_000 uluna of which 1_000 was spent, alongside a uusd deposit
+        user_dca()
+            .update(deps.as_mut().storage, (&creator, 1), |order| {
+                let mut order = order.unwrap();
+                order.replenished_tip = Uint128::new(3_000);
+                Ok::<_, ContractError>(order)
+            })
+            .unwrap();
+        let mut config = USER_CONFIG.load(&deps.storage, &creator).unwrap();
+        config.tip_balance = vec![
+            Asset {
+                info: uusd.clone(),
+                amount: Uint128::new(5_000),
+            },
+            Asset {
+                info: uluna.clone(),
+                amount: Uint128::new(2_000),
+            },
+        ];
+        USER_CONFIG
+            .save(deps.as_mut().storage, &creator, &config)
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::TransferOrder {
+                id: 1,
+                new_owner: "new_owner".to_string(),
+            },
+        )
+        .unwrap();
+
+        // the unspent replenished tip should move with the order, the uusd deposit stays
+        let sender_config = USER_CONFIG.load(&deps.storage, &creator).unwrap();
+        assert_eq!(sender_config.tip(&uluna), Uint128::zero());
+        assert_eq!(sender_config.tip(&uusd), Uint128::new(5_000));
+
+        let new_owner_config = USER_CONFIG.load(&deps.storage, &new_owner).unwrap();
+        assert_eq!(new_owner_config.tip(&uluna), Uint128::new(2_000));
+        assert_eq!(new_owner_config.tip(&uusd), Uint128::zero());
+
+        let orders = get_user_orders(&deps.storage, &new_owner).unwrap();
+        assert_eq!(orders[0].replenished_tip, Uint128::new(2_000));
+    }
+}
@@ -0,0 +1,92 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::CONFIG};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Removes `asset` from the blacklist, which has been blacklisted with
+/// [`super::blacklist_asset`], so that DCA orders can spend or purchase it again.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to unblacklist the asset.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to unblacklist.
+pub fn unblacklist_asset(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !config.is_blacklisted_asset(&asset) {
+        return Err(ContractError::AssetNotBlacklisted { asset });
+    }
+
+    config
+        .blacklisted_assets
+        .retain(|blacklisted| blacklisted != &asset);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "unblacklist_asset"),
+        attr("asset", asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::AssetInfo;
+    use astroport_dca::dca::ExecuteMsg;
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    #[test]
+    fn does_unblacklist_asset() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let unblacklist = ExecuteMsg::UnblacklistAsset {
+            asset: ujpy.clone(),
+        };
+
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &unblacklist, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetNotBlacklisted {
+                asset: ujpy.clone()
+            }
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::BlacklistAsset { asset: ujpy },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(mock_creator().sender, dca_addr.clone(), &unblacklist, &[])
+            .unwrap();
+
+        assert!(read_item(&app, &dca_addr, CONFIG)
+            .blacklisted_assets
+            .is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+
+use crate::{error::ContractError, state::CONFIG};
+
+use super::ownership::get_owner;
+
+/// ## Description
+/// Resumes the DCA purchases of the orders spending or purchasing `asset`, which has been paused
+/// with [`super::pause_asset`].
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to unpause the asset.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to unpause.
+pub fn unpause_asset(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !config.is_paused_asset(&asset) {
+        return Err(ContractError::AssetNotPaused { asset });
+    }
+
+    config.paused_assets.retain(|paused| paused != &asset);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "unpause_asset"),
+        attr("asset", asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::AssetInfo;
+    use astroport_dca::dca::ExecuteMsg;
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    #[test]
+    fn does_unpause_asset() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let unpause = ExecuteMsg::UnpauseAsset {
+            asset: ujpy.clone(),
+        };
+
+        let res = app
+            .execute_contract(mock_creator().sender, dca_addr.clone(), &unpause, &[])
+            .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::AssetNotPaused {
+                asset: ujpy.clone()
+            }
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::PauseAsset { asset: ujpy },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(mock_creator().sender, dca_addr.clone(), &unpause, &[])
+            .unwrap();
+
+        assert!(read_item(&app, &dca_addr, CONFIG).paused_assets.is_empty());
+    }
+}
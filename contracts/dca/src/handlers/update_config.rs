@@ -1,10 +1,140 @@
-use astroport::{
-    asset::{Asset, AssetInfo},
-    querier::query_factory_config,
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport_dca::dca::{BotBond, FeeTier};
+use cosmwasm_std::{
+    attr, Decimal, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128,
 };
-use cosmwasm_std::{attr, Decimal, DepsMut, MessageInfo, Response, StdError};
 
-use crate::{error::ContractError, state::CONFIG};
+use crate::{
+    error::ContractError,
+    state::{Config, PendingConfigChange, CONFIG, MAX_BPS, PAIRS, PENDING_CONFIG},
+};
+
+use super::ownership::get_owner;
+
+/// Stores the new configuration parameters of the contract
+pub struct UpdateConfigParameters {
+    /// An optional value which represents the new maximum amount of hops per swap if the user does
+    /// not specify a value.
+    pub max_hops: Option<u32>,
+    /// An optional [`Vec<Asset>`] which represents the new fee paid to bots per hop executed in a
+    /// DCA purchase.
+    pub whitelisted_fee_assets: Option<Vec<Asset>>,
+    /// An optional [`Vec<AssetInfo>`] which represents the new whitelisted tokens that can be used
+    /// in a hop route for DCA purchases.
+    pub whitelisted_tokens: Option<Vec<AssetInfo>>,
+    /// An optional [`Decimal`] which represents the new maximum spread for each DCA purchase if the
+    /// user does not specify a value.
+    pub max_spread: Option<Decimal>,
+    /// An optional [`String`] which represents the new address of the Astroport staking contract
+    /// used to stake purchased ASTRO.
+    pub staking_addr: Option<String>,
+    /// An optional [`u16`] which represents the new fee in basis points taken from the amount spent
+    /// in each DCA purchase.
+    pub protocol_fee_bps: Option<u16>,
+    /// An optional [`String`] which represents the new address the protocol fee is sent to.
+    pub fee_collector: Option<String>,
+    /// An optional [`Vec<FeeTier>`] which represents the new discounts on the protocol fee for
+    /// users holding xASTRO.
+    pub fee_tiers: Option<Vec<FeeTier>>,
+    /// An optional [`Decimal`] which represents the new share of the protocol fee that accrues to
+    /// the referrer of an order.
+    pub referral_share: Option<Decimal>,
+    /// An optional [`String`] which represents the new address of the Astroport factory contract.
+    pub factory_addr: Option<String>,
+    /// An optional [`String`] which represents the new address of the Astroport router contract.
+    pub router_addr: Option<String>,
+    /// An optional [`u64`] which represents the new delay in seconds before sensitive configuration
+    /// changes take effect.
+    pub config_timelock: Option<u64>,
+    /// An optional [`bool`] which represents whether to pause the creation of DCA orders and DCA
+    /// purchases.
+    pub paused: Option<bool>,
+    /// An optional [`Decimal`] which represents the new maximum deviation of the simulated price of
+    /// a DCA purchase from the price of the oracle set for its assets.
+    pub max_oracle_deviation: Option<Decimal>,
+    /// An optional [`bool`] which represents whether only registered bots can perform DCA
+    /// purchases.
+    pub registered_bots_only: Option<bool>,
+    /// An optional [`BotBond`] which represents the new bond that bots must post when registering.
+    pub bot_bond: Option<BotBond>,
+    /// An optional [`u64`] which represents the new window after each DCA purchase is due during
+    /// which only its assigned bot can perform it, where zero removes the window.
+    pub fairness_window: Option<u64>,
+    /// An optional [`Vec<AssetInfo>`] which represents the new assets that DCA orders can spend or
+    /// purchase while `allowed_assets_only` is set.
+    pub allowed_assets: Option<Vec<AssetInfo>>,
+    /// An optional [`bool`] which represents whether DCA orders can only spend or purchase the
+    /// allowed assets.
+    pub allowed_assets_only: Option<bool>,
+    /// An optional [`Uint128`] which represents the new minimum amount of each asset that the
+    /// pairs of a new DCA order must hold, where zero removes the minimum.
+    pub min_liquidity: Option<Uint128>,
+    /// An optional [`Vec<String>`] which represents the new cw20 tokens taking a tax on
+    /// transfers.
+    pub fee_on_transfer_tokens: Option<Vec<String>>,
+    /// An optional [`Decimal`] which represents the new minimum deposit of a new DCA order in
+    /// whole units of its initial asset.
+    pub min_deposit: Option<Decimal>,
+    /// An optional [`Decimal`] which represents the new minimum amount spent in each purchase of
+    /// a new DCA order in whole units of its initial asset.
+    pub min_dca_amount: Option<Decimal>,
+}
+
+/// ## Description
+/// Moves the sensitive changes from `current` to `config` into a [`PendingConfigChange`] taking
+/// effect at `effective_at`, restoring their current values in `config`. The sensitive changes
+/// are fee increases, whitelist removals and a decrease of the timelock itself.
+///
+/// Returns [`None`] if `config` does not contain any sensitive change.
+fn schedule_sensitive_changes(
+    current: &Config,
+    config: &mut Config,
+    effective_at: u64,
+) -> Option<PendingConfigChange> {
+    let mut change = PendingConfigChange {
+        protocol_fee_bps: None,
+        whitelisted_tokens: None,
+        whitelisted_fee_assets: None,
+        config_timelock: None,
+        effective_at,
+    };
+
+    if config.protocol_fee_bps > current.protocol_fee_bps {
+        change.protocol_fee_bps = Some(config.protocol_fee_bps);
+        config.protocol_fee_bps = current.protocol_fee_bps;
+    }
+
+    if current
+        .whitelisted_tokens
+        .iter()
+        .any(|token| !config.whitelisted_tokens.contains(token))
+    {
+        change.whitelisted_tokens = Some(std::mem::replace(
+            &mut config.whitelisted_tokens,
+            current.whitelisted_tokens.clone(),
+        ));
+    }
+
+    // a fee asset is removed if it is missing or its fee per hop is increased
+    if current.whitelisted_fee_assets.iter().any(|current_asset| {
+        !config
+            .whitelisted_fee_assets
+            .iter()
+            .any(|asset| asset.info == current_asset.info && asset.amount <= current_asset.amount)
+    }) {
+        change.whitelisted_fee_assets = Some(std::mem::replace(
+            &mut config.whitelisted_fee_assets,
+            current.whitelisted_fee_assets.clone(),
+        ));
+    }
+
+    if config.config_timelock < current.config_timelock {
+        change.config_timelock = Some(config.config_timelock);
+        config.config_timelock = current.config_timelock;
+    }
+
+    (!change.is_empty()).then(|| change)
+}
 
 /// ## Description
 /// Updates the contract configuration with the specified optional parameters.
@@ -12,62 +142,220 @@ use crate::{error::ContractError, state::CONFIG};
 /// If any new configuration value is excluded, the current configuration value will remain
 /// unchanged.
 ///
+/// If a configuration timelock is set, fee increases and whitelist removals are scheduled as a
+/// [`PendingConfigChange`] that can be applied once the timelock has passed, while the other
+/// changes take effect immediately.
+///
 /// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
 /// attributes if the operation was successful.
 /// ## Arguments
 /// * `deps` - A [`DepsMut`] that contains the dependencies.
 ///
-/// * `info` - A [`MessageInfo`] from the factory contract owner who wants to modify the
-/// configuration of the contract.
-///
-/// * `max_hops` - An optional value which represents the new maximum amount of hops per swap if the
-/// user does not specify a value.
-///
-/// * `per_hop_fee` - An optional [`Vec<Asset>`] which represents the new fee paid to bots per hop
-/// executed in a DCA purchase.
+/// * `env` - The [`Env`] of the blockchain.
 ///
-/// * `whitelisted_tokens` - An optional [`Vec<AssetInfo>`] which represents the new whitelisted
-/// tokens that can be used in a hop route for DCA purchases.
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to modify the
+/// configuration of the contract.
 ///
-/// * `max_spread` - An optional [`Decimal`] which represents the new maximum spread for each DCA
-/// purchase if the user does not specify a value.
+/// * `params` - The [`UpdateConfigParameters`] with the new configuration values.
 pub fn update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    max_hops: Option<u32>,
-    whitelisted_fee_assets: Option<Vec<Asset>>,
-    whitelisted_tokens: Option<Vec<AssetInfo>>,
-    max_spread: Option<Decimal>,
+    params: UpdateConfigParameters,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let factory_config = query_factory_config(&deps.querier, config.factory_addr)?;
-
-    if info.sender != factory_config.owner {
+    let UpdateConfigParameters {
+        max_hops,
+        whitelisted_fee_assets,
+        whitelisted_tokens,
+        max_spread,
+        staking_addr,
+        protocol_fee_bps,
+        fee_collector,
+        fee_tiers,
+        referral_share,
+        factory_addr,
+        router_addr,
+        config_timelock,
+        paused,
+        max_oracle_deviation,
+        registered_bots_only,
+        bot_bond,
+        fairness_window,
+        allowed_assets,
+        allowed_assets_only,
+        min_liquidity,
+        fee_on_transfer_tokens,
+        min_deposit,
+        min_dca_amount,
+    } = params;
+
+    let current = CONFIG.load(deps.storage)?;
+    let mut config = current.clone();
+    if info.sender != get_owner(&deps.querier, &config)? {
         return Err(ContractError::Unauthorized {});
     }
 
     // update config
-    CONFIG.update::<_, StdError>(deps.storage, |mut config| {
-        if let Some(new_max_hops) = max_hops {
-            config.max_hops = new_max_hops;
-        }
+    if let Some(new_max_hops) = max_hops {
+        config.max_hops = new_max_hops;
+    }
+
+    if let Some(new_whitelisted_fee_assets) = whitelisted_fee_assets {
+        config.whitelisted_fee_assets = new_whitelisted_fee_assets;
+    }
+
+    if let Some(new_whitelisted_tokens) = whitelisted_tokens {
+        config.whitelisted_tokens = new_whitelisted_tokens;
+    }
+
+    if let Some(new_max_spread) = max_spread {
+        config.max_spread = new_max_spread;
+    }
+
+    if let Some(new_staking_addr) = staking_addr {
+        config.staking_addr = Some(addr_validate_to_lower(deps.api, &new_staking_addr)?);
+    }
+
+    if let Some(new_protocol_fee_bps) = protocol_fee_bps {
+        config.protocol_fee_bps = new_protocol_fee_bps;
+    }
 
-        if let Some(new_whitelisted_fee_assets) = whitelisted_fee_assets {
-            config.whitelisted_fee_assets = new_whitelisted_fee_assets;
+    if let Some(new_fee_collector) = fee_collector {
+        config.fee_collector = Some(addr_validate_to_lower(deps.api, &new_fee_collector)?);
+    }
+
+    if let Some(mut new_fee_tiers) = fee_tiers {
+        new_fee_tiers.sort_by_key(|tier| tier.min_xastro);
+        config.fee_tiers = new_fee_tiers;
+    }
+
+    if let Some(new_referral_share) = referral_share {
+        config.referral_share = new_referral_share;
+    }
+
+    if let Some(new_factory_addr) = factory_addr {
+        config.factory_addr = addr_validate_to_lower(deps.api, &new_factory_addr)?;
+
+        // the cached pairs were resolved from the previous factory
+        if config.factory_addr != current.factory_addr {
+            let keys = PAIRS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for key in keys {
+                PAIRS.remove(deps.storage, key);
+            }
         }
+    }
+
+    if let Some(new_router_addr) = router_addr {
+        config.router_addr = addr_validate_to_lower(deps.api, &new_router_addr)?;
+    }
+
+    if let Some(new_config_timelock) = config_timelock {
+        config.config_timelock = new_config_timelock;
+    }
+
+    if let Some(new_paused) = paused {
+        config.paused = new_paused;
+    }
+
+    if let Some(new_max_oracle_deviation) = max_oracle_deviation {
+        config.max_oracle_deviation = Some(new_max_oracle_deviation);
+    }
+
+    if let Some(new_registered_bots_only) = registered_bots_only {
+        config.registered_bots_only = new_registered_bots_only;
+    }
 
-        if let Some(new_whitelisted_tokens) = whitelisted_tokens {
-            config.whitelisted_tokens = new_whitelisted_tokens;
+    if let Some(new_bot_bond) = bot_bond {
+        if new_bot_bond.bond.amount.is_zero() || new_bot_bond.slash_bps > MAX_BPS {
+            return Err(ContractError::InvalidBotBond { max: MAX_BPS });
         }
 
-        if let Some(new_max_spread) = max_spread {
-            config.max_spread = new_max_spread;
+        config.bot_bond = Some(new_bot_bond);
+    }
+
+    if let Some(new_fairness_window) = fairness_window {
+        config.fairness_window = (new_fairness_window > 0).then(|| new_fairness_window);
+    }
+
+    if let Some(new_allowed_assets) = allowed_assets {
+        config.allowed_assets = new_allowed_assets;
+    }
+
+    if let Some(new_allowed_assets_only) = allowed_assets_only {
+        config.allowed_assets_only = new_allowed_assets_only;
+    }
+
+    if let Some(new_min_liquidity) = min_liquidity {
+        config.min_liquidity = (!new_min_liquidity.is_zero()).then(|| new_min_liquidity);
+    }
+
+    if let Some(new_fee_on_transfer_tokens) = fee_on_transfer_tokens {
+        config.fee_on_transfer_tokens = new_fee_on_transfer_tokens
+            .iter()
+            .map(|token| addr_validate_to_lower(deps.api, token))
+            .collect::<StdResult<_>>()?;
+    }
+
+    if let Some(new_min_deposit) = min_deposit {
+        config.min_deposit = (!new_min_deposit.is_zero()).then(|| new_min_deposit);
+    }
+
+    if let Some(new_min_dca_amount) = min_dca_amount {
+        config.min_dca_amount = (!new_min_dca_amount.is_zero()).then(|| new_min_dca_amount);
+    }
+
+    // the scheduled changes must result in a valid configuration once they are applied
+    config.validate_protocol_fee()?;
+
+    let mut attrs = vec![attr("action", "update_config")];
+    if let Some(effective_at) = save_config(deps.storage, &env, &current, config)? {
+        attrs.push(attr("effective_at", effective_at.to_string()));
+    }
+
+    Ok(Response::default().add_attributes(attrs))
+}
+
+/// ## Description
+/// Saves `config` as the new configuration of the contract, scheduling its sensitive changes from
+/// `current` as a [`PendingConfigChange`] if a configuration timelock is set.
+///
+/// Returns the time in seconds at which the sensitive changes take effect, if any were scheduled.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `current` - The current [`Config`] of the contract.
+///
+/// * `config` - The new [`Config`] of the contract.
+pub fn save_config(
+    storage: &mut dyn Storage,
+    env: &Env,
+    current: &Config,
+    mut config: Config,
+) -> StdResult<Option<u64>> {
+    let mut scheduled_at = None;
+    if current.config_timelock > 0 {
+        let effective_at = env
+            .block
+            .time
+            .plus_seconds(current.config_timelock)
+            .seconds();
+
+        if let Some(change) = schedule_sensitive_changes(current, &mut config, effective_at) {
+            let mut pending = PENDING_CONFIG.may_load(storage)?.unwrap_or_default();
+            pending.push(change);
+            PENDING_CONFIG.save(storage, &pending)?;
+
+            scheduled_at = Some(effective_at);
         }
+    }
 
-        Ok(config)
-    })?;
+    CONFIG.save(storage, &config)?;
 
-    Ok(Response::default().add_attributes(vec![attr("action", "update_config")]))
+    Ok(scheduled_at)
 }
 
 #[cfg(test)]
@@ -75,13 +363,13 @@ mod test {
     use std::str::FromStr;
 
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::ExecuteMsg;
+    use astroport_dca::dca::{ExecuteMsg, SwapBackendType};
     use cosmwasm_std::{Addr, Decimal, Uint128};
     use cw_multi_test::{App, Executor};
 
     use crate::{
         error::ContractError,
-        state::{Config, CONFIG},
+        state::{Config, PendingConfigChange, CONFIG, MAX_PROTOCOL_FEE_BPS, PENDING_CONFIG},
         tests::{
             app_mock_instantiate, mock_app, mock_creator, read_item, store_dca_module_code,
             store_factory_code,
@@ -146,9 +434,31 @@ mod test {
             max_hops: config.max_hops + 1,
             max_spread: Decimal::from_str("0.1").unwrap() + config.max_spread,
             factory_addr: Addr::unchecked("contract0"),
-            router_addr: Addr::unchecked("router"),
+            router_addr: Addr::unchecked("new_router"),
             whitelisted_fee_assets: new_fee_assets,
             whitelisted_tokens: new_tokens,
+            staking_addr: Some(Addr::unchecked("staking")),
+            protocol_fee_bps: 30,
+            fee_collector: Some(Addr::unchecked("collector")),
+            fee_tiers: vec![],
+            referral_share: Decimal::zero(),
+            owner: None,
+            config_timelock: 0,
+            paused: false,
+            paused_assets: vec![],
+            nft_addr: None,
+            max_oracle_deviation: None,
+            registered_bots_only: false,
+            bot_bond: None,
+            fairness_window: None,
+            allowed_assets: vec![],
+            allowed_assets_only: false,
+            blacklisted_assets: vec![],
+            min_liquidity: None,
+            fee_on_transfer_tokens: vec![],
+            min_deposit: None,
+            min_dca_amount: None,
+            swap_backend: SwapBackendType::Router,
         };
 
         app.execute_contract(
@@ -159,6 +469,25 @@ mod test {
                 whitelisted_tokens: Some(new_config.whitelisted_tokens.clone()),
                 whitelisted_fee_assets: Some(new_config.whitelisted_fee_assets.clone()),
                 max_spread: Some(new_config.max_spread),
+                staking_addr: Some("staking".to_string()),
+                protocol_fee_bps: Some(new_config.protocol_fee_bps),
+                fee_collector: Some("collector".to_string()),
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: Some("new_router".to_string()),
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
             },
             &[],
         )
@@ -182,6 +511,25 @@ mod test {
                 whitelisted_tokens: None,
                 whitelisted_fee_assets: None,
                 max_spread: None,
+                staking_addr: None,
+                protocol_fee_bps: None,
+                fee_collector: None,
+                fee_tiers: None,
+                referral_share: None,
+                factory_addr: None,
+                router_addr: None,
+                config_timelock: None,
+                paused: None,
+                max_oracle_deviation: None,
+                registered_bots_only: None,
+                bot_bond: None,
+                fairness_window: None,
+                allowed_assets: None,
+                allowed_assets_only: None,
+                min_liquidity: None,
+                fee_on_transfer_tokens: None,
+                min_deposit: None,
+                min_dca_amount: None,
             },
             &[],
         )
@@ -212,6 +560,25 @@ mod test {
                         },
                     }]),
                     max_spread: Some(Decimal::from_str("0.075").unwrap()),
+                    staking_addr: None,
+                    protocol_fee_bps: None,
+                    fee_collector: None,
+                    fee_tiers: None,
+                    referral_share: None,
+                    factory_addr: None,
+                    router_addr: None,
+                    config_timelock: None,
+                    paused: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: None,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: None,
+                    allowed_assets_only: None,
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: None,
+                    min_deposit: None,
+                    min_dca_amount: None,
                 },
                 &[],
             )
@@ -222,4 +589,162 @@ mod test {
             ContractError::Unauthorized {}
         );
     }
+
+    #[test]
+    fn cannot_set_protocol_fee_without_collector() {
+        let (mut app, dca_addr) = instantiate();
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("factory_owner"),
+                dca_addr.clone(),
+                &ExecuteMsg::UpdateConfig {
+                    max_hops: None,
+                    whitelisted_tokens: None,
+                    whitelisted_fee_assets: None,
+                    max_spread: None,
+                    staking_addr: None,
+                    protocol_fee_bps: Some(30),
+                    fee_collector: None,
+                    fee_tiers: None,
+                    referral_share: None,
+                    factory_addr: None,
+                    router_addr: None,
+                    config_timelock: None,
+                    paused: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: None,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: None,
+                    allowed_assets_only: None,
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: None,
+                    min_deposit: None,
+                    min_dca_amount: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::FeeCollectorNotSet {}
+        );
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("factory_owner"),
+                dca_addr,
+                &ExecuteMsg::UpdateConfig {
+                    max_hops: None,
+                    whitelisted_tokens: None,
+                    whitelisted_fee_assets: None,
+                    max_spread: None,
+                    staking_addr: None,
+                    protocol_fee_bps: Some(MAX_PROTOCOL_FEE_BPS + 1),
+                    fee_collector: Some("collector".to_string()),
+                    fee_tiers: None,
+                    referral_share: None,
+                    factory_addr: None,
+                    router_addr: None,
+                    config_timelock: None,
+                    paused: None,
+                    max_oracle_deviation: None,
+                    registered_bots_only: None,
+                    bot_bond: None,
+                    fairness_window: None,
+                    allowed_assets: None,
+                    allowed_assets_only: None,
+                    min_liquidity: None,
+                    fee_on_transfer_tokens: None,
+                    min_deposit: None,
+                    min_dca_amount: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::ProtocolFeeTooHigh {
+                max: MAX_PROTOCOL_FEE_BPS
+            }
+        );
+    }
+
+    #[test]
+    fn does_timelock_sensitive_changes() {
+        let (mut app, dca_addr) = instantiate();
+
+        let update = |max_hops, whitelisted_tokens, config_timelock| ExecuteMsg::UpdateConfig {
+            max_hops,
+            whitelisted_tokens,
+            whitelisted_fee_assets: None,
+            max_spread: None,
+            staking_addr: None,
+            protocol_fee_bps: None,
+            fee_collector: None,
+            fee_tiers: None,
+            referral_share: None,
+            factory_addr: None,
+            router_addr: None,
+            config_timelock,
+            paused: None,
+            max_oracle_deviation: None,
+            registered_bots_only: None,
+            bot_bond: None,
+            fairness_window: None,
+            allowed_assets: None,
+            allowed_assets_only: None,
+            min_liquidity: None,
+            fee_on_transfer_tokens: None,
+            min_deposit: None,
+            min_dca_amount: None,
+        };
+
+        app.execute_contract(
+            Addr::unchecked("factory_owner"),
+            dca_addr.clone(),
+            &update(
+                None,
+                Some(vec![AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                }]),
+                Some(100),
+            ),
+            &[],
+        )
+        .unwrap();
+
+        // the changes made without a timelock should take effect immediately
+        let config = read_item(&app, &dca_addr, CONFIG);
+        assert_eq!(config.config_timelock, 100);
+        assert_eq!(config.whitelisted_tokens.len(), 1);
+
+        app.execute_contract(
+            Addr::unchecked("factory_owner"),
+            dca_addr.clone(),
+            &update(Some(config.max_hops + 1), Some(vec![]), Some(0)),
+            &[],
+        )
+        .unwrap();
+
+        // only the non sensitive change should take effect immediately
+        let new_config = read_item(&app, &dca_addr, CONFIG);
+        assert_eq!(new_config.max_hops, config.max_hops + 1);
+        assert_eq!(new_config.whitelisted_tokens, config.whitelisted_tokens);
+        assert_eq!(new_config.config_timelock, 100);
+
+        assert_eq!(
+            read_item(&app, &dca_addr, PENDING_CONFIG),
+            vec![PendingConfigChange {
+                protocol_fee_bps: None,
+                whitelisted_tokens: Some(vec![]),
+                whitelisted_fee_assets: None,
+                config_timelock: Some(0),
+                effective_at: app.block_info().time.seconds() + 100,
+            }]
+        );
+    }
 }
@@ -1,8 +1,9 @@
+use astroport::asset::AssetInfo;
 use cosmwasm_std::{attr, Decimal, DepsMut, MessageInfo, Response};
 
 use crate::{
     error::ContractError,
-    state::{UserConfig, USER_CONFIG},
+    state::{UserConfig, CONFIG, USER_CONFIG},
 };
 
 fn serde_option<T>(option: Option<T>) -> String
@@ -30,19 +31,47 @@ where
 ///
 /// * `max_spread` - A [`Decimal`] value wrapped in an [`Option`] which represents the new maximum
 /// spread for each DCA purchase. If `None`, the user will use the config set by the contract.
+///
+/// * `tip_priority` - A [`Vec<AssetInfo>`] of whitelisted tip assets in the order the tip balance
+/// is redeemed by bots that leave the choice of tip to the contract.
 pub fn update_user_config(
     deps: DepsMut,
     info: MessageInfo,
     max_hops: Option<u32>,
     max_spread: Option<Decimal>,
+    tip_priority: Vec<AssetInfo>,
 ) -> Result<Response, ContractError> {
+    if !tip_priority.is_empty() {
+        let contract_config = CONFIG.load(deps.storage)?;
+
+        for (i, asset) in tip_priority.iter().enumerate() {
+            if !contract_config.is_whitelisted_fee_asset(asset) {
+                return Err(ContractError::NonWhitelistedTipAsset {
+                    asset: asset.clone(),
+                });
+            }
+
+            if tip_priority[..i].contains(asset) {
+                return Err(ContractError::DuplicateTipPriority {
+                    asset: asset.clone(),
+                });
+            }
+        }
+    }
+
     let config = USER_CONFIG
         .may_load(deps.storage, &info.sender)?
         .unwrap_or_default();
 
+    let priority = tip_priority
+        .iter()
+        .map(|asset| asset.to_string())
+        .collect::<Vec<_>>();
+
     let new_config = UserConfig {
         max_hops,
         max_spread,
+        tip_priority,
         ..config
     };
 
@@ -52,6 +81,14 @@ pub fn update_user_config(
         attr("action", "update_user_config"),
         attr("max_hops", serde_option(max_hops)),
         attr("max_spread", serde_option(max_spread)),
+        attr(
+            "tip_priority",
+            if priority.is_empty() {
+                "none".to_string()
+            } else {
+                priority.join(",")
+            },
+        ),
     ]))
 }
 
@@ -80,6 +117,7 @@ mod tests {
         let msg = ExecuteMsg::UpdateUserConfig {
             max_hops: Some(6),
             max_spread: Some(Decimal::from_str("0.025").unwrap()),
+            tip_priority: vec![],
         };
 
         // does send the write response
@@ -89,7 +127,8 @@ mod tests {
             Response::new().add_attributes(vec![
                 attr("action", "update_user_config"),
                 attr("max_hops", "6"),
-                attr("max_spread", "0.025")
+                attr("max_spread", "0.025"),
+                attr("tip_priority", "none")
             ])
         );
 
@@ -103,7 +142,9 @@ mod tests {
                 last_id: 0,
                 max_hops: Some(6),
                 max_spread: Some(Decimal::from_str("0.025").unwrap()),
-                tip_balance: vec![]
+                tip_balance: vec![],
+                tip_priority: vec![],
+                deposited_tip_balance: vec![],
             }
         )
     }
@@ -125,6 +166,7 @@ mod tests {
         let msg = ExecuteMsg::UpdateUserConfig {
             max_hops: Some(6),
             max_spread: Some(Decimal::from_str("0.025").unwrap()),
+            tip_priority: vec![],
         };
 
         // add tip
@@ -155,10 +197,12 @@ mod tests {
         let update_msg = ExecuteMsg::UpdateUserConfig {
             max_hops: Some(4),
             max_spread: Some(Decimal::from_str("0.025").unwrap()),
+            tip_priority: vec![],
         };
         let reset_msg = ExecuteMsg::UpdateUserConfig {
             max_hops: Some(6),
             max_spread: None,
+            tip_priority: vec![],
         };
 
         // does reset the config
@@ -175,7 +219,9 @@ mod tests {
                 last_id: 0,
                 max_hops: Some(6),
                 max_spread: None,
-                tip_balance: vec![]
+                tip_balance: vec![],
+                tip_priority: vec![],
+                deposited_tip_balance: vec![],
             }
         )
     }
@@ -0,0 +1,381 @@
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_std::{attr, Deps, DepsMut, Env, MessageInfo, Order, Response};
+
+use crate::{
+    error::ContractError,
+    state::{user_dca, Config, CONFIG},
+};
+
+use super::{ownership::get_owner, update_config::save_config};
+
+/// ## Description
+/// Loads the configuration of the contract, checking that `info` is from the contract owner.
+fn load_owner_config(deps: Deps, info: &MessageInfo) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != get_owner(&deps.querier, &config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(config)
+}
+
+/// ## Description
+/// Saves the changed whitelist in `config`, scheduling a removal or tip increase to take effect
+/// once the configuration timelock has passed, and returns the [`Response`] of `action`.
+fn save_whitelist(
+    deps: DepsMut,
+    env: &Env,
+    current: &Config,
+    config: Config,
+    action: &str,
+    asset: &AssetInfo,
+) -> Result<Response, ContractError> {
+    let mut attrs = vec![attr("action", action), attr("asset", asset.to_string())];
+    if let Some(effective_at) = save_config(deps.storage, env, current, config)? {
+        attrs.push(attr("effective_at", effective_at.to_string()));
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// ## Description
+/// Whitelists a tip asset that bots can be paid in, or updates the tip per hop of an asset that
+/// is already whitelisted, without replacing the other whitelisted tip assets.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to whitelist the tip asset.
+///
+/// * `asset` - The [`Asset`] to whitelist, along with the tip paid to bots per hop.
+pub fn add_tip_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let current = load_owner_config(deps.as_ref(), &info)?;
+    let mut config = current.clone();
+
+    match config
+        .whitelisted_fee_assets
+        .iter_mut()
+        .find(|fee| fee.info == asset.info)
+    {
+        Some(fee) => fee.amount = asset.amount,
+        None => config.whitelisted_fee_assets.push(asset.clone()),
+    }
+
+    save_whitelist(deps, &env, &current, config, "add_tip_asset", &asset.info)
+}
+
+/// ## Description
+/// Removes a whitelisted tip asset, without replacing the other whitelisted tip assets. Users can
+/// still withdraw their tip balance of the asset.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to remove the tip asset.
+///
+/// * `asset` - The [`AssetInfo`] of the tip asset to remove.
+pub fn remove_tip_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let current = load_owner_config(deps.as_ref(), &info)?;
+
+    if !current.is_whitelisted_fee_asset(&asset) {
+        return Err(ContractError::NonWhitelistedTipAsset { asset });
+    }
+
+    let mut config = current.clone();
+    config
+        .whitelisted_fee_assets
+        .retain(|fee| fee.info != asset);
+
+    save_whitelist(deps, &env, &current, config, "remove_tip_asset", &asset)
+}
+
+/// ## Description
+/// Whitelists an asset that the hop routes of DCA purchases can swap through, without replacing
+/// the other whitelisted hop assets.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to whitelist the hop asset.
+///
+/// * `asset` - The [`AssetInfo`] of the hop asset to whitelist.
+pub fn add_hop_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let current = load_owner_config(deps.as_ref(), &info)?;
+
+    if current.is_whitelisted_asset(&asset) {
+        return Err(ContractError::HopAssetAlreadyWhitelisted { asset });
+    }
+
+    let mut config = current.clone();
+    config.whitelisted_tokens.push(asset.clone());
+
+    save_whitelist(deps, &env, &current, config, "add_hop_asset", &asset)
+}
+
+/// ## Description
+/// Removes a whitelisted hop asset, without replacing the other whitelisted hop assets. The asset
+/// cannot be removed while it is part of the stored `route` of a DCA order, which could no longer
+/// be purchased.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `info` - A [`MessageInfo`] from the contract owner who wants to remove the hop asset.
+///
+/// * `asset` - The [`AssetInfo`] of the hop asset to remove.
+pub fn remove_hop_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let current = load_owner_config(deps.as_ref(), &info)?;
+
+    if !current.is_whitelisted_asset(&asset) {
+        return Err(ContractError::NonWhitelistedHopAsset { asset });
+    }
+
+    for item in user_dca().range(deps.storage, None, None, Order::Ascending) {
+        let ((user, id), order) = item?;
+
+        if order.route.map_or(false, |route| route.contains(&asset)) {
+            return Err(ContractError::HopAssetInUse {
+                asset,
+                user: user.into_string(),
+                id,
+            });
+        }
+    }
+
+    let mut config = current.clone();
+    config.whitelisted_tokens.retain(|token| *token != asset);
+
+    save_whitelist(deps, &env, &current, config, "remove_hop_asset", &asset)
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    #[test]
+    fn does_manage_whitelisted_assets() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let native = |denom: &str| AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+
+        // an order routed through ujpy
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(50_000),
+                    info: native("uluna"),
+                },
+                target_asset: native("ukrw"),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: Some(vec![native("ujpy")]),
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(50_000, "uluna"),
+        )
+        .unwrap();
+
+        let execute = |app: &mut App, sender: &str, msg: ExecuteMsg| {
+            app.execute_contract(Addr::unchecked(sender), dca_addr.clone(), &msg, &[])
+        };
+        let owner = mock_creator().sender.into_string();
+
+        // only the contract owner can manage the whitelists
+        let res = execute(
+            &mut app,
+            "someone",
+            ExecuteMsg::RemoveHopAsset {
+                asset: native("ukrw"),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        // the hop asset of a stored route cannot be removed
+        let res = execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::RemoveHopAsset {
+                asset: native("ujpy"),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::HopAssetInUse {
+                asset: native("ujpy"),
+                user: owner.clone(),
+                id: 1,
+            }
+        );
+
+        execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::RemoveHopAsset {
+                asset: native("ukrw"),
+            },
+        )
+        .unwrap();
+        assert!(!read_item(&app, &dca_addr, CONFIG).is_whitelisted_asset(&native("ukrw")));
+
+        let res = execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::AddHopAsset {
+                asset: native("ujpy"),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::HopAssetAlreadyWhitelisted {
+                asset: native("ujpy"),
+            }
+        );
+
+        execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::AddHopAsset {
+                asset: native("ukrw"),
+            },
+        )
+        .unwrap();
+        assert!(read_item(&app, &dca_addr, CONFIG).is_whitelisted_asset(&native("ukrw")));
+
+        // tip assets are added, updated and removed one at a time
+        execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::AddTipAsset {
+                asset: Asset {
+                    info: native("ujpy"),
+                    amount: Uint128::new(2_000),
+                },
+            },
+        )
+        .unwrap();
+        execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::RemoveTipAsset {
+                asset: native("uluna"),
+            },
+        )
+        .unwrap();
+
+        let config = read_item(&app, &dca_addr, CONFIG);
+        assert!(!config.is_whitelisted_fee_asset(&native("uluna")));
+        assert_eq!(
+            config.whitelisted_fee_assets.last(),
+            Some(&Asset {
+                info: native("ujpy"),
+                amount: Uint128::new(2_000),
+            })
+        );
+
+        let res = execute(
+            &mut app,
+            &owner,
+            ExecuteMsg::RemoveTipAsset {
+                asset: native("uluna"),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::NonWhitelistedTipAsset {
+                asset: native("uluna"),
+            }
+        );
+    }
+}
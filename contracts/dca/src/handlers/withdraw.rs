@@ -1,9 +1,13 @@
 use astroport::asset::{Asset, AssetInfo};
-use cosmwasm_std::{attr, coins, BankMsg, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{
+    attr, coins, to_binary, Addr, BankMsg, CosmosMsg, DepsMut, MessageInfo, Response, StdResult,
+    Storage, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 
 use crate::{
     error::ContractError,
-    state::{CONFIG, USER_CONFIG},
+    state::{UserConfig, CONFIG, USER_CONFIG},
 };
 
 /// ## Description
@@ -28,7 +32,7 @@ pub fn withdraw(
 
     let config = CONFIG.load(deps.storage)?;
 
-    let mut send_msgs: Vec<BankMsg> = vec![];
+    let mut send_msgs: Vec<CosmosMsg> = vec![];
 
     for asset in assets {
         if !config.is_whitelisted_fee_asset(&asset.info) {
@@ -45,12 +49,7 @@ pub fn withdraw(
 
         user_balance.amount = user_balance.amount.checked_sub(asset.amount)?;
 
-        if let AssetInfo::NativeToken { denom } = asset.info {
-            send_msgs.push(BankMsg::Send {
-                to_address: info.clone().sender.into_string(),
-                amount: coins(asset.amount.u128(), denom),
-            });
-        }
+        send_msgs.extend(tip_withdrawal_msg(&mut user_config, &info.sender, asset)?);
     }
 
     USER_CONFIG.save(deps.storage, &info.sender, &user_config)?;
@@ -60,14 +59,85 @@ pub fn withdraw(
         .add_messages(send_msgs))
 }
 
+/// ## Description
+/// Withdraws the whole bot tip of `user` from the contract, returning the messages that send it
+/// back to the user.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract.
+///
+/// * `user` - The [`Addr`] of the user whose bot tip is withdrawn.
+pub fn withdraw_all_tips(storage: &mut dyn Storage, user: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    let mut user_config = match USER_CONFIG.may_load(storage, user)? {
+        Some(user_config) => user_config,
+        None => return Ok(vec![]),
+    };
+
+    let mut send_msgs: Vec<CosmosMsg> = vec![];
+    for asset in std::mem::take(&mut user_config.tip_balance) {
+        if !asset.amount.is_zero() {
+            send_msgs.extend(tip_withdrawal_msg(&mut user_config, user, asset)?);
+        }
+    }
+
+    USER_CONFIG.save(storage, user, &user_config)?;
+
+    Ok(send_msgs)
+}
+
+/// ## Description
+/// Creates the message sending `asset` withdrawn from the bot tip of `user_config` to
+/// `recipient`, if any tokens are held by the contract for it.
+fn tip_withdrawal_msg(
+    user_config: &mut UserConfig,
+    recipient: &Addr,
+    asset: Asset,
+) -> StdResult<Option<CosmosMsg>> {
+    match asset.info {
+        AssetInfo::NativeToken { denom } => Ok(Some(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(asset.amount.u128(), denom),
+            }
+            .into(),
+        )),
+        // only the tokens deposited into the contract are sent back, as the rest of the tip
+        // balance was never transferred from the allowance of the user
+        AssetInfo::Token { contract_addr } => {
+            let deposited = user_config.take_deposited_tip(
+                &AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                asset.amount,
+            );
+
+            if deposited.is_zero() {
+                return Ok(None);
+            }
+
+            Ok(Some(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.into_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount: deposited,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::ExecuteMsg;
+    use astroport_dca::dca::{Cw20HookMsg, ExecuteMsg};
     use cosmwasm_std::{
-        attr, coin, coins, testing::mock_info, Addr, BankMsg, DepsMut, Env, MessageInfo,
-        OverflowError, OverflowOperation, Response, Uint128,
+        attr, coin, coins, testing::mock_info, to_binary, Addr, BankMsg, DepsMut, Env, MessageInfo,
+        OverflowError, OverflowOperation, Response, Uint128, WasmMsg,
     };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
     use crate::{
         contract::execute,
@@ -283,4 +353,72 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn will_withdraw_deposited_token_tip() {
+        let token = AssetInfo::Token {
+            contract_addr: Addr::unchecked("token"),
+        };
+        let (mut deps, env) = mock_instantiate(
+            Addr::unchecked("factory"),
+            Addr::unchecked("router"),
+            vec![Asset {
+                amount: Uint128::new(15_000),
+                info: token.clone(),
+            }],
+            vec![],
+        );
+
+        // deposit 10,000 tokens into the contract
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("token", &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "creator".to_string(),
+                amount: Uint128::new(10_000),
+                msg: to_binary(&Cw20HookMsg::AddBotTip {}).unwrap(),
+            }),
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_creator(),
+            ExecuteMsg::Withdraw {
+                assets: vec![Asset {
+                    amount: Uint128::new(4_000),
+                    info: token.clone(),
+                }],
+            },
+        )
+        .unwrap();
+
+        // the deposited tokens should be sent back by the contract
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attributes(vec![attr("action", "withdraw")])
+                .add_message(WasmMsg::Execute {
+                    contract_addr: "token".to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: "creator".to_string(),
+                        amount: Uint128::new(4_000),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })
+        );
+
+        let config = USER_CONFIG
+            .load(&deps.storage, &mock_creator().sender)
+            .unwrap();
+        let remaining = vec![Asset {
+            amount: Uint128::new(6_000),
+            info: token,
+        }];
+        assert_eq!(config.tip_balance, remaining);
+        assert_eq!(config.deposited_tip_balance, remaining);
+    }
 }
@@ -0,0 +1,214 @@
+use astroport_dca::dca::Strategy;
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response, Uint128};
+
+use crate::{error::ContractError, refund_order::refund_order, state::user_dca};
+
+use super::{create_dca_order::validate_tranches, set_operator::get_order_owner};
+
+/// ## Description
+/// Withdraws `amount` of the remaining `initial_asset` from an existing DCA order, keeping the
+/// order active with the reduced balance.
+///
+/// The withdrawn amount is returned to the owner of the order if it is a native token or a cw20
+/// token held by the contract, otherwise the order simply reserves less of the users allowance.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Arguments
+/// * `deps` - A [`DepsMut`] that contains the dependencies.
+///
+/// * `info` - A [`MessageInfo`] from the sender who wants to withdraw from their order.
+///
+/// * `id` - The [`u64`] ID of the order to withdraw from.
+///
+/// * `amount` - The [`Uint128`] amount of the `initial_asset` to withdraw from the order.
+///
+/// * `user` - The address of the user who created the order, if the sender holds its NFT.
+pub fn withdraw_from_dca_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    amount: Uint128,
+    user: Option<String>,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    // the order can only be withdrawn from by the holder of its NFT if one was minted for it
+    let (owner, holder) = get_order_owner(deps.as_ref(), &info.sender, user, id)?;
+
+    let mut order = user_dca()
+        .may_load(deps.storage, (&owner, id))?
+        .ok_or(ContractError::NonexistentDca {})?;
+
+    if order.pooled {
+        return Err(ContractError::PooledOrder {});
+    }
+
+    order.initial_asset.amount = order
+        .initial_asset
+        .amount
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientBalance {})?;
+
+    // check that the order can still perform at least one purchase, where the last purchase
+    // spends whatever remains of initial_asset.amount
+    if order.initial_asset.amount.is_zero() {
+        return Err(ContractError::DepositTooSmall {});
+    }
+
+    // check that the remaining initial_asset.amount is still spent exactly by the tranches
+    if let Strategy::Tranches { amounts } = &order.strategy {
+        validate_tranches(amounts, order.initial_asset.amount)?;
+    }
+
+    let refund = refund_order(&order, amount, &holder)?;
+
+    user_dca().save(deps.storage, (&owner, id), &order)?;
+
+    Ok(Response::new().add_messages(refund).add_attributes(vec![
+        attr("action", "withdraw_from_dca_order"),
+        attr("id", id.to_string()),
+        attr("amount", amount),
+        attr("new_initial_asset", order.initial_asset.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, Strategy,
+    };
+    use cosmwasm_std::{
+        attr, coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        BankMsg, DepsMut, Response, Uint128,
+    };
+
+    use crate::{
+        contract::execute, error::ContractError, state::get_user_orders, tests::mock_creator,
+    };
+
+    fn create_order(deps: DepsMut) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("creator", &coins(100_000, "uluna")),
+            ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                dca_amount: Uint128::new(25_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn does_withdraw_native() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_creator(),
+            ExecuteMsg::WithdrawFromDcaOrder {
+                id: 1,
+                amount: Uint128::new(50_000),
+                user: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attributes(vec![
+                    attr("action", "withdraw_from_dca_order"),
+                    attr("id", "1"),
+                    attr("amount", "50000"),
+                    attr("new_initial_asset", "50000uluna"),
+                ])
+                .add_message(BankMsg::Send {
+                    to_address: mock_creator().sender.into_string(),
+                    amount: coins(50_000, "uluna")
+                })
+        );
+
+        let orders = get_user_orders(&deps.storage, &mock_creator().sender).unwrap();
+        assert_eq!(orders[0].initial_asset.amount, Uint128::new(50_000));
+    }
+
+    #[test]
+    fn cannot_withdraw_invalid_amount() {
+        let mut deps = mock_dependencies();
+
+        create_order(deps.as_mut());
+
+        let mut withdraw = |amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_creator(),
+                ExecuteMsg::WithdrawFromDcaOrder {
+                    id: 1,
+                    amount: Uint128::new(amount),
+                    user: None,
+                },
+            )
+            .unwrap_err()
+        };
+
+        // more than the remaining deposit
+        assert_eq!(withdraw(200_000), ContractError::InsufficientBalance {});
+
+        // the whole deposit should be withdrawn by cancelling the order
+        assert_eq!(withdraw(100_000), ContractError::DepositTooSmall {});
+    }
+}
@@ -0,0 +1,649 @@
+use astroport::{
+    asset::{Asset, AssetInfo, PairInfo},
+    factory::{ConfigResponse as FactoryConfigResponse, QueryMsg as FactoryQueryMsg},
+    pair::{ExecuteMsg as PairExecuteMsg, PoolResponse, QueryMsg as PairQueryMsg},
+};
+use astroport_dca::dca::Zap;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, QuerierWrapper, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{error::ContractError, query_pair::query_pair};
+
+/// ## Description
+/// Splits the `dca_amount` of a zapping DCA order into the amount swapped to the target asset and
+/// the amount provided as liquidity along with the purchased target asset.
+pub(crate) fn split_zap_amount(dca_amount: Uint128) -> (Uint128, Uint128) {
+    let swap_amount = dca_amount.multiply_ratio(1u128, 2u128);
+
+    (swap_amount, dca_amount - swap_amount)
+}
+
+/// ## Description
+/// Returns the [`PairInfo`] of the Astroport pair of `initial_asset` and `target_asset`.
+///
+/// Returns a [`ContractError::InvalidZap`] if the factory has no pair for the assets.
+fn query_zap_pair(
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    initial_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+) -> Result<PairInfo, ContractError> {
+    querier
+        .query_wasm_smart(
+            factory_addr,
+            &FactoryQueryMsg::Pair {
+                asset_infos: [initial_asset.clone(), target_asset.clone()],
+            },
+        )
+        .map_err(|_| ContractError::InvalidZap {})
+}
+
+/// ## Description
+/// Validates that a DCA order spending `initial_asset` on `target_asset` can zap with `zap`, which
+/// requires a pair of the assets, and a generator set in the factory if the LP tokens are staked.
+///
+/// Returns a [`ContractError`] if the order cannot zap.
+/// ## Arguments
+/// * `querier` - The [`QuerierWrapper`] used to query the factory.
+///
+/// * `factory_addr` - The [`Addr`] of the Astroport factory.
+///
+/// * `zap` - The [`Zap`] of the DCA order.
+///
+/// * `initial_asset` - The [`AssetInfo`] spent by the DCA order.
+///
+/// * `target_asset` - The [`AssetInfo`] purchased by the DCA order.
+pub(crate) fn validate_zap(
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    zap: &Zap,
+    initial_asset: &AssetInfo,
+    target_asset: &AssetInfo,
+) -> Result<(), ContractError> {
+    query_zap_pair(querier, factory_addr, initial_asset, target_asset)?;
+
+    // the pair deposits the LP tokens into the generator of the factory
+    if zap.stake_lp {
+        let factory_config: FactoryConfigResponse =
+            querier.query_wasm_smart(factory_addr, &FactoryQueryMsg::Config {})?;
+
+        if factory_config.generator_address.is_none() {
+            return Err(ContractError::GeneratorNotConfigured {});
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amounts of `offer` and `ask` that can be provided to a pool holding `pool_offer`
+/// and `pool_ask` without changing its ratio, spending as much of both as possible.
+///
+/// An empty pool accepts any ratio, so both amounts are provided in full.
+fn get_balanced_amounts(
+    offer: Uint128,
+    ask: Uint128,
+    pool_offer: Uint128,
+    pool_ask: Uint128,
+) -> (Uint128, Uint128) {
+    if pool_offer.is_zero() || pool_ask.is_zero() {
+        return (offer, ask);
+    }
+
+    let ask_needed = offer.multiply_ratio(pool_ask, pool_offer);
+    match ask_needed <= ask {
+        true => (offer, ask_needed),
+        false => (ask.multiply_ratio(pool_offer, pool_ask), ask),
+    }
+}
+
+/// ## Description
+/// Creates a message sending `amount` of `info` held by the contract to `recipient`.
+pub(crate) fn transfer_msg(
+    info: &AssetInfo,
+    amount: Uint128,
+    recipient: &Addr,
+) -> StdResult<CosmosMsg> {
+    Ok(match info {
+        AssetInfo::NativeToken { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                amount,
+                denom: denom.clone(),
+            }],
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+/// ## Description
+/// Creates the messages providing liquidity with the `offer` and `ask` assets held by the contract
+/// after the swap of a zapping DCA purchase, at the current ratio of the pair of the assets.
+///
+/// The LP tokens are sent to `recipient`, or deposited into the generator on its behalf if the
+/// [`Zap`] stakes the LP tokens. Whatever cannot be provided at the ratio of the pool is returned,
+/// the leftover `offer` to `user` and the leftover `ask` to `recipient`.
+///
+/// The address of the pair is resolved from the cache of pair addresses, only querying the
+/// factory the first time.
+///
+/// Returns a [`ContractError`] as a failure, otherwise returns the [`Vec<CosmosMsg>`] to send.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract, caching the address of the pair.
+///
+/// * `querier` - The [`QuerierWrapper`] used to query the factory and the pair.
+///
+/// * `factory_addr` - The [`Addr`] of the Astroport factory.
+///
+/// * `zap` - The [`Zap`] of the DCA order.
+///
+/// * `offer` - The [`Asset`] of the initial asset of the order left unswapped.
+///
+/// * `ask` - The [`Asset`] of the target asset received from the swap.
+///
+/// * `user` - The [`Addr`] of the owner of the order, receiving the dust of the initial asset.
+///
+/// * `recipient` - The [`Addr`] receiving the LP tokens.
+pub(crate) fn zap_msgs(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    zap: &Zap,
+    offer: Asset,
+    ask: Asset,
+    user: &Addr,
+    recipient: &Addr,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let pair_addr = query_pair(
+        storage,
+        querier,
+        factory_addr,
+        &[offer.info.clone(), ask.info.clone()],
+    )
+    .map_err(|_| ContractError::InvalidZap {})?
+    .contract_addr;
+    let pool: PoolResponse = querier.query_wasm_smart(&pair_addr, &PairQueryMsg::Pool {})?;
+
+    let pool_amount = |info: &AssetInfo| {
+        pool.assets
+            .iter()
+            .find(|asset| &asset.info == info)
+            .map(|asset| asset.amount)
+            .unwrap_or_default()
+    };
+    let (offer_amount, ask_amount) = get_balanced_amounts(
+        offer.amount,
+        ask.amount,
+        pool_amount(&offer.info),
+        pool_amount(&ask.info),
+    );
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds = vec![];
+    for (info, amount) in [(&offer.info, offer_amount), (&ask.info, ask_amount)] {
+        match info {
+            AssetInfo::NativeToken { denom } if !amount.is_zero() => funds.push(Coin {
+                amount,
+                denom: denom.clone(),
+            }),
+            AssetInfo::NativeToken { .. } => {}
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_addr.to_string(),
+                        amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: pair_addr.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: offer.info.clone(),
+                        amount: offer_amount,
+                    },
+                    Asset {
+                        info: ask.info.clone(),
+                        amount: ask_amount,
+                    },
+                ],
+                slippage_tolerance: None,
+                auto_stake: Some(zap.stake_lp),
+                receiver: Some(recipient.to_string()),
+            })?,
+            funds,
+        }
+        .into(),
+    );
+
+    // return the dust that did not match the ratio of the pool
+    if offer.amount > offer_amount {
+        messages.push(transfer_msg(
+            &offer.info,
+            offer.amount - offer_amount,
+            user,
+        )?);
+    }
+    if ask.amount > ask_amount {
+        messages.push(transfer_msg(&ask.info, ask.amount - ask_amount, recipient)?);
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::{
+        asset::{Asset, AssetInfo, PairInfo},
+        factory::QueryMsg as FactoryQueryMsg,
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Duration, ExecuteMsg, FailurePolicy, OrderDirection, QueryMsg, Strategy, Zap,
+    };
+    use cosmwasm_std::{coin, coins, Addr, Uint128};
+    use cw20::{BalanceResponse, Cw20QueryMsg};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        error::ContractError,
+        state::{ConfigResponse, CONFIG},
+        tests::{
+            mock_astroport_instantiate, mock_creator, read_item, store_cw721_code,
+            MockCw721ExecuteMsg,
+        },
+    };
+
+    use super::{get_balanced_amounts, split_zap_amount};
+
+    #[test]
+    fn does_split_zap_amount() {
+        assert_eq!(
+            split_zap_amount(Uint128::new(10_001)),
+            (Uint128::new(5_000), Uint128::new(5_001))
+        );
+    }
+
+    #[test]
+    fn does_balance_amounts_to_pool_ratio() {
+        // too much of the offer asset
+        assert_eq!(
+            get_balanced_amounts(
+                Uint128::new(1_000),
+                Uint128::new(400),
+                Uint128::new(10_000),
+                Uint128::new(5_000)
+            ),
+            (Uint128::new(800), Uint128::new(400))
+        );
+
+        // too much of the ask asset
+        assert_eq!(
+            get_balanced_amounts(
+                Uint128::new(1_000),
+                Uint128::new(600),
+                Uint128::new(10_000),
+                Uint128::new(5_000)
+            ),
+            (Uint128::new(1_000), Uint128::new(500))
+        );
+
+        // an empty pool accepts both in full
+        assert_eq!(
+            get_balanced_amounts(
+                Uint128::new(1_000),
+                Uint128::new(600),
+                Uint128::zero(),
+                Uint128::zero()
+            ),
+            (Uint128::new(1_000), Uint128::new(600))
+        );
+    }
+
+    #[test]
+    fn does_zap_purchase_into_liquidity() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Default::default(),
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: Some(Zap { stake_lp: false }),
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let luna_before = app
+            .wrap()
+            .query_balance(&mock_creator().sender, "uluna")
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: uluna.clone(),
+                    ask_asset_info: ujpy.clone(),
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the user should have received the LP tokens of the pair
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Config {})
+            .unwrap();
+        let pair: PairInfo = app
+            .wrap()
+            .query_wasm_smart(
+                config.config.factory_addr,
+                &FactoryQueryMsg::Pair {
+                    asset_infos: [uluna, ujpy],
+                },
+            )
+            .unwrap();
+        let lp_balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                pair.liquidity_token,
+                &Cw20QueryMsg::Balance {
+                    address: mock_creator().sender.into_string(),
+                },
+            )
+            .unwrap();
+        assert!(!lp_balance.balance.is_zero());
+
+        // only the remaining funds of the order should be held by the contract, with the dust
+        // returned to the user
+        let query_balance = |addr: &Addr, denom: &str| app.wrap().query_balance(addr, denom);
+        assert_eq!(
+            query_balance(&dca_addr, "uluna").unwrap(),
+            coin(90_000, "uluna")
+        );
+        assert_eq!(query_balance(&dca_addr, "ujpy").unwrap(), coin(0, "ujpy"));
+        assert!(
+            query_balance(&mock_creator().sender, "uluna")
+                .unwrap()
+                .amount
+                >= luna_before.amount
+        );
+    }
+
+    #[test]
+    fn does_zap_purchase_for_nft_holder() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let cw721_id = store_cw721_code(&mut app);
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::InstantiateNft {
+                code_id: cw721_id,
+                name: "DCA positions".to_string(),
+                symbol: "DCA".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let nft_addr = read_item(&app, &dca_addr, CONFIG).nft_addr.unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ujpy.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Default::default(),
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: Some(Zap { stake_lp: false }),
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        // sell the position by transferring its NFT before the purchase
+        app.execute_contract(
+            mock_creator().sender,
+            nft_addr,
+            &MockCw721ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "creator/1".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: uluna.clone(),
+                    ask_asset_info: ujpy.clone(),
+                }],
+                fee_redeem: vec![],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the holder of the NFT should have received the LP tokens rather than the user
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Config {})
+            .unwrap();
+        let pair: PairInfo = app
+            .wrap()
+            .query_wasm_smart(
+                config.config.factory_addr,
+                &FactoryQueryMsg::Pair {
+                    asset_infos: [uluna, ujpy],
+                },
+            )
+            .unwrap();
+        let lp_balance = |address: &str| -> BalanceResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &pair.liquidity_token,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap()
+        };
+        assert!(!lp_balance("buyer").balance.is_zero());
+        assert!(lp_balance(mock_creator().sender.as_str()).balance.is_zero());
+    }
+
+    #[test]
+    fn cannot_stake_lp_without_generator() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                dca_addr,
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(100_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Default::default(),
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: Some(Zap { stake_lp: true }),
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(100_000, "uluna"),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            res.downcast::<ContractError>().unwrap(),
+            ContractError::GeneratorNotConfigured {}
+        );
+    }
+}
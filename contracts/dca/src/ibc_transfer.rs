@@ -0,0 +1,86 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, Timestamp};
+
+use crate::protobuf::{
+    decode_field, decode_key, decode_varint, encode_bytes, encode_key, encode_string, encode_varint,
+};
+
+/// The type url of the ICS-20 transfer message of ibc-go
+const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+
+/// The port ICS-20 transfers are sent from
+const TRANSFER_PORT: &str = "transfer";
+
+/// Describes an ICS-20 transfer sent by the contract to an address on another chain
+pub struct Transfer {
+    /// The channel the transfer is sent over
+    pub channel_id: String,
+    /// The address on the other chain receiving the transfer
+    pub to_address: String,
+    /// The native coin transferred
+    pub amount: Coin,
+    /// The time after which the transfer times out
+    pub timeout: Timestamp,
+    /// The address of the contract the ibc-hooks callback reporting the outcome of the transfer
+    /// is sent to
+    pub sender: String,
+}
+
+/// ## Description
+/// Returns the message sending `transfer` through the transfer module of ibc-go, with a memo
+/// asking ibc-hooks to report the acknowledgement or timeout of the transfer to the sender.
+///
+/// The message is encoded by hand, as [`cosmwasm_std::IbcMsg::Transfer`] cannot carry a memo.
+pub fn transfer_msg(transfer: &Transfer) -> CosmosMsg {
+    let memo = format!(r#"{{"ibc_callback":"{}"}}"#, transfer.sender);
+
+    let mut token = vec![];
+    encode_string(&mut token, 1, &transfer.amount.denom);
+    encode_string(&mut token, 2, &transfer.amount.amount.to_string());
+
+    let mut value = vec![];
+    encode_string(&mut value, 1, TRANSFER_PORT);
+    encode_string(&mut value, 2, &transfer.channel_id);
+    encode_bytes(&mut value, 3, &token);
+    encode_string(&mut value, 4, &transfer.sender);
+    encode_string(&mut value, 5, &transfer.to_address);
+    encode_key(&mut value, 7, 0);
+    encode_varint(&mut value, transfer.timeout.nanos());
+    encode_string(&mut value, 8, &memo);
+
+    CosmosMsg::Stargate {
+        type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+        value: Binary(value),
+    }
+}
+
+/// ## Description
+/// Returns the sequence of the transfer sent by [`transfer_msg`] from the data of its response,
+/// which is either the `MsgTransferResponse` itself or wrapped in an `Any`.
+///
+/// Returns [`None`] if the data does not contain a sequence.
+pub fn transfer_sequence(data: &[u8]) -> Option<u64> {
+    let mut data = data;
+
+    // unwrap the value of an any, which starts with its type url
+    if data.first() == Some(&((1 << 3) | 2)) {
+        let mut any = data;
+        while !any.is_empty() {
+            let (field, wire_type) = decode_key(&mut any)?;
+            let field_value = decode_field(&mut any, wire_type)?;
+            if field == 2 && wire_type == 2 {
+                data = field_value;
+                break;
+            }
+        }
+    }
+
+    while !data.is_empty() {
+        let (field, wire_type) = decode_key(&mut data)?;
+        if field == 1 && wire_type == 0 {
+            return decode_varint(&mut data);
+        }
+        decode_field(&mut data, wire_type)?;
+    }
+
+    None
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::{Addr, Env};
+
+/// ## Description
+/// Returns a pseudo-random delay of up to `window` for the next DCA purchase of the order `id` of
+/// `user`, derived from the height, time and transaction index of the current block.
+///
+/// The delay can be computed by anyone once the block is known, so it only prevents the time of a
+/// purchase from being predicted before the previous purchase is performed.
+/// ## Arguments
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `user` - The [`Addr`] of the user who owns the order.
+///
+/// * `id` - The [`u64`] ID of the order.
+///
+/// * `window` - The [`u64`] maximum delay, in the unit of the interval of the order.
+pub fn jitter_offset(env: &Env, user: &Addr, id: u64, window: u64) -> u64 {
+    let seed = user
+        .as_bytes()
+        .iter()
+        .fold(mix(id), |seed, byte| mix(seed ^ *byte as u64));
+    let seed = mix(seed ^ env.block.height);
+    let seed = mix(seed ^ env.block.time.nanos());
+    let seed = mix(seed ^ env.transaction.as_ref().map_or(0, |tx| tx.index as u64));
+
+    seed % window.saturating_add(1)
+}
+
+/// ## Description
+/// The finalizer of the splitmix64 generator, which spreads every bit of `x` over the result.
+fn mix(x: u64) -> u64 {
+    let x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
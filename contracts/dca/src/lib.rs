@@ -5,7 +5,27 @@ pub mod state;
 mod handlers;
 mod queries;
 
+mod build_route;
+mod croncat;
+mod cw4;
+mod cw721;
+mod find_best_route;
+mod get_swap_simulation;
 mod get_token_allowance;
+mod ibc_transfer;
+mod jitter;
+mod oracle;
+mod pool_type;
+mod price_impact;
+mod protobuf;
+mod query_pair;
+mod reconciliation;
+mod refund_order;
+mod stats;
+mod swap_backend;
+mod token_decimals;
+mod validate_liquidity;
+mod validate_token;
 
 #[cfg(test)]
 pub(crate) mod tests;
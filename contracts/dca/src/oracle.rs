@@ -0,0 +1,37 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes the queries sent to a price oracle, matching the `consult` query of the Astroport
+/// TWAP oracle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    /// Returns the [`Uint256`] amount of the other asset of the oracle that `amount` of `token`
+    /// is worth at the average price
+    Consult { token: AssetInfo, amount: Uint128 },
+}
+
+/// ## Description
+/// Returns the amount of the other asset of the oracle at `oracle_addr` that `amount` of `token`
+/// is worth, as a [`Uint128`].
+/// ## Arguments
+/// * `querier` - The [`QuerierWrapper`] used to query the oracle.
+///
+/// * `oracle_addr` - The address of the oracle contract.
+///
+/// * `token` - The [`AssetInfo`] of the asset being priced.
+///
+/// * `amount` - The [`Uint128`] amount of `token` being priced.
+pub fn consult_oracle(
+    querier: &QuerierWrapper,
+    oracle_addr: &Addr,
+    token: AssetInfo,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let amount: Uint256 =
+        querier.query_wasm_smart(oracle_addr, &OracleQueryMsg::Consult { token, amount })?;
+
+    Ok(Uint128::try_from(amount)?)
+}
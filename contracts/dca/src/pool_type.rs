@@ -0,0 +1,98 @@
+use astroport::{factory::PairType, router::SwapOperation};
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, Storage, Uint128};
+
+use crate::{error::ContractError, query_pair::query_pair};
+
+/// The custom pair type of the Astroport concentrated liquidity pools
+const CONCENTRATED_PAIR_TYPE: &str = "concentrated";
+
+/// The highest spread accepted by each Astroport pair, passed to the router for routes whose
+/// slippage is bounded by their minimum receive instead
+const MAX_ALLOWED_SPREAD: u64 = 50;
+
+/// ## Description
+/// Returns whether every pair swapped through by the `hops` of a DCA purchase is a constant
+/// product pool. Stable and concentrated liquidity pools are priced by their invariant rather than
+/// by the ratio of their reserves, so the spread each of their swaps is checked against does not
+/// measure the slippage of the swap like it does for a constant product pool.
+///
+/// Returns a [`ContractError::UnsupportedPoolType`] if a pair is of any other pool type.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract, caching the pairs.
+///
+/// * `querier` - The [`QuerierWrapper`] used to query the factory.
+///
+/// * `factory_addr` - The [`Addr`] of the Astroport factory.
+///
+/// * `hops` - The [`SwapOperation`] hops of the route of the purchase.
+pub fn is_xyk_route(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    hops: &[SwapOperation],
+) -> Result<bool, ContractError> {
+    let mut xyk = true;
+
+    for hop in hops {
+        if let SwapOperation::AstroSwap {
+            offer_asset_info,
+            ask_asset_info,
+        } = hop
+        {
+            let pair = query_pair(
+                storage,
+                querier,
+                factory_addr,
+                &[offer_asset_info.clone(), ask_asset_info.clone()],
+            )?;
+
+            match pair.pair_type {
+                PairType::Xyk {} => {}
+                PairType::Stable {} => xyk = false,
+                PairType::Custom(pair_type) if pair_type == CONCENTRATED_PAIR_TYPE => xyk = false,
+                pair_type => {
+                    return Err(ContractError::UnsupportedPoolType {
+                        pair_type: pair_type.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    Ok(xyk)
+}
+
+/// ## Description
+/// Returns the maximum spread and the minimum receive of the router swap of a DCA purchase.
+///
+/// A route of constant product pools checks the spread of each of its swaps against `max_spread`.
+/// A route through a stable or concentrated liquidity pool instead bounds the slippage of the
+/// whole route, requiring at least the amount it is `simulated` to return less `max_spread`, and
+/// only passes the highest spread accepted by the pairs along.
+/// ## Arguments
+/// * `max_spread` - The [`Decimal`] maximum spread of the order.
+///
+/// * `min_receive` - The minimum amount of the order to receive from the purchase.
+///
+/// * `simulated` - The [`Uint128`] amount a route that is not of constant product pools is
+/// simulated to return, or [`None`] for a route of constant product pools.
+pub fn swap_params(
+    max_spread: Decimal,
+    min_receive: Option<Uint128>,
+    simulated: Option<Uint128>,
+) -> (Decimal, Option<Uint128>) {
+    let simulated = match simulated {
+        Some(simulated) => simulated,
+        None => return (max_spread, min_receive),
+    };
+
+    let route_min_receive = match max_spread < Decimal::one() {
+        true => simulated * (Decimal::one() - max_spread),
+        false => Uint128::zero(),
+    };
+
+    (
+        Decimal::percent(MAX_ALLOWED_SPREAD),
+        Some(route_min_receive.max(min_receive.unwrap_or_default())),
+    )
+}
@@ -0,0 +1,86 @@
+use astroport::{
+    asset::AssetInfo,
+    pair::{PoolResponse, QueryMsg as PairQueryMsg},
+    router::SwapOperation,
+};
+use cosmwasm_std::{Decimal, Deps, StdResult, Uint128};
+
+use crate::{query_pair::load_pair, state::Config};
+
+/// ## Description
+/// Returns the amount that `amount` swapped through `hops` is worth at the ratio of the reserves
+/// of each pair, before their fees and slippage.
+///
+/// Returns [`None`] if a hop is not swapped through an Astroport pair holding its offer asset.
+pub fn reserve_value(
+    deps: &Deps,
+    config: &Config,
+    amount: Uint128,
+    hops: &[SwapOperation],
+) -> StdResult<Option<Uint128>> {
+    let mut amount = amount;
+
+    for hop in hops {
+        let (offer_asset_info, ask_asset_info) = match hop {
+            SwapOperation::AstroSwap {
+                offer_asset_info,
+                ask_asset_info,
+            } => (offer_asset_info, ask_asset_info),
+            SwapOperation::NativeSwap { .. } => return Ok(None),
+        };
+
+        let pair = load_pair(
+            deps.storage,
+            &deps.querier,
+            &config.factory_addr,
+            &[offer_asset_info.clone(), ask_asset_info.clone()],
+        )?;
+        let pool: PoolResponse = deps
+            .querier
+            .query_wasm_smart(&pair.contract_addr, &PairQueryMsg::Pool {})?;
+
+        let reserve = |info: &AssetInfo| {
+            pool.assets
+                .iter()
+                .find(|asset| &asset.info == info)
+                .map_or(Uint128::zero(), |asset| asset.amount)
+        };
+        let offer_reserve = reserve(offer_asset_info);
+        if offer_reserve.is_zero() {
+            return Ok(None);
+        }
+
+        amount = amount.multiply_ratio(reserve(ask_asset_info), offer_reserve);
+    }
+
+    Ok(Some(amount))
+}
+
+/// ## Description
+/// Returns the price impact of swapping `amount` through `hops` for the `simulated` amount, which
+/// is the share of the value of the swap at the ratio of the reserves of its pairs that is lost
+/// to their fees and slippage.
+///
+/// Returns [`None`] if the price impact cannot be measured, as a hop is not swapped through an
+/// Astroport pair holding its assets.
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `config` - The [`Config`] of the contract, containing the factory.
+///
+/// * `amount` - The [`Uint128`] amount of the first asset of the route being swapped.
+///
+/// * `hops` - The [`SwapOperation`] hops of the route of the swap.
+///
+/// * `simulated` - The [`Uint128`] amount that the swap is simulated to return.
+pub fn get_price_impact(
+    deps: &Deps,
+    config: &Config,
+    amount: Uint128,
+    hops: &[SwapOperation],
+    simulated: Uint128,
+) -> StdResult<Option<Decimal>> {
+    Ok(reserve_value(deps, config, amount, hops)?
+        .filter(|value| !value.is_zero())
+        .map(|value| Decimal::from_ratio(value.saturating_sub(simulated), value)))
+}
@@ -0,0 +1,68 @@
+/// Appends the key of `field` with `wire_type` to `buf`
+pub fn encode_key(buf: &mut Vec<u8>, field: u64, wire_type: u64) {
+    encode_varint(buf, (field << 3) | wire_type);
+}
+
+/// Appends `value` encoded as a varint to `buf`
+pub fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Appends the length delimited `bytes` of `field` to `buf`
+pub fn encode_bytes(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    encode_key(buf, field, 2);
+    encode_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Appends the string `value` of `field` to `buf`
+pub fn encode_string(buf: &mut Vec<u8>, field: u64, value: &str) {
+    encode_bytes(buf, field, value.as_bytes());
+}
+
+/// Reads a varint from the start of `data`, advancing past it
+pub fn decode_varint(data: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = data.split_first()?;
+        *data = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Reads the field and wire type of the key at the start of `data`, advancing past it
+pub fn decode_key(data: &mut &[u8]) -> Option<(u64, u64)> {
+    let key = decode_varint(data)?;
+
+    Some((key >> 3, key & 0x7))
+}
+
+/// Skips the value of a field of `wire_type`, returning the bytes of a length delimited value
+pub fn decode_field<'a>(data: &mut &'a [u8], wire_type: u64) -> Option<&'a [u8]> {
+    let len = match wire_type {
+        0 => {
+            decode_varint(data)?;
+            return Some(&[]);
+        }
+        1 => 8,
+        2 => decode_varint(data)? as usize,
+        5 => 4,
+        _ => return None,
+    };
+    if data.len() < len {
+        return None;
+    }
+
+    let (value, rest) = data.split_at(len);
+    *data = rest;
+    Some(value)
+}
@@ -0,0 +1,201 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::UserOrdersInfo;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::user_dca;
+
+/// The default amount of users returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of users that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the users that currently have DCA orders, along with the amount of orders of each user.
+///
+/// The result is returned in a [`Vec<UserOrdersInfo>`] object ordered by the user address.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `start_after` - An optional user address as a [`String`] to start after.
+///
+/// * `limit` - An optional [`u32`] amount of users to return.
+pub fn get_all_users(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<UserOrdersInfo>> {
+    let start_after = start_after
+        .map(|user| addr_validate_to_lower(deps.api, &user))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // skip every order of the user to start after
+    let keys = user_dca().keys(
+        deps.storage,
+        start_after
+            .as_ref()
+            .map(|user| Bound::exclusive((user, u64::MAX))),
+        None,
+        Order::Ascending,
+    );
+
+    // orders are keyed by the user first, so the orders of each user are next to each other
+    let mut users: Vec<UserOrdersInfo> = vec![];
+    for key in keys {
+        let (user, _) = key?;
+
+        match users.last_mut() {
+            Some(last) if last.user == user => last.orders += 1,
+            _ => {
+                if users.len() == limit {
+                    break;
+                }
+
+                users.push(UserOrdersInfo { user, orders: 1 });
+            }
+        }
+    }
+
+    Ok(users)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, QueryMsg, Strategy,
+        UserOrdersInfo,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::query, state::user_dca};
+
+    #[test]
+    fn does_get_all_users() {
+        let mut deps = mock_dependencies();
+
+        let order = |id: u64| DcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            last_purchase: 0,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+
+        let users = vec![
+            Addr::unchecked("user_a"),
+            Addr::unchecked("user_b"),
+            Addr::unchecked("user_c"),
+        ];
+        for (i, user) in users.iter().enumerate() {
+            for id in 1..=(i as u64 + 1) {
+                user_dca()
+                    .save(&mut deps.storage, (user, id), &order(id))
+                    .unwrap();
+            }
+        }
+
+        let res: Vec<UserOrdersInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllUsers {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                UserOrdersInfo {
+                    user: users[0].clone(),
+                    orders: 1
+                },
+                UserOrdersInfo {
+                    user: users[1].clone(),
+                    orders: 2
+                },
+            ]
+        );
+
+        let res: Vec<UserOrdersInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllUsers {
+                    start_after: Some(users[1].to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![UserOrdersInfo {
+                user: users[2].clone(),
+                orders: 3
+            }]
+        );
+    }
+}
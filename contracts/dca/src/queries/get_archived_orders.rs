@@ -0,0 +1,162 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::ArchivedOrder;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::ARCHIVED_ORDERS;
+
+/// The default amount of archived orders returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of archived orders that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the DCA orders of a user that were archived once they were completed, starting after
+/// the order with the id `start_after`.
+///
+/// The result is returned in a [`Vec<ArchivedOrder>`] object ordered by the id of each order.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The users lowercase address as a [`String`].
+///
+/// * `start_after` - An optional [`u64`] id of the order to start after.
+///
+/// * `limit` - An optional [`u32`] amount of orders to return.
+pub fn get_archived_orders(
+    deps: Deps,
+    user: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ArchivedOrder>> {
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    ARCHIVED_ORDERS
+        .prefix(&user_address)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, archived)| archived))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        ArchivedOrder, DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, QueryMsg,
+        Strategy,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::query, state::ARCHIVED_ORDERS};
+
+    #[test]
+    fn does_get_archived_orders() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let user = Addr::unchecked("user_addr");
+        let archived = |id: u64| ArchivedOrder {
+            order: DcaInfo {
+                id,
+                initial_asset: Asset {
+                    amount: Uint128::zero(),
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ukrw".to_string(),
+                },
+                interval: Duration::Time(1_000),
+                last_purchase: env.block.time.seconds(),
+                dca_amount: Uint128::new(10_000),
+                escrowed: false,
+                paused: false,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                total_spent: Uint128::new(30_000),
+                total_received: Uint128::new(60_000),
+                targets_received: vec![],
+                executing: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                jitter_offset: 0,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                target_value: Uint128::zero(),
+                max_purchases: None,
+                pull: false,
+                last_failure: None,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                consecutive_failures: 0,
+                replenished_tip: Uint128::zero(),
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+            },
+            purchases: 3,
+            completed_at: env.block.time.seconds(),
+        };
+
+        for id in 1..=3 {
+            ARCHIVED_ORDERS
+                .save(&mut deps.storage, (&user, id), &archived(id))
+                .unwrap();
+        }
+        // an order of another user should not be returned
+        ARCHIVED_ORDERS
+            .save(
+                &mut deps.storage,
+                (&Addr::unchecked("other_user"), 1),
+                &archived(1),
+            )
+            .unwrap();
+
+        let res: Vec<ArchivedOrder> = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::ArchivedOrders {
+                    user: user.into_string(),
+                    start_after: Some(1),
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res, vec![archived(2)]);
+    }
+}
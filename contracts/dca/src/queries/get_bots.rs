@@ -0,0 +1,104 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::BotInfo;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::BOTS;
+
+/// The default amount of bots returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of bots that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the registered bots along with the performance of their DCA purchases, starting after
+/// the bot address `start_after`.
+///
+/// The result is returned in a [`Vec<BotInfo>`] object ordered by the bot address.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `start_after` - An optional bot address as a [`String`] to start after.
+///
+/// * `limit` - An optional [`u32`] amount of bots to return.
+pub fn get_bots(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BotInfo>> {
+    let start_after = start_after
+        .map(|bot| addr_validate_to_lower(deps.api, &bot))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    BOTS.range(
+        deps.storage,
+        start_after.as_ref().map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    )
+    .take(limit)
+    .map(|item| {
+        item.map(|(bot, stats)| BotInfo {
+            bot,
+            average_delay: stats.average_delay(),
+            stats,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::{BotInfo, BotStats, QueryMsg};
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::BOTS};
+
+    #[test]
+    fn does_get_bots() {
+        let mut deps = mock_dependencies();
+
+        let stats = |purchases: u64| BotStats {
+            registered_at: 1_000,
+            purchases,
+            failures: 1,
+            total_delay: 90,
+            bond: None,
+            unbonding_at: None,
+            slashes: 0,
+        };
+        for (bot, purchases) in [("bot_a", 0), ("bot_b", 3), ("bot_c", 9)] {
+            BOTS.save(&mut deps.storage, &Addr::unchecked(bot), &stats(purchases))
+                .unwrap();
+        }
+
+        let res: Vec<BotInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Bots {
+                    start_after: Some("bot_a".to_string()),
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        // the average delay is taken over the purchases performed by the bot
+        assert_eq!(
+            res,
+            vec![BotInfo {
+                bot: Addr::unchecked("bot_b"),
+                stats: stats(3),
+                average_delay: 30,
+            }]
+        );
+    }
+}
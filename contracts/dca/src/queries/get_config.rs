@@ -1,16 +1,20 @@
 use cosmwasm_std::{Deps, StdResult};
 
-use crate::state::{Config, CONFIG};
+use crate::state::{ConfigResponse, CONFIG, OWNERSHIP_PROPOSAL};
 
 /// ## Description
-/// Returns the contract configuration set by the factory address owner or contract instantiator.
+/// Returns the contract configuration set by the contract owner or contract instantiator, along
+/// with the pending proposal of a new owner of the contract.
 ///
-/// The result is returned in a [`Config`] object.
+/// The result is returned in a [`ConfigResponse`] object.
 ///
 /// ## Arguments
 /// * `deps` - A [`Deps`] that contains the dependencies.
-pub fn get_config(deps: Deps) -> StdResult<Config> {
-    CONFIG.load(deps.storage)
+pub fn get_config(deps: Deps) -> StdResult<ConfigResponse> {
+    Ok(ConfigResponse {
+        config: CONFIG.load(deps.storage)?,
+        ownership_proposal: OWNERSHIP_PROPOSAL.may_load(deps.storage)?,
+    })
 }
 
 #[cfg(test)]
@@ -18,7 +22,7 @@ mod test {
     use std::str::FromStr;
 
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::QueryMsg;
+    use astroport_dca::dca::{QueryMsg, SwapBackendType};
     use cosmwasm_std::{
         from_binary,
         testing::{mock_dependencies, mock_env},
@@ -27,7 +31,7 @@ mod test {
 
     use crate::{
         contract::query,
-        state::{Config, CONFIG},
+        state::{Config, ConfigResponse, CONFIG},
     };
 
     #[test]
@@ -48,12 +52,40 @@ mod test {
             whitelisted_tokens: vec![AssetInfo::NativeToken {
                 denom: "ukrw".to_string(),
             }],
+            staking_addr: None,
+            protocol_fee_bps: 0,
+            fee_collector: None,
+            fee_tiers: vec![],
+            referral_share: Decimal::zero(),
+            owner: None,
+            config_timelock: 0,
+            paused: false,
+            paused_assets: vec![],
+            nft_addr: None,
+            max_oracle_deviation: None,
+            registered_bots_only: false,
+            bot_bond: None,
+            fairness_window: None,
+            allowed_assets: vec![],
+            allowed_assets_only: false,
+            blacklisted_assets: vec![],
+            min_liquidity: None,
+            fee_on_transfer_tokens: vec![],
+            min_deposit: None,
+            min_dca_amount: None,
+            swap_backend: SwapBackendType::Router,
         };
 
         CONFIG.save(&mut deps.storage, &saved_config).unwrap();
 
-        let res: Config =
+        let res: ConfigResponse =
             from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
-        assert_eq!(res, saved_config);
+        assert_eq!(
+            res,
+            ConfigResponse {
+                config: saved_config,
+                ownership_proposal: None
+            }
+        );
     }
 }
@@ -0,0 +1,66 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::InterchainController;
+use cosmwasm_std::{Deps, StdResult};
+
+use crate::state::INTERCHAIN_CONTROLLERS;
+
+/// ## Description
+/// Returns the controller on a remote chain that `user` declared it is the interchain account of.
+///
+/// The result is returned in an [`Option<InterchainController>`] object, which is [`None`] if the
+/// user has not declared a controller.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The address of the user whose controller is returned.
+pub fn get_interchain_controller(
+    deps: Deps,
+    user: String,
+) -> StdResult<Option<InterchainController>> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    INTERCHAIN_CONTROLLERS.may_load(deps.storage, &user)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::{InterchainController, QueryMsg};
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::INTERCHAIN_CONTROLLERS};
+
+    #[test]
+    fn does_get_interchain_controller() {
+        let mut deps = mock_dependencies();
+
+        let controller = InterchainController {
+            connection_id: "connection-0".to_string(),
+            address: "osmo1controller".to_string(),
+        };
+        INTERCHAIN_CONTROLLERS
+            .save(deps.as_mut().storage, &Addr::unchecked("ica"), &controller)
+            .unwrap();
+
+        let get_controller = |user: &str| -> Option<InterchainController> {
+            from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::InterchainController {
+                        user: user.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(get_controller("ica"), Some(controller));
+        assert_eq!(get_controller("user"), None);
+    }
+}
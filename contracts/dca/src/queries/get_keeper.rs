@@ -0,0 +1,43 @@
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+use crate::state::KEEPER;
+
+/// ## Description
+/// Returns the tasks contract of the automation protocol that keeper jobs of DCA orders are
+/// registered on, if one has been set.
+///
+/// The result is returned in an [`Option<Addr>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+pub fn get_keeper(deps: Deps) -> StdResult<Option<Addr>> {
+    KEEPER.may_load(deps.storage)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::KEEPER};
+
+    #[test]
+    fn does_get_keeper() {
+        let mut deps = mock_dependencies();
+
+        let keeper: Option<Addr> =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Keeper {}).unwrap()).unwrap();
+        assert_eq!(keeper, None);
+
+        KEEPER
+            .save(deps.as_mut().storage, &Addr::unchecked("croncat"))
+            .unwrap();
+        let keeper: Option<Addr> =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Keeper {}).unwrap()).unwrap();
+        assert_eq!(keeper, Some(Addr::unchecked("croncat")));
+    }
+}
@@ -0,0 +1,63 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+use crate::state::MEMBER_GROUPS;
+
+/// ## Description
+/// Returns the cw4 group whose members can claim the purchases of and pause and resume the DCA
+/// orders of `user` on their behalf.
+///
+/// The result is returned in an [`Option<Addr>`] object, which is [`None`] if the user has not set
+/// a group.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The address of the user whose group is returned.
+pub fn get_member_group(deps: Deps, user: String) -> StdResult<Option<Addr>> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    MEMBER_GROUPS.may_load(deps.storage, &user)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::MEMBER_GROUPS};
+
+    #[test]
+    fn does_get_member_group() {
+        let mut deps = mock_dependencies();
+
+        MEMBER_GROUPS
+            .save(
+                deps.as_mut().storage,
+                &Addr::unchecked("dao"),
+                &Addr::unchecked("group"),
+            )
+            .unwrap();
+
+        let get_group = |user: &str| -> Option<Addr> {
+            from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::MemberGroup {
+                        user: user.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(get_group("dao"), Some(Addr::unchecked("group")));
+        assert_eq!(get_group("user"), None);
+    }
+}
@@ -0,0 +1,62 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+use crate::state::NOTIFIERS;
+
+/// ## Description
+/// Returns the contract notified of the lifecycle events of the DCA orders of `user`.
+///
+/// The result is returned in an [`Option<Addr>`] object, which is [`None`] if the user has not set
+/// a notifier.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The address of the user whose notifier is returned.
+pub fn get_notifier(deps: Deps, user: String) -> StdResult<Option<Addr>> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    NOTIFIERS.may_load(deps.storage, &user)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::NOTIFIERS};
+
+    #[test]
+    fn does_get_notifier() {
+        let mut deps = mock_dependencies();
+
+        NOTIFIERS
+            .save(
+                deps.as_mut().storage,
+                &Addr::unchecked("user"),
+                &Addr::unchecked("notifier"),
+            )
+            .unwrap();
+
+        let get_notifier = |user: &str| -> Option<Addr> {
+            from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Notifier {
+                        user: user.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(get_notifier("user"), Some(Addr::unchecked("notifier")));
+        assert_eq!(get_notifier("other"), None);
+    }
+}
@@ -0,0 +1,65 @@
+use astroport::asset::addr_validate_to_lower;
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+
+use crate::state::OPERATORS;
+
+/// ## Description
+/// Returns the operators allowed to modify, pause, resume and cancel the DCA orders of `user` on
+/// their behalf.
+///
+/// The result is returned in a [`Vec<Addr>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The address of the user whose operators are returned.
+pub fn get_operators(deps: Deps, user: String) -> StdResult<Vec<Addr>> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    OPERATORS
+        .prefix(&user)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::OPERATORS};
+
+    #[test]
+    fn does_get_operators() {
+        let mut deps = mock_dependencies();
+
+        let user = Addr::unchecked("user");
+        for operator in ["bot", "dao"] {
+            OPERATORS
+                .save(
+                    deps.as_mut().storage,
+                    (&user, &Addr::unchecked(operator)),
+                    &true,
+                )
+                .unwrap();
+        }
+
+        let res: Vec<Addr> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Operators {
+                    user: "user".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(res, vec![Addr::unchecked("bot"), Addr::unchecked("dao")]);
+    }
+}
@@ -0,0 +1,78 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+use crate::state::ORACLES;
+
+/// ## Description
+/// Returns the oracle contract that the DCA purchases spending `initial_asset` for `target_asset`
+/// are checked against, if one has been set.
+///
+/// The result is returned in an [`Option<Addr>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `initial_asset` - The [`AssetInfo`] spent by the DCA orders of the pair.
+///
+/// * `target_asset` - The [`AssetInfo`] purchased by the DCA orders of the pair.
+pub fn get_oracle(
+    deps: Deps,
+    initial_asset: AssetInfo,
+    target_asset: AssetInfo,
+) -> StdResult<Option<Addr>> {
+    ORACLES.may_load(
+        deps.storage,
+        (initial_asset.to_string(), target_asset.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::AssetInfo;
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    use crate::{contract::query, state::ORACLES};
+
+    #[test]
+    fn does_get_oracle() {
+        let mut deps = mock_dependencies();
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        ORACLES
+            .save(
+                deps.as_mut().storage,
+                (uluna.to_string(), ujpy.to_string()),
+                &Addr::unchecked("oracle"),
+            )
+            .unwrap();
+
+        let oracle = |initial_asset: &AssetInfo, target_asset: &AssetInfo| {
+            from_binary::<Option<Addr>>(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Oracle {
+                        initial_asset: initial_asset.clone(),
+                        target_asset: target_asset.clone(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(oracle(&uluna, &ujpy), Some(Addr::unchecked("oracle")));
+        // the oracle only guards the pair in the direction it was set for
+        assert_eq!(oracle(&ujpy, &uluna), None);
+    }
+}
@@ -0,0 +1,350 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::{OrderStatsResponse, Strategy};
+use cosmwasm_std::{
+    ConversionOverflowError, Decimal, Deps, Env, OverflowError, OverflowOperation, StdResult,
+};
+
+use crate::state::{get_purchase_count, user_dca};
+
+/// ## Description
+/// Returns the statistics of a DCA order of a user, based on the amounts spent and received in
+/// the purchases performed for it.
+///
+/// The result is returned in an [`OrderStatsResponse`] object. The estimated completion assumes
+/// that every remaining purchase is performed as soon as the interval or schedule of the order
+/// allows it.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `user` - The users lowercase address as a [`String`].
+///
+/// * `id` - A [`u64`] representing the ID of the DCA order for the user.
+pub fn get_order_stats(
+    deps: Deps,
+    env: Env,
+    user: String,
+    id: u64,
+) -> StdResult<OrderStatsResponse> {
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+
+    let order = user_dca().load(deps.storage, (&user_address, id))?;
+    let purchases = get_purchase_count(deps.storage, &user_address, id)?;
+
+    // the amount spent by an order with several target assets is spread across all of them, so it
+    // has no single average price
+    let average_price = match order.total_received.is_zero() || !order.targets.is_empty() {
+        true => None,
+        false => Some(Decimal::from_ratio(order.total_spent, order.total_received)),
+    };
+
+    // orders are created with a non zero dca amount, where the last purchase spends the remainder
+    // of the deposit, unless each purchase spends one of the remaining tranches of the order
+    let remaining_purchases = match &order.strategy {
+        Strategy::Tranches { amounts } => amounts.len() as u64,
+        _ => {
+            let remainder = order.initial_asset.amount % order.dca_amount;
+            let purchases = order.initial_asset.amount / order.dca_amount;
+            u64::try_from(purchases.u128())
+                .ok()
+                .and_then(|purchases| purchases.checked_add(u64::from(!remainder.is_zero())))
+                .ok_or_else(|| {
+                    ConversionOverflowError::new("Uint128", "u64", purchases.to_string())
+                })?
+        }
+    };
+
+    // an order with a maximum amount of purchases is closed once it has performed them
+    let remaining_purchases = match order.max_purchases {
+        Some(max) => remaining_purchases.min(max.saturating_sub(purchases)),
+        None => remaining_purchases,
+    };
+
+    // the next purchase can be performed once the interval has passed, or now if it already has
+    // and has not been skipped, measured in seconds or blocks depending on the interval
+    let next_purchase = order.upcoming_purchase(&env.block);
+    let estimated_completion = match (remaining_purchases, &order.schedule) {
+        (0, _) => None,
+        // the times of a schedule are not evenly spaced, so each purchase is found in turn
+        (remaining, Some(_)) => {
+            Some((1..remaining).fold(next_purchase, |purchase, _| order.purchase_after(purchase)))
+        }
+        (remaining, None) => {
+            let interval = order.interval.value();
+            let completion = (remaining - 1)
+                .checked_mul(interval)
+                .and_then(|duration| next_purchase.checked_add(duration))
+                .ok_or_else(|| {
+                    OverflowError::new(OverflowOperation::Mul, remaining - 1, interval)
+                })?;
+            Some(completion)
+        }
+    };
+
+    Ok(OrderStatsResponse {
+        purchases,
+        total_spent: order.total_spent,
+        total_received: order.total_received,
+        targets_received: order.targets_received,
+        average_price,
+        remaining_purchases,
+        estimated_completion,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, OrderStatsResponse,
+        PurchaseRecord, QueryMsg, Schedule, Strategy,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Decimal, StdError, StdResult, Uint128,
+    };
+
+    use crate::{
+        contract::query,
+        state::{user_dca, PURCHASE_HISTORY},
+    };
+
+    #[test]
+    fn does_get_order_stats() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let user = Addr::unchecked("user_addr");
+        user_dca()
+            .save(
+                &mut deps.storage,
+                (&user, 1),
+                &DcaInfo {
+                    id: 1,
+                    initial_asset: Asset {
+                        amount: Uint128::new(30_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    target_asset: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    interval: Duration::Time(1_000),
+                    last_purchase: env.block.time.seconds(),
+                    dca_amount: Uint128::new(10_000),
+                    escrowed: false,
+                    paused: false,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    total_spent: Uint128::new(20_000),
+                    total_received: Uint128::new(40_000),
+                    targets_received: vec![],
+                    executing: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    jitter_offset: 0,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    target_value: Uint128::zero(),
+                    max_purchases: None,
+                    pull: false,
+                    last_failure: None,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    consecutive_failures: 0,
+                    replenished_tip: Uint128::zero(),
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                },
+            )
+            .unwrap();
+
+        for seq in 1..=2 {
+            PURCHASE_HISTORY
+                .save(
+                    &mut deps.storage,
+                    (&user, 1, seq),
+                    &PurchaseRecord {
+                        timestamp: env.block.time.seconds(),
+                        amount_in: Uint128::new(10_000),
+                        amount_out: Uint128::new(20_000),
+                        outputs: vec![],
+                        bot: Addr::unchecked("bot_user"),
+                        tip: vec![],
+                    },
+                )
+                .unwrap();
+        }
+
+        let res: OrderStatsResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::OrderStats {
+                    user: user.into_string(),
+                    id: 1,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            OrderStatsResponse {
+                purchases: 2,
+                total_spent: Uint128::new(20_000),
+                total_received: Uint128::new(40_000),
+                targets_received: vec![],
+                average_price: Some(Decimal::percent(50)),
+                remaining_purchases: 3,
+                // the three remaining purchases are an interval apart, starting after an interval
+                estimated_completion: Some(env.block.time.seconds() + 3_000),
+            }
+        );
+
+        // a deposit of more purchases than fit in a u64 should not be truncated
+        let user = Addr::unchecked("user_addr");
+        user_dca()
+            .update(&mut deps.storage, (&user, 1), |order| {
+                let mut order = order.unwrap();
+                order.initial_asset.amount = Uint128::MAX;
+                order.dca_amount = Uint128::new(1);
+                StdResult::Ok(order)
+            })
+            .unwrap();
+
+        let err = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::OrderStats {
+                user: user.into_string(),
+                id: 1,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, StdError::ConversionOverflow { .. }));
+    }
+
+    #[test]
+    fn does_estimate_scheduled_completion() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // the block time of the mock environment is Wednesday 2019-10-23 02:23:39 UTC
+        let user = Addr::unchecked("user_addr");
+        let order = |id: u64, day_of_week: Option<u8>, day_of_month: Option<u8>| DcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            schedule: Some(Schedule {
+                minute: 0,
+                hour: 14,
+                day_of_week,
+                day_of_month,
+            }),
+            last_purchase: env.block.time.seconds(),
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+        user_dca()
+            .save(&mut deps.storage, (&user, 1), &order(1, Some(1), None))
+            .unwrap();
+        user_dca()
+            .save(&mut deps.storage, (&user, 2), &order(2, None, Some(31)))
+            .unwrap();
+
+        let estimated_completion = |id: u64| {
+            from_binary::<OrderStatsResponse>(
+                &query(
+                    deps.as_ref(),
+                    env.clone(),
+                    QueryMsg::OrderStats {
+                        user: user.to_string(),
+                        id,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .estimated_completion
+        };
+
+        // purchased on the following three mondays at 14:00, the last being 2019-11-11
+        assert_eq!(estimated_completion(1), Some(1_573_480_800));
+        // purchased on the 31st of october, december and january, the last being 2020-01-31
+        assert_eq!(estimated_completion(2), Some(1_580_479_200));
+    }
+}
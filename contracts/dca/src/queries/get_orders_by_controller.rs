@@ -0,0 +1,214 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::UserDcaInfo;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::{user_dca, CONTROLLED_ACCOUNTS};
+
+/// The default amount of orders returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of orders that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the DCA orders of the interchain accounts that declared they are owned by `controller`
+/// on the remote chain of `connection_id`, so that front-ends of the remote chain can manage the
+/// orders of their users. As the declaration is made by each account, front-ends should only
+/// trust the orders of the interchain account registered for the controller on the remote chain.
+///
+/// The result is returned in a [`Vec<UserDcaInfo>`] object ordered by the user address and id of
+/// each order.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `connection_id` - The connection that the interchain accounts were registered over.
+///
+/// * `controller` - The address of the controller on the remote chain.
+///
+/// * `start_after` - An optional user address and [`u64`] id of the order to start after.
+///
+/// * `limit` - An optional [`u32`] amount of orders to return.
+pub fn get_orders_by_controller(
+    deps: Deps,
+    connection_id: String,
+    controller: String,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<UserDcaInfo>> {
+    let start_after = start_after
+        .map(|(user, id)| addr_validate_to_lower(deps.api, &user).map(|user| (user, id)))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let accounts = CONTROLLED_ACCOUNTS
+        .prefix((connection_id.as_str(), controller.as_str()))
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(|(user, _)| Bound::inclusive(user)),
+            None,
+            Order::Ascending,
+        );
+
+    let mut orders = vec![];
+    for account in accounts {
+        let account = account?;
+
+        // the orders of the account the page starts in follow the order it starts after
+        let start = match &start_after {
+            Some((user, id)) if user == &account => Some(Bound::exclusive(*id)),
+            _ => None,
+        };
+        for item in user_dca()
+            .prefix(&account)
+            .range(deps.storage, start, None, Order::Ascending)
+        {
+            if orders.len() == limit {
+                return Ok(orders);
+            }
+
+            let (_, order) = item?;
+            orders.push(UserDcaInfo {
+                user: account.clone(),
+                order,
+            });
+        }
+    }
+
+    Ok(orders)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, QueryMsg, Strategy, UserDcaInfo,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{
+        contract::query,
+        state::{user_dca, CONTROLLED_ACCOUNTS},
+    };
+
+    #[test]
+    fn does_get_orders_by_controller() {
+        let mut deps = mock_dependencies();
+
+        let order = |id: u64| DcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            last_purchase: 0,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+
+        let ica = Addr::unchecked("ica_addr");
+        let other_ica = Addr::unchecked("ica_other");
+        let user = Addr::unchecked("user_addr");
+        let orders = vec![
+            (&ica, order(1)),
+            (&ica, order(2)),
+            (&other_ica, order(1)),
+            // the orders of an account without a controller should not be returned
+            (&user, order(1)),
+        ];
+        for (owner, order) in &orders {
+            user_dca()
+                .save(&mut deps.storage, (owner, order.id), order)
+                .unwrap();
+        }
+        for account in [&ica, &other_ica] {
+            CONTROLLED_ACCOUNTS
+                .save(
+                    &mut deps.storage,
+                    ("connection-0", "osmo1controller", account),
+                    &true,
+                )
+                .unwrap();
+        }
+
+        let get_orders = |start_after: Option<(String, u64)>, limit: Option<u32>| {
+            from_binary::<Vec<UserDcaInfo>>(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::OrdersByController {
+                        connection_id: "connection-0".to_string(),
+                        controller: "osmo1controller".to_string(),
+                        start_after,
+                        limit,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        let info = |i: usize| UserDcaInfo {
+            user: orders[i].0.clone(),
+            order: orders[i].1.clone(),
+        };
+        assert_eq!(get_orders(None, None), vec![info(0), info(1), info(2)]);
+        assert_eq!(get_orders(None, Some(2)), vec![info(0), info(1)]);
+        assert_eq!(
+            get_orders(Some((ica.to_string(), 1)), None),
+            vec![info(1), info(2)]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use astroport::asset::{addr_validate_to_lower, AssetInfo};
+use astroport_dca::dca::{OrderDirection, UserDcaInfo};
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::user_dca;
+
+/// The default amount of orders returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of orders that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the DCA orders of all users that spend `initial_asset` to purchase `target_asset`,
+/// read from the `asset_pair` index of [`user_dca`].
+///
+/// The result is returned in a [`Vec<UserDcaInfo>`] object ordered by the user address and id of
+/// each order.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `initial_asset` - The [`AssetInfo`] spent by the orders.
+///
+/// * `target_asset` - The [`AssetInfo`] purchased by the orders.
+///
+/// * `start_after` - An optional user address and [`u64`] id of the order to start after.
+///
+/// * `limit` - An optional [`u32`] amount of orders to return.
+///
+/// * `direction` - The [`OrderDirection`] of the orders to return, or every order if not
+/// specified.
+pub fn get_orders_by_pair(
+    deps: Deps,
+    initial_asset: AssetInfo,
+    target_asset: AssetInfo,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+    direction: Option<OrderDirection>,
+) -> StdResult<Vec<UserDcaInfo>> {
+    let start_after = start_after
+        .map(|(user, id)| addr_validate_to_lower(deps.api, &user).map(|user| (user, id)))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    user_dca()
+        .idx
+        .asset_pair
+        .prefix((initial_asset.to_string(), target_asset.to_string()))
+        .range(
+            deps.storage,
+            start_after
+                .as_ref()
+                .map(|(user, id)| Bound::exclusive((user, *id))),
+            None,
+            Order::Ascending,
+        )
+        .filter(|item| match (item, direction) {
+            (Ok((_, order)), Some(direction)) => order.direction == direction,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|((user, _), order)| UserDcaInfo { user, order }))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, QueryMsg, Strategy, UserDcaInfo,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::query, state::user_dca};
+
+    #[test]
+    fn does_get_orders_by_pair() {
+        let mut deps = mock_dependencies();
+
+        let native = |denom: &str| AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+        let order = |id: u64, initial_asset: AssetInfo, target_asset: AssetInfo| DcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: initial_asset,
+            },
+            target_asset,
+            interval: Duration::Time(1_000),
+            last_purchase: 0,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused: false,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+
+        let user = Addr::unchecked("user_addr");
+        let other_user = Addr::unchecked("user_other");
+        let mut orders = vec![
+            (&user, order(1, native("uluna"), native("ukrw"))),
+            // the reverse pair should not be returned
+            (&user, order(2, native("ukrw"), native("uluna"))),
+            (&user, order(3, native("uluna"), native("ukrw"))),
+            (&other_user, order(1, native("uluna"), native("ukrw"))),
+            (&other_user, order(2, native("uluna"), native("ujpy"))),
+        ];
+        orders[3].1.direction = OrderDirection::Distribute;
+        for (owner, order) in &orders {
+            user_dca()
+                .save(&mut deps.storage, (owner, order.id), order)
+                .unwrap();
+        }
+
+        let res: Vec<UserDcaInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OrdersByPair {
+                    initial_asset: native("uluna"),
+                    target_asset: native("ukrw"),
+                    start_after: Some((user.to_string(), 1)),
+                    limit: None,
+                    direction: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                UserDcaInfo {
+                    user: user.clone(),
+                    order: orders[2].1.clone(),
+                },
+                UserDcaInfo {
+                    user: other_user.clone(),
+                    order: orders[3].1.clone(),
+                },
+            ]
+        );
+
+        // only the distributing orders of the pair are returned when filtered by direction
+        let res: Vec<UserDcaInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OrdersByPair {
+                    initial_asset: native("uluna"),
+                    target_asset: native("ukrw"),
+                    start_after: None,
+                    limit: None,
+                    direction: Some(OrderDirection::Distribute),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![UserDcaInfo {
+                user: other_user,
+                order: orders[3].1.clone(),
+            }]
+        );
+    }
+}
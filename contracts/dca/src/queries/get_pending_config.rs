@@ -0,0 +1,55 @@
+use cosmwasm_std::{Deps, StdResult};
+
+use crate::state::{PendingConfigChange, PENDING_CONFIG};
+
+/// ## Description
+/// Returns the sensitive configuration changes scheduled by the owner of the contract, which take
+/// effect once their timelock has passed.
+///
+/// The result is returned in a [`Vec<PendingConfigChange>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+pub fn get_pending_config(deps: Deps) -> StdResult<Vec<PendingConfigChange>> {
+    Ok(PENDING_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+    };
+
+    use crate::{
+        contract::query,
+        state::{PendingConfigChange, PENDING_CONFIG},
+    };
+
+    #[test]
+    fn does_get_pending_config() {
+        let mut deps = mock_dependencies();
+
+        let res: Vec<PendingConfigChange> =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingConfig {}).unwrap())
+                .unwrap();
+        assert!(res.is_empty());
+
+        let pending = vec![PendingConfigChange {
+            protocol_fee_bps: Some(50),
+            whitelisted_tokens: None,
+            whitelisted_fee_assets: None,
+            config_timelock: None,
+            effective_at: 1_000,
+        }];
+        PENDING_CONFIG
+            .save(deps.as_mut().storage, &pending)
+            .unwrap();
+
+        let res: Vec<PendingConfigChange> =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingConfig {}).unwrap())
+                .unwrap();
+        assert_eq!(res, pending);
+    }
+}
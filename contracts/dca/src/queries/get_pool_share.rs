@@ -0,0 +1,143 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::PoolShareResponse;
+use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
+
+use crate::{
+    error::ContractError,
+    state::{user_dca, POOLS, POOL_SHARES},
+};
+
+/// ## Description
+/// Returns the shares that a depositor holds of a pooled DCA order of a user, along with the
+/// amount of the remaining deposit of the order owed to them and the purchased target asset they
+/// can claim.
+///
+/// The result is returned in a [`PoolShareResponse`] object, where a depositor without any shares
+/// of the order holds none of it.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The lowercase address of the user the order is stored under as a [`String`].
+///
+/// * `id` - A [`u64`] representing the ID of the pooled DCA order for the user.
+///
+/// * `depositor` - The lowercase address of the depositor as a [`String`].
+pub fn get_pool_share(
+    deps: Deps,
+    user: String,
+    id: u64,
+    depositor: String,
+) -> StdResult<PoolShareResponse> {
+    let to_std_err = |e: ContractError| StdError::generic_err(e.to_string());
+
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+    let depositor = addr_validate_to_lower(deps.api, &depositor)?;
+
+    let pool = POOLS
+        .may_load(deps.storage, (&user_address, id))?
+        .ok_or_else(|| to_std_err(ContractError::NotPooledOrder {}))?;
+    let mut share = POOL_SHARES
+        .may_load(deps.storage, (&user_address, id, &depositor))?
+        .unwrap_or_default();
+    share.settle(&pool)?;
+
+    // a fulfilled order has no deposit left
+    let deposit = match user_dca().may_load(deps.storage, (&user_address, id))? {
+        Some(order) if !pool.total_shares.is_zero() => order
+            .initial_asset
+            .amount
+            .multiply_ratio(share.shares, pool.total_shares),
+        _ => Uint128::zero(),
+    };
+
+    Ok(PoolShareResponse {
+        shares: share.shares,
+        total_shares: pool.total_shares,
+        deposit,
+        claimable: share.claimable,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use astroport_dca::dca::{PoolShareResponse, QueryMsg};
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Decimal, Deps, Uint128,
+    };
+
+    use crate::{
+        contract::query,
+        state::{Pool, PoolShare, POOLS, POOL_SHARES},
+    };
+
+    #[test]
+    fn does_get_pool_share() {
+        let mut deps = mock_dependencies();
+
+        let user = Addr::unchecked("user_addr");
+        let depositor = Addr::unchecked("depositor_addr");
+        POOLS
+            .save(
+                &mut deps.storage,
+                (&user, 1),
+                &Pool {
+                    total_shares: Uint128::new(4_000),
+                    output_per_share: Decimal::percent(150),
+                },
+            )
+            .unwrap();
+        POOL_SHARES
+            .save(
+                &mut deps.storage,
+                (&user, 1, &depositor),
+                &PoolShare {
+                    shares: Uint128::new(1_000),
+                    output_per_share: Decimal::percent(100),
+                    claimable: Uint128::new(200),
+                },
+            )
+            .unwrap();
+
+        let get_share = |deps: Deps, depositor: &str| -> PoolShareResponse {
+            from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::PoolShare {
+                        user: user.to_string(),
+                        id: 1,
+                        depositor: depositor.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        // the purchases since the share was last settled are owed to it along with its unclaimed
+        // purchases, while the fulfilled order has no deposit left
+        assert_eq!(
+            get_share(deps.as_ref(), "depositor_addr"),
+            PoolShareResponse {
+                shares: Uint128::new(1_000),
+                total_shares: Uint128::new(4_000),
+                deposit: Uint128::zero(),
+                claimable: Uint128::new(700),
+            }
+        );
+
+        // a depositor without shares holds none of the order
+        assert_eq!(
+            get_share(deps.as_ref(), "other_addr"),
+            PoolShareResponse {
+                shares: Uint128::zero(),
+                total_shares: Uint128::new(4_000),
+                deposit: Uint128::zero(),
+                claimable: Uint128::zero(),
+            }
+        );
+    }
+}
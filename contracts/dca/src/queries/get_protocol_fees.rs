@@ -0,0 +1,63 @@
+use astroport::asset::Asset;
+use cosmwasm_std::{Deps, Order, StdResult};
+
+use crate::state::PROTOCOL_FEES;
+
+/// ## Description
+/// Returns the cumulative protocol fees collected from DCA purchases, with one entry for each
+/// asset that has been spent in a purchase while a protocol fee was set.
+///
+/// The result is returned in a [`Vec<Asset>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+pub fn get_protocol_fees(deps: Deps) -> StdResult<Vec<Asset>> {
+    PROTOCOL_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, fee)| fee))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::query, state::PROTOCOL_FEES};
+
+    #[test]
+    fn does_get_protocol_fees() {
+        let mut deps = mock_dependencies();
+
+        let fees = vec![
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("token"),
+                },
+                amount: Uint128::new(30),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                amount: Uint128::new(150),
+            },
+        ];
+        for fee in &fees {
+            PROTOCOL_FEES
+                .save(deps.as_mut().storage, fee.info.to_string(), fee)
+                .unwrap();
+        }
+
+        let res: Vec<Asset> =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ProtocolFees {}).unwrap())
+                .unwrap();
+
+        assert_eq!(res, fees);
+    }
+}
@@ -0,0 +1,135 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::PurchaseQueryInfo;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::PURCHASE_HISTORY;
+
+/// The default amount of purchases returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of purchases that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the purchases performed for a DCA order of a user, starting after the purchase with
+/// the sequence number `start_after`.
+///
+/// The result is returned in a [`Vec<PurchaseQueryInfo>`] object ordered by the sequence number of
+/// each purchase.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The users lowercase address as a [`String`].
+///
+/// * `id` - A [`u64`] representing the ID of the DCA order for the user.
+///
+/// * `start_after` - An optional [`u64`] sequence number of the purchase to start after.
+///
+/// * `limit` - An optional [`u32`] amount of purchases to return.
+pub fn get_purchase_history(
+    deps: Deps,
+    user: String,
+    id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PurchaseQueryInfo>> {
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    PURCHASE_HISTORY
+        .prefix((&user_address, id))
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(seq, record)| PurchaseQueryInfo { seq, record }))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{PurchaseQueryInfo, PurchaseRecord, QueryMsg};
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{contract::query, state::PURCHASE_HISTORY};
+
+    #[test]
+    fn does_get_purchase_history() {
+        let mut deps = mock_dependencies();
+
+        let user = Addr::unchecked("user_addr");
+        let record = |timestamp: u64| PurchaseRecord {
+            timestamp,
+            amount_in: Uint128::new(10_000),
+            amount_out: Uint128::new(9_558),
+            outputs: vec![],
+            bot: Addr::unchecked("bot_user"),
+            tip: vec![Asset {
+                amount: Uint128::new(15_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+        };
+
+        for seq in 1..=3 {
+            PURCHASE_HISTORY
+                .save(&mut deps.storage, (&user, 1, seq), &record(seq * 1_000))
+                .unwrap();
+        }
+        // purchase of another order should not be returned
+        PURCHASE_HISTORY
+            .save(&mut deps.storage, (&user, 2, 1), &record(500))
+            .unwrap();
+
+        let res: Vec<PurchaseQueryInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PurchaseHistory {
+                    user: user.to_string(),
+                    id: 1,
+                    start_after: Some(1),
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![PurchaseQueryInfo {
+                seq: 2,
+                record: record(2_000)
+            }]
+        );
+
+        let res: Vec<PurchaseQueryInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PurchaseHistory {
+                    user: user.into_string(),
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.into_iter().map(|p| p.seq).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use astroport::asset::addr_validate_to_lower;
+use astroport_dca::dca::{DcaInfo, Duration, ReadyOrderInfo};
+use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::{
+    next_purchase_key, order_purchase_key, user_dca, HEIGHT_UNIT, TIME_UNIT, USER_CONFIG,
+};
+
+/// The default amount of orders returned in a page
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of orders that can be returned in a page
+const MAX_LIMIT: u32 = 30;
+
+/// ## Description
+/// Returns the DCA orders of all users that a DCA purchase can currently be performed for, which
+/// are the orders that are not paused or expired and whose interval has passed since their last
+/// purchase.
+///
+/// The orders are read from the `next_purchase` index of [`user_dca`], where paused orders and
+/// orders expiring before their next purchase are indexed after the due orders, so only the
+/// orders that are due, along with any orders that expired while due, are iterated over.
+///
+/// The result is returned in a [`Vec<ReadyOrderInfo>`] object with the orders with an interval in
+/// blocks first, then the orders with an interval in seconds, each ordered by the next purchase,
+/// user address and id of each order, along with the tip balance of the user.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `start_after` - An optional [`Duration`] next purchase block height or time, user address
+/// and [`u64`] id of the order to start after.
+///
+/// * `limit` - An optional [`u32`] amount of orders to return.
+pub fn get_ready_orders(
+    deps: Deps,
+    env: Env,
+    start_after: Option<(Duration, String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ReadyOrderInfo>> {
+    let start_after = start_after
+        .map(|(next_purchase, user, id)| {
+            addr_validate_to_lower(deps.api, &user)
+                .map(|user| (next_purchase_key(&next_purchase), user, id))
+        })
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // the orders are ascending by their next purchase in each unit, so the orders with an interval
+    // in blocks are read up to the current height and the orders with an interval in seconds up to
+    // the current time, skipping the units before the page starts
+    let mut ready_orders = vec![];
+    let first = Addr::unchecked("");
+    for now in [
+        (HEIGHT_UNIT, env.block.height),
+        (TIME_UNIT, env.block.time.seconds()),
+    ] {
+        let remaining = limit - ready_orders.len();
+        if remaining == 0 {
+            break;
+        }
+
+        let min = match &start_after {
+            Some((key, ..)) if key.0 > now.0 => continue,
+            Some((key, user, id)) if key.0 == now.0 => Bound::exclusive((*key, (user, *id))),
+            _ => Bound::inclusive(((now.0, 0), (&first, 0))),
+        };
+
+        ready_orders.extend(
+            user_dca()
+                .idx
+                .next_purchase
+                .range(deps.storage, Some(min), None, Order::Ascending)
+                .take_while(|item| match item {
+                    Ok((_, order)) => order_purchase_key(order) <= now,
+                    Err(_) => true,
+                })
+                .filter(|item| match item {
+                    Ok((_, order)) => order.is_due(&env.block) && is_active(order, &env),
+                    Err(_) => true,
+                })
+                .take(remaining)
+                .map(|item| {
+                    let ((user, _), order) = item?;
+                    let tip_balance = USER_CONFIG
+                        .may_load(deps.storage, &user)?
+                        .unwrap_or_default()
+                        .prioritized_tip_balance();
+
+                    Ok(ReadyOrderInfo {
+                        next_purchase: order.next_purchase_at(),
+                        user,
+                        order,
+                        tip_balance,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?,
+        );
+    }
+
+    Ok(ready_orders)
+}
+
+/// Checks if `order` has not been paused or expired
+fn is_active(order: &DcaInfo, env: &Env) -> bool {
+    !order.paused
+        && !order
+            .expires_at
+            .as_ref()
+            .map_or(false, |expires_at| expires_at.is_expired(&env.block))
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        DcaInfo, Delivery, Duration, FailurePolicy, OrderDirection, QueryMsg, ReadyOrderInfo,
+        Strategy,
+    };
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Order, StdResult, Uint128,
+    };
+    use cw20::Expiration;
+
+    use crate::{
+        contract::query,
+        state::{user_dca, UserConfig, INACTIVE_UNIT, USER_CONFIG},
+    };
+
+    #[test]
+    fn does_get_ready_orders() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let order = |id: u64, last_purchase: u64, paused: bool| DcaInfo {
+            id,
+            initial_asset: Asset {
+                amount: Uint128::new(30_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+            target_asset: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            interval: Duration::Time(1_000),
+            last_purchase,
+            dca_amount: Uint128::new(10_000),
+            escrowed: false,
+            paused,
+            expires_at: None,
+            max_price: None,
+            max_spread: None,
+            min_receive_per_purchase: None,
+            route: None,
+            auto_route: false,
+            total_spent: Uint128::zero(),
+            total_received: Uint128::zero(),
+            targets_received: vec![],
+            executing: false,
+            delivery: Delivery::Immediate,
+            recipient: None,
+            purchase_hooks: vec![],
+            auto_stake: false,
+            zap: None,
+            referrer: None,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        };
+
+        let now = env.block.time.seconds();
+        let height = env.block.height;
+        let in_blocks = |order: DcaInfo| DcaInfo {
+            interval: Duration::Height(100),
+            ..order
+        };
+        let user = Addr::unchecked("user_addr");
+        let other_user = Addr::unchecked("user_other");
+        let orders = vec![
+            (&user, order(1, now - 1_000, false)),
+            // purchased too recently
+            (&user, order(2, now - 999, false)),
+            // paused by the user
+            (&user, order(3, now - 1_000, true)),
+            (&user, order(4, 0, false)),
+            (&other_user, order(1, 0, false)),
+            (&user, in_blocks(order(5, height - 100, false))),
+            // not due yet at the current height, even though it is far behind the current time
+            (&user, in_blocks(order(6, height - 99, false))),
+            // expired before its next purchase
+            (
+                &user,
+                DcaInfo {
+                    expires_at: Some(Expiration::AtTime(env.block.time.minus_seconds(500))),
+                    ..order(7, now - 1_000, false)
+                },
+            ),
+        ];
+        for (owner, order) in &orders {
+            user_dca()
+                .save(&mut deps.storage, (owner, order.id), order)
+                .unwrap();
+        }
+
+        let tip_balance = vec![Asset {
+            amount: Uint128::new(15_000),
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }];
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &user,
+                &UserConfig {
+                    tip_balance: tip_balance.clone(),
+                    ..UserConfig::default()
+                },
+            )
+            .unwrap();
+
+        // orders in blocks are returned first, then orders are returned by their next purchase time
+        let res: Vec<ReadyOrderInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::ReadyOrders {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                ReadyOrderInfo {
+                    next_purchase: Duration::Height(height),
+                    user: user.clone(),
+                    order: orders[5].1.clone(),
+                    tip_balance: tip_balance.clone(),
+                },
+                ReadyOrderInfo {
+                    next_purchase: Duration::Time(1_000),
+                    user: user.clone(),
+                    order: orders[3].1.clone(),
+                    tip_balance: tip_balance.clone(),
+                },
+            ]
+        );
+
+        // a page starting after an order in blocks continues with the orders in seconds
+        let res: Vec<ReadyOrderInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::ReadyOrders {
+                    start_after: Some((Duration::Height(height), user.to_string(), 5)),
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![ReadyOrderInfo {
+                next_purchase: Duration::Time(1_000),
+                user: user.clone(),
+                order: orders[3].1.clone(),
+                tip_balance: tip_balance.clone(),
+            }]
+        );
+
+        let res: Vec<ReadyOrderInfo> = from_binary(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ReadyOrders {
+                    start_after: Some((Duration::Time(1_000), user.to_string(), 4)),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                ReadyOrderInfo {
+                    next_purchase: Duration::Time(1_000),
+                    user: other_user.clone(),
+                    order: orders[4].1.clone(),
+                    tip_balance: vec![],
+                },
+                ReadyOrderInfo {
+                    next_purchase: Duration::Time(now),
+                    user,
+                    order: orders[0].1.clone(),
+                    tip_balance,
+                },
+            ]
+        );
+
+        // the paused order and the order expiring before its next purchase are indexed after the
+        // due orders, so they are not iterated over
+        let inactive = user_dca()
+            .idx
+            .next_purchase
+            .prefix((INACTIVE_UNIT, 0))
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            inactive,
+            vec![
+                (Addr::unchecked("user_addr"), 3),
+                (Addr::unchecked("user_addr"), 7)
+            ]
+        );
+    }
+}
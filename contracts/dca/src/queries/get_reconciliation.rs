@@ -0,0 +1,131 @@
+use astroport_dca::dca::AssetReconciliation;
+use cosmwasm_std::{Deps, Env, StdResult};
+
+use crate::reconciliation::reconcile;
+
+/// ## Description
+/// Returns the balance of every native token held by the contract, every asset it owes and every
+/// whitelisted token against the liabilities the contract tracks for the deposits of DCA orders,
+/// tip balances, escrowed purchases, referral rewards, ICS-20 transfers in flight and bot bonds,
+/// along with the surplus that can be swept to the fee collector.
+///
+/// The result is returned in a [`Vec<AssetReconciliation>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+pub fn get_reconciliation(deps: Deps, env: Env) -> StdResult<Vec<AssetReconciliation>> {
+    reconcile(deps, &env, None)
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::{
+        AssetReconciliation, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection,
+        QueryMsg, Strategy,
+    };
+    use cosmwasm_std::{coins, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::tests::{mock_astroport_instantiate, mock_creator};
+
+    #[test]
+    fn does_get_reconciliation() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(20_000),
+                    info: uluna.clone(),
+                },
+                target_asset: AssetInfo::NativeToken {
+                    denom: "ujpy".to_string(),
+                },
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(20_000, "uluna"),
+        )
+        .unwrap();
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(15_000),
+                    info: uluna.clone(),
+                }],
+            },
+            &coins(15_000, "uluna"),
+        )
+        .unwrap();
+
+        // tokens sent to the contract directly are not owed to anyone
+        app.send_tokens(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &coins(123, "uluna"),
+        )
+        .unwrap();
+
+        let reconciliation: Vec<AssetReconciliation> = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Reconciliation {})
+            .unwrap();
+        assert_eq!(
+            reconciliation
+                .into_iter()
+                .find(|reconciliation| reconciliation.info == uluna),
+            Some(AssetReconciliation {
+                info: uluna,
+                balance: Uint128::new(35_123),
+                liabilities: Uint128::new(35_000),
+                surplus: Uint128::new(123),
+                deficit: Uint128::zero(),
+            })
+        );
+    }
+}
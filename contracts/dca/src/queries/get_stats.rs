@@ -0,0 +1,200 @@
+use astroport_dca::dca::StatsResponse;
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+
+use crate::{
+    state::{GLOBAL_STATS, PAIR_VOLUMES, TIPS_PAID},
+    stats::{current_epoch, EPOCH_LENGTH},
+};
+
+/// ## Description
+/// Returns the global statistics of the contract, which are the running counters of the active
+/// orders, the purchases performed overall and in the current and last epoch, the tips paid to
+/// bots and the volume of each asset pair purchased.
+///
+/// The result is returned in a [`StatsResponse`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+pub fn get_stats(deps: Deps, env: Env) -> StdResult<StatsResponse> {
+    let stats = current_epoch(
+        GLOBAL_STATS.may_load(deps.storage)?.unwrap_or_default(),
+        env.block.time.seconds(),
+    );
+
+    Ok(StatsResponse {
+        active_orders: stats.active_orders,
+        total_purchases: stats.total_purchases,
+        epoch_length: EPOCH_LENGTH,
+        epoch_start: stats.epoch_start,
+        epoch_purchases: stats.epoch_purchases,
+        last_epoch_purchases: stats.last_epoch_purchases,
+        tips_paid: TIPS_PAID
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, paid)| paid))
+            .collect::<StdResult<Vec<_>>>()?,
+        volumes: PAIR_VOLUMES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, volume)| volume))
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, PairVolume, QueryMsg,
+        StatsResponse, Strategy,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        stats::EPOCH_LENGTH,
+        tests::{mock_astroport_instantiate, mock_creator, read_user_orders},
+    };
+
+    #[test]
+    fn does_get_stats() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+
+        for _ in 0..2 {
+            app.execute_contract(
+                mock_creator().sender,
+                dca_addr.clone(),
+                &ExecuteMsg::CreateDcaOrder {
+                    initial_asset: Asset {
+                        amount: Uint128::new(20_000),
+                        info: uluna.clone(),
+                    },
+                    target_asset: ujpy.clone(),
+                    interval: Duration::Time(500),
+                    dca_amount: Uint128::new(10_000),
+                    first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
+                },
+                &coins(20_000, "uluna"),
+            )
+            .unwrap();
+        }
+
+        let tip = Asset {
+            amount: Uint128::new(15_000),
+            info: uluna.clone(),
+        };
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![tip.clone()],
+            },
+            &coins(15_000, "uluna"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bot_user"),
+            dca_addr.clone(),
+            &ExecuteMsg::PerformDcaPurchase {
+                user: mock_creator().sender.into_string(),
+                id: 1,
+                hops: vec![SwapOperation::AstroSwap {
+                    offer_asset_info: uluna.clone(),
+                    ask_asset_info: ujpy.clone(),
+                }],
+                fee_redeem: vec![tip.clone()],
+                deadline: None,
+                accepted_tips: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CancelDcaOrder {
+                id: 2,
+                user: None,
+                withdraw_tips: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let order = read_user_orders(&app, dca_addr.clone(), &mock_creator().sender).remove(0);
+        let stats: StatsResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Stats {})
+            .unwrap();
+        assert_eq!(stats.active_orders, 1);
+        assert_eq!(stats.total_purchases, 1);
+        assert_eq!(stats.epoch_purchases, 1);
+        assert_eq!(stats.last_epoch_purchases, 0);
+        assert_eq!(stats.tips_paid, vec![tip]);
+        assert_eq!(
+            stats.volumes,
+            vec![PairVolume {
+                initial_asset: uluna,
+                target_asset: ujpy,
+                purchases: 1,
+                amount_in: order.total_spent,
+                amount_out: order.total_received,
+            }]
+        );
+
+        // the purchases of the epoch become the purchases of the last epoch once it has passed
+        app.update_block(|block| block.time = block.time.plus_seconds(EPOCH_LENGTH));
+        let stats: StatsResponse = app
+            .wrap()
+            .query_wasm_smart(&dca_addr, &QueryMsg::Stats {})
+            .unwrap();
+        assert_eq!(stats.total_purchases, 1);
+        assert_eq!(stats.epoch_purchases, 0);
+        assert_eq!(stats.last_epoch_purchases, 1);
+    }
+}
@@ -0,0 +1,108 @@
+use astroport::asset::{addr_validate_to_lower, Asset};
+use cosmwasm_std::{Deps, StdResult};
+
+use crate::state::USER_CONFIG;
+
+/// ## Description
+/// Returns the tip balance a user has deposited for each asset, ordered by the priority set by the
+/// user for redeeming the tips of bots that leave the choice of tip to the contract.
+///
+/// The result is returned in a [`Vec<Asset>`] object.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `user` - The users lowercase address as a [`String`].
+pub fn get_tip_balances(deps: Deps, user: String) -> StdResult<Vec<Asset>> {
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+
+    Ok(USER_CONFIG
+        .may_load(deps.storage, &user_address)?
+        .map(|config| config.prioritized_tip_balance())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::asset::{Asset, AssetInfo};
+    use astroport_dca::dca::QueryMsg;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint128,
+    };
+
+    use crate::{
+        contract::query,
+        state::{UserConfig, USER_CONFIG},
+    };
+
+    #[test]
+    fn does_get_tip_balances() {
+        let mut deps = mock_dependencies();
+
+        let tip = |denom: &str, amount: u128| Asset {
+            amount: Uint128::new(amount),
+            info: AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+        };
+
+        let user = Addr::unchecked("user_addr");
+        USER_CONFIG
+            .save(
+                &mut deps.storage,
+                &user,
+                &UserConfig {
+                    last_id: 0,
+                    max_hops: None,
+                    max_spread: None,
+                    tip_balance: vec![
+                        tip("uluna", 10_000),
+                        tip("ukrw", 20_000),
+                        tip("ujpy", 30_000),
+                    ],
+                    tip_priority: vec![AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    }],
+                    deposited_tip_balance: vec![],
+                },
+            )
+            .unwrap();
+
+        // the prioritized tip comes first, followed by the others in the order they were deposited
+        let res: Vec<Asset> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TipBalances {
+                    user: user.into_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                tip("ujpy", 30_000),
+                tip("uluna", 10_000),
+                tip("ukrw", 20_000)
+            ]
+        );
+
+        // a user without a configuration has no tip balance
+        let res: Vec<Asset> = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TipBalances {
+                    user: "other_user".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(res.is_empty());
+    }
+}
@@ -47,6 +47,8 @@ mod test {
                     denom: "uluna".to_string(),
                 },
             }],
+            tip_priority: vec![],
+            deposited_tip_balance: vec![],
         };
 
         let key = Addr::unchecked("user_addr");
@@ -1,14 +1,17 @@
 use astroport::asset::{addr_validate_to_lower, AssetInfo};
-use astroport_dca::dca::DcaQueryInfo;
+use astroport_dca::dca::{DcaQueryInfo, OrderDirection};
 use cosmwasm_std::{Deps, Env, StdResult};
 
-use crate::{get_token_allowance::get_token_allowance, state::USER_DCA};
+use crate::{
+    get_token_allowance::get_token_allowance, state::get_user_orders, token_decimals::load_decimals,
+};
 
 /// ## Description
 /// Returns a users DCA orders currently set.
 ///
 /// The result is returned in a [`Vec<DcaQueryInfo`] object of the users current DCA orders with the
-/// `amount` of each order set to the native token amount that can be spent, or the token allowance.
+/// `amount` of each order set to the native token amount that can be spent, or the token allowance,
+/// along with the decimals of the assets of each order.
 ///
 /// ## Arguments
 /// * `deps` - A [`Deps`] that contains the dependencies.
@@ -16,14 +19,28 @@ use crate::{get_token_allowance::get_token_allowance, state::USER_DCA};
 /// * `env` - The [`Env`] of the blockchain.
 ///
 /// * `user` - The users lowercase address as a [`String`].
-pub fn get_user_dca_orders(deps: Deps, env: Env, user: String) -> StdResult<Vec<DcaQueryInfo>> {
+///
+/// * `direction` - The [`OrderDirection`] of the orders to return, or every order if not
+/// specified.
+pub fn get_user_dca_orders(
+    deps: Deps,
+    env: Env,
+    user: String,
+    direction: Option<OrderDirection>,
+) -> StdResult<Vec<DcaQueryInfo>> {
     let user_address = addr_validate_to_lower(deps.api, &user)?;
 
-    USER_DCA
-        .load(deps.storage, &user_address)?
+    get_user_orders(deps.storage, &user_address)?
         .into_iter()
+        .filter(|order| direction.map_or(true, |direction| order.direction == direction))
         .map(|order| {
             Ok(DcaQueryInfo {
+                initial_decimals: load_decimals(
+                    deps.storage,
+                    &deps.querier,
+                    &order.initial_asset.info,
+                )?,
+                target_decimals: load_decimals(deps.storage, &deps.querier, &order.target_asset)?,
                 order: order.clone(),
                 token_allowance: match &order.initial_asset.info {
                     AssetInfo::NativeToken { .. } => order.initial_asset.amount,
@@ -40,7 +57,10 @@ pub fn get_user_dca_orders(deps: Deps, env: Env, user: String) -> StdResult<Vec<
 #[cfg(test)]
 mod test {
     use astroport::asset::{Asset, AssetInfo};
-    use astroport_dca::dca::{DcaInfo, DcaQueryInfo, ExecuteMsg, QueryMsg};
+    use astroport_dca::dca::{
+        DcaInfo, DcaQueryInfo, Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection,
+        QueryMsg, Strategy,
+    };
     use cosmwasm_std::{coins, Addr, Uint128};
     use cw20::Cw20Coin;
     use cw_multi_test::{App, Executor};
@@ -96,9 +116,43 @@ mod test {
                     target_asset: astroport::asset::AssetInfo::NativeToken {
                         denom: "ukrw".to_string(),
                     },
-                    interval: 1_000,
+                    interval: Duration::Time(1_000),
                     dca_amount: Uint128::new(10_000),
                     first_purchase: None,
+                    expires_at: None,
+                    max_price: None,
+                    max_spread: None,
+                    min_receive_per_purchase: None,
+                    route: None,
+                    auto_route: false,
+                    delivery: Delivery::Immediate,
+                    recipient: None,
+                    purchase_hooks: vec![],
+                    auto_stake: false,
+                    zap: None,
+                    referrer: None,
+                    owner: None,
+                    schedule: None,
+                    max_delay: None,
+                    jitter: None,
+                    only_below_price: None,
+                    max_price_impact: None,
+                    stop_price: None,
+                    take_profit_price: None,
+                    direction: OrderDirection::Accumulate,
+                    distribution: None,
+                    targets: vec![],
+                    pooled: false,
+                    strategy: Strategy::Fixed,
+                    max_purchases: None,
+                    pull: false,
+                    max_failures: None,
+                    failure_policy: FailurePolicy::Pause,
+                    swap_tip_bps: None,
+                    tip_escalation: None,
+                    tip_replenish: None,
+                    allowed_bots: vec![],
+                    allow_low_liquidity: false,
                 },
                 &match asset.info {
                     AssetInfo::NativeToken { denom } => coins(20_000, denom),
@@ -160,6 +214,7 @@ mod test {
                 dca_addr,
                 &QueryMsg::UserDcaOrders {
                     user: mock_creator().sender.into_string(),
+                    direction: None,
                 },
             )
             .unwrap();
@@ -176,13 +231,58 @@ mod test {
                             },
                             amount: Uint128::new(20_000)
                         },
-                        interval: 1_000,
+                        interval: Duration::Time(1_000),
                         last_purchase: 0,
                         target_asset: AssetInfo::NativeToken {
                             denom: "ukrw".to_string()
-                        }
+                        },
+                        escrowed: false,
+                        paused: false,
+                        expires_at: None,
+                        max_price: None,
+                        max_spread: None,
+                        min_receive_per_purchase: None,
+                        route: None,
+                        auto_route: false,
+                        total_spent: Uint128::zero(),
+                        total_received: Uint128::zero(),
+                        targets_received: vec![],
+                        executing: false,
+                        delivery: Delivery::Immediate,
+                        recipient: None,
+                        purchase_hooks: vec![],
+                        auto_stake: false,
+                        zap: None,
+                        referrer: None,
+                        schedule: None,
+                        max_delay: None,
+                        jitter: None,
+                        jitter_offset: 0,
+                        only_below_price: None,
+                        max_price_impact: None,
+                        stop_price: None,
+                        take_profit_price: None,
+                        direction: OrderDirection::Accumulate,
+                        distribution: None,
+                        targets: vec![],
+                        pooled: false,
+                        strategy: Strategy::Fixed,
+                        target_value: Uint128::zero(),
+                        max_purchases: None,
+                        pull: false,
+                        last_failure: None,
+                        max_failures: None,
+                        failure_policy: FailurePolicy::Pause,
+                        consecutive_failures: 0,
+                        replenished_tip: Uint128::zero(),
+                        swap_tip_bps: None,
+                        tip_escalation: None,
+                        tip_replenish: None,
+                        allowed_bots: vec![],
                     },
-                    token_allowance: Uint128::new(20_000)
+                    token_allowance: Uint128::new(20_000),
+                    initial_decimals: 6,
+                    target_decimals: 6,
                 },
                 DcaQueryInfo {
                     order: DcaInfo {
@@ -196,11 +296,56 @@ mod test {
                         target_asset: AssetInfo::NativeToken {
                             denom: "ukrw".to_string()
                         },
-                        interval: 1_000,
+                        interval: Duration::Time(1_000),
                         last_purchase: 0,
-                        dca_amount: Uint128::new(10_000)
+                        dca_amount: Uint128::new(10_000),
+                        escrowed: false,
+                        paused: false,
+                        expires_at: None,
+                        max_price: None,
+                        max_spread: None,
+                        min_receive_per_purchase: None,
+                        route: None,
+                        auto_route: false,
+                        total_spent: Uint128::zero(),
+                        total_received: Uint128::zero(),
+                        targets_received: vec![],
+                        executing: false,
+                        delivery: Delivery::Immediate,
+                        recipient: None,
+                        purchase_hooks: vec![],
+                        auto_stake: false,
+                        zap: None,
+                        referrer: None,
+                        schedule: None,
+                        max_delay: None,
+                        jitter: None,
+                        jitter_offset: 0,
+                        only_below_price: None,
+                        max_price_impact: None,
+                        stop_price: None,
+                        take_profit_price: None,
+                        direction: OrderDirection::Accumulate,
+                        distribution: None,
+                        targets: vec![],
+                        pooled: false,
+                        strategy: Strategy::Fixed,
+                        target_value: Uint128::zero(),
+                        max_purchases: None,
+                        pull: false,
+                        last_failure: None,
+                        max_failures: None,
+                        failure_policy: FailurePolicy::Pause,
+                        consecutive_failures: 0,
+                        replenished_tip: Uint128::zero(),
+                        swap_tip_bps: None,
+                        tip_escalation: None,
+                        tip_replenish: None,
+                        allowed_bots: vec![],
                     },
-                    token_allowance: Uint128::new(10_000)
+                    token_allowance: Uint128::new(10_000),
+                    initial_decimals: 6,
+                    target_decimals: 6,
                 }
             ]
         );
@@ -1,7 +1,51 @@
+mod get_all_users;
+mod get_archived_orders;
+mod get_bots;
 mod get_config;
+mod get_interchain_controller;
+mod get_keeper;
+mod get_member_group;
+mod get_notifier;
+mod get_operators;
+mod get_oracle;
+mod get_order_stats;
+mod get_orders_by_controller;
+mod get_orders_by_pair;
+mod get_pending_config;
+mod get_pool_share;
+mod get_protocol_fees;
+mod get_purchase_history;
+mod get_ready_orders;
+mod get_reconciliation;
+mod get_stats;
+mod get_tip_balances;
 mod get_user_config;
 mod get_user_dca_orders;
+mod simulate_order_plan;
+mod simulate_purchase;
 
+pub use get_all_users::get_all_users;
+pub use get_archived_orders::get_archived_orders;
+pub use get_bots::get_bots;
 pub use get_config::get_config;
+pub use get_interchain_controller::get_interchain_controller;
+pub use get_keeper::get_keeper;
+pub use get_member_group::get_member_group;
+pub use get_notifier::get_notifier;
+pub use get_operators::get_operators;
+pub use get_oracle::get_oracle;
+pub use get_order_stats::get_order_stats;
+pub use get_orders_by_controller::get_orders_by_controller;
+pub use get_orders_by_pair::get_orders_by_pair;
+pub use get_pending_config::get_pending_config;
+pub use get_pool_share::get_pool_share;
+pub use get_protocol_fees::get_protocol_fees;
+pub use get_purchase_history::get_purchase_history;
+pub use get_ready_orders::get_ready_orders;
+pub use get_reconciliation::get_reconciliation;
+pub use get_stats::get_stats;
+pub use get_tip_balances::get_tip_balances;
 pub use get_user_config::get_user_config;
 pub use get_user_dca_orders::get_user_dca_orders;
+pub use simulate_order_plan::simulate_order_plan;
+pub use simulate_purchase::simulate_purchase;
@@ -0,0 +1,344 @@
+use astroport::asset::{addr_validate_to_lower, Asset};
+use astroport_dca::dca::{OrderPlanResponse, PlannedPurchase, Strategy};
+use cosmwasm_std::{Deps, Env, StdError, StdResult, Uint128};
+
+use crate::{
+    error::ContractError,
+    find_best_route::find_best_route,
+    get_swap_simulation::get_swap_simulation,
+    handlers::{purchase_amounts, purchase_hops},
+    state::{get_purchase_count, user_dca, CONFIG, USER_CONFIG},
+};
+
+/// The maximum amount of remaining purchases listed in the plan of an order
+const MAX_PLANNED_PURCHASES: usize = 50;
+
+/// ## Description
+/// Adds `asset` to `assets`, merging it into the entry of the same asset if there is one.
+fn add_asset(assets: &mut Vec<Asset>, asset: Asset) -> StdResult<()> {
+    if asset.amount.is_zero() {
+        return Ok(());
+    }
+
+    match assets.iter_mut().find(|a| a.info == asset.info) {
+        Some(a) => a.amount = a.amount.checked_add(asset.amount)?,
+        None => assets.push(asset),
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the remaining DCA purchases of a DCA order of a user, along with the total output and
+/// tips that they are estimated at by the current prices, so that front-ends can preview what the
+/// order will purchase.
+///
+/// The result is returned in an [`OrderPlanResponse`] object. An order without a stored route is
+/// estimated through the best route found on-chain, and a value averaging order is estimated to
+/// spend dca_amount in each of its purchases.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `user` - The users lowercase address as a [`String`].
+///
+/// * `id` - A [`u64`] representing the ID of the DCA order for the user.
+pub fn simulate_order_plan(
+    deps: Deps,
+    env: Env,
+    user: String,
+    id: u64,
+) -> StdResult<OrderPlanResponse> {
+    let to_std_err = |e: ContractError| StdError::generic_err(e.to_string());
+
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+    let config = CONFIG.load(deps.storage)?;
+    let user_config = USER_CONFIG
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+    let order = user_dca().load(deps.storage, (&user_address, id))?;
+
+    // an order that is not routed by the contract is estimated through the best route found
+    // on-chain, as the route of its purchases is chosen by the bots performing them
+    let hops = match (&order.route, order.auto_route) {
+        (None, false) => find_best_route(
+            &deps,
+            &config,
+            user_config.max_hops.unwrap_or(config.max_hops),
+            &order.initial_asset.info,
+            &order.target_asset,
+            order.dca_amount,
+        )
+        .map_err(to_std_err)?,
+        _ => vec![],
+    };
+    let (hops, _) =
+        purchase_hops(&deps, &config, &user_config, Some(&order), hops).map_err(to_std_err)?;
+
+    // the amounts spent by the remaining purchases along with the amount of purchases spending
+    // each, where every purchase spends dca_amount until the last one spends the remainder of the
+    // deposit, unless each purchase spends one of the remaining tranches of the order
+    let mut amounts: Vec<(Uint128, u64)> = match &order.strategy {
+        Strategy::Tranches { amounts } => amounts.iter().map(|amount| (*amount, 1)).collect(),
+        _ => {
+            let remainder = order.initial_asset.amount % order.dca_amount;
+            vec![
+                (
+                    order.dca_amount,
+                    (order.initial_asset.amount / order.dca_amount).u128() as u64,
+                ),
+                (remainder, 1),
+            ]
+        }
+    };
+    amounts.retain(|(amount, purchases)| !amount.is_zero() && *purchases > 0);
+
+    // an order with a maximum amount of purchases is closed once it has performed them
+    if let Some(max) = order.max_purchases {
+        let mut remaining =
+            max.saturating_sub(get_purchase_count(deps.storage, &user_address, id)?);
+        for (_, purchases) in amounts.iter_mut() {
+            *purchases = (*purchases).min(remaining);
+            remaining -= *purchases;
+        }
+        amounts.retain(|(_, purchases)| *purchases > 0);
+    }
+
+    // the tip redeemed for every hop of each purchase from the first whitelisted fee asset in the
+    // priority of the user, unless the order pays a share of each purchase instead
+    let redeemed_tip = match order.swap_tip_bps {
+        Some(_) => None,
+        None => user_config
+            .prioritized_tip_balance()
+            .into_iter()
+            .find_map(|balance| {
+                config
+                    .whitelisted_fee_assets
+                    .iter()
+                    .find(|fee| fee.info == balance.info)
+            })
+            .or_else(|| config.whitelisted_fee_assets.first())
+            .map(|fee| -> StdResult<_> {
+                Ok(Asset {
+                    info: fee.info.clone(),
+                    amount: fee.amount.checked_mul(Uint128::from(hops.len() as u64))?,
+                })
+            })
+            .transpose()?,
+    };
+
+    let mut estimated_output = Uint128::zero();
+    let mut estimated_tips = vec![];
+    for (amount, purchases) in &amounts {
+        let split = purchase_amounts(
+            &deps.querier,
+            &config,
+            &user_config,
+            &order,
+            &user_address,
+            *amount,
+        )
+        .map_err(to_std_err)?;
+
+        let output = match split.swap_amount.is_zero() {
+            true => Uint128::zero(),
+            false => {
+                get_swap_simulation(&deps, &config.router_addr, split.swap_amount, hops.clone())?
+            }
+        };
+        estimated_output =
+            estimated_output.checked_add(output.checked_mul(Uint128::from(*purchases))?)?;
+
+        add_asset(
+            &mut estimated_tips,
+            Asset {
+                info: order.initial_asset.info.clone(),
+                amount: split.swap_tip.checked_mul(Uint128::from(*purchases))?,
+            },
+        )?;
+        if let Some(tip) = &redeemed_tip {
+            add_asset(
+                &mut estimated_tips,
+                Asset {
+                    info: tip.info.clone(),
+                    amount: tip.amount.checked_mul(Uint128::from(*purchases))?,
+                },
+            )?;
+        }
+    }
+
+    // the next purchase can be performed once the interval has passed, or now if it already has
+    // and has not been skipped, with each following purchase found in turn
+    let mut at = order.upcoming_purchase(&env.block);
+    let mut purchases = vec![];
+    for amount in amounts
+        .iter()
+        .flat_map(|(amount, purchases)| (0..*purchases).map(move |_| *amount))
+        .take(MAX_PLANNED_PURCHASES)
+    {
+        purchases.push(PlannedPurchase { at, amount });
+        at = order.purchase_after(at);
+    }
+
+    Ok(OrderPlanResponse {
+        hops,
+        remaining_purchases: amounts.iter().map(|(_, purchases)| purchases).sum(),
+        purchases,
+        estimated_output,
+        estimated_tips,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::{QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, OrderPlanResponse,
+        PlannedPurchase, QueryMsg, Strategy,
+    };
+    use cosmwasm_std::{coins, Uint128};
+    use cw_multi_test::Executor;
+
+    use crate::{
+        state::CONFIG,
+        tests::{mock_astroport_instantiate, mock_creator, read_item},
+    };
+
+    #[test]
+    fn does_simulate_order_plan() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+        let config = read_item(&app, &dca_addr, CONFIG);
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(25_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ukrw.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(25_000, "uluna"),
+        )
+        .unwrap();
+
+        // the only route to ukrw is through ujpy
+        let hops = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: uluna.clone(),
+                ask_asset_info: ujpy.clone(),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: ujpy,
+                ask_asset_info: ukrw,
+            },
+        ];
+        let simulate = |amount: u128| -> Uint128 {
+            let simulation: SimulateSwapOperationsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &config.router_addr,
+                    &RouterQueryMsg::SimulateSwapOperations {
+                        offer_amount: Uint128::new(amount),
+                        operations: hops.clone(),
+                    },
+                )
+                .unwrap();
+            simulation.amount
+        };
+
+        let res: OrderPlanResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dca_addr,
+                &QueryMsg::SimulateOrderPlan {
+                    user: mock_creator().sender.into_string(),
+                    id: 1,
+                },
+            )
+            .unwrap();
+
+        // two purchases of dca_amount are followed by one spending the rest of the deposit, each
+        // redeeming the tip of the first whitelisted tip asset for both hops
+        let now = app.block_info().time.seconds();
+        assert_eq!(
+            res,
+            OrderPlanResponse {
+                hops: hops.clone(),
+                remaining_purchases: 3,
+                purchases: vec![
+                    PlannedPurchase {
+                        at: now,
+                        amount: Uint128::new(10_000),
+                    },
+                    PlannedPurchase {
+                        at: now + 500,
+                        amount: Uint128::new(10_000),
+                    },
+                    PlannedPurchase {
+                        at: now + 1_000,
+                        amount: Uint128::new(5_000),
+                    },
+                ],
+                estimated_output: simulate(10_000) * Uint128::new(2) + simulate(5_000),
+                estimated_tips: vec![Asset {
+                    amount: Uint128::new(90_000),
+                    info: uluna,
+                }],
+            }
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use astroport::{
+    asset::{addr_validate_to_lower, Asset, AssetInfo},
+    router::SwapOperation,
+};
+use astroport_dca::dca::SimulatePurchaseResponse;
+use cosmwasm_std::{Deps, Env, StdError, StdResult, Uint128};
+
+use crate::{
+    error::ContractError,
+    get_swap_simulation::get_swap_simulation,
+    handlers::{purchase_amounts, purchase_hops, purchase_spend_amount, select_tip},
+    price_impact::get_price_impact,
+    state::{user_dca, CONFIG, USER_CONFIG},
+};
+
+/// ## Description
+/// Simulates the next DCA purchase of a DCA order of a user through the hop route specified,
+/// validating the route and splitting the amount spent the same way a purchase does, without
+/// performing it. Bots can use it to estimate whether a purchase is worth performing.
+///
+/// The result is returned in a [`SimulatePurchaseResponse`] object, or an error that the purchase
+/// would fail with for an invalid route.
+///
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `user` - The users lowercase address as a [`String`].
+///
+/// * `id` - A [`u64`] representing the ID of the DCA order for the user.
+///
+/// * `hops` - The [`SwapOperation`] hops to simulate the purchase through, which are ignored for
+/// an order with a stored route or that is auto routed.
+pub fn simulate_purchase(
+    deps: Deps,
+    env: Env,
+    user: String,
+    id: u64,
+    hops: Vec<SwapOperation>,
+) -> StdResult<SimulatePurchaseResponse> {
+    let to_std_err = |e: ContractError| StdError::generic_err(e.to_string());
+
+    let user_address = addr_validate_to_lower(deps.api, &user)?;
+    let config = CONFIG.load(deps.storage)?;
+    let user_config = USER_CONFIG
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+    let order = user_dca()
+        .may_load(deps.storage, (&user_address, id))?
+        .ok_or_else(|| to_std_err(ContractError::NonexistentDca {}))?;
+
+    // a purchase through a non-whitelisted token fails, even though a bonded bot performing it
+    // would be slashed instead
+    let (hops, invalid_hop) =
+        purchase_hops(&deps, &config, &user_config, Some(&order), hops).map_err(to_std_err)?;
+    if let Some(token) = invalid_hop {
+        return Err(to_std_err(ContractError::InvalidHopRoute { token }));
+    }
+
+    // check that last hop is target asset
+    match hops.last() {
+        Some(last_hop) if last_hop.get_target_asset_info() == order.target_asset => {}
+        _ => return Err(to_std_err(ContractError::TargetAssetAssertion {})),
+    }
+
+    let (spend_amount, _) =
+        purchase_spend_amount(&deps, &config, &order, &hops).map_err(to_std_err)?;
+    let amounts = purchase_amounts(
+        &deps.querier,
+        &config,
+        &user_config,
+        &order,
+        &user_address,
+        spend_amount,
+    )
+    .map_err(to_std_err)?;
+
+    let (expected_output, price_impact) = match amounts.swap_amount.is_zero() {
+        true => (Uint128::zero(), None),
+        false => {
+            let expected_output = get_swap_simulation(
+                &deps,
+                &config.router_addr,
+                amounts.swap_amount,
+                hops.clone(),
+            )?;
+            let price_impact =
+                get_price_impact(&deps, &config, amounts.swap_amount, &hops, expected_output)?;
+
+            (expected_output, price_impact)
+        }
+    };
+
+    // the tip redeemed for every hop from the first whitelisted fee asset in the priority of the
+    // user that covers it, unless the order pays a share of the purchase instead, along with the
+    // tip that an overdue purchase has grown by as far as the rest of the tip balance covers it
+    let mut tip_cost = match order.swap_tip_bps {
+        Some(_) => vec![Asset {
+            info: order.initial_asset.info.clone(),
+            amount: amounts.swap_tip,
+        }],
+        None => {
+            let accepted_tips: Vec<AssetInfo> = config
+                .whitelisted_fee_assets
+                .iter()
+                .map(|fee| fee.info.clone())
+                .collect();
+            select_tip(&config, &user_config, &accepted_tips, hops.len() as u32).unwrap_or_default()
+        }
+    };
+    if let Some(escalated_tip) = order.escalated_tip(&env.block) {
+        // a swap tip is paid from the amount spent rather than from the tip balance
+        let redeemed = match order.swap_tip_bps {
+            Some(_) => Uint128::zero(),
+            None => tip_cost
+                .iter()
+                .find(|tip| tip.info == escalated_tip.info)
+                .map_or(Uint128::zero(), |tip| tip.amount),
+        };
+        let amount = user_config
+            .tip(&escalated_tip.info)
+            .saturating_sub(redeemed)
+            .min(escalated_tip.amount);
+
+        if !amount.is_zero() {
+            match tip_cost
+                .iter_mut()
+                .find(|tip| tip.info == escalated_tip.info)
+            {
+                Some(tip) => tip.amount = tip.amount.checked_add(amount)?,
+                None => tip_cost.push(Asset {
+                    info: escalated_tip.info,
+                    amount,
+                }),
+            }
+        }
+    }
+    tip_cost.retain(|tip| !tip.amount.is_zero());
+
+    Ok(SimulatePurchaseResponse {
+        hops,
+        due: order.is_due(&env.block),
+        spend_amount,
+        swap_amount: amounts.swap_amount,
+        expected_output,
+        price_impact,
+        tip_cost,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use astroport::{
+        asset::{Asset, AssetInfo},
+        router::SwapOperation,
+    };
+    use astroport_dca::dca::{
+        Delivery, Duration, ExecuteMsg, FailurePolicy, OrderDirection, QueryMsg,
+        SimulatePurchaseResponse, Strategy,
+    };
+    use cosmwasm_std::{coins, Decimal, StdResult, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use crate::{
+        error::ContractError,
+        tests::{mock_astroport_instantiate, mock_creator},
+    };
+
+    #[test]
+    fn does_simulate_purchase() {
+        let (mut app, dca_addr, ..) = mock_astroport_instantiate(None);
+
+        let uluna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let ujpy = AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        };
+        let ukrw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::CreateDcaOrder {
+                initial_asset: Asset {
+                    amount: Uint128::new(100_000),
+                    info: uluna.clone(),
+                },
+                target_asset: ukrw.clone(),
+                interval: Duration::Time(500),
+                dca_amount: Uint128::new(10_000),
+                first_purchase: None,
+                expires_at: None,
+                max_price: None,
+                max_spread: None,
+                min_receive_per_purchase: None,
+                route: None,
+                auto_route: false,
+                delivery: Delivery::Immediate,
+                recipient: None,
+                purchase_hooks: vec![],
+                auto_stake: false,
+                zap: None,
+                referrer: None,
+                owner: None,
+                schedule: None,
+                max_delay: None,
+                jitter: None,
+                only_below_price: None,
+                max_price_impact: None,
+                stop_price: None,
+                take_profit_price: None,
+                direction: OrderDirection::Accumulate,
+                distribution: None,
+                targets: vec![],
+                pooled: false,
+                strategy: Strategy::Fixed,
+                max_purchases: None,
+                pull: false,
+                max_failures: None,
+                failure_policy: FailurePolicy::Pause,
+                swap_tip_bps: None,
+                tip_escalation: None,
+                tip_replenish: None,
+                allowed_bots: vec![],
+                allow_low_liquidity: false,
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        let simulate =
+            |app: &App, hops: Vec<SwapOperation>| -> StdResult<SimulatePurchaseResponse> {
+                app.wrap().query_wasm_smart(
+                    &dca_addr,
+                    &QueryMsg::SimulatePurchase {
+                        user: mock_creator().sender.into_string(),
+                        id: 1,
+                        hops,
+                    },
+                )
+            };
+
+        let hops = vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: uluna.clone(),
+                ask_asset_info: ujpy.clone(),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: ujpy.clone(),
+                ask_asset_info: ukrw,
+            },
+        ];
+
+        // the route must end in the target asset of the order
+        let res = simulate(&app, hops[..1].to_vec()).unwrap_err();
+        assert!(res
+            .to_string()
+            .contains(&ContractError::TargetAssetAssertion {}.to_string()));
+
+        // no tip is paid without a tip balance covering it
+        let res = simulate(&app, hops.clone()).unwrap();
+        assert!(res.tip_cost.is_empty());
+
+        app.execute_contract(
+            mock_creator().sender,
+            dca_addr.clone(),
+            &ExecuteMsg::AddBotTip {
+                assets: vec![Asset {
+                    amount: Uint128::new(45_000),
+                    info: uluna.clone(),
+                }],
+            },
+            &coins(45_000, "uluna"),
+        )
+        .unwrap();
+
+        // both pools hold 500_000 of each asset, so the 10_000 swapped is worth 10_000 before the
+        // fees and slippage of the pairs
+        let res = simulate(&app, hops.clone()).unwrap();
+        assert_eq!(
+            res,
+            SimulatePurchaseResponse {
+                hops,
+                due: true,
+                spend_amount: Uint128::new(10_000),
+                swap_amount: Uint128::new(10_000),
+                expected_output: Uint128::new(9_558),
+                price_impact: Some(Decimal::from_ratio(442u128, 10_000u128)),
+                tip_cost: vec![Asset {
+                    amount: Uint128::new(30_000),
+                    info: uluna,
+                }],
+            }
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use astroport::{
+    asset::{AssetInfo, PairInfo},
+    factory::QueryMsg as FactoryQueryMsg,
+};
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Storage};
+
+use crate::state::PAIRS;
+
+/// ## Description
+/// Returns the key of the pair of `asset_infos` in [`PAIRS`], which is the same for both orders
+/// of the assets.
+pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> (String, String) {
+    let (a, b) = (asset_infos[0].to_string(), asset_infos[1].to_string());
+
+    match a <= b {
+        true => (a, b),
+        false => (b, a),
+    }
+}
+
+/// ## Description
+/// Queries the Astroport factory for the [`PairInfo`] of the pair of `asset_infos`.
+pub fn query_factory_pair(
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    querier.query_wasm_smart(
+        factory_addr,
+        &FactoryQueryMsg::Pair {
+            asset_infos: asset_infos.clone(),
+        },
+    )
+}
+
+/// ## Description
+/// Returns the [`PairInfo`] of the Astroport pair of `asset_infos`, including its address and
+/// pool type, which is only queried from the factory the first time and cached in [`PAIRS`]
+/// afterwards.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract, caching the pairs.
+///
+/// * `querier` - The [`QuerierWrapper`] used to query the factory.
+///
+/// * `factory_addr` - The [`Addr`] of the Astroport factory.
+///
+/// * `asset_infos` - The [`AssetInfo`] of both assets of the pair.
+pub fn query_pair(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    let key = pair_key(asset_infos);
+    if let Some(pair) = PAIRS.may_load(storage, key.clone())? {
+        return Ok(pair);
+    }
+
+    let pair = query_factory_pair(querier, factory_addr, asset_infos)?;
+    PAIRS.save(storage, key, &pair)?;
+
+    Ok(pair)
+}
+
+/// ## Description
+/// Returns the [`PairInfo`] of the Astroport pair of `asset_infos` cached in [`PAIRS`], or queries
+/// it from the factory without caching it if it has not been cached yet.
+pub fn load_pair(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    match PAIRS.may_load(storage, pair_key(asset_infos))? {
+        Some(pair) => Ok(pair),
+        None => query_factory_pair(querier, factory_addr, asset_infos),
+    }
+}
@@ -0,0 +1,166 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::AssetReconciliation;
+use cosmwasm_std::{Deps, Env, Order, StdResult, Storage, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+
+use crate::state::{
+    user_dca, BOTS, CONFIG, IBC_TRANSFERS, PURCHASED_BALANCE, REFERRAL_REWARDS, USER_CONFIG,
+};
+
+/// ## Description
+/// Adds `amount` of `info` to the `assets` it is owed in.
+fn add_liability(assets: &mut Vec<Asset>, info: &AssetInfo, amount: Uint128) -> StdResult<()> {
+    match assets.iter_mut().find(|asset| &asset.info == info) {
+        Some(asset) => asset.amount = asset.amount.checked_add(amount)?,
+        None => assets.push(Asset {
+            info: info.clone(),
+            amount,
+        }),
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amount of each asset that the contract owes to its users and bots, which are the
+/// deposits of DCA orders held by the contract, the tip balances held by the contract, the
+/// purchased assets escrowed for users to claim, the unclaimed referral rewards, the ICS-20
+/// transfers in flight, which stay owed until their outcome is reported in case they are refunded
+/// to the contract, and the bonds of bots.
+///
+/// Every order and user is iterated over, so this is only meant for queries and for the contract
+/// owner.
+fn tracked_liabilities(storage: &dyn Storage) -> StdResult<Vec<Asset>> {
+    let mut liabilities = vec![];
+
+    // cw20 orders spending the allowance of the user hold nothing in the contract
+    for item in user_dca().range(storage, None, None, Order::Ascending) {
+        let (_, order) = item?;
+        if order.initial_asset.info.is_native_token() || order.escrowed {
+            add_liability(
+                &mut liabilities,
+                &order.initial_asset.info,
+                order.initial_asset.amount,
+            )?;
+        }
+    }
+
+    // and neither do cw20 tips that have not been deposited into the contract
+    for item in USER_CONFIG.range(storage, None, None, Order::Ascending) {
+        let (_, user_config) = item?;
+        let held_tips = user_config
+            .tip_balance
+            .iter()
+            .filter(|tip| tip.info.is_native_token())
+            .chain(&user_config.deposited_tip_balance);
+        for tip in held_tips {
+            add_liability(&mut liabilities, &tip.info, tip.amount)?;
+        }
+    }
+
+    for item in PURCHASED_BALANCE.range(storage, None, None, Order::Ascending) {
+        let (_, balance) = item?;
+        add_liability(&mut liabilities, &balance.info, balance.amount)?;
+    }
+
+    for item in REFERRAL_REWARDS.range(storage, None, None, Order::Ascending) {
+        let (_, rewards) = item?;
+        add_liability(&mut liabilities, &rewards.info, rewards.amount)?;
+    }
+
+    for item in IBC_TRANSFERS.range(storage, None, None, Order::Ascending) {
+        let (_, transfer) = item?;
+        add_liability(
+            &mut liabilities,
+            &transfer.asset.info,
+            transfer.asset.amount,
+        )?;
+    }
+
+    for item in BOTS.range(storage, None, None, Order::Ascending) {
+        let (_, stats) = item?;
+        if let Some(bond) = stats.bond {
+            add_liability(
+                &mut liabilities,
+                &AssetInfo::NativeToken { denom: bond.denom },
+                bond.amount,
+            )?;
+        }
+    }
+
+    Ok(liabilities)
+}
+
+/// ## Description
+/// Returns the balance of each asset held by the contract against the liabilities it tracks for
+/// the asset, along with the surplus held over the liabilities or the deficit of the balance.
+///
+/// If `assets` is not specified, every native token held by the contract, every asset it owes and
+/// every whitelisted token is reconciled. Each asset is reconciled once.
+/// ## Arguments
+/// * `deps` - A [`Deps`] that contains the dependencies.
+///
+/// * `env` - The [`Env`] of the blockchain.
+///
+/// * `assets` - The [`AssetInfo`]s to reconcile, or [`None`] to reconcile every known asset.
+pub fn reconcile(
+    deps: Deps,
+    env: &Env,
+    assets: Option<Vec<AssetInfo>>,
+) -> StdResult<Vec<AssetReconciliation>> {
+    let liabilities = tracked_liabilities(deps.storage)?;
+    let native_balances = deps.querier.query_all_balances(&env.contract.address)?;
+
+    let candidates = match assets {
+        Some(assets) => assets,
+        None => native_balances
+            .iter()
+            .map(|coin| AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            })
+            .chain(liabilities.iter().map(|asset| asset.info.clone()))
+            .chain(CONFIG.load(deps.storage)?.whitelisted_tokens)
+            .collect(),
+    };
+
+    let mut infos: Vec<AssetInfo> = vec![];
+    for info in candidates {
+        if !infos.contains(&info) {
+            infos.push(info);
+        }
+    }
+
+    infos
+        .into_iter()
+        .map(|info| {
+            let balance = match &info {
+                AssetInfo::NativeToken { denom } => native_balances
+                    .iter()
+                    .find(|coin| &coin.denom == denom)
+                    .map_or(Uint128::zero(), |coin| coin.amount),
+                AssetInfo::Token { contract_addr } => {
+                    deps.querier
+                        .query_wasm_smart::<BalanceResponse>(
+                            contract_addr,
+                            &Cw20QueryMsg::Balance {
+                                address: env.contract.address.to_string(),
+                            },
+                        )?
+                        .balance
+                }
+            };
+            let owed = liabilities
+                .iter()
+                .find(|asset| asset.info == info)
+                .map_or(Uint128::zero(), |asset| asset.amount);
+
+            Ok(AssetReconciliation {
+                info,
+                balance,
+                liabilities: owed,
+                surplus: balance.saturating_sub(owed),
+                deficit: owed.saturating_sub(balance),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,47 @@
+use astroport::asset::AssetInfo;
+use astroport_dca::dca::DcaInfo;
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+/// ## Description
+/// Creates the message to refund `amount` of the `initial_asset` of a DCA order held by the
+/// contract back to `recipient`.
+///
+/// Returns [`None`] if the order spends a cw20 token from the users allowance, as the funds never
+/// left the user.
+/// ## Arguments
+/// * `order` - The [`DcaInfo`] of the order being refunded.
+///
+/// * `amount` - The [`Uint128`] amount of the `initial_asset` to refund.
+///
+/// * `recipient` - The [`Addr`] to send the refund to.
+pub fn refund_order(
+    order: &DcaInfo,
+    amount: Uint128,
+    recipient: &Addr,
+) -> StdResult<Option<CosmosMsg>> {
+    Ok(match &order.initial_asset.info {
+        AssetInfo::NativeToken { denom } => Some(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    amount,
+                    denom: denom.clone(),
+                }],
+            }
+            .into(),
+        ),
+        AssetInfo::Token { contract_addr } if order.escrowed => Some(
+            WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        ),
+        AssetInfo::Token { .. } => None,
+    })
+}
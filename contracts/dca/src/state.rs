@@ -1,10 +1,29 @@
-use astroport::asset::{Asset, AssetInfo};
-use cosmwasm_std::{Addr, Decimal};
-use cw_storage_plus::{Item, Map};
+use astroport::{
+    asset::{Asset, AssetInfo, PairInfo},
+    common::OwnershipProposal,
+};
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use astroport_dca::dca::DcaInfo;
+use astroport_dca::dca::{
+    ArchivedOrder, BotBond, BotStats, DcaInfo, Delivery, Duration, FailurePolicy, FeeTier,
+    InterchainController, OrderDirection, PairVolume, PurchaseHook, PurchaseRecord, Strategy,
+    SwapBackendType, Zap,
+};
+use cw20::Expiration;
+
+use crate::error::ContractError;
+
+/// The amount of basis points in a whole
+pub const MAX_BPS: u16 = 10_000;
+
+/// The maximum protocol fee that can be configured, in basis points
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+/// The maximum share of each DCA purchase that an order can pay to bots, in basis points
+pub const MAX_SWAP_TIP_BPS: u16 = 500;
 
 /// Stores the main dca module parameters.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -22,6 +41,89 @@ pub struct Config {
     pub factory_addr: Addr,
     /// The address of the Astroport router contract
     pub router_addr: Addr,
+    /// The address of the Astroport staking contract used to stake purchased ASTRO
+    pub staking_addr: Option<Addr>,
+    /// The fee in basis points taken from the amount spent in each DCA purchase
+    #[serde(default)]
+    pub protocol_fee_bps: u16,
+    /// The address the protocol fee is sent to
+    pub fee_collector: Option<Addr>,
+    /// The discounts on the protocol fee for users holding xASTRO, ordered by their minimum
+    /// balance
+    #[serde(default)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// The share of the protocol fee of each DCA purchase that accrues to the referrer of the order
+    #[serde(default)]
+    pub referral_share: Decimal,
+    /// The owner of the contract, which is the owner of the factory if not set
+    pub owner: Option<Addr>,
+    /// The delay in seconds before sensitive configuration changes take effect, which are fee
+    /// increases and whitelist removals
+    #[serde(default)]
+    pub config_timelock: u64,
+    /// Whether the contract has been paused, preventing new DCA orders and DCA purchases while
+    /// still allowing users to cancel their orders and withdraw their funds
+    #[serde(default)]
+    pub paused: bool,
+    /// The assets whose DCA purchases have been paused, for orders either spending or purchasing
+    /// them
+    #[serde(default)]
+    pub paused_assets: Vec<AssetInfo>,
+    /// The address of the companion cw721 contract minting an NFT for each new DCA order, whose
+    /// holder is treated as the owner of the order
+    pub nft_addr: Option<Addr>,
+    /// The maximum deviation of the simulated price of a DCA purchase from the price of the
+    /// oracle set for its assets, above which the purchase is refused
+    pub max_oracle_deviation: Option<Decimal>,
+    /// Whether only the bots registered in [`BOTS`] can perform DCA purchases
+    #[serde(default)]
+    pub registered_bots_only: bool,
+    /// The bond that bots must post when registering, which is slashed for routing DCA purchases
+    /// through an invalid or worse route
+    #[serde(default)]
+    pub bot_bond: Option<BotBond>,
+    /// The window after each DCA purchase is due, in the unit of the interval of its order, during
+    /// which only the bot assigned to the purchase can perform it
+    #[serde(default)]
+    pub fairness_window: Option<u64>,
+    /// The assets that DCA orders can spend or purchase while `allowed_assets_only` is set
+    #[serde(default)]
+    pub allowed_assets: Vec<AssetInfo>,
+    /// Whether DCA orders can only spend or purchase the `allowed_assets`, rather than any asset
+    #[serde(default)]
+    pub allowed_assets_only: bool,
+    /// The malicious or deprecated assets that DCA orders cannot spend or purchase
+    #[serde(default)]
+    pub blacklisted_assets: Vec<AssetInfo>,
+    /// The minimum amount of each asset that the pairs a new DCA order swaps through must hold,
+    /// unless the order allows low liquidity
+    #[serde(default)]
+    pub min_liquidity: Option<Uint128>,
+    /// The cw20 tokens taking a tax on transfers, which are pulled into the contract to measure
+    /// the amount received before it is swapped
+    #[serde(default)]
+    pub fee_on_transfer_tokens: Vec<Addr>,
+    /// The minimum deposit of a new DCA order, in whole units of its initial asset that are
+    /// scaled by the decimals of the asset
+    #[serde(default)]
+    pub min_deposit: Option<Decimal>,
+    /// The minimum amount spent in each purchase of a new DCA order, in whole units of its
+    /// initial asset that are scaled by the decimals of the asset
+    #[serde(default)]
+    pub min_dca_amount: Option<Decimal>,
+    /// The backend that the swaps of DCA purchases are constructed for, selected per deployment
+    #[serde(default)]
+    pub swap_backend: SwapBackendType,
+}
+
+/// Describes the contract configuration returned by a config query, along with the pending
+/// proposal of a new owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    #[serde(flatten)]
+    pub config: Config,
+    /// The pending proposal of a new owner of the contract
+    pub ownership_proposal: Option<OwnershipProposal>,
 }
 
 impl Config {
@@ -30,10 +132,131 @@ impl Config {
         self.whitelisted_tokens.contains(asset)
     }
 
+    /// Checks if DCA orders can spend or purchase a given `asset`, which is any asset unless the
+    /// contract owner only allows the `allowed_assets`
+    pub fn is_allowed_asset(&self, asset: &AssetInfo) -> bool {
+        !self.allowed_assets_only || self.allowed_assets.contains(asset)
+    }
+
+    /// Checks if a given `asset` has been blacklisted by the contract owner
+    pub fn is_blacklisted_asset(&self, asset: &AssetInfo) -> bool {
+        self.blacklisted_assets.contains(asset)
+    }
+
+    /// Checks if a given `asset` is a cw20 token taking a tax on transfers
+    pub fn is_fee_on_transfer_token(&self, asset: &AssetInfo) -> bool {
+        match asset {
+            AssetInfo::Token { contract_addr } => {
+                self.fee_on_transfer_tokens.contains(contract_addr)
+            }
+            AssetInfo::NativeToken { .. } => false,
+        }
+    }
+
+    /// Checks if DCA purchases spending or purchasing a given `asset` have been paused
+    pub fn is_paused_asset(&self, asset: &AssetInfo) -> bool {
+        self.paused_assets.contains(asset)
+    }
+
     /// Checks if a given `asset` is a whitelisted asset for paying bot tips
     pub fn is_whitelisted_fee_asset(&self, asset: &AssetInfo) -> bool {
         self.whitelisted_fee_assets.iter().any(|a| &a.info == asset)
     }
+
+    /// Returns the protocol fee taken from `amount` spent in a DCA purchase by a user holding
+    /// `xastro_balance`, after the discount of the highest fee tier the balance reaches
+    pub fn protocol_fee(&self, amount: Uint128, xastro_balance: Uint128) -> Uint128 {
+        let fee = amount.multiply_ratio(self.protocol_fee_bps, MAX_BPS);
+
+        match self
+            .fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| xastro_balance >= tier.min_xastro)
+        {
+            Some(tier) => fee - fee * tier.discount,
+            None => fee,
+        }
+    }
+
+    /// Checks that the protocol fee does not exceed [`MAX_PROTOCOL_FEE_BPS`], that a fee collector
+    /// is set to receive it, and that the fee tiers and referral share are valid
+    pub fn validate_protocol_fee(&self) -> Result<(), ContractError> {
+        if self.protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(ContractError::ProtocolFeeTooHigh {
+                max: MAX_PROTOCOL_FEE_BPS,
+            });
+        }
+
+        if self.protocol_fee_bps > 0 && self.fee_collector.is_none() {
+            return Err(ContractError::FeeCollectorNotSet {});
+        }
+
+        // the xASTRO balance of users is found through the staking contract
+        if !self.fee_tiers.is_empty() && self.staking_addr.is_none() {
+            return Err(ContractError::StakingNotConfigured {});
+        }
+
+        if self
+            .fee_tiers
+            .iter()
+            .any(|tier| tier.discount > Decimal::one())
+        {
+            return Err(ContractError::InvalidFeeTier {});
+        }
+
+        if self.referral_share > Decimal::one() {
+            return Err(ContractError::InvalidReferralShare {});
+        }
+
+        Ok(())
+    }
+}
+
+/// Stores a change of sensitive configuration parameters, which takes effect once its timelock
+/// has passed so that users can exit their orders before it does
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfigChange {
+    /// The increased fee in basis points taken from the amount spent in each DCA purchase
+    pub protocol_fee_bps: Option<u16>,
+    /// The whitelisted tokens that can be used in a DCA hop route, with some tokens removed
+    pub whitelisted_tokens: Option<Vec<AssetInfo>>,
+    /// The whitelisted tokens that can be used for bot tips, with some tokens removed or their fee
+    /// per hop increased
+    pub whitelisted_fee_assets: Option<Vec<Asset>>,
+    /// The decreased delay in seconds before sensitive configuration changes take effect
+    pub config_timelock: Option<u64>,
+    /// The time in seconds after which the change can be applied
+    pub effective_at: u64,
+}
+
+impl PendingConfigChange {
+    /// Returns whether the change does not modify any parameter
+    pub fn is_empty(&self) -> bool {
+        self.protocol_fee_bps.is_none()
+            && self.whitelisted_tokens.is_none()
+            && self.whitelisted_fee_assets.is_none()
+            && self.config_timelock.is_none()
+    }
+
+    /// Applies the change to `config`
+    pub fn apply(self, config: &mut Config) {
+        if let Some(protocol_fee_bps) = self.protocol_fee_bps {
+            config.protocol_fee_bps = protocol_fee_bps;
+        }
+
+        if let Some(whitelisted_tokens) = self.whitelisted_tokens {
+            config.whitelisted_tokens = whitelisted_tokens;
+        }
+
+        if let Some(whitelisted_fee_assets) = self.whitelisted_fee_assets {
+            config.whitelisted_fee_assets = whitelisted_fee_assets;
+        }
+
+        if let Some(config_timelock) = self.config_timelock {
+            config.config_timelock = config_timelock;
+        }
+    }
 }
 
 /// Stores the users custom configuration
@@ -49,11 +272,559 @@ pub struct UserConfig {
     pub max_spread: Option<Decimal>,
     /// The tip balance the user has deposited for their tips when performing DCA purchases
     pub tip_balance: Vec<Asset>,
+    /// The order in which the tip balance is redeemed by bots that leave the choice of tip to the
+    /// contract, with the assets not listed redeemed after those listed
+    #[serde(default)]
+    pub tip_priority: Vec<AssetInfo>,
+    /// The part of `tip_balance` in cw20 tokens that was deposited into the contract, which is
+    /// paid out by the contract rather than transferred from the allowance of the user
+    #[serde(default)]
+    pub deposited_tip_balance: Vec<Asset>,
+}
+
+impl UserConfig {
+    /// Returns the amount of the tip balance of `info` that is held by the contract
+    pub fn deposited_tip(&self, info: &AssetInfo) -> Uint128 {
+        self.deposited_tip_balance
+            .iter()
+            .find(|a| &a.info == info)
+            .map_or(Uint128::zero(), |a| a.amount)
+    }
+
+    /// Returns the amount of the tip balance of `info`
+    pub fn tip(&self, info: &AssetInfo) -> Uint128 {
+        self.tip_balance
+            .iter()
+            .find(|a| &a.info == info)
+            .map_or(Uint128::zero(), |a| a.amount)
+    }
+
+    /// Adds `asset` held by the contract to the tip balance, where cw20 tokens are also recorded as
+    /// deposited into the contract
+    pub fn add_held_tip(&mut self, asset: &Asset) -> StdResult<()> {
+        let mut balances = vec![&mut self.tip_balance];
+        if !asset.info.is_native_token() {
+            balances.push(&mut self.deposited_tip_balance);
+        }
+
+        for balance in balances {
+            match balance.iter_mut().find(|a| a.info == asset.info) {
+                Some(balance) => balance.amount = balance.amount.checked_add(asset.amount)?,
+                None => balance.push(asset.clone()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes up to `amount` of the tip balance of `info` held by the contract, returning the amount
+    /// taken, with the rest of `amount` left to be transferred from the allowance of the user
+    pub fn take_deposited_tip(&mut self, info: &AssetInfo, amount: Uint128) -> Uint128 {
+        match self
+            .deposited_tip_balance
+            .iter_mut()
+            .find(|a| &a.info == info)
+        {
+            Some(deposited) => {
+                let taken = deposited.amount.min(amount);
+                deposited.amount -= taken;
+                taken
+            }
+            None => Uint128::zero(),
+        }
+    }
+
+    /// Takes up to `amount` of the tip balance of `info` that is held by the contract, as added by
+    /// [`UserConfig::add_held_tip`], returning the amount taken
+    pub fn take_held_tip(&mut self, info: &AssetInfo, amount: Uint128) -> Uint128 {
+        // the tip balance of a cw20 token is only held by the contract as far as it was deposited
+        let held = match info.is_native_token() {
+            true => self.tip(info),
+            false => self.tip(info).min(self.deposited_tip(info)),
+        };
+        let taken = held.min(amount);
+
+        if let Some(balance) = self.tip_balance.iter_mut().find(|a| &a.info == info) {
+            balance.amount -= taken;
+        }
+        self.take_deposited_tip(info, taken);
+
+        taken
+    }
+
+    /// Returns the tip balance of the user in the order it is redeemed, with the assets of
+    /// `tip_priority` first and the other assets in the order they were deposited
+    pub fn prioritized_tip_balance(&self) -> Vec<Asset> {
+        let mut balance = self.tip_balance.clone();
+        // a stable sort keeps the assets without a priority in the order they were deposited
+        balance.sort_by_key(|asset| {
+            self.tip_priority
+                .iter()
+                .position(|info| info == &asset.info)
+                .unwrap_or(self.tip_priority.len())
+        });
+
+        balance
+    }
+}
+
+/// Stores a DCA purchase while the router swap is being performed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingPurchase {
+    /// The address of the user the purchase is being performed for
+    pub user: Addr,
+    /// The id of the DCA order being purchased
+    pub id: u64,
+    /// The address of the bot performing the purchase
+    pub bot: Addr,
+    /// The tip paid to the bot for performing the purchase
+    pub tip: Vec<Asset>,
+    /// The amount of the initial asset spent by the purchase, including the protocol fee
+    #[serde(default)]
+    pub amount_in: Uint128,
+    /// The amount of the initial asset kept to provide liquidity with if the order zaps
+    #[serde(default)]
+    pub provide_amount: Uint128,
+    /// The delay of the purchase after it was due, in the unit of the interval of the order
+    #[serde(default)]
+    pub delay: u64,
+}
+
+/// Stores a transfer of a token taking a tax on transfers into the contract for a DCA purchase,
+/// while the amount received is being measured
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingTransfer {
+    /// The address of the token being transferred
+    pub token: Addr,
+    /// The balance of the token held by the contract before the transfer
+    pub balance: Uint128,
+    /// The amount of the token requested to be swapped by the DCA purchase
+    pub swap_amount: Uint128,
+    /// The amount of the token requested to be provided as liquidity by the DCA purchase
+    pub provide_amount: Uint128,
+}
+
+/// Stores the swaps of a DCA purchase split across several routes that have not replied yet,
+/// along with the amount received from those that have
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSplit {
+    /// The amount of swaps of the purchase that have not replied yet
+    pub remaining: u32,
+    /// The total amount of the target asset received from the swaps that have replied
+    pub received: Uint128,
+    /// The minimum amount of the target asset that all the swaps of the purchase must receive
+    pub minimum_receive: Option<Uint128>,
+    /// The target asset purchased by each swap, in order, if the purchase is split across the
+    /// several target assets of its order rather than across routes to one target asset
+    #[serde(default)]
+    pub targets: Vec<AssetInfo>,
+    /// The amount of each target asset received from the swaps that have replied
+    #[serde(default)]
+    pub outputs: Vec<Asset>,
+}
+
+/// Stores the ICS-20 transfer of the asset purchased for a DCA order with IBC delivery, so that it
+/// can be held for the user to claim if the transfer fails
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransfer {
+    /// The address of the user the transfer was sent for
+    pub user: Addr,
+    /// The id of the DCA order the transfer was sent for
+    pub id: u64,
+    /// The channel the transfer was sent over
+    pub channel_id: String,
+    /// The purchased asset transferred
+    pub asset: Asset,
+}
+
+/// Stores the shares of a pooled DCA order, along with the amount of the target asset purchased for
+/// each share
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Pool {
+    /// The total shares of the order held by all depositors
+    pub total_shares: Uint128,
+    /// The cumulative amount of the target asset purchased for each share
+    pub output_per_share: Decimal,
+}
+
+/// Stores the shares of a pooled DCA order held by a depositor
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PoolShare {
+    /// The shares of the order held by the depositor
+    pub shares: Uint128,
+    /// The amount of the target asset purchased for each share when the purchases owed to the
+    /// depositor were last added to `claimable`
+    pub output_per_share: Decimal,
+    /// The amount of the target asset owed to the depositor that has not been claimed yet
+    pub claimable: Uint128,
+}
+
+impl PoolShare {
+    /// ## Description
+    /// Adds the target asset purchased for the shares of the depositor since they were last
+    /// settled against `pool` to the amount the depositor can claim.
+    pub fn settle(&mut self, pool: &Pool) -> StdResult<()> {
+        let owed = self.shares * (pool.output_per_share - self.output_per_share);
+        self.claimable = self.claimable.checked_add(owed)?;
+        self.output_per_share = pool.output_per_share;
+
+        Ok(())
+    }
+}
+
+/// Stores the DCA purchases of a batch performed through `perform_dca_purchases` that have not
+/// replied yet, in the order they are performed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBatch {
+    /// The address of the bot performing the purchases
+    pub bot: Addr,
+    /// The user address and the id of the DCA order of each purchase
+    pub orders: Vec<(String, u64)>,
+}
+
+/// Stores the running counters of the global statistics of the contract
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct GlobalStats {
+    /// The amount of DCA orders stored
+    pub active_orders: u64,
+    /// The amount of DCA purchases performed
+    pub total_purchases: u64,
+    /// The time in seconds that the current epoch started at
+    pub epoch_start: u64,
+    /// The amount of DCA purchases performed in the current epoch
+    pub epoch_purchases: u64,
+    /// The amount of DCA purchases performed in the last epoch
+    pub last_epoch_purchases: u64,
+}
+
+/// Stores the ASTRO of a DCA purchase being staked while the staking contract is entered
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingStake {
+    /// The address the xASTRO received is sent to
+    pub recipient: Addr,
+    /// The address of the xASTRO token
+    pub share_token: Addr,
+    /// The xASTRO balance of the contract before entering the staking contract
+    pub balance: Uint128,
+}
+
+/// A DCA order as stored prior to intervals being expressed as a [`Duration`], where `interval`
+/// always was an amount of seconds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyDcaInfo {
+    pub id: u64,
+    pub initial_asset: Asset,
+    pub target_asset: AssetInfo,
+    pub interval: u64,
+    pub last_purchase: u64,
+    pub dca_amount: Uint128,
+    #[serde(default)]
+    pub escrowed: bool,
+    #[serde(default)]
+    pub paused: bool,
+    pub expires_at: Option<Expiration>,
+    pub max_price: Option<Decimal>,
+    pub max_spread: Option<Decimal>,
+    pub min_receive_per_purchase: Option<Uint128>,
+    pub route: Option<Vec<AssetInfo>>,
+    #[serde(default)]
+    pub auto_route: bool,
+    #[serde(default)]
+    pub delivery: Delivery,
+    pub recipient: Option<Addr>,
+    #[serde(default)]
+    pub purchase_hooks: Vec<PurchaseHook>,
+    #[serde(default)]
+    pub auto_stake: bool,
+    pub zap: Option<Zap>,
+    pub referrer: Option<Addr>,
+    #[serde(default)]
+    pub total_spent: Uint128,
+    #[serde(default)]
+    pub total_received: Uint128,
+    #[serde(default)]
+    pub executing: bool,
+}
+
+impl From<LegacyDcaInfo> for DcaInfo {
+    fn from(order: LegacyDcaInfo) -> Self {
+        DcaInfo {
+            id: order.id,
+            initial_asset: order.initial_asset,
+            target_asset: order.target_asset,
+            interval: Duration::Time(order.interval),
+            last_purchase: order.last_purchase,
+            dca_amount: order.dca_amount,
+            escrowed: order.escrowed,
+            paused: order.paused,
+            expires_at: order.expires_at,
+            max_price: order.max_price,
+            max_spread: order.max_spread,
+            min_receive_per_purchase: order.min_receive_per_purchase,
+            route: order.route,
+            auto_route: order.auto_route,
+            delivery: order.delivery,
+            recipient: order.recipient,
+            purchase_hooks: order.purchase_hooks,
+            auto_stake: order.auto_stake,
+            zap: order.zap,
+            referrer: order.referrer,
+            total_spent: order.total_spent,
+            total_received: order.total_received,
+            targets_received: vec![],
+            executing: order.executing,
+            schedule: None,
+            max_delay: None,
+            jitter: None,
+            jitter_offset: 0,
+            only_below_price: None,
+            max_price_impact: None,
+            stop_price: None,
+            take_profit_price: None,
+            direction: OrderDirection::Accumulate,
+            distribution: None,
+            targets: vec![],
+            pooled: false,
+            strategy: Strategy::Fixed,
+            target_value: Uint128::zero(),
+            max_purchases: None,
+            pull: false,
+            last_failure: None,
+            max_failures: None,
+            failure_policy: FailurePolicy::Pause,
+            consecutive_failures: 0,
+            replenished_tip: Uint128::zero(),
+            swap_tip_bps: None,
+            tip_escalation: None,
+            tip_replenish: None,
+            allowed_bots: vec![],
+        }
+    }
 }
 
 /// The contract configuration
 pub const CONFIG: Item<Config> = Item::new("config");
+/// The DCA orders that were removed once they were completed, keyed by the user address and the
+/// id of the order
+pub const ARCHIVED_ORDERS: Map<(&Addr, u64), ArchivedOrder> = Map::new("archived_orders");
 /// The configuration set by each user
 pub const USER_CONFIG: Map<&Addr, UserConfig> = Map::new("user_config");
-/// The DCA orders for a user
-pub const USER_DCA: Map<&Addr, Vec<DcaInfo>> = Map::new("user_dca");
+/// The DCA orders for a user, stored as a single [`Vec<LegacyDcaInfo>`] prior to the migration to
+/// [`user_dca`]
+pub const LEGACY_USER_DCA: Map<&Addr, Vec<LegacyDcaInfo>> = Map::new("user_dca");
+/// The DCA orders stored in [`user_dca`] prior to intervals being expressed as a [`Duration`]
+pub const LEGACY_ORDERS: Map<(&Addr, u64), LegacyDcaInfo> = Map::new("user_dca_orders");
+/// The DCA purchases of a batch being performed, which are recorded as each purchase replies
+pub const PENDING_BATCH: Item<PendingBatch> = Item::new("pending_batch");
+/// The DCA purchase being performed, which is recorded once the router swap replies
+pub const PENDING_PURCHASE: Item<PendingPurchase> = Item::new("pending_purchase");
+/// The swaps of the DCA purchase being performed that is split across several routes, while the
+/// swaps reply
+pub const PENDING_SPLIT: Item<PendingSplit> = Item::new("pending_split");
+/// The ICS-20 transfer of a DCA purchase being sent, which is stored by its sequence once the
+/// transfer module replies
+pub const PENDING_IBC_TRANSFER: Item<IbcTransfer> = Item::new("pending_ibc_transfer");
+/// The ICS-20 transfers of DCA purchases waiting for their acknowledgement or timeout, keyed by
+/// the channel they were sent over and their sequence
+pub const IBC_TRANSFERS: Map<(&str, u64), IbcTransfer> = Map::new("ibc_transfers");
+/// The next purchase key, user address and id of the last DCA order read by the chain scheduler,
+/// which the next scheduled purchases start after
+pub const SCHEDULER_CURSOR: Item<(NextPurchaseKey, Addr, u64)> = Item::new("scheduler_cursor");
+/// The ASTRO being staked for a DCA purchase, which is sent to the recipient once the staking
+/// contract replies
+pub const PENDING_STAKE: Item<PendingStake> = Item::new("pending_stake");
+/// The token being transferred into the contract for a DCA purchase, whose amount received is
+/// measured once the transfer replies
+pub const PENDING_TRANSFER: Item<PendingTransfer> = Item::new("pending_transfer");
+/// The purchases performed for each DCA order, keyed by the user address, the id of the order and
+/// the sequence number of the purchase
+pub const PURCHASE_HISTORY: Map<(&Addr, u64, u64), PurchaseRecord> = Map::new("purchase_history");
+/// The `target_asset` purchased for each DCA order with escrowed delivery that is held by the
+/// contract until it is claimed, keyed by the user address and the id of the order
+pub const PURCHASED_BALANCE: Map<(&Addr, u64), Asset> = Map::new("purchased_balance");
+/// The shares of each pooled DCA order, keyed by the user address and the id of the order
+pub const POOLS: Map<(&Addr, u64), Pool> = Map::new("pools");
+/// The shares of the pooled DCA orders held by each depositor, keyed by the user address and the
+/// id of the order, and the address of the depositor
+pub const POOL_SHARES: Map<(&Addr, u64, &Addr), PoolShare> = Map::new("pool_shares");
+/// The sensitive configuration changes waiting for their timelock to pass, in the order they were
+/// scheduled
+pub const PENDING_CONFIG: Item<Vec<PendingConfigChange>> = Item::new("pending_config");
+/// The operators allowed to manage the DCA orders of a user, keyed by the user address and the
+/// operator address
+pub const OPERATORS: Map<(&Addr, &Addr), bool> = Map::new("operators");
+/// The cw4 group whose members can claim, pause and resume the DCA orders of a user, keyed by the
+/// user address
+pub const MEMBER_GROUPS: Map<&Addr, Addr> = Map::new("member_groups");
+/// The contract notified of the lifecycle events of the DCA orders of a user, keyed by the user
+/// address
+pub const NOTIFIERS: Map<&Addr, Addr> = Map::new("notifiers");
+/// The controller on a remote chain that a user declared it is the interchain account of, keyed by
+/// the user address
+pub const INTERCHAIN_CONTROLLERS: Map<&Addr, InterchainController> =
+    Map::new("interchain_controllers");
+/// The interchain accounts that declared they are owned by a controller, keyed by the connection
+/// of the controller, the address of the controller and the address of the account
+pub const CONTROLLED_ACCOUNTS: Map<(&str, &str, &Addr), bool> = Map::new("controlled_accounts");
+/// The token id of the NFT minted for each DCA order, keyed by the user address and the id of the
+/// order
+pub const ORDER_NFTS: Map<(&Addr, u64), String> = Map::new("order_nfts");
+/// The oracle contract that the DCA purchases of an asset pair are checked against, keyed by the
+/// initial asset and the target asset of the orders
+pub const ORACLES: Map<(String, String), Addr> = Map::new("oracles");
+/// The tasks contract of the CronCat automation protocol that keeper jobs of DCA orders are
+/// registered on
+pub const KEEPER: Item<Addr> = Item::new("keeper");
+/// The Astroport pairs resolved from the factory, including their address and pool type, keyed by
+/// the sorted asset infos of each pair
+pub const PAIRS: Map<(String, String), PairInfo> = Map::new("pairs");
+/// The pending proposal of a new owner of the contract
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+/// The cumulative protocol fees collected, keyed by the asset they were collected in
+pub const PROTOCOL_FEES: Map<String, Asset> = Map::new("protocol_fees");
+/// The running counters of the global statistics of the contract
+pub const GLOBAL_STATS: Item<GlobalStats> = Item::new("global_stats");
+/// The total tips paid to bots, keyed by the asset they were paid in
+pub const TIPS_PAID: Map<String, Asset> = Map::new("tips_paid");
+/// The volume of the DCA purchases of each asset pair, keyed by the initial asset and the target
+/// asset of the orders
+pub const PAIR_VOLUMES: Map<(String, String), PairVolume> = Map::new("pair_volumes");
+/// The protocol fees accrued to referrers, keyed by the referrer and the asset they accrued in
+pub const REFERRAL_REWARDS: Map<(&Addr, String), Asset> = Map::new("referral_rewards");
+/// The performance of the DCA purchases of each registered bot, keyed by the bot address
+pub const BOTS: Map<&Addr, BotStats> = Map::new("bots");
+/// Stores the decimals of the cw20 tokens queried once for the minimums of new DCA orders
+pub const TOKEN_DECIMALS: Map<&Addr, u8> = Map::new("token_decimals");
+/// Stores the decimals of the native denoms with bank module metadata, such as tokenfactory
+/// denoms, queried once for the minimums of new DCA orders
+pub const DENOM_DECIMALS: Map<&str, u8> = Map::new("denom_decimals");
+
+/// The unit of the orders with an interval in blocks in the `next_purchase` index of [`user_dca`]
+pub const HEIGHT_UNIT: u8 = 0;
+/// The unit of the orders with an interval in seconds in the `next_purchase` index of
+/// [`user_dca`]
+pub const TIME_UNIT: u8 = 1;
+/// The unit of the orders that no purchase can currently be performed for in the `next_purchase`
+/// index of [`user_dca`], which are indexed after the orders of every other unit
+pub const INACTIVE_UNIT: u8 = 2;
+
+/// The unit of the interval of a DCA order along with the block height or time in seconds that
+/// its next purchase can be performed at, as indexed by the `next_purchase` index of [`user_dca`]
+pub type NextPurchaseKey = (u8, u64);
+
+/// Returns the [`NextPurchaseKey`] of a purchase at `next_purchase`, keeping the purchases in
+/// blocks apart from the purchases in seconds so that each is compared in its own unit
+pub fn next_purchase_key(next_purchase: &Duration) -> NextPurchaseKey {
+    match next_purchase {
+        Duration::Height(height) => (HEIGHT_UNIT, *height),
+        Duration::Time(seconds) => (TIME_UNIT, *seconds),
+    }
+}
+
+/// Returns the [`NextPurchaseKey`] that `order` is indexed by in the `next_purchase` index of
+/// [`user_dca`], which is the key of its next purchase, unless the order is paused or expires
+/// before its next purchase. Those orders are indexed under the [`INACTIVE_UNIT`] instead, so that
+/// they are not iterated over with the due orders until they are resumed or modified.
+pub fn order_purchase_key(order: &DcaInfo) -> NextPurchaseKey {
+    let next_purchase = order.next_purchase_at();
+    let expires_first = match (&order.expires_at, &next_purchase) {
+        (Some(Expiration::AtHeight(expiry)), Duration::Height(height)) => height >= expiry,
+        (Some(Expiration::AtTime(expiry)), Duration::Time(seconds)) => *seconds >= expiry.seconds(),
+        _ => false,
+    };
+
+    match order.paused || expires_first {
+        true => (INACTIVE_UNIT, 0),
+        false => next_purchase_key(&next_purchase),
+    }
+}
+
+/// The raw entries of the `next_purchase` index of [`user_dca`], through which the entries stored
+/// under a previous key format are cleared when migrating
+pub const NEXT_PURCHASE_INDEX: Map<Vec<u8>, u32> = Map::new("user_dca_orders__next_purchase");
+
+/// The raw entries of the `initial_asset` index that [`user_dca`] no longer keeps, which are
+/// cleared when migrating
+pub const INITIAL_ASSET_INDEX: Map<Vec<u8>, u32> = Map::new("user_dca_orders__initial_asset");
+
+/// The indexes available for the DCA orders stored in [`user_dca`]
+pub struct DcaIndexes<'a> {
+    /// Indexes the DCA orders by the unit of their interval and the block height or time in
+    /// seconds that their next purchase can be performed at, as returned by
+    /// [`order_purchase_key`]
+    pub next_purchase: MultiIndex<'a, NextPurchaseKey, DcaInfo, (&'a Addr, u64)>,
+    /// Indexes the DCA orders by the `initial_asset` they are spending and the `target_asset` they
+    /// are purchasing
+    pub asset_pair: MultiIndex<'a, (String, String), DcaInfo, (&'a Addr, u64)>,
+}
+
+impl<'a> IndexList<DcaInfo> for DcaIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DcaInfo>> + '_> {
+        let v: Vec<&dyn Index<DcaInfo>> = vec![&self.next_purchase, &self.asset_pair];
+        Box::new(v.into_iter())
+    }
+}
+
+/// The DCA orders for a user, keyed by the user address and the id of the order
+pub fn user_dca<'a>() -> IndexedMap<'a, (&'a Addr, u64), DcaInfo, DcaIndexes<'a>> {
+    let indexes = DcaIndexes {
+        next_purchase: MultiIndex::new(
+            |_pk, order: &DcaInfo| order_purchase_key(order),
+            "user_dca_orders",
+            "user_dca_orders__next_purchase",
+        ),
+        asset_pair: MultiIndex::new(
+            |_pk, order: &DcaInfo| {
+                (
+                    order.initial_asset.info.to_string(),
+                    order.target_asset.to_string(),
+                )
+            },
+            "user_dca_orders",
+            "user_dca_orders__asset_pair",
+        ),
+    };
+
+    IndexedMap::new("user_dca_orders", indexes)
+}
+
+/// Returns all the DCA orders of `user`, ordered by their id
+pub fn get_user_orders(storage: &dyn Storage, user: &Addr) -> StdResult<Vec<DcaInfo>> {
+    user_dca()
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, order)| order))
+        .collect()
+}
+
+/// Returns the amount of the cw20 token `contract_addr` that is reserved by the allowance based
+/// DCA orders of `user`, excluding any orders where the tokens are held by the contract or pulled
+/// at each purchase
+pub fn get_reserved_allowance(
+    storage: &dyn Storage,
+    user: &Addr,
+    contract_addr: &Addr,
+) -> StdResult<Uint128> {
+    Ok(get_user_orders(storage, user)?
+        .iter()
+        .map(|o| match &o.initial_asset.info {
+            AssetInfo::Token {
+                contract_addr: o_contract_addr,
+            } if contract_addr == o_contract_addr && !o.escrowed && !o.pull => {
+                o.initial_asset.amount
+            }
+            _ => Uint128::zero(),
+        })
+        .sum())
+}
+
+/// Returns the amount of purchases that have been performed for the DCA order `id` of `user`,
+/// which is the sequence number of the last recorded purchase
+pub fn get_purchase_count(storage: &dyn Storage, user: &Addr, id: u64) -> StdResult<u64> {
+    Ok(PURCHASE_HISTORY
+        .prefix((user, id))
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default())
+}
@@ -0,0 +1,89 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport_dca::dca::PairVolume;
+use cosmwasm_std::{StdResult, Storage, Uint128};
+
+use crate::state::{GlobalStats, GLOBAL_STATS, PAIR_VOLUMES, TIPS_PAID};
+
+/// The length of an epoch of the global statistics in seconds
+pub const EPOCH_LENGTH: u64 = 86_400;
+
+/// ## Description
+/// Returns `stats` with the epoch rolled over to the epoch containing `now`, where the purchases
+/// of the last epoch are zero if no purchases were performed in it.
+pub fn current_epoch(mut stats: GlobalStats, now: u64) -> GlobalStats {
+    if now < stats.epoch_start + EPOCH_LENGTH {
+        return stats;
+    }
+
+    stats.last_epoch_purchases = match now < stats.epoch_start + 2 * EPOCH_LENGTH {
+        true => stats.epoch_purchases,
+        false => 0,
+    };
+    stats.epoch_start = now - (now - stats.epoch_start) % EPOCH_LENGTH;
+    stats.epoch_purchases = 0;
+    stats
+}
+
+/// ## Description
+/// Counts a DCA order being stored in the active orders of the global statistics.
+pub fn record_order_created(storage: &mut dyn Storage) -> StdResult<()> {
+    let mut stats = GLOBAL_STATS.may_load(storage)?.unwrap_or_default();
+    stats.active_orders += 1;
+    GLOBAL_STATS.save(storage, &stats)
+}
+
+/// ## Description
+/// Counts a DCA order being removed from the active orders of the global statistics, once it has
+/// been completed, cancelled, stopped or swept.
+pub fn record_order_removed(storage: &mut dyn Storage) -> StdResult<()> {
+    let mut stats = GLOBAL_STATS.may_load(storage)?.unwrap_or_default();
+    stats.active_orders = stats.active_orders.saturating_sub(1);
+    GLOBAL_STATS.save(storage, &stats)
+}
+
+/// ## Description
+/// Counts a DCA purchase at `now` spending `amount_in` of `initial_asset` for `amount_out` of
+/// `target_asset` in the global statistics, along with the `tip` paid to the bot.
+pub fn record_purchase_stats(
+    storage: &mut dyn Storage,
+    now: u64,
+    (initial_asset, amount_in): (&AssetInfo, Uint128),
+    (target_asset, amount_out): (&AssetInfo, Uint128),
+    tip: &[Asset],
+) -> StdResult<()> {
+    let mut stats = current_epoch(GLOBAL_STATS.may_load(storage)?.unwrap_or_default(), now);
+    stats.total_purchases += 1;
+    stats.epoch_purchases += 1;
+    GLOBAL_STATS.save(storage, &stats)?;
+
+    PAIR_VOLUMES.update(
+        storage,
+        (initial_asset.to_string(), target_asset.to_string()),
+        |volume| -> StdResult<_> {
+            let mut volume = volume.unwrap_or(PairVolume {
+                initial_asset: initial_asset.clone(),
+                target_asset: target_asset.clone(),
+                purchases: 0,
+                amount_in: Uint128::zero(),
+                amount_out: Uint128::zero(),
+            });
+            volume.purchases += 1;
+            volume.amount_in = volume.amount_in.checked_add(amount_in)?;
+            volume.amount_out = volume.amount_out.checked_add(amount_out)?;
+            Ok(volume)
+        },
+    )?;
+
+    for asset in tip.iter().filter(|asset| !asset.amount.is_zero()) {
+        TIPS_PAID.update(storage, asset.info.to_string(), |paid| -> StdResult<_> {
+            let mut paid = paid.unwrap_or(Asset {
+                info: asset.info.clone(),
+                amount: Uint128::zero(),
+            });
+            paid.amount = paid.amount.checked_add(asset.amount)?;
+            Ok(paid)
+        })?;
+    }
+
+    Ok(())
+}
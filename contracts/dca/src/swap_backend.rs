@@ -0,0 +1,167 @@
+use astroport::{
+    asset::{Asset, AssetInfo},
+    pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg},
+    router::{ExecuteMsg as RouterExecuteMsg, SwapOperation},
+};
+use astroport_dca::dca::SwapBackendType;
+use cosmwasm_std::{
+    to_binary, Addr, Coin, CosmosMsg, Decimal, Env, QuerierWrapper, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::{error::ContractError, query_pair::query_pair, state::Config};
+
+/// Describes the swap of a DCA purchase that a [`SwapBackend`] constructs the message of
+pub struct Swap {
+    /// The asset offered in the swap, which is already held by the deposit address of the
+    /// backend if it is a cw20 token
+    pub offer: Asset,
+    /// The hop route of the swap
+    pub hops: Vec<SwapOperation>,
+    /// The maximum spread of the swap
+    pub max_spread: Decimal,
+    /// The minimum amount to receive from the swap
+    pub minimum_receive: Option<Uint128>,
+    /// The address receiving the purchased asset, or the contract if not specified
+    pub to: Option<String>,
+}
+
+/// Describes how the swaps of DCA purchases are constructed, so that the same DCA orders can be
+/// purchased from wherever the liquidity of a deployment lives
+pub trait SwapBackend {
+    /// ## Description
+    /// Returns the address that the cw20 tokens offered in a swap are moved to before the swap
+    /// message is executed.
+    fn deposit_addr(&self, env: &Env, config: &Config) -> Addr;
+
+    /// ## Description
+    /// Returns the message performing `swap`.
+    ///
+    /// Returns a [`ContractError`] if the backend cannot perform the swap.
+    fn swap_msg(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        config: &Config,
+        swap: Swap,
+    ) -> Result<CosmosMsg, ContractError>;
+}
+
+/// Swaps the hop route of each purchase through the Astroport router, which swaps whatever amount
+/// of the offered cw20 token it holds
+pub struct RouterBackend;
+
+impl SwapBackend for RouterBackend {
+    fn deposit_addr(&self, _env: &Env, config: &Config) -> Addr {
+        config.router_addr.clone()
+    }
+
+    fn swap_msg(
+        &self,
+        _storage: &mut dyn Storage,
+        _querier: &QuerierWrapper,
+        config: &Config,
+        swap: Swap,
+    ) -> Result<CosmosMsg, ContractError> {
+        Ok(WasmMsg::Execute {
+            contract_addr: config.router_addr.to_string(),
+            funds: native_funds(&swap.offer),
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: swap.hops,
+                minimum_receive: swap.minimum_receive,
+                to: swap.to,
+                max_spread: Some(swap.max_spread),
+            })?,
+        }
+        .into())
+    }
+}
+
+/// Swaps directly with the Astroport pair of the single hop of each purchase, sending the offered
+/// cw20 token from the contract along with the swap
+pub struct PairBackend;
+
+impl SwapBackend for PairBackend {
+    fn deposit_addr(&self, env: &Env, _config: &Config) -> Addr {
+        env.contract.address.clone()
+    }
+
+    fn swap_msg(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        config: &Config,
+        swap: Swap,
+    ) -> Result<CosmosMsg, ContractError> {
+        let ask_asset_info = match swap.hops.as_slice() {
+            [SwapOperation::AstroSwap { ask_asset_info, .. }] => ask_asset_info.clone(),
+            _ => return Err(ContractError::InvalidSwapBackendRoute {}),
+        };
+
+        let pair = query_pair(
+            storage,
+            querier,
+            &config.factory_addr,
+            &[swap.offer.info.clone(), ask_asset_info],
+        )?;
+
+        // a pair swap has no minimum receive, which is enforced as the belief price of the swap
+        // without any spread instead
+        let (belief_price, max_spread) = match swap.minimum_receive {
+            Some(minimum_receive) if !minimum_receive.is_zero() => (
+                Some(Decimal::from_ratio(swap.offer.amount, minimum_receive)),
+                Decimal::zero(),
+            ),
+            _ => (None, swap.max_spread),
+        };
+
+        let msg = match &swap.offer.info {
+            AssetInfo::NativeToken { .. } => WasmMsg::Execute {
+                contract_addr: pair.contract_addr.to_string(),
+                funds: native_funds(&swap.offer),
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: swap.offer,
+                    belief_price,
+                    max_spread: Some(max_spread),
+                    to: swap.to,
+                })?,
+            },
+            AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: pair.contract_addr.to_string(),
+                    amount: swap.offer.amount,
+                    msg: to_binary(&PairCw20HookMsg::Swap {
+                        belief_price,
+                        max_spread: Some(max_spread),
+                        to: swap.to,
+                    })?,
+                })?,
+            },
+        };
+
+        Ok(msg.into())
+    }
+}
+
+/// ## Description
+/// Returns the [`SwapBackend`] of `backend_type`.
+pub fn swap_backend(backend_type: &SwapBackendType) -> &'static dyn SwapBackend {
+    match backend_type {
+        SwapBackendType::Router => &RouterBackend,
+        SwapBackendType::Pair => &PairBackend,
+    }
+}
+
+/// ## Description
+/// Returns the funds sent along with the swap of `offer`, which are only sent for a native token.
+fn native_funds(offer: &Asset) -> Vec<Coin> {
+    match &offer.info {
+        AssetInfo::NativeToken { denom } => vec![Coin {
+            amount: offer.amount,
+            denom: denom.clone(),
+        }],
+        AssetInfo::Token { .. } => vec![],
+    }
+}
@@ -0,0 +1,263 @@
+use astroport::{
+    asset::{Asset, AssetInfo},
+    factory::{PairConfig, PairType},
+};
+use astroport_dca::dca::{InstantiateMsg, SwapBackendType};
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use cw20::Cw20Coin;
+use cw_multi_test::{App, Executor};
+
+use super::{
+    mock_app_with_balance, mock_creator, store_astroport_pair_code, store_cw20_token_code,
+    store_dca_module_code, store_factory_code, store_router_code,
+};
+
+/// Instantiates the dca module along with an Astroport factory and router, with pools for
+/// uluna-ujpy, ujpy-ukrw and cw20-ujpy. Returns the app, the dca module address and the cw20 token
+/// address.
+pub fn mock_astroport_instantiate(max_hops: Option<u32>) -> (App, Addr, Addr) {
+    let admin = Addr::unchecked("admin");
+
+    let mut app = mock_app_with_balance(vec![
+        (mock_creator().sender, coins(500_000, "uluna")),
+        (
+            admin.clone(),
+            vec![
+                coin(1_000_000, "uluna"),
+                coin(1_500_000, "ujpy"),
+                coin(1_000_000, "ukrw"),
+            ],
+        ),
+    ]);
+
+    let cw20_token_id = store_cw20_token_code(&mut app);
+    let dca_module_id = store_dca_module_code(&mut app);
+    let astroport_pair_id = store_astroport_pair_code(&mut app);
+    let factory_id = store_factory_code(&mut app);
+    let router_id = store_router_code(&mut app);
+
+    // instantiate cw20 token
+    let cw20_addr = app
+        .instantiate_contract(
+            cw20_token_id,
+            mock_creator().sender,
+            &cw20_base::msg::InstantiateMsg {
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: admin.clone().into_string(),
+                        amount: Uint128::new(1_000_000),
+                    },
+                    Cw20Coin {
+                        address: mock_creator().sender.into_string(),
+                        amount: Uint128::new(500_000),
+                    },
+                ],
+                marketing: None,
+                mint: None,
+                name: "cw20 token".to_string(),
+                symbol: "cwT".to_string(),
+            },
+            &[],
+            "cw20 mock contract",
+            None,
+        )
+        .unwrap();
+
+    // instantiate random whitelisted contract
+    let cw20_whitelist_addr = app
+        .instantiate_contract(
+            cw20_token_id,
+            mock_creator().sender,
+            &cw20_base::msg::InstantiateMsg {
+                decimals: 6,
+                initial_balances: vec![],
+                marketing: None,
+                mint: None,
+                name: "cw20 whitelisted token".to_string(),
+                symbol: "cwWT".to_string(),
+            },
+            &[],
+            "cw20 mock whitelisted contract",
+            None,
+        )
+        .unwrap();
+
+    // instantiate factory
+    let factory_addr = app
+        .instantiate_contract(
+            factory_id,
+            mock_creator().sender,
+            &astroport::factory::InstantiateMsg {
+                fee_address: None,
+                generator_address: None,
+                owner: mock_creator().sender.into_string(),
+                pair_configs: vec![PairConfig {
+                    pair_type: PairType::Xyk {},
+                    is_disabled: false,
+                    is_generator_disabled: true,
+                    maker_fee_bps: 30,
+                    total_fee_bps: 30,
+                    code_id: astroport_pair_id,
+                }],
+                token_code_id: cw20_token_id,
+                whitelist_code_id: 100,
+            },
+            &[],
+            "astroport factory",
+            None,
+        )
+        .unwrap();
+
+    // instantiate router
+    let router_addr = app
+        .instantiate_contract(
+            router_id,
+            mock_creator().sender,
+            &astroport::router::InstantiateMsg {
+                astroport_factory: factory_addr.clone().into_string(),
+            },
+            &[],
+            "astroport router",
+            None,
+        )
+        .unwrap();
+
+    // instantiate dca module
+    let dca_addr = app
+        .instantiate_contract(
+            dca_module_id,
+            mock_creator().sender,
+            &InstantiateMsg {
+                factory_addr: factory_addr.clone().into_string(),
+                max_hops: max_hops.unwrap_or(4),
+                max_spread: "0.05".to_string(),
+                router_addr: router_addr.into_string(),
+                whitelisted_fee_assets: vec![
+                    Asset {
+                        amount: Uint128::new(15_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    },
+                    Asset {
+                        amount: Uint128::new(15_000),
+                        info: AssetInfo::Token {
+                            contract_addr: cw20_addr.clone(),
+                        },
+                    },
+                ],
+                whitelisted_tokens: vec![
+                    AssetInfo::Token {
+                        contract_addr: cw20_whitelist_addr,
+                    },
+                    AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    AssetInfo::NativeToken {
+                        denom: "ujpy".to_string(),
+                    },
+                ],
+                staking_addr: None,
+                protocol_fee_bps: 0,
+                fee_collector: None,
+                fee_tiers: vec![],
+                referral_share: Decimal::zero(),
+                owner: None,
+                config_timelock: 0,
+                swap_backend: SwapBackendType::Router,
+            },
+            &[],
+            "dca module",
+            None,
+        )
+        .unwrap();
+
+    // create some pools
+    let mut create_pool = |assets: [AssetInfo; 2]| {
+        let pair_res = app
+            .execute_contract(
+                mock_creator().sender,
+                factory_addr.clone(),
+                &astroport::factory::ExecuteMsg::CreatePair {
+                    pair_type: PairType::Xyk {},
+                    asset_infos: [assets[0].clone(), assets[1].clone()],
+                    init_params: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        let pair_addr = Addr::unchecked(pair_res.events[2].attributes[0].value.clone());
+
+        // add some liquidity to the pools
+        let mut funds = vec![];
+        for asset in &assets {
+            match asset {
+                AssetInfo::NativeToken { denom } => funds.push(coin(500_000, denom)),
+                AssetInfo::Token { contract_addr } => {
+                    app.execute_contract(
+                        admin.clone(),
+                        contract_addr.clone(),
+                        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                            spender: pair_addr.clone().into_string(),
+                            amount: Uint128::new(500_000),
+                            expires: None,
+                        },
+                        &[],
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        app.execute_contract(
+            admin.clone(),
+            pair_addr,
+            &astroport::pair::ExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        amount: Uint128::new(500_000),
+                        info: assets[0].clone(),
+                    },
+                    Asset {
+                        amount: Uint128::new(500_000),
+                        info: assets[1].clone(),
+                    },
+                ],
+                slippage_tolerance: None,
+                auto_stake: None,
+                receiver: None,
+            },
+            &funds,
+        )
+        .unwrap();
+    };
+
+    create_pool([
+        AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        },
+    ]);
+    create_pool([
+        AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        },
+    ]);
+    create_pool([
+        AssetInfo::Token {
+            contract_addr: cw20_addr.clone(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ujpy".to_string(),
+        },
+    ]);
+
+    (app, dca_addr, cw20_addr)
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError, StdResult,
+};
+use cw_storage_plus::Map;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::cw4::{Cw4QueryMsg, MemberResponse, TotalWeightResponse};
+
+const MEMBERS: Map<&str, u64> = Map::new("members");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub members: Vec<String>,
+}
+
+/// Instantiates the mock cw4 group, where each member has a weight of one
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    for member in msg.members {
+        MEMBERS.save(deps.storage, &member, &1)?;
+    }
+
+    Ok(Response::new())
+}
+
+/// The members of the mock cw4 group cannot be changed
+pub fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Err(StdError::generic_err("unsupported"))
+}
+
+/// Returns the weight of a member or the total weight of the mock cw4 group
+pub fn query(deps: Deps, _env: Env, msg: Cw4QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw4QueryMsg::Member { addr, .. } => to_binary(&MemberResponse {
+            weight: MEMBERS.may_load(deps.storage, &addr)?,
+        }),
+        Cw4QueryMsg::TotalWeight { .. } => to_binary(&TotalWeightResponse {
+            weight: MEMBERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|member| member.map(|(_, weight)| weight))
+                .sum::<StdResult<u64>>()?,
+        }),
+    }
+}
@@ -0,0 +1,72 @@
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::cw721::{Cw721InstantiateMsg, Cw721QueryMsg, OwnerOfResponse};
+
+const MINTER: Item<String> = Item::new("minter");
+const OWNERS: Map<&str, String> = Map::new("owners");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Option<Empty>,
+    },
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+}
+
+/// Instantiates the mock cw721 contract, which only allows the minter to mint tokens
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Cw721InstantiateMsg,
+) -> StdResult<Response> {
+    MINTER.save(deps.storage, &msg.minter)?;
+
+    Ok(Response::new())
+}
+
+/// Mints tokens from the minter, or transfers tokens from their owner
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Mint {
+            token_id, owner, ..
+        } if info.sender == MINTER.load(deps.storage)? => {
+            OWNERS.save(deps.storage, &token_id, &owner)?;
+        }
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } if info.sender == OWNERS.load(deps.storage, &token_id)? => {
+            OWNERS.save(deps.storage, &token_id, &recipient)?;
+        }
+        _ => return Err(StdError::generic_err("unauthorized")),
+    }
+
+    Ok(Response::new())
+}
+
+/// Returns the owner of a token of the mock cw721 contract
+pub fn query(deps: Deps, _env: Env, msg: Cw721QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw721QueryMsg::OwnerOf { token_id, .. } => to_binary(&OwnerOfResponse {
+            owner: OWNERS.load(deps.storage, &token_id)?,
+        }),
+    }
+}
@@ -1,8 +1,8 @@
 use astroport::asset::{Asset, AssetInfo};
-use astroport_dca::dca::InstantiateMsg;
+use astroport_dca::dca::{InstantiateMsg, SwapBackendType};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage},
-    Addr, Env, OwnedDeps,
+    Addr, Decimal, Env, OwnedDeps,
 };
 use cw_multi_test::{App, Executor};
 
@@ -33,6 +33,14 @@ pub fn mock_instantiate(
             max_spread: "0.05".to_string(),
             whitelisted_fee_assets,
             whitelisted_tokens,
+            staking_addr: None,
+            protocol_fee_bps: 0,
+            fee_collector: None,
+            fee_tiers: vec![],
+            referral_share: Decimal::zero(),
+            owner: None,
+            config_timelock: 0,
+            swap_backend: SwapBackendType::Router,
         },
     )
     .unwrap();
@@ -59,6 +67,14 @@ pub fn app_mock_instantiate(
             max_spread: "0.05".to_string(),
             whitelisted_fee_assets,
             whitelisted_tokens: vec![],
+            staking_addr: None,
+            protocol_fee_bps: 0,
+            fee_collector: None,
+            fee_tiers: vec![],
+            referral_share: Decimal::zero(),
+            owner: None,
+            config_timelock: 0,
+            swap_backend: SwapBackendType::Router,
         },
         &[],
         "dca_module",
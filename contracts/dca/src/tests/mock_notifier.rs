@@ -0,0 +1,44 @@
+use astroport_dca::dca::{NotifierMsg, OrderEvent};
+use cosmwasm_std::{
+    attr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+/// Instantiates the mock notifier contract
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+/// Accepts a notification of a DCA order event, recording it in the attributes of the response
+pub fn execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: NotifierMsg,
+) -> StdResult<Response> {
+    match msg {
+        NotifierMsg::DcaOrderEvent { id, event, .. } => {
+            let event = match event {
+                OrderEvent::Executed { .. } => "executed",
+                OrderEvent::Failed { .. } => "failed",
+                OrderEvent::Completed {} => "completed",
+                OrderEvent::Cancelled {} => "cancelled",
+            };
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "dca_notification"),
+                attr("id", id.to_string()),
+                attr("event", event),
+            ]))
+        }
+    }
+}
+
+/// The mock notifier contract has no queries
+pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
@@ -0,0 +1,56 @@
+use cosmwasm_std::{
+    to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint256,
+};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::oracle::OracleQueryMsg;
+
+const PRICE: Item<Decimal> = Item::new("price");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    SetPrice { price: Decimal },
+}
+
+/// Instantiates the mock oracle, which prices every asset at the same fixed price
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    PRICE.save(deps.storage, &msg.price)?;
+
+    Ok(Response::new())
+}
+
+/// Sets the price returned by the mock oracle
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::SetPrice { price } => PRICE.save(deps.storage, &price)?,
+    }
+
+    Ok(Response::new())
+}
+
+/// Returns the amount consulted multiplied by the price of the mock oracle
+pub fn query(deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
+    match msg {
+        OracleQueryMsg::Consult { amount, .. } => {
+            to_binary(&Uint256::from(amount * PRICE.load(deps.storage)?))
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use astroport_dca::dca::DcaHookMsg;
+use cosmwasm_std::{
+    attr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+/// Instantiates the mock purchase hook contract
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+/// Accepts a DCA purchase, recording it in the attributes of the response
+pub fn execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: DcaHookMsg,
+) -> StdResult<Response> {
+    match msg {
+        DcaHookMsg::DcaPurchase {
+            recipient, asset, ..
+        } => Ok(Response::new().add_attributes(vec![
+            attr("action", "dca_purchase_hook"),
+            attr("recipient", recipient),
+            attr("asset", asset.to_string()),
+        ])),
+    }
+}
+
+/// The mock purchase hook contract has no queries
+pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
@@ -0,0 +1,64 @@
+use astroport::staking::{ConfigResponse, Cw20HookMsg, QueryMsg};
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const CONFIG: Item<ConfigResponse> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Instantiates the mock staking contract, which pays out the share tokens it holds
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ConfigResponse,
+) -> StdResult<Response> {
+    CONFIG.save(deps.storage, &msg)?;
+
+    Ok(Response::new())
+}
+
+/// Stakes the deposit tokens sent, sending the sender the same amount of share tokens
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match msg {
+        ExecuteMsg::Receive(cw20_msg) if info.sender == config.deposit_token_addr => {
+            match from_binary(&cw20_msg.msg)? {
+                Cw20HookMsg::Enter {} => Ok(Response::new().add_message(WasmMsg::Execute {
+                    contract_addr: config.share_token_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: cw20_msg.sender,
+                        amount: cw20_msg.amount,
+                    })?,
+                    funds: vec![],
+                })),
+                _ => Err(StdError::generic_err("unsupported hook")),
+            }
+        }
+        _ => Err(StdError::generic_err("unauthorized")),
+    }
+}
+
+/// Returns the configuration of the mock staking contract
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        _ => Err(StdError::generic_err("unsupported query")),
+    }
+}
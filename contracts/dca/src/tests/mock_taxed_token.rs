@@ -0,0 +1,41 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
+use cw20_base::{
+    contract::{execute as cw20_execute, execute_burn},
+    msg::ExecuteMsg,
+    ContractError,
+};
+
+/// The share of every transfer taken as a tax by the mock taxed token, in basis points
+const TAX_BPS: u128 = 1_000;
+
+/// Executes the messages of the base cw20 token, burning a tax from the recipient of each transfer
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let transfer = match &msg {
+        ExecuteMsg::Transfer { recipient, amount }
+        | ExecuteMsg::TransferFrom {
+            recipient, amount, ..
+        } => Some((Addr::unchecked(recipient), *amount)),
+        _ => None,
+    };
+
+    let response = cw20_execute(deps.branch(), env.clone(), info, msg)?;
+
+    if let Some((recipient, amount)) = transfer {
+        execute_burn(
+            deps,
+            env,
+            MessageInfo {
+                sender: recipient,
+                funds: vec![],
+            },
+            amount.multiply_ratio(TAX_BPS, 10_000u128),
+        )?;
+    }
+
+    Ok(response)
+}
@@ -1,16 +1,33 @@
 mod mock_app;
+mod mock_astroport_instantiate;
 mod mock_creator;
+mod mock_cw4_group;
+mod mock_cw721;
 mod mock_instantiate;
+mod mock_notifier;
+mod mock_oracle;
+mod mock_purchase_hook;
+mod mock_staking;
+mod mock_taxed_token;
 mod read_item;
 mod read_map;
+mod read_user_orders;
 mod store_code;
 
 pub use mock_app::{mock_app, mock_app_with_balance};
+pub use mock_astroport_instantiate::mock_astroport_instantiate;
 pub use mock_creator::mock_creator;
+pub use mock_cw4_group::InstantiateMsg as MockCw4GroupInstantiateMsg;
+pub use mock_cw721::ExecuteMsg as MockCw721ExecuteMsg;
 pub use mock_instantiate::{app_mock_instantiate, mock_instantiate};
+pub use mock_oracle::{
+    ExecuteMsg as MockOracleExecuteMsg, InstantiateMsg as MockOracleInstantiateMsg,
+};
 pub use read_item::read_item;
 pub use read_map::read_map;
+pub use read_user_orders::read_user_orders;
 pub use store_code::{
-    store_astroport_pair_code, store_cw20_token_code, store_dca_module_code, store_factory_code,
-    store_router_code,
+    store_astroport_pair_code, store_cw20_token_code, store_cw4_group_code, store_cw721_code,
+    store_dca_module_code, store_factory_code, store_notifier_code, store_oracle_code,
+    store_purchase_hook_code, store_router_code, store_staking_code, store_taxed_token_code,
 };
@@ -0,0 +1,18 @@
+use astroport_dca::dca::DcaInfo;
+use cosmwasm_std::Addr;
+use cosmwasm_storage::ReadonlyPrefixedStorage;
+use cw_multi_test::App;
+
+use crate::state::get_user_orders;
+
+const NAMESPACE_WASM: &[u8] = b"wasm";
+
+pub fn read_user_orders(app: &App, contract_addr: Addr, user: &Addr) -> Vec<DcaInfo> {
+    app.read_module(|_, _, storage| {
+        let mut name = b"contract_data/".to_vec();
+        name.extend_from_slice(contract_addr.as_bytes());
+        let storage = ReadonlyPrefixedStorage::multilevel(storage, &[NAMESPACE_WASM, &name]);
+
+        get_user_orders(&storage, user).unwrap()
+    })
+}
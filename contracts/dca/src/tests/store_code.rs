@@ -1,11 +1,20 @@
 use cw_multi_test::{App, ContractWrapper};
 
-use crate::contract::{execute, instantiate, migrate, query};
+use crate::contract::{execute, instantiate, migrate, query, reply, sudo};
+
+use super::{
+    mock_cw4_group, mock_cw721, mock_notifier, mock_oracle, mock_purchase_hook, mock_staking,
+    mock_taxed_token,
+};
 
 /// Stores the DCA module contract to the app
 pub fn store_dca_module_code(app: &mut App) -> u64 {
-    let contract =
-        Box::new(ContractWrapper::new(execute, instantiate, query).with_migrate(migrate));
+    let contract = Box::new(
+        ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply)
+            .with_sudo(sudo),
+    );
 
     app.store_code(contract)
 }
@@ -21,6 +30,17 @@ pub fn store_cw20_token_code(app: &mut App) -> u64 {
     app.store_code(contract)
 }
 
+/// Stores the mock cw20 token taking a tax on transfers to the app
+pub fn store_taxed_token_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_taxed_token::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
+
+    app.store_code(contract)
+}
+
 /// Stores the Astroport factory to the app
 pub fn store_factory_code(app: &mut App) -> u64 {
     let contract = Box::new(
@@ -59,3 +79,69 @@ pub fn store_astroport_pair_code(app: &mut App) -> u64 {
 
     app.store_code(contract)
 }
+
+/// Stores the mock purchase hook to the app
+pub fn store_purchase_hook_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_purchase_hook::execute,
+        mock_purchase_hook::instantiate,
+        mock_purchase_hook::query,
+    ));
+
+    app.store_code(contract)
+}
+
+/// Stores the mock notifier contract to the app
+pub fn store_notifier_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_notifier::execute,
+        mock_notifier::instantiate,
+        mock_notifier::query,
+    ));
+
+    app.store_code(contract)
+}
+
+/// Stores the mock staking contract to the app
+pub fn store_staking_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_staking::execute,
+        mock_staking::instantiate,
+        mock_staking::query,
+    ));
+
+    app.store_code(contract)
+}
+
+/// Stores the mock cw4 group to the app
+pub fn store_cw4_group_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_cw4_group::execute,
+        mock_cw4_group::instantiate,
+        mock_cw4_group::query,
+    ));
+
+    app.store_code(contract)
+}
+
+/// Stores the mock cw721 contract to the app
+pub fn store_cw721_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_cw721::execute,
+        mock_cw721::instantiate,
+        mock_cw721::query,
+    ));
+
+    app.store_code(contract)
+}
+
+/// Stores the mock oracle to the app
+pub fn store_oracle_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_oracle::execute,
+        mock_oracle::instantiate,
+        mock_oracle::query,
+    ));
+
+    app.store_code(contract)
+}
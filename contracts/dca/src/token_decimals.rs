@@ -0,0 +1,216 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    from_slice, to_vec, ContractResult, Decimal, Empty, OverflowError, OverflowOperation,
+    QuerierWrapper, QueryRequest, StdResult, Storage, SystemResult, Uint128, Uint256,
+};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use serde::Deserialize;
+
+use crate::{
+    error::ContractError,
+    protobuf::{decode_field, decode_key, decode_varint, encode_string},
+    state::{DENOM_DECIMALS, TOKEN_DECIMALS},
+};
+
+/// The decimals of native tokens without denom metadata in the bank module
+const NATIVE_DECIMALS: u8 = 6;
+
+/// The path of the query of the bank module returning the metadata of a denom
+const DENOM_METADATA_PATH: &str = "/cosmos.bank.v1beta1.Query/DenomMetadata";
+
+/// Describes the response to the denom metadata query of the bank module, as returned by chains
+/// answering stargate queries in JSON
+#[derive(Deserialize)]
+struct DenomMetadataResponse {
+    metadata: DenomMetadata,
+}
+
+#[derive(Deserialize)]
+struct DenomMetadata {
+    denom_units: Vec<DenomUnit>,
+    display: String,
+}
+
+#[derive(Deserialize)]
+struct DenomUnit {
+    denom: String,
+    #[serde(default)]
+    exponent: u32,
+}
+
+impl DenomMetadata {
+    /// Decodes the metadata from the protobuf `QueryDenomMetadataResponse` returned by chains
+    /// answering stargate queries in protobuf
+    fn decode(mut data: &[u8]) -> Option<Self> {
+        let mut metadata = None;
+        while !data.is_empty() {
+            let (field, wire_type) = decode_key(&mut data)?;
+            let value = decode_field(&mut data, wire_type)?;
+            if field == 1 && wire_type == 2 {
+                metadata = Some(value);
+            }
+        }
+
+        let mut data = metadata?;
+        let mut denom_units = vec![];
+        let mut display = String::new();
+        while !data.is_empty() {
+            let (field, wire_type) = decode_key(&mut data)?;
+            let value = decode_field(&mut data, wire_type)?;
+            match (field, wire_type) {
+                (2, 2) => denom_units.push(DenomUnit::decode(value)?),
+                (4, 2) => display = String::from_utf8(value.to_vec()).ok()?,
+                _ => {}
+            }
+        }
+
+        Some(DenomMetadata {
+            denom_units,
+            display,
+        })
+    }
+
+    /// Returns the exponent of the display unit of the denom
+    fn decimals(&self) -> Option<u8> {
+        self.denom_units
+            .iter()
+            .find(|unit| unit.denom == self.display)
+            .and_then(|unit| u8::try_from(unit.exponent).ok())
+    }
+}
+
+impl DenomUnit {
+    fn decode(mut data: &[u8]) -> Option<Self> {
+        let mut denom = String::new();
+        let mut exponent = 0;
+        while !data.is_empty() {
+            let (field, wire_type) = decode_key(&mut data)?;
+            match (field, wire_type) {
+                (2, 0) => exponent = u32::try_from(decode_varint(&mut data)?).ok()?,
+                _ => {
+                    let value = decode_field(&mut data, wire_type)?;
+                    if field == 1 && wire_type == 2 {
+                        denom = String::from_utf8(value.to_vec()).ok()?;
+                    }
+                }
+            }
+        }
+
+        Some(DenomUnit { denom, exponent })
+    }
+}
+
+/// ## Description
+/// Returns the decimals of the display unit in the bank module metadata of `denom`, such as the
+/// metadata set by the creator of a tokenfactory denom.
+///
+/// Returns [`None`] if the denom has no metadata, or if the chain does not answer the query.
+fn query_denom_decimals(querier: &QuerierWrapper, denom: &str) -> Option<u8> {
+    let mut data = vec![];
+    encode_string(&mut data, 1, denom);
+
+    let request = to_vec(&QueryRequest::<Empty>::Stargate {
+        path: DENOM_METADATA_PATH.to_string(),
+        data: data.into(),
+    })
+    .ok()?;
+    let response = match querier.raw_query(&request) {
+        SystemResult::Ok(ContractResult::Ok(response)) => response,
+        _ => return None,
+    };
+
+    from_slice::<DenomMetadataResponse>(&response)
+        .ok()
+        .map(|response| response.metadata)
+        .or_else(|| DenomMetadata::decode(&response))?
+        .decimals()
+}
+
+/// ## Description
+/// Returns the decimals of `asset` cached in [`TOKEN_DECIMALS`] or [`DENOM_DECIMALS`].
+fn cached_decimals(storage: &dyn Storage, asset: &AssetInfo) -> StdResult<Option<u8>> {
+    match asset {
+        AssetInfo::NativeToken { denom } => DENOM_DECIMALS.may_load(storage, denom),
+        AssetInfo::Token { contract_addr } => TOKEN_DECIMALS.may_load(storage, contract_addr),
+    }
+}
+
+/// ## Description
+/// Queries the decimals of `asset` from the [`Cw20QueryMsg::TokenInfo`] of a Cw20 token, or from
+/// the denom metadata of a native token, which is [`None`] if the denom has no metadata.
+fn fetch_decimals(querier: &QuerierWrapper, asset: &AssetInfo) -> StdResult<Option<u8>> {
+    match asset {
+        AssetInfo::NativeToken { denom } => Ok(query_denom_decimals(querier, denom)),
+        AssetInfo::Token { contract_addr } => Ok(Some(
+            querier
+                .query_wasm_smart::<TokenInfoResponse>(contract_addr, &Cw20QueryMsg::TokenInfo {})?
+                .decimals,
+        )),
+    }
+}
+
+/// ## Description
+/// Returns the decimals of `asset`, querying the [`Cw20QueryMsg::TokenInfo`] of a Cw20 token or
+/// the denom metadata of a native token only the first time and caching its decimals in
+/// [`TOKEN_DECIMALS`] or [`DENOM_DECIMALS`] afterwards.
+///
+/// Native tokens without denom metadata are assumed to have 6 decimals, which is not cached so
+/// that metadata set later is used.
+/// ## Arguments
+/// * `storage` - The [`Storage`] of the contract, caching the decimals of the asset.
+///
+/// * `querier` - The [`QuerierWrapper`] to query the token with.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to return the decimals of.
+pub fn query_decimals(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    asset: &AssetInfo,
+) -> StdResult<u8> {
+    if let Some(decimals) = cached_decimals(storage, asset)? {
+        return Ok(decimals);
+    }
+
+    let decimals = fetch_decimals(querier, asset)?;
+    if let Some(decimals) = decimals {
+        match asset {
+            AssetInfo::NativeToken { denom } => DENOM_DECIMALS.save(storage, denom, &decimals)?,
+            AssetInfo::Token { contract_addr } => {
+                TOKEN_DECIMALS.save(storage, contract_addr, &decimals)?
+            }
+        }
+    }
+
+    Ok(decimals.unwrap_or(NATIVE_DECIMALS))
+}
+
+/// ## Description
+/// Returns the decimals of `asset` like [`query_decimals`] does, without caching them, for the
+/// queries of the contract.
+pub fn load_decimals(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    asset: &AssetInfo,
+) -> StdResult<u8> {
+    if let Some(decimals) = cached_decimals(storage, asset)? {
+        return Ok(decimals);
+    }
+
+    Ok(fetch_decimals(querier, asset)?.unwrap_or(NATIVE_DECIMALS))
+}
+
+/// ## Description
+/// Scales `amount` in whole units of an asset into its smallest unit with `decimals`.
+///
+/// Returns a [`ContractError::OverflowError`] if the scaled amount does not fit a [`Uint128`].
+pub fn scale_by_decimals(amount: Decimal, decimals: u8) -> Result<Uint128, ContractError> {
+    let unit = 10u128
+        .checked_pow(decimals.into())
+        .ok_or_else(|| OverflowError::new(OverflowOperation::Pow, 10u128, decimals))?;
+
+    // the amount is rounded down to the smallest unit, like multiplying by a decimal does
+    let scaled = amount.atomics().full_mul(unit) / Uint256::from(Decimal::one().atomics());
+
+    Uint128::try_from(scaled)
+        .map_err(|_| OverflowError::new(OverflowOperation::Mul, amount, unit).into())
+}
@@ -0,0 +1,84 @@
+use astroport::{
+    asset::{AssetInfo, PairInfo},
+    factory::QueryMsg as FactoryQueryMsg,
+    pair::{PoolResponse, QueryMsg as PairQueryMsg},
+    router::SwapOperation,
+};
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
+
+use crate::error::ContractError;
+
+/// ## Description
+/// Returns whether the Astroport pair of `offer_asset` and `ask_asset` holds at least
+/// `min_liquidity` of both of its assets, where a missing pair holds no liquidity.
+fn has_liquidity(
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    offer_asset: &AssetInfo,
+    ask_asset: &AssetInfo,
+    min_liquidity: Uint128,
+) -> StdResult<bool> {
+    let pair: StdResult<PairInfo> = querier.query_wasm_smart(
+        factory_addr,
+        &FactoryQueryMsg::Pair {
+            asset_infos: [offer_asset.clone(), ask_asset.clone()],
+        },
+    );
+
+    let pair = match pair {
+        Ok(pair) => pair,
+        Err(_) => return Ok(false),
+    };
+
+    let pool: PoolResponse =
+        querier.query_wasm_smart(&pair.contract_addr, &PairQueryMsg::Pool {})?;
+
+    Ok(pool
+        .assets
+        .iter()
+        .all(|asset| asset.amount >= min_liquidity))
+}
+
+/// ## Description
+/// Validates that every pair swapped through by the `hops` of a DCA order holds at least
+/// `min_liquidity` of both of its assets, so that the purchases of the order do not always revert
+/// on slippage.
+///
+/// Returns a [`ContractError::InsufficientLiquidity`] for the first pair holding less.
+/// ## Arguments
+/// * `querier` - The [`QuerierWrapper`] used to query the factory and the pairs.
+///
+/// * `factory_addr` - The [`Addr`] of the Astroport factory.
+///
+/// * `hops` - The [`SwapOperation`] hops of the route of the order.
+///
+/// * `min_liquidity` - The [`Uint128`] minimum amount of each asset that a pair must hold.
+pub fn validate_liquidity(
+    querier: &QuerierWrapper,
+    factory_addr: &Addr,
+    hops: &[SwapOperation],
+    min_liquidity: Uint128,
+) -> Result<(), ContractError> {
+    for hop in hops {
+        if let SwapOperation::AstroSwap {
+            offer_asset_info,
+            ask_asset_info,
+        } = hop
+        {
+            if !has_liquidity(
+                querier,
+                factory_addr,
+                offer_asset_info,
+                ask_asset_info,
+                min_liquidity,
+            )? {
+                return Err(ContractError::InsufficientLiquidity {
+                    offer_asset: offer_asset_info.clone(),
+                    ask_asset: ask_asset_info.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
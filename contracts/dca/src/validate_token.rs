@@ -0,0 +1,72 @@
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{Api, QuerierWrapper};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+
+use crate::error::ContractError;
+
+/// The prefix of the denoms created through the tokenfactory module
+const TOKEN_FACTORY_PREFIX: &str = "factory/";
+
+/// The maximum length of the subdenom of a tokenfactory denom
+const MAX_SUBDENOM_LENGTH: usize = 44;
+
+/// The maximum length of a denom of the bank module
+const MAX_DENOM_LENGTH: usize = 128;
+
+/// ## Description
+/// Validates that `asset` is either a native token, or a Cw20 token whose contract responds to a
+/// [`Cw20QueryMsg::TokenInfo`] query like a token, so that an order of a mistyped contract address
+/// that could never be purchased is not accepted. A native tokenfactory denom must be of the form
+/// `factory/{creator}/{subdenom}` with a valid creator address.
+///
+/// Returns a [`ContractError::InvalidToken`] if the contract is not a Cw20 token, or a
+/// [`ContractError::InvalidTokenFactoryDenom`] if the tokenfactory denom is malformed.
+/// ## Arguments
+/// * `api` - The [`Api`] to validate the creator of a tokenfactory denom with.
+///
+/// * `querier` - The [`QuerierWrapper`] to query the token with.
+///
+/// * `asset` - The [`AssetInfo`] of the asset to validate.
+pub fn validate_token(
+    api: &dyn Api,
+    querier: &QuerierWrapper,
+    asset: &AssetInfo,
+) -> Result<(), ContractError> {
+    match asset {
+        AssetInfo::Token { contract_addr } => {
+            querier
+                .query_wasm_smart::<TokenInfoResponse>(contract_addr, &Cw20QueryMsg::TokenInfo {})
+                .map_err(|_| ContractError::InvalidToken {
+                    contract_addr: contract_addr.to_string(),
+                })?;
+        }
+        AssetInfo::NativeToken { denom } => {
+            if let Some(factory_denom) = denom.strip_prefix(TOKEN_FACTORY_PREFIX) {
+                if !is_valid_factory_denom(api, factory_denom) || denom.len() > MAX_DENOM_LENGTH {
+                    return Err(ContractError::InvalidTokenFactoryDenom {
+                        denom: denom.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns whether the `{creator}/{subdenom}` part of a tokenfactory denom has a valid creator
+/// address and a subdenom of the characters allowed in denoms.
+fn is_valid_factory_denom(api: &dyn Api, factory_denom: &str) -> bool {
+    let (creator, subdenom) = match factory_denom.split_once('/') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    api.addr_validate(creator).is_ok()
+        && !subdenom.is_empty()
+        && subdenom.len() <= MAX_SUBDENOM_LENGTH
+        && subdenom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
@@ -6,7 +6,150 @@ use astroport::{
     router::SwapOperation,
 };
 
-use cosmwasm_std::{Decimal, Uint128};
+use std::fmt;
+
+use cosmwasm_std::{Addr, Binary, BlockInfo, Coin, Decimal, Uint128};
+use cw20::{Cw20ReceiveMsg, Expiration};
+
+/// Describes the interval between DCA purchases, either in seconds or in blocks
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Duration {
+    /// An interval in seconds
+    Time(u64),
+    /// An interval in blocks
+    Height(u64),
+}
+
+impl Duration {
+    /// Returns the length of the interval, in seconds or in blocks
+    pub fn value(&self) -> u64 {
+        match self {
+            Duration::Time(seconds) => *seconds,
+            Duration::Height(blocks) => *blocks,
+        }
+    }
+
+    /// Returns the current point of `block` in the unit of the interval, which is the time of the
+    /// block in seconds or its height
+    pub fn current(&self, block: &BlockInfo) -> u64 {
+        match self {
+            Duration::Time(_) => block.time.seconds(),
+            Duration::Height(_) => block.height,
+        }
+    }
+
+    /// Returns `value` in the unit of the interval
+    pub fn with_value(&self, value: u64) -> Duration {
+        match self {
+            Duration::Time(_) => Duration::Time(value),
+            Duration::Height(_) => Duration::Height(value),
+        }
+    }
+
+    /// Checks if `other` is measured in the same unit as the interval
+    pub fn same_unit(&self, other: &Duration) -> bool {
+        matches!(
+            (self, other),
+            (Duration::Time(_), Duration::Time(_)) | (Duration::Height(_), Duration::Height(_))
+        )
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Duration::Time(seconds) => write!(f, "{} seconds", seconds),
+            Duration::Height(blocks) => write!(f, "{} blocks", blocks),
+        }
+    }
+}
+
+/// The amount of seconds in a day
+const DAY: u64 = 86_400;
+
+/// Describes a cron-like schedule of DCA purchases at a time of day in UTC, performed every day,
+/// on a day of the week or on a day of the month
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Schedule {
+    /// The minute of the hour, from 0 to 59
+    pub minute: u8,
+    /// The hour of the day in UTC, from 0 to 23
+    pub hour: u8,
+    /// The day of the week, from 0 for Sunday to 6 for Saturday
+    pub day_of_week: Option<u8>,
+    /// The day of the month, from 1 to 31, where the months without the day are skipped
+    pub day_of_month: Option<u8>,
+}
+
+impl Schedule {
+    /// Checks that every field of the schedule is in range, and that at most one of
+    /// `day_of_week` and `day_of_month` is set
+    pub fn is_valid(&self) -> bool {
+        self.minute < 60
+            && self.hour < 24
+            && self.day_of_week.map_or(true, |day| day < 7)
+            && self
+                .day_of_month
+                .map_or(true, |day| (1..=31).contains(&day))
+            && (self.day_of_week.is_none() || self.day_of_month.is_none())
+    }
+
+    /// Returns the first time in seconds of the schedule after `time`, which must be valid
+    pub fn next_after(&self, time: u64) -> u64 {
+        let time_of_day = self.hour as u64 * 3_600 + self.minute as u64 * 60;
+
+        let mut day = time / DAY;
+        if day * DAY + time_of_day <= time {
+            day += 1;
+        }
+        while !self.includes_day(day) {
+            day += 1;
+        }
+
+        day * DAY + time_of_day
+    }
+
+    /// Checks if the schedule includes the `day` since the unix epoch
+    fn includes_day(&self, day: u64) -> bool {
+        // the unix epoch was a Thursday
+        self.day_of_week
+            .map_or(true, |day_of_week| (day + 4) % 7 == day_of_week as u64)
+            && self
+                .day_of_month
+                .map_or(true, |day_of_month| day_of_month_of(day) == day_of_month)
+    }
+}
+
+/// Returns the day of the month of the `day` since the unix epoch, following the civil from days
+/// algorithm of the proleptic Gregorian calendar
+fn day_of_month_of(day: u64) -> u8 {
+    // shift the epoch to 0000-03-01, so that leap days are at the end of each year
+    let days = day + 719_468;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+
+    (day_of_year - (153 * month + 2) / 5 + 1) as u8
+}
+
+impl fmt::Display for Schedule {
+    /// Formats the schedule as a cron expression
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = |value: Option<u8>| value.map_or("*".to_string(), |value| value.to_string());
+
+        write!(
+            f,
+            "{} {} {} * {}",
+            self.minute,
+            self.hour,
+            field(self.day_of_month),
+            field(self.day_of_week)
+        )
+    }
+}
 
 /// Describes information about a DCA order
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -18,12 +161,651 @@ pub struct DcaInfo {
     pub initial_asset: Asset,
     /// The asset being purchased in DCA purchases
     pub target_asset: AssetInfo,
-    /// The interval in seconds between DCA purchases
-    pub interval: u64,
-    /// The last time the `target_asset` was purchased
+    /// The interval between DCA purchases, in seconds or in blocks
+    pub interval: Duration,
+    /// The schedule of DCA purchases, which takes precedence over `interval` if set
+    pub schedule: Option<Schedule>,
+    /// The maximum delay after which a scheduled DCA purchase is skipped rather than performed
+    /// late, in the unit of `interval`
+    pub max_delay: Option<u64>,
+    /// The window within which the time of each DCA purchase is pseudo-randomly delayed, in the
+    /// unit of `interval`
+    pub jitter: Option<u64>,
+    /// The delay within `jitter` of the next DCA purchase, drawn when the previous purchase was
+    /// performed
+    #[serde(default)]
+    pub jitter_offset: u64,
+    /// The last time in seconds or block height that the `target_asset` was purchased at, in the
+    /// unit of `interval`
     pub last_purchase: u64,
     /// The amount of `initial_asset` to spend each DCA purchase
     pub dca_amount: Uint128,
+    /// If the `initial_asset` is a cw20 token, whether it has been deposited into the contract
+    /// through [`Cw20HookMsg::CreateDcaOrder`] rather than being spent from the users allowance
+    #[serde(default)]
+    pub escrowed: bool,
+    /// If the `initial_asset` is a cw20 token spent from the users allowance, whether each DCA
+    /// purchase pulls its amount from the users wallet as long as the allowance and balance cover
+    /// it, rather than the allowance reserving the whole deposit up front
+    #[serde(default)]
+    pub pull: bool,
+    /// Whether the order has been paused by the user, preventing DCA purchases until it is resumed
+    #[serde(default)]
+    pub paused: bool,
+    /// The time after which no more DCA purchases can be performed, allowing anyone to sweep the
+    /// remaining funds back to the user
+    pub expires_at: Option<Expiration>,
+    /// The number of DCA purchases after which the order is closed, refunding the remaining funds
+    /// to the user
+    #[serde(default)]
+    pub max_purchases: Option<u64>,
+    /// The amount of DCA purchases failing in a row after which `failure_policy` is applied
+    #[serde(default)]
+    pub max_failures: Option<u32>,
+    /// What happens to the order once `max_failures` of its purchases have failed in a row
+    #[serde(default)]
+    pub failure_policy: FailurePolicy,
+    /// The basis points of the amount spent by each DCA purchase that are paid to the bot
+    /// performing it, instead of a tip redeemed from the tip balance of the user
+    #[serde(default)]
+    pub swap_tip_bps: Option<u16>,
+    /// The tip paid to the bot on top of the tip it redeems, growing the longer a due DCA purchase
+    /// goes unperformed
+    #[serde(default)]
+    pub tip_escalation: Option<TipEscalation>,
+    /// The part of each DCA purchase added to the tip balance of the user while it is running low
+    #[serde(default)]
+    pub tip_replenish: Option<TipReplenish>,
+    /// The only bots that can perform the DCA purchases of the order, or any bot if empty
+    #[serde(default)]
+    pub allowed_bots: Vec<Addr>,
+    /// The maximum price of `target_asset` in `initial_asset` that a DCA purchase can be performed
+    /// at, based on a simulation of the swap route
+    pub max_price: Option<Decimal>,
+    /// The price of `target_asset` in `initial_asset` above which a due DCA purchase is skipped
+    /// and rescheduled rather than performed, based on a simulation of the swap route
+    pub only_below_price: Option<Decimal>,
+    /// The share of the value of a DCA purchase at the ratio of the reserves of its pairs above
+    /// which the purchase is skipped and rescheduled rather than performed, based on a simulation
+    /// of the swap route
+    #[serde(default)]
+    pub max_price_impact: Option<Decimal>,
+    /// The price of `target_asset` in `initial_asset` below which the order is cancelled at the
+    /// next DCA purchase, refunding the remaining deposit, based on a simulation of the swap route
+    #[serde(default)]
+    pub stop_price: Option<Decimal>,
+    /// The price of `target_asset` in `initial_asset` above which the order is closed at the next
+    /// DCA purchase, refunding the remaining deposit, based on a simulation of the swap route
+    #[serde(default)]
+    pub take_profit_price: Option<Decimal>,
+    /// Whether the order accumulates `target_asset` or distributes `initial_asset`, such as by
+    /// selling it into a stablecoin
+    #[serde(default)]
+    pub direction: OrderDirection,
+    /// The distribution order that the order is followed by once it completes, selling the
+    /// `target_asset` it accumulated
+    #[serde(default)]
+    pub distribution: Option<Distribution>,
+    /// The target assets that each DCA purchase is split across by weight, including
+    /// `target_asset`, or empty if the order only purchases `target_asset`
+    #[serde(default)]
+    pub targets: Vec<TargetWeight>,
+    /// Whether the order is pooled, being joined by other depositors holding shares of it
+    #[serde(default)]
+    pub pooled: bool,
+    /// An override for the maximum amount of spread when performing the swaps of this order
+    pub max_spread: Option<Decimal>,
+    /// The minimum amount of `target_asset` that must be received from each DCA purchase
+    pub min_receive_per_purchase: Option<Uint128>,
+    /// The intermediate assets to swap through from `initial_asset` to `target_asset`, which are
+    /// used instead of the hop route provided by the bot performing the DCA purchase
+    pub route: Option<Vec<AssetInfo>>,
+    /// Whether DCA purchases should swap through the route with the best simulated return, out of
+    /// the direct route and every route through one whitelisted asset. A stored `route` takes
+    /// precedence over this
+    #[serde(default)]
+    pub auto_route: bool,
+    /// Whether the `target_asset` purchased is sent to the user immediately, or held by the
+    /// contract until the user claims it
+    #[serde(default)]
+    pub delivery: Delivery,
+    /// The address the purchased `target_asset` is sent to, instead of the user who created the
+    /// order
+    pub recipient: Option<Addr>,
+    /// The contracts called with the purchased `target_asset` after each DCA purchase
+    #[serde(default)]
+    pub purchase_hooks: Vec<PurchaseHook>,
+    /// Whether the purchased ASTRO is staked for xASTRO, which is sent instead of the ASTRO
+    #[serde(default)]
+    pub auto_stake: bool,
+    /// If set, each DCA purchase swaps half of `dca_amount` and provides liquidity with both
+    /// halves to the Astroport pair of `initial_asset` and `target_asset`, sending the LP tokens
+    /// instead of the purchased `target_asset`
+    pub zap: Option<Zap>,
+    /// The address that referred the user, which accrues a share of the protocol fee of each DCA
+    /// purchase
+    pub referrer: Option<Addr>,
+    /// How much of `initial_asset` each DCA purchase spends
+    #[serde(default)]
+    pub strategy: Strategy,
+    /// The value of `target_asset` in `initial_asset` that a value averaging order aims to have
+    /// received by its next DCA purchase, less `dca_amount`
+    #[serde(default)]
+    pub target_value: Uint128,
+    /// The total amount of `initial_asset` spent in DCA purchases
+    #[serde(default)]
+    pub total_spent: Uint128,
+    /// The total amount of `target_asset` received from DCA purchases, as reported by the swaps
+    #[serde(default)]
+    pub total_received: Uint128,
+    /// The total amount of each of the `targets` received from DCA purchases, for an order
+    /// purchasing several target assets
+    #[serde(default)]
+    pub targets_received: Vec<Asset>,
+    /// Whether a DCA purchase is currently being performed for the order, which is set until the
+    /// swap of the purchase replies
+    #[serde(default)]
+    pub executing: bool,
+    /// The last DCA purchase of the order performed through [`ExecuteMsg::PerformDcaPurchases`]
+    /// that failed after passing the checks before its swap, if any
+    #[serde(default)]
+    pub last_failure: Option<PurchaseFailure>,
+    /// The amount of DCA purchases of the order that have failed in a row
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// The amount of `initial_asset` added to the tip balance of the user by `tip_replenish`,
+    /// which moves along with the order when it is transferred
+    #[serde(default)]
+    pub replenished_tip: Uint128,
+}
+
+impl DcaInfo {
+    /// Returns the time in seconds or block height that the next DCA purchase can be performed
+    /// at, in the unit of `interval`, which is the next time of the schedule if it is set,
+    /// delayed by `jitter_offset`
+    pub fn next_purchase(&self) -> u64 {
+        self.purchase_after(self.last_purchase) + self.jitter_offset
+    }
+
+    /// Returns the time in seconds or block height that the next DCA purchase can be performed
+    /// at along with its unit, as a [`Duration`] in the unit of `interval`
+    pub fn next_purchase_at(&self) -> Duration {
+        self.interval.with_value(self.next_purchase())
+    }
+
+    /// Returns the time in seconds or block height that the DCA purchase after a purchase at
+    /// `purchase` can be performed at, in the unit of `interval`
+    pub fn purchase_after(&self, purchase: u64) -> u64 {
+        match &self.schedule {
+            Some(schedule) => schedule.next_after(purchase),
+            None => purchase + self.interval.value(),
+        }
+    }
+
+    /// Returns the latest purchase of the order that has been reached at `now`, skipping the
+    /// purchases that were missed, or none if the next purchase has not been reached yet
+    pub fn latest_purchase(&self, now: u64) -> Option<u64> {
+        let next_purchase = self.next_purchase();
+        if next_purchase > now {
+            return None;
+        }
+
+        Some(match (&self.schedule, self.max_delay) {
+            // without a maximum delay the next purchase can be performed however late it is
+            (_, None) => next_purchase,
+            (Some(_), Some(_)) => {
+                let mut purchase = next_purchase;
+                loop {
+                    let following = self.purchase_after(purchase);
+                    if following > now {
+                        break purchase;
+                    }
+                    purchase = following;
+                }
+            }
+            (None, Some(_)) => {
+                let interval = self.interval.value();
+                next_purchase + (now - next_purchase) / interval * interval
+            }
+        })
+    }
+
+    /// Checks if a DCA purchase can be performed at `block`, which is once the next purchase has
+    /// been reached and for at most `max_delay` after each scheduled purchase
+    pub fn is_due(&self, block: &BlockInfo) -> bool {
+        let now = self.interval.current(block);
+
+        match self.latest_purchase(now) {
+            Some(purchase) => self
+                .max_delay
+                .map_or(true, |max_delay| now <= purchase + max_delay),
+            None => false,
+        }
+    }
+
+    /// Returns how long the due DCA purchase at `block` has been overdue for, in the unit of
+    /// `interval`, or none if no purchase is due or the order has never been purchased without a
+    /// `first_purchase`, as its first purchase is then due from whenever the order was made
+    pub fn overdue(&self, block: &BlockInfo) -> Option<u64> {
+        if self.last_purchase == 0 {
+            return None;
+        }
+
+        let now = self.interval.current(block);
+        Some(now - self.latest_purchase(now)?)
+    }
+
+    /// Returns the amount the tip of a due DCA purchase at `block` has grown by for how long the
+    /// purchase is overdue, or none if the order does not escalate its tip or is not overdue
+    pub fn escalated_tip(&self, block: &BlockInfo) -> Option<Asset> {
+        let escalation = self.tip_escalation.as_ref()?;
+        let overdue = self.overdue(block)?;
+
+        let amount = escalation
+            .step
+            .amount
+            .checked_mul(Uint128::from(overdue.checked_div(escalation.period)?))
+            .unwrap_or(Uint128::MAX)
+            .min(escalation.max);
+
+        (!amount.is_zero()).then(|| Asset {
+            info: escalation.step.info.clone(),
+            amount,
+        })
+    }
+
+    /// Returns the time in seconds or block height from which the next DCA purchase can be
+    /// performed at `block`, which is the following scheduled purchase if the delay of the latest
+    /// one has passed
+    pub fn upcoming_purchase(&self, block: &BlockInfo) -> u64 {
+        let now = self.interval.current(block);
+
+        match self.latest_purchase(now) {
+            Some(_) if self.is_due(block) => now,
+            Some(purchase) => self.purchase_after(purchase),
+            None => self.next_purchase(),
+        }
+    }
+}
+
+/// Describes the parameters of a DCA order created through [`ExecuteMsg::CreateDcaOrders`], which
+/// are the same as in [`ExecuteMsg::CreateDcaOrder`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateDcaOrderParams {
+    pub initial_asset: Asset,
+    pub target_asset: AssetInfo,
+    pub interval: Duration,
+    pub schedule: Option<Schedule>,
+    pub max_delay: Option<u64>,
+    pub jitter: Option<u64>,
+    pub dca_amount: Uint128,
+    #[serde(default)]
+    pub strategy: Strategy,
+    pub first_purchase: Option<u64>,
+    pub expires_at: Option<Expiration>,
+    #[serde(default)]
+    pub max_purchases: Option<u64>,
+    #[serde(default)]
+    pub max_failures: Option<u32>,
+    #[serde(default)]
+    pub failure_policy: FailurePolicy,
+    #[serde(default)]
+    pub swap_tip_bps: Option<u16>,
+    #[serde(default)]
+    pub tip_escalation: Option<TipEscalation>,
+    #[serde(default)]
+    pub tip_replenish: Option<TipReplenish>,
+    #[serde(default)]
+    pub allowed_bots: Vec<String>,
+    #[serde(default)]
+    pub allow_low_liquidity: bool,
+    pub max_price: Option<Decimal>,
+    pub only_below_price: Option<Decimal>,
+    #[serde(default)]
+    pub max_price_impact: Option<Decimal>,
+    #[serde(default)]
+    pub stop_price: Option<Decimal>,
+    #[serde(default)]
+    pub take_profit_price: Option<Decimal>,
+    #[serde(default)]
+    pub direction: OrderDirection,
+    #[serde(default)]
+    pub distribution: Option<Distribution>,
+    #[serde(default)]
+    pub targets: Vec<TargetWeight>,
+    #[serde(default)]
+    pub pooled: bool,
+    pub max_spread: Option<Decimal>,
+    pub min_receive_per_purchase: Option<Uint128>,
+    pub route: Option<Vec<AssetInfo>>,
+    #[serde(default)]
+    pub auto_route: bool,
+    #[serde(default)]
+    pub delivery: Delivery,
+    pub recipient: Option<String>,
+    #[serde(default)]
+    pub purchase_hooks: Vec<PurchaseHook>,
+    #[serde(default)]
+    pub auto_stake: bool,
+    pub zap: Option<Zap>,
+    pub referrer: Option<String>,
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub pull: bool,
+}
+
+/// Describes the data of the response to creating a DCA order, which is set on the response to
+/// [`ExecuteMsg::CreateDcaOrder`] and to [`Cw20HookMsg::CreateDcaOrder`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateDcaOrderResponse {
+    /// The id of the new DCA order
+    pub id: u64,
+}
+
+/// Describes the data of the response to creating multiple DCA orders through
+/// [`ExecuteMsg::CreateDcaOrders`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateDcaOrdersResponse {
+    /// The ids of the new DCA orders, in the order they were provided
+    pub ids: Vec<u64>,
+}
+
+/// Describes a DCA purchase performed through [`ExecuteMsg::PerformDcaPurchases`], with the same
+/// parameters as in [`ExecuteMsg::PerformDcaPurchase`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PurchaseRequest {
+    pub user: String,
+    pub id: u64,
+    pub hops: Vec<SwapOperation>,
+    pub fee_redeem: Vec<Asset>,
+    pub deadline: Option<u64>,
+    #[serde(default)]
+    pub accepted_tips: Vec<AssetInfo>,
+}
+
+/// This structure describes the callback messages that the contract executes on itself
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Performs a DCA purchase on behalf of `bot`, as part of [`ExecuteMsg::PerformDcaPurchases`]
+    PerformDcaPurchase {
+        bot: Addr,
+        purchase: PurchaseRequest,
+    },
+}
+
+/// Describes how the `target_asset` purchased for a DCA order is delivered to the user
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Delivery {
+    /// The purchased asset is sent to the user with each DCA purchase
+    Immediate,
+    /// The purchased asset is held by the contract until the user claims it with
+    /// [`ExecuteMsg::ClaimPurchased`]
+    Escrow,
+    /// The purchased asset, which must be a native token, is sent with each DCA purchase to
+    /// `to_address` on another chain through an ICS-20 transfer over `channel_id`, timing out
+    /// after `timeout` seconds. The asset of a transfer that fails or times out is held by the
+    /// contract until the user claims it with [`ExecuteMsg::ClaimPurchased`]
+    Ibc {
+        channel_id: String,
+        to_address: String,
+        timeout: u64,
+    },
+}
+
+impl Default for Delivery {
+    fn default() -> Self {
+        Delivery::Immediate
+    }
+}
+
+/// Describes how much of `initial_asset` each DCA purchase of an order spends
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// Each DCA purchase spends `dca_amount`
+    Fixed,
+    /// Each DCA purchase spends whatever is needed to bring the value of the `target_asset`
+    /// received so far up to a target path growing by `dca_amount` with every purchase, capped by
+    /// the remaining deposit, so that more is purchased when the price falls and less when it rises
+    ValueAveraging,
+    /// Each DCA purchase spends the first of the remaining `amounts`, which sum to the remaining
+    /// deposit, so that the amount spent can change over time, such as ramping up linearly
+    Tranches { amounts: Vec<Uint128> },
+}
+
+impl Default for Strategy {
+    fn default() -> Self {
+        Strategy::Fixed
+    }
+}
+
+/// Describes whether a DCA order accumulates its `target_asset` or distributes its
+/// `initial_asset`. Both are purchased the same way, but they are told apart when queried
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderDirection {
+    /// The order buys into `target_asset`
+    Accumulate,
+    /// The order sells out of `initial_asset`, such as into a stablecoin
+    Distribute,
+}
+
+impl Default for OrderDirection {
+    fn default() -> Self {
+        OrderDirection::Accumulate
+    }
+}
+
+/// Describes the distribution order that an accumulating DCA order is followed by, which is
+/// created once the accumulating order completes to sell the `target_asset` it has accumulated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Distribution {
+    /// The asset that the accumulated asset is sold for
+    pub target_asset: AssetInfo,
+    /// The amount of the accumulated asset sold in each DCA purchase of the distribution order
+    pub dca_amount: Uint128,
+    /// The interval between the DCA purchases of the distribution order
+    pub interval: Duration,
+}
+
+/// Describes one of the target assets of a DCA order splitting each purchase across several
+/// assets, along with the share of each purchase spent on it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TargetWeight {
+    /// The asset that is purchased
+    pub asset: AssetInfo,
+    /// The share of the amount swapped in each DCA purchase that is spent on `asset`
+    pub weight: Decimal,
+}
+
+/// Describes the account on a remote chain controlling an interchain account that owns DCA
+/// orders through the interchain accounts module of ibc-go
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InterchainController {
+    /// The connection between the chains that the interchain account was registered over
+    pub connection_id: String,
+    /// The address of the controller on the remote chain
+    pub address: String,
+}
+
+/// Describes what happens to a DCA order once `max_failures` of its purchases have failed in a
+/// row
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// The order is paused until the user resumes it
+    Pause,
+    /// The maximum spread of the order is widened by `step`, up to `max_spread`, after which the
+    /// order is paused
+    WidenSlippage { step: Decimal, max_spread: Decimal },
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        FailurePolicy::Pause
+    }
+}
+
+/// Describes a tip that grows the longer a due DCA purchase of an order goes unperformed, which is
+/// paid to the bot from the tip balance of the user on top of the tip it redeems
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TipEscalation {
+    /// The whitelisted tip asset and amount the tip grows by for every `period` the purchase is
+    /// overdue
+    pub step: Asset,
+    /// The period the purchase must be overdue for the tip to grow by `step`, in the unit of the
+    /// interval of the order
+    pub period: u64,
+    /// The maximum amount the tip can grow by
+    pub max: Uint128,
+}
+
+/// Describes the part of each DCA purchase of an order that is kept in the tip balance of the
+/// user instead of being swapped, while the balance of `initial_asset` is running low
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TipReplenish {
+    /// The amount of `initial_asset` added to the tip balance at each purchase
+    pub amount: Uint128,
+    /// The tip balance of `initial_asset` below which the tip balance is replenished
+    pub threshold: Uint128,
+}
+
+/// Describes the performance of the DCA purchases of a registered bot
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotStats {
+    /// The time in seconds at which the bot registered
+    pub registered_at: u64,
+    /// The amount of DCA purchases performed by the bot
+    pub purchases: u64,
+    /// The amount of DCA purchases performed by the bot through `perform_dca_purchases` that
+    /// failed
+    pub failures: u64,
+    /// The total delay of the DCA purchases performed by the bot after they were due, in the unit
+    /// of the interval of each order
+    pub total_delay: u64,
+    /// The bond posted by the bot when registering, which is slashed to the user of an order for
+    /// which the bot performs a purchase through an invalid or worse route
+    #[serde(default)]
+    pub bond: Option<Coin>,
+    /// The time in seconds from which the bot can withdraw its bond, if it is unbonding
+    #[serde(default)]
+    pub unbonding_at: Option<u64>,
+    /// The amount of times the bond of the bot has been slashed
+    #[serde(default)]
+    pub slashes: u64,
+}
+
+impl BotStats {
+    /// Returns the average delay of the DCA purchases performed by the bot after they were due
+    pub fn average_delay(&self) -> u64 {
+        self.total_delay.checked_div(self.purchases).unwrap_or(0)
+    }
+}
+
+/// Describes the bond that bots post to register, which is slashed for routing purchases through
+/// an invalid or worse route
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotBond {
+    /// The minimum native token bond that a bot must send when registering
+    pub bond: Coin,
+    /// The share of the bond in basis points slashed to the user for each invalid purchase
+    pub slash_bps: u16,
+    /// The delay in seconds after a bot starts unbonding before it can withdraw its bond
+    pub unbonding_period: u64,
+}
+
+/// Describes a contract that is called after each DCA purchase of an order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PurchaseHook {
+    /// The address of the contract to call
+    pub contract_addr: String,
+    /// The message passed to the contract in [`DcaHookMsg::DcaPurchase`]
+    pub msg: Binary,
+}
+
+/// Describes a discount on the protocol fee for users holding xASTRO
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeTier {
+    /// The minimum xASTRO balance of the user for the discount to apply
+    pub min_xastro: Uint128,
+    /// The fraction of the protocol fee waived, between zero and one
+    pub discount: Decimal,
+}
+
+/// Describes how the liquidity provided with each DCA purchase of a zapping order is held
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Zap {
+    /// Whether the LP tokens are deposited into the Astroport generator on behalf of the
+    /// recipient to earn ASTRO emissions, rather than being sent to it. This requires the factory
+    /// to have a generator set
+    #[serde(default)]
+    pub stake_lp: bool,
+}
+
+/// This structure describes the message sent to a [`PurchaseHook`] contract after a DCA purchase
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DcaHookMsg {
+    /// Notifies the hook contract of a DCA purchase of `asset` for the order `id` of `user`
+    ///
+    /// The purchased `asset` is sent along with the message to the first hook of the order, as
+    /// native funds or through a cw20 `send`, and should be credited to `recipient`
+    DcaPurchase {
+        user: String,
+        id: u64,
+        recipient: String,
+        asset: Asset,
+        msg: Binary,
+    },
+}
+
+/// This structure describes the message sent to the notifier contract of a user on the lifecycle
+/// events of their DCA orders
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifierMsg {
+    /// Notifies the notifier contract of `event` of the order `id` of `user`
+    DcaOrderEvent {
+        user: String,
+        id: u64,
+        event: OrderEvent,
+    },
+}
+
+/// Describes a lifecycle event of a DCA order that the notifier of its user is notified of
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderEvent {
+    /// A DCA purchase spent `amount_in` of the initial asset for `amount_out` of the target asset
+    Executed {
+        amount_in: Uint128,
+        amount_out: Uint128,
+    },
+    /// A DCA purchase performed in a batch of purchases failed with `error`
+    Failed { error: String },
+    /// The order spent its whole deposit or performed its maximum amount of purchases
+    Completed {},
+    /// The order was cancelled, stopped at its stop or take profit price, swept or left by all of
+    /// its depositors
+    Cancelled {},
+}
+
+/// Describes the backend that the swaps of DCA purchases are constructed for
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapBackendType {
+    /// The hop route of each purchase is swapped through the Astroport router
+    Router,
+    /// Each purchase swaps directly with the Astroport pair of its single hop
+    Pair,
+}
+
+impl Default for SwapBackendType {
+    fn default() -> Self {
+        SwapBackendType::Router
+    }
 }
 
 /// Describes the parameters used for creating a contract
@@ -42,6 +824,27 @@ pub struct InstantiateMsg {
     pub factory_addr: String,
     /// The address of the Astroport router contract
     pub router_addr: String,
+    /// The address of the Astroport staking contract used to stake purchased ASTRO
+    pub staking_addr: Option<String>,
+    /// The fee in basis points taken from the amount spent in each DCA purchase
+    #[serde(default)]
+    pub protocol_fee_bps: u16,
+    /// The address the protocol fee is sent to, which must be set if `protocol_fee_bps` is
+    pub fee_collector: Option<String>,
+    /// The discounts on the protocol fee for users holding xASTRO, which require `staking_addr`
+    #[serde(default)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// The share of the protocol fee of each DCA purchase that accrues to the referrer of the order
+    #[serde(default)]
+    pub referral_share: Decimal,
+    /// The owner of the contract, which is the owner of the factory if not specified
+    pub owner: Option<String>,
+    /// The delay in seconds before sensitive configuration changes take effect
+    #[serde(default)]
+    pub config_timelock: u64,
+    /// The backend that the swaps of DCA purchases are constructed for
+    #[serde(default)]
+    pub swap_backend: SwapBackendType,
 }
 
 /// This structure describes the execute messages available in the contract
@@ -51,38 +854,249 @@ pub enum ExecuteMsg {
     /// Add top-up for bots to perform DCA requests with the specified `assets` sent in the tx
     AddBotTip { assets: Vec<Asset> },
     /// Cancels a DCA order given the id of the order, returning any native asset back to the user
-    CancelDcaOrder { id: u64 },
+    CancelDcaOrder {
+        id: u64,
+        /// The user who owns the order, if the sender is an operator managing it on their behalf
+        user: Option<String>,
+        /// Whether the bot tip of the user is also withdrawn if it was the last order of the user
+        #[serde(default)]
+        withdraw_tips: bool,
+    },
+    /// Cancels multiple DCA orders given the ids of the orders, or every order of the user if no
+    /// ids are given, returning any native asset back to the user
+    CancelDcaOrders {
+        ids: Option<Vec<u64>>,
+        /// The user who owns the orders, if the sender is an operator managing it on their behalf
+        user: Option<String>,
+        /// Whether the bot tip of the user is also withdrawn if no orders of the user remain
+        #[serde(default)]
+        withdraw_tips: bool,
+    },
     /// Creates a new DCA order where `dca_amount` of token `initial_asset` will purchase
     /// `target_asset` every `interval`
     ///
     /// If `initial_asset` is a cw20 token, the user needs to have increased the allowance prior to
-    /// calling this execution
+    /// calling this execution, or can instead deposit the tokens with [`Cw20HookMsg::CreateDcaOrder`]
     ///
     /// DCA purchases can start from `first_purchase` if specified, otherwise as soon as the DCA
-    /// order is made
+    /// order is made. The `interval` is either in seconds or in blocks, and `first_purchase` is a
+    /// time in seconds or a block height in the same unit
+    ///
+    /// If `schedule` is specified, DCA purchases are performed at each time of the schedule after
+    /// the last purchase instead of every `interval`, which must then be in seconds
+    ///
+    /// If `max_delay` is specified, a DCA purchase that is not performed within `max_delay` of its
+    /// scheduled time, in the unit of `interval`, is skipped and the order waits for the next one
+    ///
+    /// If `jitter` is specified, each DCA purchase can only be performed after a pseudo-random
+    /// delay of up to `jitter`, in the unit of `interval`, which is drawn when the previous
+    /// purchase is performed so that the time of a purchase cannot be predicted long in advance
+    ///
+    /// With the [`Strategy::ValueAveraging`] `strategy`, each DCA purchase spends whatever is
+    /// needed to bring the value of the `target_asset` received up to `dca_amount` times the
+    /// number of purchases, rather than `dca_amount`, and cannot be used with `zap`. With the
+    /// [`Strategy::Tranches`] `strategy`, each DCA purchase spends the next of its amounts, which
+    /// must sum to the amount of `initial_asset`
+    ///
+    /// If `expires_at` is specified, no DCA purchases can be performed after it, and the remaining
+    /// funds can be swept back to the user with [`ExecuteMsg::SweepExpired`]
+    ///
+    /// If `max_purchases` is specified, the order is closed once that many DCA purchases have been
+    /// performed, refunding the remaining funds to the user
+    ///
+    /// If `max_failures` is specified, `failure_policy` pauses the order or widens its maximum
+    /// spread once that many of its DCA purchases performed through
+    /// [`ExecuteMsg::PerformDcaPurchases`] have failed in a row
+    ///
+    /// If `swap_tip_bps` is specified, the bot performing each DCA purchase is paid that share of
+    /// the amount spent instead of a tip from the tip balance of the user
+    ///
+    /// If `tip_escalation` is specified, the bot is also paid a tip from the tip balance of the
+    /// user that grows the longer a due DCA purchase goes unperformed
+    ///
+    /// If `tip_replenish` is specified, part of each DCA purchase is added to the tip balance of
+    /// the user while it is below a threshold
+    ///
+    /// If `allowed_bots` is not empty, only those bots can perform the DCA purchases of the order
+    ///
+    /// If `allow_low_liquidity` is set, the order can be created for pairs holding less than the
+    /// minimum liquidity set by the contract owner
+    ///
+    /// If `max_price` is specified, DCA purchases are only performed while the simulated price of
+    /// `target_asset` in `initial_asset` is at or below it
+    ///
+    /// If `only_below_price` is specified, a due DCA purchase is skipped and rescheduled to the
+    /// next interval while the simulated price of `target_asset` in `initial_asset` is above it,
+    /// rather than failing
+    ///
+    /// If `max_price_impact` is specified, a due DCA purchase is skipped and rescheduled to the
+    /// next interval while the simulated swap loses more than that share of its value at the
+    /// ratio of the reserves of its pairs, rather than being performed
+    ///
+    /// If `stop_price` is specified, the order is cancelled and its remaining deposit refunded
+    /// once a due DCA purchase finds the simulated price of `target_asset` in `initial_asset`
+    /// below it, and likewise closed once the price is above `take_profit_price` if specified
+    ///
+    /// `direction` sets whether the order accumulates `target_asset` or distributes
+    /// `initial_asset`. If `distribution` is specified for an accumulating order with
+    /// [`Delivery::Escrow`], a distributing order selling the purchased balance of the order as
+    /// described by it is created once the order completes
+    ///
+    /// If `targets` are specified, the amount swapped in each DCA purchase is split across their
+    /// assets by weight, the weights summing to one, and each is swapped through the best route
+    /// found on-chain. `target_asset` must be one of them
+    ///
+    /// If `pooled` is set, other depositors can join the order with
+    /// [`ExecuteMsg::JoinPooledOrder`], each deposit minting shares of the order, and the purchased
+    /// `target_asset` is claimed pro-rata to the shares with [`ExecuteMsg::ClaimPoolShare`]
+    ///
+    /// `max_spread` overrides the users maximum spread for this order, and
+    /// `min_receive_per_purchase` sets the minimum amount of `target_asset` each purchase must
+    /// receive
+    ///
+    /// If `route` is specified, DCA purchases swap through the whitelisted intermediate assets in
+    /// order, rather than the hop route provided when performing the purchase. Otherwise, if
+    /// `auto_route` is set, DCA purchases swap through the best route found on-chain
+    ///
+    /// If `delivery` is [`Delivery::Escrow`], the purchased `target_asset` is held by the contract
+    /// until it is claimed with [`ExecuteMsg::ClaimPurchased`]. Otherwise, it is sent to
+    /// `recipient` if specified, or the user who created the order
+    ///
+    /// If `purchase_hooks` are specified, each hook is called in order with a
+    /// [`DcaHookMsg::DcaPurchase`] after every DCA purchase, where the purchased `target_asset` is
+    /// sent to the first hook instead of `recipient`
+    ///
+    /// If `auto_stake` is set for an order purchasing ASTRO, the purchased ASTRO is staked in the
+    /// Astroport staking contract and the xASTRO received is sent instead
+    ///
+    /// If `zap` is specified, each DCA purchase swaps half of `dca_amount` to `target_asset` and
+    /// provides liquidity with both halves to their Astroport pair. The LP tokens are sent to
+    /// `recipient`, or deposited into the generator for it, and any amount left over from
+    /// matching the ratio of the pool is returned
+    ///
+    /// If `referrer` is specified, it accrues a share of the protocol fee of each DCA purchase,
+    /// which it can claim with [`ExecuteMsg::ClaimReferralRewards`]
+    ///
+    /// If `owner` is specified, the order is funded by the sender but belongs to `owner`, who
+    /// receives its refunds and purchased assets. A cw20 order for another owner must be created
+    /// by sending the tokens with [`Cw20HookMsg::CreateDcaOrder`]
+    ///
+    /// If `pull` is specified for a cw20 order, the allowance does not need to cover the deposit
+    /// up front. Each DCA purchase pulls its amount from the users wallet, and is skipped and
+    /// rescheduled if the allowance or balance of the user does not cover it
     CreateDcaOrder {
         initial_asset: Asset,
         target_asset: AssetInfo,
-        interval: u64,
+        interval: Duration,
+        schedule: Option<Schedule>,
+        max_delay: Option<u64>,
+        jitter: Option<u64>,
         dca_amount: Uint128,
+        #[serde(default)]
+        strategy: Strategy,
         first_purchase: Option<u64>,
+        expires_at: Option<Expiration>,
+        #[serde(default)]
+        max_purchases: Option<u64>,
+        #[serde(default)]
+        max_failures: Option<u32>,
+        #[serde(default)]
+        failure_policy: FailurePolicy,
+        #[serde(default)]
+        swap_tip_bps: Option<u16>,
+        #[serde(default)]
+        tip_escalation: Option<TipEscalation>,
+        #[serde(default)]
+        tip_replenish: Option<TipReplenish>,
+        #[serde(default)]
+        allowed_bots: Vec<String>,
+        #[serde(default)]
+        allow_low_liquidity: bool,
+        max_price: Option<Decimal>,
+        only_below_price: Option<Decimal>,
+        #[serde(default)]
+        max_price_impact: Option<Decimal>,
+        #[serde(default)]
+        stop_price: Option<Decimal>,
+        #[serde(default)]
+        take_profit_price: Option<Decimal>,
+        #[serde(default)]
+        direction: OrderDirection,
+        #[serde(default)]
+        distribution: Option<Distribution>,
+        #[serde(default)]
+        targets: Vec<TargetWeight>,
+        #[serde(default)]
+        pooled: bool,
+        max_spread: Option<Decimal>,
+        min_receive_per_purchase: Option<Uint128>,
+        route: Option<Vec<AssetInfo>>,
+        #[serde(default)]
+        auto_route: bool,
+        #[serde(default)]
+        delivery: Delivery,
+        recipient: Option<String>,
+        #[serde(default)]
+        purchase_hooks: Vec<PurchaseHook>,
+        #[serde(default)]
+        auto_stake: bool,
+        zap: Option<Zap>,
+        referrer: Option<String>,
+        owner: Option<String>,
+        #[serde(default)]
+        pull: bool,
     },
+    /// Creates multiple new DCA orders at once, as described in [`ExecuteMsg::CreateDcaOrder`]
+    ///
+    /// The native tokens sent must match the total `initial_asset` amount of each native token
+    /// across all the orders, and either every order is created or none are
+    CreateDcaOrders { orders: Vec<CreateDcaOrderParams> },
     /// Modifies an existing DCA order, allowing the user to change certain parameters
+    ///
+    /// The modified order is validated as when creating an order, so its new assets and amounts
+    /// must still suit its route, auto staking, zap, delivery, distribution and tip replenishment,
+    /// and it cannot be modified while the contract is paused
     ModifyDcaOrder {
         id: u64,
         new_initial_asset: Asset,
         new_target_asset: AssetInfo,
-        new_interval: u64,
+        new_interval: Duration,
+        /// The new schedule of the order, or none to purchase every `new_interval`
+        new_schedule: Option<Schedule>,
         new_dca_amount: Uint128,
         new_first_purchase: Option<u64>,
+        /// The user who owns the order, if the sender is an operator managing it on their behalf
+        user: Option<String>,
     },
     /// Performs a DCA purchase for a specified user given a hop route and requested fee redemption
+    ///
+    /// If `deadline` is specified, the purchase fails once the block time has passed it
+    ///
+    /// If `fee_redeem` is empty and `accepted_tips` is specified, the tip for every hop of the
+    /// purchase is redeemed from the first asset of the tip balance of the user, in the priority
+    /// set by the user, that the bot accepts and the balance covers
     PerformDcaPurchase {
         user: String,
         id: u64,
         hops: Vec<SwapOperation>,
         fee_redeem: Vec<Asset>,
+        deadline: Option<u64>,
+        #[serde(default)]
+        accepted_tips: Vec<AssetInfo>,
     },
+    /// Performs DCA purchases for multiple orders at once, as described in
+    /// [`ExecuteMsg::PerformDcaPurchase`]
+    ///
+    /// Each purchase is performed in its own submessage, so that a purchase that fails does not
+    /// revert the other purchases. A purchase rejected by the checks before its swap is ignored
+    /// rather than recorded as a failure of its order
+    PerformDcaPurchases { purchases: Vec<PurchaseRequest> },
+    /// Proposes a new owner of the contract, who has `expires_in` seconds to claim the ownership
+    ProposeNewOwner { owner: String, expires_in: u64 },
+    /// Removes the pending proposal of a new owner of the contract
+    DropOwnershipProposal {},
+    /// Claims the ownership of the contract by the proposed new owner
+    ClaimOwnership {},
     /// Updates the configuration of the contract
     UpdateConfig {
         /// The new maximum amount of hops to perform from `initial_asset` to `target_asset` when
@@ -94,28 +1108,493 @@ pub enum ExecuteMsg {
         whitelisted_fee_assets: Option<Vec<Asset>>,
         /// The new maximum spread for DCA purchases
         max_spread: Option<Decimal>,
+        /// The new address of the Astroport staking contract used to stake purchased ASTRO
+        staking_addr: Option<String>,
+        /// The new fee in basis points taken from the amount spent in each DCA purchase
+        protocol_fee_bps: Option<u16>,
+        /// The new address the protocol fee is sent to
+        fee_collector: Option<String>,
+        /// The new discounts on the protocol fee for users holding xASTRO
+        fee_tiers: Option<Vec<FeeTier>>,
+        /// The new share of the protocol fee that accrues to the referrer of an order
+        referral_share: Option<Decimal>,
+        /// The new address of the Astroport factory contract
+        factory_addr: Option<String>,
+        /// The new address of the Astroport router contract
+        router_addr: Option<String>,
+        /// The new delay in seconds before sensitive configuration changes take effect
+        config_timelock: Option<u64>,
+        /// Whether to pause the creation of DCA orders and DCA purchases
+        paused: Option<bool>,
+        /// The new maximum deviation of the simulated price of a DCA purchase from the price of
+        /// the oracle set for its assets
+        max_oracle_deviation: Option<Decimal>,
+        /// Whether only registered bots can perform DCA purchases
+        registered_bots_only: Option<bool>,
+        /// The new bond that bots must post when registering
+        #[serde(default)]
+        bot_bond: Option<BotBond>,
+        /// The new window after each DCA purchase is due during which only its assigned bot can
+        /// perform it, where zero opens every purchase to all bots
+        #[serde(default)]
+        fairness_window: Option<u64>,
+        /// The new assets that DCA orders can spend or purchase while `allowed_assets_only` is set
+        #[serde(default)]
+        allowed_assets: Option<Vec<AssetInfo>>,
+        /// Whether DCA orders can only spend or purchase the allowed assets
+        #[serde(default)]
+        allowed_assets_only: Option<bool>,
+        /// The new minimum amount of each asset that the pairs of a new DCA order must hold, where
+        /// zero removes the minimum
+        #[serde(default)]
+        min_liquidity: Option<Uint128>,
+        /// The new cw20 tokens taking a tax on transfers, whose amount received by the contract
+        /// is measured rather than assumed
+        #[serde(default)]
+        fee_on_transfer_tokens: Option<Vec<String>>,
+        /// The new minimum deposit of a new DCA order in whole units of its initial asset, where
+        /// zero removes the minimum
+        #[serde(default)]
+        min_deposit: Option<Decimal>,
+        /// The new minimum amount spent in each purchase of a new DCA order in whole units of its
+        /// initial asset, where zero removes the minimum
+        #[serde(default)]
+        min_dca_amount: Option<Decimal>,
     },
+    /// Applies the pending configuration changes whose timelock has passed
+    ApplyPendingConfig {},
+    /// Pauses the DCA purchases of every order spending or purchasing `asset`
+    PauseAsset { asset: AssetInfo },
+    /// Resumes the DCA purchases of the orders spending or purchasing a paused `asset`
+    UnpauseAsset { asset: AssetInfo },
     /// Update the configuration for a user
     UpdateUserConfig {
         /// The maximum amount of hops per swap
         max_hops: Option<u32>,
         /// The maximum spread per token when performing DCA purchases
         max_spread: Option<Decimal>,
+        /// The order in which the tip balance is redeemed by bots that leave the choice of tip to
+        /// the contract, with the assets not listed redeemed after those listed
+        #[serde(default)]
+        tip_priority: Vec<AssetInfo>,
     },
     /// Withdraws the `assets` amount of a users bot tip from the contract.
     Withdraw { assets: Vec<Asset> },
+    /// Receives a cw20 token sent to the contract, executing the [`Cw20HookMsg`] in the message
+    Receive(Cw20ReceiveMsg),
+    /// Receives the native tokens transferred to the contract over IBC with an ibc-hooks memo
+    /// executing the [`Cw20HookMsg::CreateDcaOrder`] hook, which creates a DCA order of the single
+    /// coin received for its `owner`, as the sender is an intermediary address derived from the
+    /// channel and the sender on the other chain that nobody controls
+    ReceiveIbc(Cw20HookMsg),
+    /// Sends the `target_asset` purchased for a DCA order with [`Delivery::Escrow`] that is held
+    /// by the contract to the owner of the order, which is the holder of the NFT of the order if
+    /// one was minted for it
+    ClaimPurchased {
+        id: u64,
+        /// The user the order is stored under, if the sender is the holder of the NFT of the order,
+        /// or an operator or a member of the group of the owner claiming on behalf of the owner
+        user: Option<String>,
+    },
+    /// Sends the share of the protocol fees accrued to the sender as the referrer of DCA orders
+    ClaimReferralRewards {},
+    /// Adds the native tokens sent to the pooled DCA order `id` of `user`, minting shares of the
+    /// order to the sender in proportion to the deposit remaining in the order. Cw20 tokens are
+    /// added with [`Cw20HookMsg::JoinPooledOrder`]
+    JoinPooledOrder { user: String, id: u64 },
+    /// Sends the share of the `target_asset` purchased for the pooled DCA order `id` of `user`
+    /// that is owed to the shares of the sender
+    ClaimPoolShare { user: String, id: u64 },
+    /// Burns the shares of the sender in the pooled DCA order `id` of `user`, sending back their
+    /// share of the remaining deposit along with their share of the purchased `target_asset`
+    LeavePooledOrder { user: String, id: u64 },
+    /// Executes a [`CallbackMsg`], which can only be called by the contract itself
+    Callback(CallbackMsg),
+    /// Adds `amount` of the `initial_asset` to an existing DCA order, keeping its schedule
+    ///
+    /// Native tokens need to be sent with the message, while cw20 tokens need to have the
+    /// allowance increased. Escrowed cw20 orders are topped up with [`Cw20HookMsg::TopUpDcaOrder`]
+    TopUpDcaOrder { id: u64, amount: Uint128 },
+    /// Withdraws `amount` of the remaining `initial_asset` from an existing DCA order, keeping the
+    /// order active with the reduced balance
+    WithdrawFromDcaOrder {
+        id: u64,
+        amount: Uint128,
+        /// The user who owns the order, if the sender holds the NFT of an order created by them
+        user: Option<String>,
+    },
+    /// Pauses a DCA order, preventing DCA purchases from being performed until it is resumed
+    PauseDcaOrder {
+        id: u64,
+        /// The user who owns the order, if the sender is an operator managing it on their behalf
+        user: Option<String>,
+    },
+    /// Resumes a paused DCA order, keeping the schedule of the order
+    ResumeDcaOrder {
+        id: u64,
+        /// The user who owns the order, if the sender is an operator managing it on their behalf
+        user: Option<String>,
+    },
+    /// Allows or disallows `operator` to modify, pause, resume and cancel the DCA orders of the
+    /// sender on their behalf, where any refunded funds are always sent to the sender
+    SetOperator { operator: String, allowed: bool },
+    /// Sets the cw4 `group` whose members can claim the purchases of and pause and resume the DCA
+    /// orders of the sender, such as the group of a multisig or DAO contract owning the orders,
+    /// where any refunded or claimed funds are always sent to the sender. No group is set if not
+    /// specified
+    SetMemberGroup { group: Option<String> },
+    /// Sets the `notifier` contract that is called with a [`NotifierMsg`] whenever a DCA order of
+    /// the sender is purchased, fails to be purchased, completes or is cancelled. The notifier is
+    /// removed if not specified
+    SetNotifier { notifier: Option<String> },
+    /// Declares the sender to be an interchain account owned by `controller` on a remote chain,
+    /// so that front-ends of the remote chain can find the DCA orders of the sender by their
+    /// controller. The declaration is removed if `controller` is not specified
+    SetInterchainController {
+        controller: Option<InterchainController>,
+    },
+    /// Refunds the remaining funds of an expired DCA order, or of an order spending or purchasing
+    /// a blacklisted asset, back to the `user` and removes the order, which can be called by anyone
+    SweepExpired { user: String, id: u64 },
+    /// Transfers a DCA order of the sender to `new_owner` along with its remaining deposit,
+    /// purchase history and escrowed purchases, where the order is given the next id of
+    /// `new_owner`
+    TransferOrder { id: u64, new_owner: String },
+    /// Instantiates the companion cw721 contract from `code_id`, after which an NFT is minted for
+    /// each new DCA order whose holder is treated as the owner of the order
+    InstantiateNft {
+        code_id: u64,
+        name: String,
+        symbol: String,
+    },
+    /// Sets the oracle contract that the DCA purchases of orders spending `initial_asset` for
+    /// `target_asset` are checked against, or removes it if `oracle_addr` is not specified
+    SetOracle {
+        initial_asset: AssetInfo,
+        target_asset: AssetInfo,
+        oracle_addr: Option<String>,
+    },
+    /// Sets the tasks contract of the CronCat automation protocol that keeper jobs of DCA orders
+    /// are registered on, or removes it if `keeper` is not specified, which can only be called by
+    /// the contract owner
+    SetKeeper { keeper: Option<String> },
+    /// Registers a recurring task on the keeper set by the contract owner that performs the DCA
+    /// purchases of the order `id` of `user` every `interval` blocks, funded with the native
+    /// `funds` taken from the tip balance of the user. The order must have a stored route or be
+    /// auto routed. Jobs of another `user` can only be registered by the contract owner, and the
+    /// job is registered for the sender if `user` is not specified
+    RegisterKeeperJob {
+        id: u64,
+        user: Option<String>,
+        interval: u64,
+        funds: Asset,
+    },
+    /// Removes up to `limit` archived DCA orders of any user that were completed more than
+    /// `retention` seconds ago, which can only be called by the contract owner
+    PruneArchivedOrders { retention: u64, limit: Option<u32> },
+    /// Sends the surplus of each of `assets` held by the contract over what it owes to its users
+    /// and bots, as reported by [`QueryMsg::Reconciliation`], to the fee collector, which can only
+    /// be called by the contract owner
+    SweepSurplus { assets: Vec<AssetInfo> },
+    /// Registers the sender as a bot performing DCA purchases, tracking the performance of its
+    /// purchases, where the bond set by the contract owner must be sent along
+    RegisterBot {},
+    /// Starts the unbonding period of the bond of the sender as a registered bot
+    UnbondBot {},
+    /// Withdraws the bond of the sender once its unbonding period has passed, removing the sender
+    /// from the registered bots
+    WithdrawBotBond {},
+    /// Whitelists a tip asset along with its tip per hop, or updates the tip per hop of a
+    /// whitelisted tip asset, which can only be called by the contract owner
+    AddTipAsset { asset: Asset },
+    /// Removes a whitelisted tip asset, which can only be called by the contract owner
+    RemoveTipAsset { asset: AssetInfo },
+    /// Whitelists an asset that the hop routes of DCA purchases can swap through, which can only
+    /// be called by the contract owner
+    AddHopAsset { asset: AssetInfo },
+    /// Removes a whitelisted hop asset that is not part of the stored route of any DCA order,
+    /// which can only be called by the contract owner
+    RemoveHopAsset { asset: AssetInfo },
+    /// Blacklists a malicious or deprecated `asset`, so that no DCA order can spend or purchase it
+    /// and the existing orders of the asset can be swept back to their owners by anyone, which can
+    /// only be called by the contract owner
+    BlacklistAsset { asset: AssetInfo },
+    /// Removes `asset` from the blacklist, which can only be called by the contract owner
+    UnblacklistAsset { asset: AssetInfo },
+    /// Resolves the address of the Astroport pair of `asset_infos` from the factory again,
+    /// replacing or removing its cached address, which can be called by anyone
+    RefreshPair { asset_infos: [AssetInfo; 2] },
+}
+
+/// This structure describes the cw20 receive hooks available in the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates a new DCA order where `dca_amount` of the sent cw20 token will purchase
+    /// `target_asset` every `interval`
+    ///
+    /// The sent tokens are held by the contract until they are spent on DCA purchases, so no
+    /// allowance needs to be set for the DCA contract
+    ///
+    /// DCA purchases can start from `first_purchase` if specified, otherwise as soon as the DCA
+    /// order is made, are performed at each time of `schedule` if specified, are skipped if they
+    /// are more than `max_delay` late, are delayed pseudo-randomly by up to `jitter`, and stop
+    /// after `expires_at` or `max_purchases` if specified, while `max_failures` and
+    /// `failure_policy` set how repeated failures are handled, `swap_tip_bps` pays bots a share
+    /// of each purchase, `tip_escalation` grows the tip of overdue purchases, `tip_replenish`
+    /// keeps part of each purchase in the tip balance while it runs low, `allowed_bots` restricts
+    /// which bots can perform the purchases, `allow_low_liquidity` allows pairs below the minimum
+    /// liquidity and `strategy` sets whether each purchase spends `dca_amount` or averages the
+    /// value purchased
+    ///
+    /// If `max_price` is specified, DCA purchases are only performed while the simulated price of
+    /// `target_asset` in the sent token is at or below it, and skipped while it is above
+    /// `only_below_price` or losing more than `max_price_impact` of their value if specified, the
+    /// order being closed once the price falls below `stop_price` or rises above
+    /// `take_profit_price` if specified, `direction` and `distribution` setting whether it
+    /// accumulates or distributes and the distribution that follows it, and `targets` splitting
+    /// each purchase across several assets, and `pooled` letting other depositors join the order,
+    /// and `max_spread` and `min_receive_per_purchase`
+    /// tighten the slippage protection of each purchase, and `route` sets the intermediate assets
+    /// to swap through, or `auto_route` lets the contract find the best route, while `delivery` sets whether the purchased asset is held by the contract,
+    /// `recipient` sets who it is sent to and `purchase_hooks` sets the contracts called with it,
+    /// while `auto_stake` stakes purchased ASTRO for xASTRO and `zap` provides liquidity with each
+    /// purchase, and `referrer` sets who accrues a share of the protocol fee, while `owner` sets
+    /// who the order belongs to instead of the sender
+    CreateDcaOrder {
+        target_asset: AssetInfo,
+        interval: Duration,
+        schedule: Option<Schedule>,
+        max_delay: Option<u64>,
+        jitter: Option<u64>,
+        dca_amount: Uint128,
+        #[serde(default)]
+        strategy: Strategy,
+        first_purchase: Option<u64>,
+        expires_at: Option<Expiration>,
+        #[serde(default)]
+        max_purchases: Option<u64>,
+        #[serde(default)]
+        max_failures: Option<u32>,
+        #[serde(default)]
+        failure_policy: FailurePolicy,
+        #[serde(default)]
+        swap_tip_bps: Option<u16>,
+        #[serde(default)]
+        tip_escalation: Option<TipEscalation>,
+        #[serde(default)]
+        tip_replenish: Option<TipReplenish>,
+        #[serde(default)]
+        allowed_bots: Vec<String>,
+        #[serde(default)]
+        allow_low_liquidity: bool,
+        max_price: Option<Decimal>,
+        only_below_price: Option<Decimal>,
+        #[serde(default)]
+        max_price_impact: Option<Decimal>,
+        #[serde(default)]
+        stop_price: Option<Decimal>,
+        #[serde(default)]
+        take_profit_price: Option<Decimal>,
+        #[serde(default)]
+        direction: OrderDirection,
+        #[serde(default)]
+        distribution: Option<Distribution>,
+        #[serde(default)]
+        targets: Vec<TargetWeight>,
+        #[serde(default)]
+        pooled: bool,
+        max_spread: Option<Decimal>,
+        min_receive_per_purchase: Option<Uint128>,
+        route: Option<Vec<AssetInfo>>,
+        #[serde(default)]
+        auto_route: bool,
+        #[serde(default)]
+        delivery: Delivery,
+        recipient: Option<String>,
+        #[serde(default)]
+        purchase_hooks: Vec<PurchaseHook>,
+        #[serde(default)]
+        auto_stake: bool,
+        zap: Option<Zap>,
+        referrer: Option<String>,
+        owner: Option<String>,
+    },
+    /// Adds the sent cw20 tokens to an existing escrowed DCA order, keeping its schedule
+    TopUpDcaOrder { id: u64 },
+    /// Adds the sent cw20 tokens to the pooled DCA order `id` of `user`, minting shares of the
+    /// order to the sender
+    JoinPooledOrder { user: String, id: u64 },
+    /// Adds the sent cw20 tokens to the tip balance of the user, holding them in the contract until
+    /// they are paid to bots or withdrawn
+    AddBotTip {},
 }
 
 /// This structure describes the query messages available in the contract
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// Returns information about the users current active DCA orders in a [`Vec<DcaInfo>`] object.
-    UserDcaOrders { user: String },
+    /// Returns information about the users current active DCA orders in a [`Vec<DcaInfo>`] object,
+    /// only returning the orders of `direction` if specified.
+    UserDcaOrders {
+        user: String,
+        #[serde(default)]
+        direction: Option<OrderDirection>,
+    },
     /// Returns information about the contract configuration in a [`Config`] object.
     Config {},
     /// Returns the users current configuration as a [`UserConfig`] object.
     UserConfig { user: String },
+    /// Returns the purchases performed for a DCA order of a user in a [`Vec<PurchaseQueryInfo>`]
+    /// object, ordered by their sequence number.
+    PurchaseHistory {
+        user: String,
+        id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the statistics of a DCA order of a user in an [`OrderStatsResponse`] object.
+    OrderStats { user: String, id: u64 },
+    /// Returns the DCA orders of all users that a DCA purchase can currently be performed for in a
+    /// [`Vec<ReadyOrderInfo>`] object, with the orders with an interval in blocks first, then
+    /// the orders with an interval in seconds, each ordered by the block height or time their next
+    /// purchase can be performed at. Pages start after the order with the next purchase, user
+    /// address and id of `start_after`.
+    ReadyOrders {
+        start_after: Option<(Duration, String, u64)>,
+        limit: Option<u32>,
+    },
+    /// Returns the users that have DCA orders, along with the amount of orders of each user, in a
+    /// [`Vec<UserOrdersInfo>`] object, starting after the user address of `start_after`.
+    AllUsers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the DCA orders of all users spending `initial_asset` to purchase `target_asset` in
+    /// a [`Vec<UserDcaInfo>`] object, starting after the order with the user address and id of
+    /// `start_after`, only returning the orders of `direction` if specified.
+    OrdersByPair {
+        initial_asset: AssetInfo,
+        target_asset: AssetInfo,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+        #[serde(default)]
+        direction: Option<OrderDirection>,
+    },
+    /// Returns the cumulative protocol fees collected from DCA purchases in a [`Vec<Asset>`]
+    /// object, with one entry for each asset spent.
+    ProtocolFees {},
+    /// Returns the global statistics of the contract in a [`StatsResponse`] object, including the
+    /// volume of every asset pair purchased.
+    Stats {},
+    /// Returns the balance of each asset held by the contract against the liabilities it tracks
+    /// for the deposits of DCA orders, tip balances, escrowed purchases, referral rewards, ICS-20
+    /// transfers in flight and bot bonds, in a [`Vec<AssetReconciliation>`] object.
+    Reconciliation {},
+    /// Returns the operators allowed to manage the DCA orders of `user` in a [`Vec<Addr>`] object.
+    Operators { user: String },
+    /// Returns the cw4 group whose members can claim, pause and resume the DCA orders of `user` in
+    /// an [`Option<Addr>`] object.
+    MemberGroup { user: String },
+    /// Returns the contract notified of the lifecycle events of the DCA orders of `user` in an
+    /// [`Option<Addr>`] object.
+    Notifier { user: String },
+    /// Returns the controller on a remote chain that `user` declared it is the interchain account
+    /// of in an [`Option<InterchainController>`] object.
+    InterchainController { user: String },
+    /// Returns the DCA orders of the interchain accounts that declared they are owned by the
+    /// `controller` address on the remote chain of `connection_id` in a [`Vec<UserDcaInfo>`]
+    /// object, starting after the order with the user address and id of `start_after`.
+    OrdersByController {
+        connection_id: String,
+        controller: String,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    /// Returns the configuration changes scheduled to take effect once their timelock has passed
+    /// in a [`Vec<PendingConfigChange>`] object.
+    PendingConfig {},
+    /// Returns the oracle contract set for the DCA orders spending `initial_asset` for
+    /// `target_asset` in an [`Option<Addr>`] object.
+    Oracle {
+        initial_asset: AssetInfo,
+        target_asset: AssetInfo,
+    },
+    /// Returns the tasks contract of the automation protocol that keeper jobs are registered on
+    /// in an [`Option<Addr>`] object.
+    Keeper {},
+    /// Returns the completed DCA orders of `user` in a [`Vec<ArchivedOrder>`] object, starting
+    /// after the order with the id of `start_after`.
+    ArchivedOrders {
+        user: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the tip balance of `user` in a [`Vec<Asset>`] object, ordered by the priority in
+    /// which the tips are redeemed.
+    TipBalances { user: String },
+    /// Returns the registered bots along with the performance of their purchases in a
+    /// [`Vec<BotInfo>`] object, starting after the bot address of `start_after`.
+    Bots {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the outcome of the next DCA purchase of the order `id` of `user` through `hops` in
+    /// a [`SimulatePurchaseResponse`] object, without performing it. The hops are ignored for an
+    /// order with a stored route or that is auto routed.
+    SimulatePurchase {
+        user: String,
+        id: u64,
+        hops: Vec<SwapOperation>,
+    },
+    /// Returns the remaining DCA purchases of the order `id` of `user`, along with the output and
+    /// tips they are estimated at by the current prices, in an [`OrderPlanResponse`] object.
+    SimulateOrderPlan { user: String, id: u64 },
+    /// Returns the shares that `depositor` holds of the pooled DCA order `id` of `user`, along
+    /// with their share of its deposit and of its purchases, in a [`PoolShareResponse`] object.
+    PoolShare {
+        user: String,
+        id: u64,
+        depositor: String,
+    },
+}
+
+/// This structure describes the privileged messages that can be executed by the chain, such as
+/// through on-chain governance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Pauses the creation of DCA orders and DCA purchases
+    Pause {},
+    /// Resumes the creation of DCA orders and DCA purchases
+    Resume {},
+    /// Transfers the ownership of the contract to `owner`, removing any pending ownership
+    /// proposal
+    UpdateOwner { owner: String },
+    /// Reports the outcome of an ICS-20 transfer sent by the contract through the ibc-hooks
+    /// callback of the transfer
+    IbcLifecycleComplete(IbcLifecycleComplete),
+    /// Performs the DCA purchases of up to `limit` due orders without a bot, which is called by the
+    /// scheduler of chains supporting native scheduling such as the cron module of Neutron. The
+    /// purchases pay no tips, and orders without a stored route are routed through the best route
+    /// found on-chain
+    ExecuteDuePurchases { limit: u32 },
+}
+
+/// Describes the outcome of an ICS-20 transfer reported by ibc-hooks, matching the
+/// `IBCLifecycleComplete` message of ibc-hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IbcLifecycleComplete {
+    /// The transfer `sequence` sent over `channel` was acknowledged, which failed unless `success`
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    /// The transfer `sequence` sent over `channel` timed out
+    IbcTimeout { channel: String, sequence: u64 },
 }
 
 /// This structure describes a migration message.
@@ -130,8 +1609,240 @@ pub struct MigrateMsg {}
 ///
 /// This is useful for bots and front-end to distinguish between a users token allowance (which may
 /// have changed) for the DCA contract, and the created DCA order size.
+///
+/// The decimals of the initial and target asset are included so that front-ends can display the
+/// amounts of the order, read from the denom metadata of native tokens where available.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct DcaQueryInfo {
     pub token_allowance: Uint128,
     pub order: DcaInfo,
+    pub initial_decimals: u8,
+    pub target_decimals: u8,
+}
+
+/// Describes a DCA purchase that was performed for an order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PurchaseRecord {
+    /// The time in seconds that the purchase was performed at
+    pub timestamp: u64,
+    /// The amount of `initial_asset` spent in the purchase
+    pub amount_in: Uint128,
+    /// The amount of `target_asset` received from the purchase
+    pub amount_out: Uint128,
+    /// The amount of each target asset received from the purchase, for an order purchasing
+    /// several target assets
+    #[serde(default)]
+    pub outputs: Vec<Asset>,
+    /// The address of the bot that performed the purchase
+    pub bot: Addr,
+    /// The tip paid to the bot for performing the purchase
+    pub tip: Vec<Asset>,
+}
+
+/// Describes a DCA purchase that failed, which is recorded on the order it was performed for
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PurchaseFailure {
+    /// The error that the purchase failed with
+    pub error: String,
+    /// The block height that the purchase failed at
+    pub height: u64,
+    /// The address of the bot that performed the purchase
+    pub bot: Addr,
+}
+
+/// Describes information for a PurchaseHistory query
+///
+/// Contains the purchase record along with its sequence number, which can be used as
+/// `start_after` to paginate through the history.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PurchaseQueryInfo {
+    pub seq: u64,
+    pub record: PurchaseRecord,
+}
+
+/// Describes a DCA order that was removed once it was completed, along with its final stats
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ArchivedOrder {
+    /// The DCA order as it was when it was completed
+    pub order: DcaInfo,
+    /// The amount of DCA purchases performed for the order
+    pub purchases: u64,
+    /// The time in seconds that the order was completed at
+    pub completed_at: u64,
+}
+
+/// Describes the statistics of a DCA order returned by an OrderStats query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderStatsResponse {
+    /// The amount of DCA purchases performed for the order
+    pub purchases: u64,
+    /// The total amount of `initial_asset` spent in DCA purchases
+    pub total_spent: Uint128,
+    /// The total amount of `target_asset` received from DCA purchases
+    pub total_received: Uint128,
+    /// The total amount of each target asset received from DCA purchases, for an order purchasing
+    /// several target assets
+    pub targets_received: Vec<Asset>,
+    /// The average price of `target_asset` in `initial_asset` paid in DCA purchases, if any of
+    /// `target_asset` has been received
+    pub average_price: Option<Decimal>,
+    /// The amount of DCA purchases remaining until the order is fulfilled
+    pub remaining_purchases: u64,
+    /// The estimated time in seconds or block height that the last DCA purchase of the order will
+    /// be performed at, in the unit of the interval of the order, if any purchases are remaining
+    pub estimated_completion: Option<u64>,
+}
+
+/// Describes the volume of the DCA purchases of an asset pair returned by a Stats query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairVolume {
+    /// The asset spent in the DCA purchases of the pair
+    pub initial_asset: AssetInfo,
+    /// The asset purchased in the DCA purchases of the pair
+    pub target_asset: AssetInfo,
+    /// The amount of DCA purchases performed for the pair
+    pub purchases: u64,
+    /// The total amount of `initial_asset` spent in the DCA purchases of the pair
+    pub amount_in: Uint128,
+    /// The total amount of `target_asset` received from the DCA purchases of the pair
+    pub amount_out: Uint128,
+}
+
+/// Describes the global statistics of the contract returned by a Stats query, counted from when
+/// the contract started keeping them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    /// The amount of DCA orders that have not been completed, cancelled, stopped or swept
+    pub active_orders: u64,
+    /// The amount of DCA purchases performed
+    pub total_purchases: u64,
+    /// The length of an epoch in seconds
+    pub epoch_length: u64,
+    /// The time in seconds that the current epoch started at
+    pub epoch_start: u64,
+    /// The amount of DCA purchases performed in the current epoch
+    pub epoch_purchases: u64,
+    /// The amount of DCA purchases performed in the last epoch
+    pub last_epoch_purchases: u64,
+    /// The total tips paid to bots for DCA purchases, with one entry for each asset paid
+    pub tips_paid: Vec<Asset>,
+    /// The volume of the DCA purchases of each asset pair
+    pub volumes: Vec<PairVolume>,
+}
+
+/// Describes the balance of an asset held by the contract against the liabilities tracked for it,
+/// returned by a Reconciliation query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetReconciliation {
+    /// The asset reconciled
+    pub info: AssetInfo,
+    /// The amount of the asset held by the contract
+    pub balance: Uint128,
+    /// The amount of the asset owed by the contract to its users and bots
+    pub liabilities: Uint128,
+    /// The amount of the asset held over the liabilities, left by rounding, refunds or transfers
+    /// that are not tracked, which can be swept to the fee collector
+    pub surplus: Uint128,
+    /// The amount of the liabilities of the asset that the balance does not cover, which includes
+    /// the ICS-20 transfers in flight until their outcome is reported
+    pub deficit: Uint128,
+}
+
+/// Describes the outcome of a DCA purchase returned by a SimulatePurchase query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatePurchaseResponse {
+    /// The hop route that the purchase would be performed through
+    pub hops: Vec<SwapOperation>,
+    /// Whether the purchase is currently due
+    pub due: bool,
+    /// The amount of `initial_asset` spent in the purchase
+    pub spend_amount: Uint128,
+    /// The amount of `initial_asset` swapped for `target_asset`, after the protocol fee, the swap
+    /// tip and the replenished tip are taken and a zapping order keeps its share to provide
+    pub swap_amount: Uint128,
+    /// The amount of `target_asset` the swap is simulated to return
+    pub expected_output: Uint128,
+    /// The share of the value of the swap at the ratio of the reserves of the pairs that is lost to
+    /// their fees and slippage, if the route only swaps through Astroport pairs holding its assets
+    pub price_impact: Option<Decimal>,
+    /// The tip that the bot would be paid for the purchase, without the tip redeemed from the tip
+    /// balance if the user cannot cover it
+    pub tip_cost: Vec<Asset>,
+}
+
+/// Describes a remaining DCA purchase of an order returned by a SimulateOrderPlan query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlannedPurchase {
+    /// The time in seconds or block height, in the unit of the interval of the order, that the
+    /// purchase can be performed from
+    pub at: u64,
+    /// The amount of `initial_asset` spent in the purchase
+    pub amount: Uint128,
+}
+
+/// Describes the remaining DCA purchases of an order returned by a SimulateOrderPlan query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderPlanResponse {
+    /// The hop route that the purchases are estimated through
+    pub hops: Vec<SwapOperation>,
+    /// The amount of DCA purchases remaining until the order is fulfilled
+    pub remaining_purchases: u64,
+    /// The next remaining purchases, up to the first 50, assuming each of them is performed as
+    /// soon as the interval or schedule of the order allows it
+    pub purchases: Vec<PlannedPurchase>,
+    /// The total amount of `target_asset` that every remaining purchase is estimated to return at
+    /// the current prices
+    pub estimated_output: Uint128,
+    /// The total tip that bots are estimated to be paid for every remaining purchase, assuming
+    /// none of them is overdue
+    pub estimated_tips: Vec<Asset>,
+}
+
+/// Describes information for a ReadyOrders query
+///
+/// Contains the DCA order ready to be purchased along with the user it belongs to, the time or
+/// block height its next purchase could be performed at and the tip balance of the user that bots can redeem for
+/// performing the purchase, in the priority it is redeemed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReadyOrderInfo {
+    pub next_purchase: Duration,
+    pub user: Addr,
+    pub order: DcaInfo,
+    pub tip_balance: Vec<Asset>,
+}
+
+/// Describes information for an AllUsers query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserOrdersInfo {
+    pub user: Addr,
+    pub orders: u64,
+}
+
+/// Describes information for a Bots query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotInfo {
+    pub bot: Addr,
+    #[serde(flatten)]
+    pub stats: BotStats,
+    pub average_delay: u64,
+}
+
+/// Describes information for an OrdersByPair query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserDcaInfo {
+    pub user: Addr,
+    pub order: DcaInfo,
+}
+
+/// Describes the share of a depositor in a pooled DCA order returned by a PoolShare query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolShareResponse {
+    /// The shares of the order held by the depositor
+    pub shares: Uint128,
+    /// The total shares of the order held by all depositors
+    pub total_shares: Uint128,
+    /// The amount of the remaining deposit of the order owed to the shares of the depositor
+    pub deposit: Uint128,
+    /// The amount of the purchased `target_asset` that the depositor can claim
+    pub claimable: Uint128,
 }